@@ -83,6 +83,7 @@ fn translate_csv(name: &str, csv: &mut csv::Reader<impl Read>, out: &mut impl Wr
                 writeln!(out, "            Jump: {},", &row[i_jump]).unwrap();
                 writeln!(out, "            Boost: {},", &row[i_boost]).unwrap();
                 writeln!(out, "            Handbrake: {},", &row[i_handbrake]).unwrap();
+                writeln!(out, "            UseItem: false,").unwrap();
                 writeln!(out, "        }},").unwrap();
             }
             writeln!(out, "    ];\n").unwrap();
@@ -0,0 +1,140 @@
+use crate::{
+    eeg::{color, Drawable, Event},
+    helpers::self_contact::predict_own_goal_contact,
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::{prelude::*, rl};
+use nameof::name_of_type;
+
+/// A reflex for when we're about to knock the ball into our own net. There's
+/// no time to plan anything, so either swerve around the ball or jump over it.
+pub struct AvoidOwnGoal {
+    start_time: Option<f32>,
+    jump_start: Option<f32>,
+}
+
+impl AvoidOwnGoal {
+    /// If the ball is this far to one side of our nose, we can swerve around
+    /// it. Otherwise, we need to jump over it.
+    const SWERVE_OFFSET: f32 = 60.0;
+    /// Balls higher than this are too high to jump over in time.
+    const MAX_JUMP_BALL_Z: f32 = 120.0;
+    /// Give up after this long, and let the strategy take over again.
+    const TIMEOUT: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            start_time: None,
+            jump_start: None,
+        }
+    }
+
+    /// Returns `true` if the reflex should kick in.
+    pub fn triggered(ctx: &mut Context<'_>) -> bool {
+        predict_own_goal_contact(ctx.game, ctx.me(), ctx.scenario.ball_prediction()).is_some()
+    }
+
+    fn jump(&mut self, ctx: &mut Context<'_>, now: f32) -> Action {
+        let jump_start = *self.jump_start.get_or_insert(now);
+        let elapsed = now - jump_start;
+        if elapsed >= rl::CAR_JUMP_FORCE_TIME && ctx.me().OnGround {
+            ctx.eeg.log(self.name(), "landed");
+            return Action::Return;
+        }
+        Action::Yield(common::halfway_house::PlayerInput {
+            Jump: elapsed < rl::CAR_JUMP_FORCE_TIME,
+            ..Default::default()
+        })
+    }
+}
+
+impl Behavior for AvoidOwnGoal {
+    fn name(&self) -> &str {
+        name_of_type!(AvoidOwnGoal)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Force
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::AvoidOwnGoal);
+
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start_time = *self.start_time.get_or_insert(now);
+
+        // Once we've left the ground, we're committed.
+        if self.jump_start.is_some() {
+            return self.jump(ctx, now);
+        }
+
+        if now - start_time >= Self::TIMEOUT {
+            ctx.eeg.log(self.name(), "timeout");
+            return Action::Return;
+        }
+
+        let me = ctx.me();
+        let contact = predict_own_goal_contact(ctx.game, me, ctx.scenario.ball_prediction());
+        let contact = some_or_else!(contact, {
+            ctx.eeg.log(self.name(), "crisis averted");
+            return Action::Return;
+        });
+
+        ctx.eeg.draw(Drawable::print("own goal!", color::RED));
+        ctx.eeg.draw(Drawable::ghost_ball(contact.ball_loc));
+        ctx.eeg.print_time("contact", contact.time);
+        ctx.eeg.print_time("time_to_goal", contact.time_to_goal);
+
+        let car_loc = me.Physics.loc_2d() + me.Physics.vel_2d() * contact.time;
+        let offset = (contact.ball_loc.to_2d() - car_loc).dot(&me.Physics.right_axis().to_2d());
+
+        if offset.abs() < Self::SWERVE_OFFSET
+            && contact.ball_loc.z < Self::MAX_JUMP_BALL_Z
+            && me.OnGround
+        {
+            ctx.eeg.log(self.name(), "jumping over the ball");
+            return self.jump(ctx, now);
+        }
+
+        // Steer away from whichever side the ball is on, and slow down so the touch
+        // is as soft as possible if it happens anyway.
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: -1.0,
+            Steer: -offset.signum(),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn dont_knock_it_in_while_retreating() {
+        tags!(Defense);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, -3600.0, 92.74),
+                ball_vel: Vector3::new(0.0, -300.0, 0.0),
+                car_loc: Point3::new(20.0, -2900.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, -1800.0, 0.0),
+                enemy_loc: Point3::new(3000.0, 3000.0, 17.01),
+                ..Default::default()
+            })
+            .soccar()
+            .run_for_millis(3000);
+
+        assert!(!test.enemy_has_scored());
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::AvoidOwnGoal));
+        });
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{
+    eeg::{color, Drawable, Event},
+    helpers::ball::BallFrame,
+    strategy::{Action, Behavior, Context, Game},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3};
+use nameof::name_of_type;
+use simulate::Car1D;
+use std::f32::consts::PI;
+
+/// When the ball is rolling up our back wall, drive up after it and knock it
+/// sideways into the corner, instead of waiting for it to drop back down in
+/// front of our goal.
+///
+/// Like `WallHit`, everything is worked out with the walls folded flat onto the
+/// floor.
+pub struct BackwallClear;
+
+/// Where to meet the ball, folded flat.
+struct Clear {
+    time: f32,
+    ball_loc: Point3<f32>,
+    flat_contact_loc: Point2<f32>,
+}
+
+impl BackwallClear {
+    /// The ball counts as rolling up the wall once it's at least this high,
+    const MIN_BALL_Z: f32 = 250.0;
+    /// and this close to the surface. Any further out and it's in the air.
+    const MAX_SURFACE_DIST: f32 = rl::BALL_RADIUS + 50.0;
+    /// Don't plan further ahead than this.
+    const MAX_TIME: f32 = 3.0;
+    /// We can only push the ball towards the corner if we're coming from the
+    /// other side of it.
+    const MAX_APPROACH_ANGLE: f32 = PI / 3.0;
+    /// How far from the ball our car's center is at contact.
+    const CONTACT_DIST: f32 = rl::BALL_RADIUS + 70.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns `true` if the ball will be up our back wall somewhere we can
+    /// reach from the right side.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        plan(ctx).is_some()
+    }
+}
+
+impl Behavior for BackwallClear {
+    fn name(&self) -> &str {
+        name_of_type!(BackwallClear)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::BackwallClear);
+
+        let me = ctx.me();
+        if !me.OnGround {
+            ctx.eeg.log(self.name(), "not on ground");
+            return Action::Abort;
+        }
+
+        let clear = some_or_else!(plan(ctx), {
+            ctx.eeg.log(self.name(), "the ball is off the wall");
+            return Action::Return;
+        });
+
+        let me_to_flat = ctx.game.pitch().unfold_to_ground(&me.Physics.loc());
+        let flat_me_loc = me_to_flat * me.Physics.loc();
+        let flat_forward = me_to_flat * me.Physics.forward_axis();
+        let steer = flat_forward.angle_to(&(clear.flat_contact_loc - flat_me_loc));
+
+        ctx.eeg.print_time("clear_time", clear.time);
+        ctx.eeg.draw(Drawable::ghost_ball(clear.ball_loc));
+        ctx.eeg.draw(Drawable::unfolded(Drawable::Line(
+            flat_me_loc,
+            clear.flat_contact_loc,
+            color::YELLOW,
+        )));
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Steer: (steer * 2.0).max(-1.0).min(1.0),
+            Boost: me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED,
+            ..Default::default()
+        })
+    }
+}
+
+/// Find the first moment the ball is up our back wall and we can get to it in
+/// time, coming from a side that sends it to the corner.
+fn plan(ctx: &mut Context<'_>) -> Option<Clear> {
+    let me = ctx.me();
+    let pitch = ctx.game.pitch();
+    let me_to_flat = pitch.unfold_to_ground(&me.Physics.loc());
+    let flat_me_loc = me_to_flat * me.Physics.loc();
+
+    let mut sim = Car1D::new()
+        .with_speed((me_to_flat * me.Physics.vel()).norm())
+        .with_boost(me.Boost as f32);
+
+    for ball in ctx.scenario.ball_prediction().iter() {
+        if ball.t >= BackwallClear::MAX_TIME {
+            break;
+        }
+        sim.advance(ball.t - sim.time(), 1.0, true);
+        if !on_own_back_wall(ctx.game, ball) {
+            continue;
+        }
+
+        let flat_ball_loc = pitch.unfold_to_ground(&ball.loc) * ball.loc;
+        let corner = corner_loc(ctx.game, flat_ball_loc);
+        let flat_contact_loc = some_or_else!(contact_loc(flat_me_loc, flat_ball_loc, corner), {
+            continue;
+        });
+        if sim.distance() >= (flat_contact_loc - flat_me_loc).norm() {
+            return Some(Clear {
+                time: ball.t,
+                ball_loc: ball.loc,
+                flat_contact_loc,
+            });
+        }
+    }
+    None
+}
+
+fn on_own_back_wall(game: &Game<'_>, ball: &BallFrame) -> bool {
+    let pitch = game.pitch();
+    // The back wall inside the goal faces the same way, but that's a goal,
+    // not a clear.
+    if ball.loc.z < BackwallClear::MIN_BALL_Z || pitch.is_in_goal(ball.loc.to_2d()) {
+        return false;
+    }
+    let surface = pitch.closest_plane(&ball.loc);
+    surface.normal.dot(&game.own_goal().normal_2d.to_3d()) >= 0.99
+        && surface.distance_to_point(&ball.loc) < BackwallClear::MAX_SURFACE_DIST
+}
+
+/// The corner on the ball's side of the goal, folded flat. This is where the
+/// wall meets the floor, so the clear comes down into the corner instead of
+/// hanging up on the wall.
+fn corner_loc(game: &Game<'_>, flat_ball_loc: Point2<f32>) -> Point2<f32> {
+    let back_wall = game.own_back_wall_center();
+    Point2::new(flat_ball_loc.x.signum() * game.field_max_x(), back_wall.y)
+}
+
+/// Where our car should be when it touches the ball, to send it towards
+/// `flat_corner_loc`. Returns `None` if we'd have to come at it from the wrong
+/// side.
+fn contact_loc(
+    flat_me_loc: Point2<f32>,
+    flat_ball_loc: Point2<f32>,
+    flat_corner_loc: Point2<f32>,
+) -> Option<Point2<f32>> {
+    let aim = (flat_corner_loc - flat_ball_loc).to_axis();
+    let approach = flat_ball_loc - flat_me_loc;
+    if approach.angle_to(&aim).abs() >= BackwallClear::MAX_APPROACH_ANGLE {
+        return None;
+    }
+    Some(flat_ball_loc - aim.into_inner() * BackwallClear::CONTACT_DIST)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::defense::backwall_clear::contact_loc;
+    use nalgebra::Point2;
+
+    #[test]
+    fn clear_from_the_middle_side() {
+        // Blue's back wall, folded flat, is everything past y = -5120.
+        let ball = Point2::new(500.0, -5700.0);
+        let corner = Point2::new(4096.0, -5120.0);
+        let contact = contact_loc(Point2::new(-2500.0, -4500.0), ball, corner).unwrap();
+        // The car touches the ball on the side away from the corner.
+        assert!(contact.x < ball.x);
+    }
+
+    #[test]
+    fn dont_clear_from_straight_below() {
+        let ball = Point2::new(500.0, -5700.0);
+        let corner = Point2::new(4096.0, -5120.0);
+        assert!(contact_loc(Point2::new(500.0, -3500.0), ball, corner).is_none());
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::defense::BackwallClear,
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, Rotation3, Vector3};
+
+    #[test]
+    fn clear_ball_rolling_up_back_wall() {
+        tags!(Defense, Wall);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(500.0, -rl::FIELD_MAX_Y + 93.0, 400.0),
+                ball_vel: Vector3::new(0.0, 0.0, 800.0),
+                car_loc: Point3::new(-2500.0, -4500.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, -0.4, 0.0),
+                car_vel: Vector3::new(1000.0, -400.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(BackwallClear::new())
+            .run_for_millis(3000);
+
+        assert!(!test.enemy_has_scored());
+        let packet = test.sniff_packet();
+        println!("ball loc = {:?}", packet.GameBall.Physics.loc());
+        assert!(packet.GameBall.Physics.loc().x >= 1500.0);
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::BackwallClear));
+        });
+    }
+}
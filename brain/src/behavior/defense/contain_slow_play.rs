@@ -0,0 +1,104 @@
+use crate::{
+    behavior::{
+        defense::defensive_hit, higher_order::Chain, movement::drive_towards, strike::GroundedHit,
+    },
+    eeg::Event,
+    helpers::{
+        drive::arrive_on_time,
+        telepathy::{enemy_intent, EnemyIntent},
+    },
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use vec_box::vec_box;
+
+/// When the enemy is slow-playing the ball, hold a blocking position between
+/// the ball and our goal instead of lunging at every little touch. Once we run
+/// out of patience, challenge during a lull in their touches.
+pub struct ContainSlowPlay {
+    start_time: Option<f32>,
+}
+
+impl ContainSlowPlay {
+    /// How far from the ball to hold, along the line to our goal.
+    const BLOCK_DIST: f32 = 1200.0;
+    /// Never hold closer to the goal than this.
+    const MIN_GOAL_DIST: f32 = 800.0;
+    /// How long to wait them out before challenging.
+    const PATIENCE: f32 = 3.0;
+    /// Only challenge if the enemy hasn't touched the ball for this long, i.e.,
+    /// they're not in the middle of a move.
+    const TOUCH_QUIET: f32 = 0.5;
+    /// Aim to be in position this far in the future.
+    const HORIZON: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self { start_time: None }
+    }
+
+    fn block_loc(ctx: &mut Context<'_>) -> Point2<f32> {
+        let goal = ctx.game.own_goal();
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let ball_to_goal = goal.center_2d - ball_loc;
+        let dist = (ball_to_goal.norm() - Self::MIN_GOAL_DIST)
+            .min(Self::BLOCK_DIST)
+            .max(0.0);
+        ball_loc + ball_to_goal.normalize() * dist
+    }
+
+    fn hold(&self, ctx: &mut Context<'_>) -> Action {
+        let target_loc = Self::block_loc(ctx);
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let me = ctx.me();
+
+        // Once in position, stay put and keep facing the ball.
+        if (target_loc - me.Physics.loc_2d()).norm() < 200.0 {
+            let mut input = drive_towards(ctx, ball_loc);
+            input.Throttle = 0.0;
+            input.Boost = false;
+            return Action::Yield(input);
+        }
+
+        Action::Yield(arrive_on_time(ctx, target_loc, Self::HORIZON))
+    }
+}
+
+impl Behavior for ContainSlowPlay {
+    fn name(&self) -> &str {
+        name_of_type!(ContainSlowPlay)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::ContainSlowPlay);
+
+        if enemy_intent(ctx) != EnemyIntent::SlowPlay {
+            ctx.eeg.log(self.name(), "the enemy stopped slow-playing");
+            return Action::Return;
+        }
+
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start_time = *self.start_time.get_or_insert(now);
+        let touches = ctx.match_memory.enemy_touches_since(start_time);
+        ctx.eeg.print_value("enemy_touches", touches.to_string());
+
+        let quiet = ctx
+            .match_memory
+            .last_enemy_touch()
+            .map_or(true, |t| now - t >= Self::TOUCH_QUIET);
+        if now - start_time >= Self::PATIENCE && quiet {
+            ctx.eeg.log(self.name(), "out of patience; challenging");
+            return Action::tail_call(Chain::new(
+                Priority::Idle,
+                vec_box![
+                    FollowRoute::new(GroundIntercept::new()),
+                    GroundedHit::hit_towards(defensive_hit),
+                ],
+            ));
+        }
+
+        self.hold(ctx)
+    }
+}
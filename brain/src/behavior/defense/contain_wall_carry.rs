@@ -0,0 +1,125 @@
+use crate::{
+    behavior::{defense::defensive_hit, higher_order::Chain, strike::GroundedHit},
+    eeg::Event,
+    helpers::{
+        drive::arrive_on_time,
+        telepathy::{enemy_intent, EnemyIntent},
+    },
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use vec_box::vec_box;
+
+/// When the enemy is carrying the ball along the side wall towards our corner,
+/// neither commit nor run away. Mirror their progress from the ground, a bit
+/// goalside and far enough off the wall to cover a cut to the middle, then
+/// challenge the instant the ball comes off the wall.
+pub struct ContainWallCarry {
+    side: Option<f32>,
+}
+
+impl ContainWallCarry {
+    /// How far from the wall to shadow, to take away the cut to the middle.
+    const WALL_OFFSET: f32 = 1200.0;
+    /// How far goalside of the ball to shadow, in seconds of the ball's travel.
+    const TIME_GAP: f32 = 0.5;
+    /// Shadow at least this far goalside, even if the ball is slow.
+    const MIN_GOALSIDE: f32 = 500.0;
+    /// Never shadow closer to the goal than this.
+    const MIN_GOAL_DIST: f32 = 1000.0;
+    /// Aim to be in position this far in the future.
+    const HORIZON: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self { side: None }
+    }
+
+    /// Where we want to be `HORIZON` seconds from now, given the ball's progress
+    /// along the wall.
+    fn shadow_loc(ctx: &mut Context<'_>, side: f32) -> Point2<f32> {
+        let goal = ctx.game.own_goal();
+        let ball = ctx
+            .scenario
+            .ball_prediction()
+            .at_time_or_last(Self::HORIZON);
+
+        // Keep a fixed time gap rather than a fixed distance, so we give a fast
+        // carry more room.
+        let ball_speed = ball.vel.to_2d().dot(&-goal.normal_2d).max(0.0);
+        let goalside = (ball_speed * Self::TIME_GAP).max(Self::MIN_GOALSIDE);
+
+        let target_loc = Point2::new(
+            side * (ctx.game.field_max_x() - Self::WALL_OFFSET),
+            ball.loc.y,
+        ) - goal.normal_2d.into_inner() * goalside;
+        let goal_dist = (target_loc - goal.center_2d).dot(&goal.normal_2d);
+        if goal_dist < Self::MIN_GOAL_DIST {
+            target_loc + goal.normal_2d.into_inner() * (Self::MIN_GOAL_DIST - goal_dist)
+        } else {
+            target_loc
+        }
+    }
+
+    fn ball_left_wall(ctx: &mut Context<'_>) -> bool {
+        let ball_loc = ctx.packet.GameBall.Physics.loc();
+        ball_loc.x.abs() < ctx.game.field_max_x() - 300.0 || ball_loc.z < 150.0
+    }
+
+    /// Returns `true` if the ball is already past us, in which case there's
+    /// nothing left to contain.
+    fn beaten(ctx: &mut Context<'_>) -> bool {
+        let goal = ctx.game.own_goal();
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let me_loc = ctx.me().Physics.loc_2d();
+        (me_loc - ball_loc).dot(&goal.normal_2d) > 500.0
+    }
+
+    fn shadow(&self, ctx: &mut Context<'_>, side: f32) -> Action {
+        let target_loc = Self::shadow_loc(ctx, side);
+        // Once we're in position, this matches the ball's speed.
+        Action::Yield(arrive_on_time(ctx, target_loc, Self::HORIZON))
+    }
+}
+
+impl Behavior for ContainWallCarry {
+    fn name(&self) -> &str {
+        name_of_type!(ContainWallCarry)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::ContainWallCarry);
+
+        if Self::beaten(ctx) {
+            ctx.eeg.log(self.name(), "the ball is past us");
+            return Action::Return;
+        }
+
+        if let EnemyIntent::WallCarry { side } = enemy_intent(ctx) {
+            self.side = Some(side);
+            return self.shadow(ctx, side);
+        }
+
+        if self.side.is_none() {
+            ctx.eeg.log(self.name(), "nobody is carrying the ball");
+            return Action::Abort;
+        }
+
+        if Self::ball_left_wall(ctx) {
+            ctx.eeg
+                .log(self.name(), "the ball left the wall; challenging");
+            return Action::tail_call(Chain::new(
+                Priority::Idle,
+                vec_box![
+                    FollowRoute::new(GroundIntercept::new()),
+                    GroundedHit::hit_towards(defensive_hit),
+                ],
+            ));
+        }
+
+        ctx.eeg.log(self.name(), "the carry is over");
+        Action::Return
+    }
+}
@@ -1,12 +1,19 @@
 use crate::{
     behavior::{
-        defense::{retreat::Retreat, retreating_save::RetreatingSave, PanicDefense},
+        defense::{
+            retreat::Retreat, retreating_save::RetreatingSave, ContainSlowPlay, ContainWallCarry,
+            FakeChallenge, Goalkeeper, PanicDefense, Shadow, Support, TimedJumpSave,
+        },
         offense::TepidHit,
         strike::{GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
     },
     eeg::Event,
-    helpers::hit_angle::blocking_angle,
-    strategy::{Action, Behavior, Context, Game, Scenario},
+    helpers::{
+        danger_zones::{draw_danger_zones, most_dangerous_zone},
+        hit_angle::blocking_angle,
+        telepathy::{enemy_intent, EnemyIntent},
+    },
+    strategy::{Action, Behavior, Context, Game, Role, Scenario},
     utils::{geometry::ExtendF32, WallRayCalculator},
 };
 use common::prelude::*;
@@ -57,8 +64,11 @@ impl Defense {
             return false;
         }
 
-        let defending_angle = (ball_loc - goal_loc).angle(&(me_loc - goal_loc));
-        if defending_angle.abs() >= PI / 6.0 {
+        // Guard the shot from the most dangerous zone, rather than the goal as a
+        // whole.
+        let zone = most_dangerous_zone(goal, ball_loc);
+        let defending_angle = (ball_loc - zone.aim_loc).angle(&(me_loc - zone.aim_loc));
+        if defending_angle.abs() >= PI / 6.0 && !zone.contains(me_loc) {
             // If we're in net, chances are our angle of defense is fine already. e.g. we
             // might be opposite the desired angle, which would be 180° away according to
             // the math, but is a perfectly fine place to be.
@@ -95,7 +105,7 @@ impl Defense {
     }
 
     pub fn enemy_can_attack(ctx: &mut Context<'_>) -> bool {
-        if ctx.scenario.possession() >= -2.0 {
+        if ctx.scenario.possession() >= -ctx.risk.challenge_deficit(ctx.personality) {
             return false;
         }
         let (enemy, intercept) = match ctx.scenario.enemy_intercept() {
@@ -109,6 +119,22 @@ impl Defense {
         enemy_to_ball.angle_to(&-goal.normal_2d).abs() < PI / 3.0
             && enemy_forward_axis.angle_to(&enemy_to_ball).abs() < PI / 3.0
     }
+
+    /// `impending_concede_if_touched` assumes they hit the ball dead at our
+    /// goal no matter where they come from. Only believe it if they're coming
+    /// at the ball from somewhere they could actually shoot.
+    pub fn enemy_touch_would_score(ctx: &mut Context<'_>) -> bool {
+        if ctx.scenario.impending_concede_if_touched().is_none() {
+            return false;
+        }
+        let (enemy, intercept) = some_or_else!(ctx.scenario.enemy_intercept(), {
+            return false;
+        });
+        let ball_loc = intercept.ball_loc.to_2d();
+        let approach = ball_loc - enemy.Physics.loc_2d();
+        let shot = ctx.game.own_goal().center_2d - ball_loc;
+        approach.angle_to(&shot).abs() < PI / 4.0
+    }
 }
 
 impl Behavior for Defense {
@@ -116,33 +142,102 @@ impl Behavior for Defense {
         name_of_type!(Defense)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.track(Event::Defense);
 
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        draw_danger_zones(ctx.eeg, ctx.game.own_goal(), ball_loc);
+
+        // Behind late, a goal against costs nothing that isn't already lost.
+        // Save what's going in, and go for the ball otherwise.
+        if ctx.risk.all_out_offense() && ctx.scenario.impending_concede().is_none() {
+            return self.decide(ctx, "all out offense", TepidHit::new());
+        }
+
         // If we're not between the ball and our goal, get there.
         if !Self::is_between_ball_and_own_goal(ctx.game, ctx.me(), ctx.scenario) {
-            ctx.eeg.log(self.name(), "not between ball and goal");
-            return Action::tail_call(Retreat::new());
+            return self.decide(ctx, "not between ball and goal", Retreat::new());
+        }
+
+        // If the shot is going in over our heads, jump to block it.
+        if TimedJumpSave::applicable(ctx) {
+            return self.decide(ctx, "high shot", TimedJumpSave::new());
         }
 
         // If we need to make a save, do so.
         if RetreatingSave::applicable(ctx).is_ok() {
-            ctx.eeg.log(self.name(), "retreating save");
-            return Action::tail_call(Retreat::new());
+            return self.decide(ctx, "retreating save", Retreat::new());
+        }
+
+        match enemy_intent(ctx) {
+            // If they're carrying the ball down the wall, hang back and wait for it
+            // to come off.
+            EnemyIntent::WallCarry { .. } => {
+                return self.decide(ctx, "containing wall carry", ContainWallCarry::new());
+            }
+            // If they're holding the ball, don't take the bait.
+            EnemyIntent::SlowPlay => {
+                return self.decide(ctx, "containing slow play", ContainSlowPlay::new());
+            }
+            EnemyIntent::Unknown => {}
         }
 
         if Self::enemy_can_shoot(ctx) {
-            ctx.eeg.log(self.name(), "enemy_can_shoot");
-            return Action::tail_call(Retreat::new());
+            return self.decide(ctx, "enemy_can_shoot", Retreat::new());
         }
 
         if Self::enemy_can_attack(ctx) {
-            ctx.eeg.log(self.name(), "enemy_can_attack");
-            return Action::tail_call(Retreat::new());
+            return self.decide(ctx, "enemy_can_attack", Retreat::new());
+        }
+
+        // Judge the commit against the worst case, not only the ball's current
+        // path. If they'll beat us there and a shot from that touch would go in,
+        // stay home.
+        if ctx.scenario.possession() < -Scenario::POSSESSION_CONTESTABLE
+            && Self::enemy_touch_would_score(ctx)
+        {
+            return self.decide(ctx, "enemy touch would score", Retreat::new());
+        }
+
+        // If they'll have the ball long before we could, don't dive in. Stay
+        // goal-side and wait for a better chance.
+        if ctx.scenario.possession() < -Shadow::MIN_DEFICIT {
+            return self.decide(ctx, "they have the ball", Shadow::new());
+        }
+
+        // Leave the ball to whoever's going for it.
+        if !ctx.scenario.team().may_commit() {
+            // Whoever's hanging back keeps goal, and anyone else backs up the
+            // first man.
+            if ctx.scenario.team().my_role() == Role::BackPost {
+                return self.decide(ctx, "a teammate has the ball", Goalkeeper::new());
+            }
+            return self.decide(ctx, "a teammate has the ball", Support::new());
+        }
+
+        // If they'll get there a little before us, charge anyway. They might
+        // rush the touch.
+        if FakeChallenge::applicable(ctx) {
+            return self.decide(ctx, "close race", FakeChallenge::new());
         }
 
         // If we're already in goal, try to take control of the ball.
-        Action::tail_call(TepidHit::new())
+        self.decide(ctx, "in goal", TepidHit::new())
+    }
+}
+
+impl Defense {
+    /// Hand off to `behavior`, and log why, as a plain line for humans and as a
+    /// `decision` record for tests.
+    fn decide(
+        &self,
+        ctx: &mut Context<'_>,
+        reason: &str,
+        behavior: impl Behavior + 'static,
+    ) -> Action {
+        ctx.eeg.log(self.name(), reason);
+        ctx.eeg.log_value(self.name(), "decision", behavior.name());
+        Action::tail_call(behavior)
     }
 }
 
@@ -181,15 +276,89 @@ mod integration_tests {
     use crate::{
         behavior::defense::{Defense, HitToOwnCorner},
         eeg::Event,
-        integration_tests::{TestRunner, TestScenario},
+        integration_tests::{LogMatcher, TestRunner, TestScenario},
         strategy::SOCCAR_GOAL_BLUE,
     };
     use brain_test_data::recordings;
     use common::{prelude::*, rl};
     use nalgebra::{Point2, Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
+
+    /// Plays where we shouldn't challenge, but shouldn't run home either. Each
+    /// case is the decision `Defense` should log and the event the behavior it
+    /// picks should raise.
+    #[test]
+    fn hold_back_decisions() {
+        tags!(Defense, Wall);
+        let cases = vec![
+            (
+                "ContainWallCarry",
+                Event::ContainWallCarry,
+                TestScenario {
+                    ball_loc: Point3::new(3980.0, 500.0, 600.0),
+                    ball_vel: Vector3::new(0.0, -900.0, 0.0),
+                    car_loc: Point3::new(1500.0, -3000.0, 17.01),
+                    car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                    enemy_loc: Point3::new(4080.0, 700.0, 550.0),
+                    enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, PI / 2.0),
+                    enemy_vel: Vector3::new(0.0, -900.0, 0.0),
+                    ..Default::default()
+                },
+            ),
+            (
+                "ContainSlowPlay",
+                Event::ContainSlowPlay,
+                TestScenario {
+                    ball_loc: Point3::new(0.0, 0.0, 93.15),
+                    ball_vel: Vector3::new(0.0, -200.0, 0.0),
+                    car_loc: Point3::new(0.0, -3000.0, 17.01),
+                    car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                    enemy_loc: Point3::new(0.0, -200.0, 17.01),
+                    enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                    enemy_vel: Vector3::new(0.0, -200.0, 0.0),
+                    ..Default::default()
+                },
+            ),
+            (
+                "Shadow",
+                Event::Shadow,
+                TestScenario {
+                    ball_loc: Point3::new(1000.0, 1500.0, 93.15),
+                    ball_vel: Vector3::new(0.0, -1000.0, 0.0),
+                    car_loc: Point3::new(0.0, -4000.0, 17.01),
+                    car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                    enemy_loc: Point3::new(1000.0, 1800.0, 17.01),
+                    enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                    enemy_vel: Vector3::new(0.0, -1000.0, 0.0),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        for (decision, event, scenario) in cases {
+            println!("expecting {}", decision);
+            let test = TestRunner::new()
+                .scenario(scenario)
+                .behavior(Defense::new())
+                .run_for_millis(1000);
+
+            test.assert_logged(
+                LogMatcher::new()
+                    .behavior("Defense")
+                    .key("decision")
+                    .value(decision),
+            );
+            test.examine_events(move |events| {
+                assert!(events.contains(&event));
+                assert!(!events.contains(&Event::PanicDefense));
+            });
+            assert!(!test.enemy_has_scored());
+        }
+    }
 
     #[test]
     fn coming_in_hot_swat_away() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1004.2267, -1863.0571, 93.15),
@@ -210,6 +379,7 @@ mod integration_tests {
 
     #[test]
     fn bouncing_save() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3143.9788, -241.96017, 1023.1816),
@@ -227,6 +397,7 @@ mod integration_tests {
 
     #[test]
     fn redirect_away_from_goal() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2667.985, 779.3049, 186.92154),
@@ -252,6 +423,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn last_second_save() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1150.811, -1606.0569, 102.36157),
@@ -271,6 +443,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn slow_bouncer() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2849.355, -2856.8281, 1293.4608),
@@ -290,6 +463,7 @@ mod integration_tests {
 
     #[test]
     fn falling_save_from_the_side() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2353.9868, -5024.7144, 236.38712),
@@ -310,6 +484,7 @@ mod integration_tests {
 
     #[test]
     fn retreating_push_to_corner() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(436.92395, 1428.1085, 93.15),
@@ -337,6 +512,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn retreating_push_to_corner_from_awkward_side() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1948.3385, 1729.5826, 97.89405),
@@ -362,6 +538,7 @@ mod integration_tests {
 
     #[test]
     fn retreating_push_to_corner_from_awkward_angle() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2365.654, -86.64402, 114.0818),
@@ -383,6 +560,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "The great bankruptcy of 2018")]
     fn push_from_corner_to_corner() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1620.9868, -4204.8145, 93.14),
@@ -406,6 +584,7 @@ mod integration_tests {
 
     #[test]
     fn push_from_corner_to_corner_2() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2517.809, -4768.475, 93.13),
@@ -432,6 +611,7 @@ mod integration_tests {
 
     #[test]
     fn same_side_corner_push() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2545.9438, -4174.64, 318.26862),
@@ -459,6 +639,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "I think I need more specific logic for this")]
     fn slow_rolling_save() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1455.9731, -4179.0796, 93.15),
@@ -479,6 +660,7 @@ mod integration_tests {
 
     #[test]
     fn slow_retreating_save() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1446.3031, -2056.4917, 213.57251),
@@ -500,6 +682,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "it's broke, because BounceShot::rough_shooting_spot is getting too complex")]
     fn fast_retreating_save() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(63.619453, -336.2556, 93.03),
@@ -520,6 +703,7 @@ mod integration_tests {
 
     #[test]
     fn jump_save_from_inside_goal() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::JUMP_SAVE_FROM_INSIDE_GOAL, 106.0)
             .starting_boost(0.0)
@@ -531,6 +715,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "The great bankruptcy of 2018")]
     fn retreat_then_save() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2503.1099, -3172.46, 92.65),
@@ -552,6 +737,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "The great bankruptcy of 2018")]
     fn clear_around_goal_wall() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::CLEAR_AROUND_GOAL_WALL, 327.0)
             .starting_boost(100.0)
@@ -564,11 +750,42 @@ mod integration_tests {
         assert!(!test.enemy_has_scored());
     }
 
+    /// The enemy will get there a bit first, but from the side, so their touch
+    /// isn't a shot. Don't run home; go for the ball.
+    #[test]
+    fn contested_midfield_goes_for_the_ball() {
+        tags!(Defense);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, -500.0, 93.15),
+                ball_vel: Vector3::new(0.0, -300.0, 0.0),
+                car_loc: Point3::new(0.0, -3000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI / 2.0, 0.0),
+                car_vel: Vector3::new(0.0, 800.0, 0.0),
+                enemy_loc: Point3::new(2000.0, -600.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, PI, 0.0),
+                enemy_vel: Vector3::new(-1200.0, 0.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(Defense::new())
+            .run_for_millis(500);
+
+        test.examine_events(|events| {
+            assert!(events.contains(&Event::Defense));
+            assert!(
+                events.contains(&Event::TepidHitTowardEnemyGoal)
+                    || events.contains(&Event::TepidHitBlockAngleToGoal)
+                    || events.contains(&Event::TepidHitAwayFromOwnGoal),
+            );
+        });
+    }
+
     /// This guards against a behavior where even a tiny touch by the enemy
     /// triggers SameBallTrajectory and causes us to turn around and retreat
     /// back to goal.
     #[test]
     fn defensive_confidence() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::DEFENSIVE_CONFIDENCE, 24.0)
             .starting_boost(65.0)
@@ -581,6 +798,7 @@ mod integration_tests {
 
     #[test]
     fn do_not_own_goal() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2972.65, -4341.88, 1418.28),
@@ -599,6 +817,7 @@ mod integration_tests {
 
     #[test]
     fn low_boost_block_goal() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::BLOCK_GOAL_WITH_NO_BOOST, 61.5)
             .starting_boost(0.0)
@@ -611,6 +830,7 @@ mod integration_tests {
 
     #[test]
     fn inconvenient_angle_hit_to_the_side() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::INCONVENIENT_ANGLE_HIT_TO_THE_SIDE, 419.5)
             .starting_boost(0.0)
@@ -624,6 +844,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "not working")]
     fn wide_shots_are_not_safe() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::WIDE_SHOTS_ARE_NOT_SAFE, 301.0)
             .starting_boost(12.0)
@@ -639,6 +860,7 @@ mod integration_tests {
 
     #[test]
     fn falling_in_front_of_far_corner() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(882.9138, -5002.2944, 608.2664),
@@ -661,6 +883,7 @@ mod integration_tests {
 
     #[test]
     fn rolling_quickly() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2792.5564, 2459.176, 94.02834),
@@ -683,6 +906,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn rolling_around_corner_into_box() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3042.6016, -4141.044, 180.57321),
@@ -700,6 +924,7 @@ mod integration_tests {
 
     #[test]
     fn low_bouncing_directly_ahead() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-916.57043, -5028.2397, 449.42386),
@@ -721,6 +946,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn high_loft_in_front_of_goal() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2285.6035, -5024.131, 438.6606),
@@ -738,6 +964,7 @@ mod integration_tests {
 
     #[test]
     fn loft_in_front_of_goal_from_the_side() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2288.2634, -4688.248, 93.15),
@@ -761,6 +988,7 @@ mod integration_tests {
 
     #[test]
     fn prepare_for_shot() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::PREPARE_FOR_SHOT, 221.0)
             .starting_boost(50.0)
@@ -772,6 +1000,7 @@ mod integration_tests {
 
     #[test]
     fn dont_spin_around_in_goal() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::DONT_SPIN_AROUND_IN_GOAL, 259.0)
             .starting_boost(0.0)
@@ -788,6 +1017,7 @@ mod integration_tests {
 
     #[test]
     fn turn_around_and_clear() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2666.5999, -5017.36, 243.87),
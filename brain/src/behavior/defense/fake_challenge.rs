@@ -0,0 +1,113 @@
+use crate::{
+    behavior::{defense::Shadow, movement::drive_towards, offense::TepidHit},
+    eeg::Event,
+    strategy::{Action, Behavior, Context, Scenario},
+};
+use common::prelude::*;
+use nameof::name_of_type;
+
+/// When the enemy will get to the ball a little before us, drive at it anyway,
+/// as if we're going for it. Someone who thinks they're about to be challenged
+/// tends to rush their touch. If it turns out their touch really would beat
+/// ours, peel off and shadow instead of diving in.
+pub struct FakeChallenge;
+
+impl FakeChallenge {
+    /// Commit for real if we'll beat their touch by this much.
+    const COMMIT_MARGIN: f32 = 0.25;
+    /// Keep up the act until we're this close to the ball.
+    const BAIL_DIST: f32 = 1200.0;
+    /// After bailing, how far to stay goal-side of the ball. Closer than a
+    /// normal shadow, to keep the pressure on.
+    const SHADOW_DISTANCE: f32 = 1200.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns `true` if the enemy is ahead in the race to the ball, but not by
+    /// so much that charging at them is a waste of time.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        let deficit = -ctx.scenario.possession();
+        deficit >= Scenario::POSSESSION_CONTESTABLE && deficit < Shadow::MIN_DEFICIT
+    }
+}
+
+impl Behavior for FakeChallenge {
+    fn name(&self) -> &str {
+        name_of_type!(FakeChallenge)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::FakeChallenge);
+
+        let me = ctx.me();
+        if !me.OnGround {
+            ctx.eeg.log(self.name(), "not on ground");
+            return Action::Abort;
+        }
+
+        let me_intercept = some_or_else!(ctx.scenario.me_intercept(), {
+            ctx.eeg.log(self.name(), "no intercept");
+            return Action::Abort;
+        });
+        let me_time = me_intercept.time;
+        let target_loc = me_intercept.ball_loc.to_2d();
+        let enemy_time = ctx.scenario.enemy_touch_time();
+        let ball_dist = (target_loc - me.Physics.loc_2d()).norm();
+
+        ctx.eeg.print_time("me_touch_time", me_time);
+        ctx.eeg.print_time("enemy_touch_time", enemy_time);
+
+        match decide(me_time, enemy_time, ball_dist) {
+            Decision::Bluff => Action::Yield(drive_towards(ctx, target_loc)),
+            Decision::Commit => {
+                ctx.eeg
+                    .log(self.name(), "we'll get there first; committing");
+                Action::tail_call(TepidHit::new())
+            }
+            Decision::Shadow => {
+                ctx.eeg.log(self.name(), "they'd beat us; backing off");
+                Action::tail_call(Shadow::new().distance(Self::SHADOW_DISTANCE))
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Decision {
+    /// Keep driving at the ball.
+    Bluff,
+    Commit,
+    Shadow,
+}
+
+fn decide(me_time: f32, enemy_time: f32, ball_dist: f32) -> Decision {
+    if me_time < enemy_time - FakeChallenge::COMMIT_MARGIN {
+        Decision::Commit
+    } else if ball_dist >= FakeChallenge::BAIL_DIST {
+        Decision::Bluff
+    } else if enemy_time < me_time {
+        Decision::Shadow
+    } else {
+        // Too close to call, and too close to back out gracefully.
+        Decision::Commit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::defense::fake_challenge::{decide, Decision};
+
+    #[test]
+    fn bluff_then_back_off() {
+        assert_eq!(decide(1.5, 1.0, 3000.0), Decision::Bluff);
+        assert_eq!(decide(0.5, 0.2, 1000.0), Decision::Shadow);
+    }
+
+    #[test]
+    fn commit_when_they_slow_down() {
+        assert_eq!(decide(1.0, 1.5, 3000.0), Decision::Commit);
+        assert_eq!(decide(0.5, 0.6, 1000.0), Decision::Commit);
+    }
+}
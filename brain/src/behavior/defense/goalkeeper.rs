@@ -0,0 +1,123 @@
+use crate::{
+    behavior::{defense::TimedJumpSave, movement::drive_towards},
+    eeg::{Drawable, Event},
+    helpers::drive::hold_position,
+    strategy::{Action, Behavior, Context, Goal},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// Keep goal while a teammate plays the ball.
+///
+/// Instead of sitting in the middle of the goal, stand on the line from where
+/// the shot will come from, shaded towards the near post, since that's where
+/// shots beat a keeper. If a shot is coming in over our head, hand off to
+/// `TimedJumpSave`.
+pub struct Goalkeeper;
+
+impl Goalkeeper {
+    /// How far to shade from the shot line towards the near post, as a
+    /// fraction of the distance between them.
+    const NEAR_POST_BIAS: f32 = 0.3;
+    /// Stand this far in front of the goal line.
+    const STANDOFF: f32 = 150.0;
+    /// Stop once we're this close to the spot.
+    const ARRIVE_DIST: f32 = 100.0;
+
+    pub fn new() -> Self {
+        Goalkeeper
+    }
+
+    /// Where the next shot will come from: where they'll touch the ball, or
+    /// failing that, where it is now.
+    fn shot_origin(ctx: &Context<'_>) -> Point2<f32> {
+        match ctx.scenario.enemy_intercept() {
+            Some((_, intercept)) => intercept.ball_loc.to_2d(),
+            None => ctx.packet.GameBall.Physics.loc_2d(),
+        }
+    }
+}
+
+impl Behavior for Goalkeeper {
+    fn name(&self) -> &str {
+        name_of_type!(Goalkeeper)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::Goalkeeper);
+
+        if ctx.scenario.team().may_commit() {
+            ctx.eeg.log(self.name(), "the ball is ours to play");
+            return Action::Return;
+        }
+
+        if TimedJumpSave::applicable(ctx) {
+            ctx.eeg.log(self.name(), "high shot");
+            return Action::tail_call(TimedJumpSave::new());
+        }
+
+        let shot_origin = Self::shot_origin(ctx);
+        let spot = hold_spot(ctx.game.own_goal(), shot_origin);
+        ctx.eeg.draw(Drawable::Crosshair(spot));
+
+        let me = ctx.me();
+        if (spot - me.Physics.loc_2d()).norm() >= Self::ARRIVE_DIST {
+            return Action::Yield(drive_towards(ctx, spot));
+        }
+
+        // Hold the spot, facing the shot.
+        Action::Yield(hold_position(me, shot_origin))
+    }
+}
+
+/// Where to stand in `goal` against a shot from `shot_origin`.
+fn hold_spot(goal: &Goal, shot_origin: Point2<f32>) -> Point2<f32> {
+    let normal = goal.normal_2d.into_inner();
+
+    // Follow the line from the ball to the middle of the goal until it's
+    // `STANDOFF` in front of the goal line.
+    let depth = (shot_origin - goal.center_2d).dot(&normal);
+    let on_line = if depth > Goalkeeper::STANDOFF {
+        shot_origin + (goal.center_2d - shot_origin) * (1.0 - Goalkeeper::STANDOFF / depth)
+    } else {
+        Point2::new(shot_origin.x, goal.center_2d.y) + normal * Goalkeeper::STANDOFF
+    };
+
+    // Then shade towards the near post.
+    let near_post = goal.near_post(shot_origin);
+    let x = on_line.x + (near_post.x - on_line.x) * Goalkeeper::NEAR_POST_BIAS;
+    let x = x
+        .max(goal.center_2d.x - goal.max_x)
+        .min(goal.center_2d.x + goal.max_x);
+    Point2::new(x, on_line.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::defense::goalkeeper::hold_spot, strategy::SOCCAR_GOAL_BLUE};
+    use nalgebra::Point2;
+
+    #[test]
+    fn straight_on_stays_central() {
+        let goal = &*SOCCAR_GOAL_BLUE;
+        let spot = hold_spot(goal, Point2::new(0.0, 0.0));
+        assert!(spot.x.abs() < 1.0);
+        assert!((spot.y - (goal.center_2d.y + 150.0)).abs() < 1.0);
+    }
+
+    #[test]
+    fn angled_shot_shades_to_near_post() {
+        let goal = &*SOCCAR_GOAL_BLUE;
+        let shot_origin = Point2::new(2500.0, -3000.0);
+        let spot = hold_spot(goal, shot_origin);
+        // The plain shot line would put us only a little off center.
+        let on_line_x = shot_origin.x * 150.0 / (shot_origin.y - goal.center_2d.y);
+        assert!(spot.x > on_line_x + 100.0);
+        assert!(spot.x <= goal.max_x);
+        assert!((spot.y - (goal.center_2d.y + 150.0)).abs() < 1.0);
+
+        let mirrored = hold_spot(goal, Point2::new(-2500.0, -3000.0));
+        assert!((mirrored.x + spot.x).abs() < 1.0);
+    }
+}
@@ -6,19 +6,27 @@ use crate::{
         strike::{GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
     },
     eeg::{color, Drawable, Event},
+    helpers::line_of_sight::Corridor,
     routing::{behavior::FollowRoute, plan::GroundIntercept},
     strategy::{Action, Behavior, Context, Priority},
     utils::{Wall, WallRayCalculator},
 };
 use common::prelude::*;
-use nalgebra::Rotation2;
+use nalgebra::{Point2, Rotation2};
 use nameof::name_of_type;
-use std::f32::consts::PI;
+use std::{f32::consts::PI, ptr};
 
 pub struct HitToOwnCorner;
 
 impl HitToOwnCorner {
     pub const MAX_BALL_Z: f32 = GroundedHitTarget::MAX_BALL_Z;
+    /// Aim a pass at where a teammate will be this many seconds from now.
+    const PASS_LEAD: f32 = 1.0;
+    /// A pass must be within this angle of the way we're already hitting.
+    const PASS_MAX_ANGLE: f32 = PI / 3.0;
+    /// A pass must go at least this much further from our goal than the ball
+    /// already is.
+    const PASS_MIN_UPFIELD: f32 = 1000.0;
 
     pub fn new() -> Self {
         Self
@@ -30,7 +38,7 @@ impl Behavior for HitToOwnCorner {
         name_of_type!(HitToOwnCorner)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.track(Event::HitToOwnCorner);
 
         let skid_recover_loc = ctx.scenario.ball_prediction().at_time_or_last(0.1).loc;
@@ -52,6 +60,15 @@ impl Behavior for HitToOwnCorner {
 
 impl HitToOwnCorner {
     fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        // A clear that finds a teammate is a pass. Those are better than any
+        // corner, but only exist in team modes.
+        if let Some(pass_loc) = Self::pass(ctx) {
+            ctx.eeg.track(Event::ClearToTeammate);
+            ctx.eeg
+                .draw(Drawable::print("clear to teammate", color::GREEN));
+            return Ok(Self::target(ctx, pass_loc));
+        }
+
         let avoid = ctx.game.own_goal().center_2d;
 
         let me_loc = ctx.car.Physics.loc_2d();
@@ -63,7 +80,24 @@ impl HitToOwnCorner {
         let rtl_dir = Rotation2::new(-PI / 6.0) * me_to_ball;
         let rtl = WallRayCalculator::calculate(ball_loc, ball_loc + rtl_dir);
 
-        let result = if (avoid - ltr).norm() > (avoid - rtl).norm() {
+        // Clear away from our goal, unless an enemy is in the way and the
+        // other side is open.
+        let (game, intercept_time) = (ctx.game, ctx.intercept_time);
+        let blocked = |target| {
+            Corridor::new(ball_loc, target)
+                .start_time(intercept_time)
+                .first_blocker(game.cars(game.enemy_team))
+                .is_some()
+        };
+        let mut prefer_ltr = (avoid - ltr).norm() > (avoid - rtl).norm();
+        let (preferred, other) = if prefer_ltr { (ltr, rtl) } else { (rtl, ltr) };
+        if blocked(preferred) && !blocked(other) {
+            ctx.eeg
+                .log(name_of_type!(HitToOwnCorner), "clearing around an enemy");
+            prefer_ltr = !prefer_ltr;
+        }
+
+        let result = if prefer_ltr {
             ctx.eeg.track(Event::PushFromLeftToRight);
             ctx.eeg
                 .draw(Drawable::print("push from left to right", color::GREEN));
@@ -81,26 +115,64 @@ impl HitToOwnCorner {
                     .log(name_of_type!(HitToOwnCorner), "avoiding the own goal");
                 Err(())
             }
-            _ => {
-                let ball = ctx
-                    .scenario
-                    .ball_prediction()
-                    .at_time(ctx.intercept_time)
-                    .unwrap();
-                let dont_dodge = RetreatingSave::safer_not_to_dodge(
-                    ctx.game,
-                    ctx.scenario.ball_prediction().start(),
-                    &ctx.car.into(),
-                    ball,
-                );
-                Ok(GroundedHitTarget::new(
-                    ctx.intercept_time,
-                    GroundedHitTargetAdjust::RoughAim,
-                    result,
-                )
-                .jump(!dont_dodge)
-                .dodge(!dont_dodge))
-            }
+            _ => Ok(Self::target(ctx, result)),
         }
     }
+
+    fn target(ctx: &mut GroundedHitAimContext<'_, '_>, aim_loc: Point2<f32>) -> GroundedHitTarget {
+        let ball = ctx
+            .scenario
+            .ball_prediction()
+            .at_time(ctx.intercept_time)
+            .unwrap();
+        let dont_dodge = RetreatingSave::safer_not_to_dodge(
+            ctx.game,
+            ctx.scenario.ball_prediction().start(),
+            &ctx.car.into(),
+            ball,
+        );
+        GroundedHitTarget::new(
+            ctx.intercept_time,
+            GroundedHitTargetAdjust::RoughAim,
+            aim_loc,
+        )
+        .jump(!dont_dodge)
+        .dodge(!dont_dodge)
+    }
+
+    /// Returns where to aim a pass to a teammate, if there's one worth making.
+    ///
+    /// We lead each teammate by where they're driving, since they're usually
+    /// rotating somewhere. The pass has to go upfield, be close to the way
+    /// we're already hitting, and have no enemy in the way. Of those, take
+    /// the one that needs the least change of direction.
+    fn pass(ctx: &mut GroundedHitAimContext<'_, '_>) -> Option<Point2<f32>> {
+        let goal_loc = ctx.game.own_goal().center_2d;
+        let ball_loc = ctx.intercept_ball_loc.to_2d();
+        let hit_dir = ball_loc - ctx.car.Physics.loc_2d();
+        let (game, car, intercept_time) = (ctx.game, ctx.car, ctx.intercept_time);
+
+        game.cars(game.team)
+            .filter(|mate| !ptr::eq(*mate, car) && !mate.Demolished)
+            .map(|mate| {
+                let loc = mate.Physics.loc_2d() + mate.Physics.vel_2d() * Self::PASS_LEAD;
+                Point2::new(
+                    loc.x.max(-game.field_max_x()).min(game.field_max_x()),
+                    loc.y.max(-game.field_max_y()).min(game.field_max_y()),
+                )
+            })
+            .filter(|&loc| {
+                (loc - goal_loc).norm() >= (ball_loc - goal_loc).norm() + Self::PASS_MIN_UPFIELD
+            })
+            .map(|loc| (loc, hit_dir.angle_to(&(loc - ball_loc)).abs()))
+            .filter(|&(_, angle)| angle < Self::PASS_MAX_ANGLE)
+            .filter(|&(loc, _)| {
+                Corridor::new(ball_loc, loc)
+                    .start_time(intercept_time)
+                    .first_blocker(game.cars(game.enemy_team))
+                    .is_none()
+            })
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(loc, _)| loc)
+    }
 }
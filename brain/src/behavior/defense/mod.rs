@@ -1,15 +1,33 @@
 pub use self::{
+    avoid_own_goal::AvoidOwnGoal,
+    backwall_clear::BackwallClear,
+    contain_slow_play::ContainSlowPlay,
+    contain_wall_carry::ContainWallCarry,
     defense::{defensive_hit, Defense},
+    fake_challenge::FakeChallenge,
+    goalkeeper::Goalkeeper,
     hit_to_own_corner::HitToOwnCorner,
     panic_defense::PanicDefense,
     push_to_own_corner::PushToOwnCorner,
     retreat::Retreat,
+    shadow::Shadow,
+    support::Support,
+    timed_jump_save::TimedJumpSave,
 };
 
+mod avoid_own_goal;
+mod backwall_clear;
+mod contain_slow_play;
+mod contain_wall_carry;
 #[allow(clippy::module_inception)]
 mod defense;
+mod fake_challenge;
+mod goalkeeper;
 mod hit_to_own_corner;
 mod panic_defense;
 mod push_to_own_corner;
 mod retreat;
 mod retreating_save;
+mod shadow;
+mod support;
+mod timed_jump_save;
@@ -22,6 +22,10 @@ enum Phase {
     Start,
     Rush {
         aim_hint: Point2<f32>,
+        blitz_loc: Point2<f32>,
+        /// If set, we're still on the way to the back post approach, and will
+        /// turn towards `blitz_loc` after we pass it.
+        waypoint: Option<Point2<f32>>,
         child: BlitzToLocation,
     },
     Turn {
@@ -33,6 +37,9 @@ enum Phase {
 }
 
 impl PanicDefense {
+    /// How far upfield of the back post to start the final approach.
+    const APPROACH_UPFIELD: f32 = 1500.0;
+
     pub fn new() -> Self {
         Self {
             use_boost: true,
@@ -47,10 +54,10 @@ impl Behavior for PanicDefense {
         name_of_type!(PanicDefense)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.track(Event::PanicDefense);
 
-        return_some!(self.same_ball_trajectory.execute_old(ctx));
+        return_some!(self.same_ball_trajectory.execute(ctx));
 
         if !GetToFlatGround::on_flat_ground(ctx.me()) {
             ctx.eeg.log(self.name(), "not on flat ground");
@@ -68,7 +75,7 @@ impl Behavior for PanicDefense {
             Phase::Start => unreachable!(),
             Phase::Rush { ref mut child, .. } => {
                 ctx.eeg.draw(Drawable::print("Rush", color::GREEN));
-                child.execute_old(ctx)
+                child.execute(ctx)
             }
             Phase::Turn { aim_hint, .. } => {
                 ctx.eeg.draw(Drawable::print("Turn", color::GREEN));
@@ -94,16 +101,8 @@ impl PanicDefense {
         // If we're already very close, we don't have enough time to steer.
         let future_loc = me_loc + ctx.me().Physics.vel_2d() * 1.0;
         let already_close = Self::finished_panicking(goal, future_loc, me_vel);
-        let shallow = Self::shallow_angle_approach(ctx);
-
-        if shallow {
-            // If the approach is shallow, just drive to the near post and we're already
-            // covering the entire goal.
-            Point2::new(
-                800.0 * me_loc.x.signum(),
-                goal.center_2d.y - 50.0 * goal.center_2d.y.signum(),
-            )
-        } else if already_close {
+
+        if already_close {
             // We don't have time to steer, just pick the closest corner.
             let left_post = Point2::new(-800.0, goal.center_2d.y);
             let right_post = Point2::new(800.0, goal.center_2d.y);
@@ -115,8 +114,34 @@ impl PanicDefense {
                 right_post
             }
         } else {
-            Point2::new(800.0 * -aim_loc.x.signum(), goal.center_2d.y)
+            // Rotate to the back post. The near post and the center both leave the far
+            // side of the goal open, and block our view of the play. If the approach is
+            // shallow, we'll slide across the goal on the way and cover it anyway.
+            let far_post = goal.far_post(aim_loc);
+            Point2::new(800.0 * far_post.x.signum(), far_post.y)
+        }
+    }
+
+    /// If driving straight to the back post would take us across the goal mouth,
+    /// return a point to swing through first so we come in from behind the back
+    /// post instead.
+    fn back_post_waypoint(
+        ctx: &mut Context<'_>,
+        aim_loc: Point2<f32>,
+        blitz_loc: Point2<f32>,
+    ) -> Option<Point2<f32>> {
+        let goal = ctx.game.own_goal();
+        let me_loc = ctx.me().Physics.loc_2d();
+
+        if goal.is_y_within_range(me_loc.y, ..Self::APPROACH_UPFIELD + 500.0) {
+            // Too close to the goal already; just get there.
+            return None;
+        }
+        if me_loc.x.signum() == blitz_loc.x.signum() {
+            // We're already on the back post side.
+            return None;
         }
+        Some(goal.back_post_approach(aim_loc, Self::APPROACH_UPFIELD))
     }
 
     fn shallow_angle_approach(ctx: &mut Context<'_>) -> bool {
@@ -164,13 +189,40 @@ impl PanicDefense {
 
             let aim_hint = calc_aim_hint(ctx);
             let blitz_loc = Self::blitz_loc(ctx, aim_hint);
+            let waypoint = Self::back_post_waypoint(ctx, aim_hint, blitz_loc);
+            if let Some(waypoint) = waypoint {
+                ctx.eeg
+                    .draw(Drawable::ghost_car_ground(waypoint, me.Physics.rot()));
+            }
             return Some(Phase::Rush {
                 // Powerslide towards the post opposite the one we're driving to.
                 aim_hint: Point2::new(blitz_loc.x.signum() * -2000.0, own_goal.center_2d.y),
-                child: BlitzToLocation::new(blitz_loc),
+                blitz_loc,
+                waypoint,
+                child: BlitzToLocation::new(waypoint.unwrap_or(blitz_loc)),
             });
         }
 
+        if let Phase::Rush {
+            aim_hint,
+            blitz_loc,
+            waypoint: Some(waypoint),
+            ..
+        } = self.phase
+        {
+            let passed = own_goal.is_y_within_range(me.Physics.loc().y, ..Self::APPROACH_UPFIELD)
+                || (me.Physics.loc_2d() - waypoint).norm() < 500.0;
+            if passed {
+                ctx.eeg.log(self.name(), "rounding the back post");
+                return Some(Phase::Rush {
+                    aim_hint,
+                    blitz_loc,
+                    waypoint: None,
+                    child: BlitzToLocation::new(blitz_loc),
+                });
+            }
+        }
+
         if let Phase::Turn {
             start_time,
             target_yaw,
@@ -250,6 +302,7 @@ mod integration_tests {
                 but this test is worth keeping around in case I need to tweak."
     )]
     fn panic_defense() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 car_loc: Point3::new(500.0, -1000.0, 17.01),
@@ -1,11 +1,13 @@
 use crate::{
     behavior::{
-        defense::{retreating_save::RetreatingSave, HitToOwnCorner},
+        defense::{retreating_save::RetreatingSave, BackwallClear, HitToOwnCorner},
         higher_order::TryChoose,
-        strike::GroundedHit,
     },
     eeg::{color, Drawable},
-    helpers::intercept::naive_ground_intercept_2,
+    helpers::{
+        intercept::naive_ground_intercept_2,
+        intercept_predicate::{BelowGroundedReach, BelowZ, InsideField, InterceptPredicate},
+    },
     strategy::{Action, Behavior, Context, Goal, Priority, Scenario},
     utils::geometry::ExtendF32,
 };
@@ -46,7 +48,14 @@ impl Behavior for PushToOwnCorner {
         name_of_type!(PushToOwnCorner)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        // A ball up our back wall is too high for any of the ground hits below,
+        // but we can still chase it up there.
+        if BackwallClear::applicable(ctx) {
+            ctx.eeg.log(self.name(), "clearing off the back wall");
+            return Action::tail_call(BackwallClear::new());
+        }
+
         let impending_concede_soon = ctx
             .scenario
             .impending_concede()
@@ -60,7 +69,7 @@ impl Behavior for PushToOwnCorner {
 
         let me_intercept =
             naive_ground_intercept_2(&ctx.me().into(), ctx.scenario.ball_prediction(), |ball| {
-                ball.loc.z < Self::MAX_BALL_Z
+                BelowZ(Self::MAX_BALL_Z).matches(ball)
             });
 
         // If the ball is moving quickly towards our half, assume the enemy can't change
@@ -80,7 +89,7 @@ impl Behavior for PushToOwnCorner {
             .enemy_cars()
             .filter_map(|enemy| {
                 naive_ground_intercept_2(&enemy.into(), ctx.scenario.ball_prediction(), |ball| {
-                    ball.loc.z < GroundedHit::MAX_BALL_Z
+                    BelowGroundedReach.and(InsideField).matches(ball)
                         && Self::shot_angle(ball.loc, enemy.Physics.loc(), own_goal.center_2d)
                             < danger_angle
                         && Self::goal_angle(ball.loc, own_goal) < PI / 3.0
@@ -181,6 +190,7 @@ mod integration_tests {
 
     #[test]
     fn let_the_ball_enter_our_corner() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::LET_THE_BALL_ENTER_OUR_CORNER, 133.0)
             .soccar()
@@ -201,6 +211,7 @@ mod integration_tests {
 
     #[test]
     fn save_ball_rolling_towards_box() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::SAVE_BALL_ROLLING_TOWARDS_BOX, 160.0)
             .soccar()
@@ -2,9 +2,11 @@ use crate::{
     behavior::{
         defense::{retreating_save::RetreatingSave, PanicDefense, PushToOwnCorner},
         higher_order::TryChoose,
+        movement::HalfFlip,
         offense::TepidHit,
     },
     eeg::Event,
+    routing::{behavior::FollowRoute, models::RoutePlanner, plan::drive_through_goal},
     strategy::{Action, Behavior, Context, Priority},
 };
 use common::prelude::*;
@@ -43,6 +45,19 @@ impl Retreat {
 
         me_dist > ball_dist + 500.0
     }
+
+    /// If we're deep in the corner on the wrong side of the goal, the fastest
+    /// safe way to the far post might be through the goal itself.
+    fn through_goal(ctx: &mut Context<'_>) -> Option<Box<dyn RoutePlanner>> {
+        let goal = ctx.game.own_goal();
+        let me_loc = ctx.me().Physics.loc_2d();
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let target_loc = goal.far_post(ball_loc);
+        let route = drive_through_goal(goal, me_loc, target_loc)?;
+        ctx.eeg
+            .log(name_of_type!(Retreat), "can go through the goal");
+        Some(route)
+    }
 }
 
 impl Behavior for Retreat {
@@ -50,9 +65,20 @@ impl Behavior for Retreat {
         name_of_type!(Retreat)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.track(Event::Retreat);
 
+        // Facing upfield with no speed, turning around the slow way could
+        // cost us the goal. Head for the back post, like `PanicDefense`, not the
+        // middle of the goal.
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let back_post = ctx.game.own_goal().far_post(ball_loc);
+        if HalfFlip::applicable(ctx.me(), back_post) {
+            ctx.eeg
+                .log(self.name(), "half-flipping to face the back post");
+            return Action::tail_call(HalfFlip::new());
+        }
+
         let mut choices = Vec::<Box<dyn Behavior>>::new();
 
         if Self::out_of_position(ctx) {
@@ -61,6 +87,11 @@ impl Behavior for Retreat {
         } else {
             choices.push(Box::new(RetreatingSave::new()));
         }
+        // Only when there's no save or push to make, since it takes us away
+        // from the ball.
+        if let Some(route) = Self::through_goal(ctx) {
+            choices.push(Box::new(FollowRoute::new_boxed(route)));
+        }
         choices.push(Box::new(PanicDefense::new()));
         // We should never get this far, but it's here as a fail-safe:
         choices.push(Box::new(TepidHit::new()));
@@ -81,6 +112,7 @@ mod integration_tests {
 
     #[test]
     fn retreating_hit_to_own_corner() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::RETREATING_HIT_TO_OWN_CORNER, 104.5)
             .starting_boost(74.0)
@@ -95,6 +127,7 @@ mod integration_tests {
 
     #[test]
     fn last_ditch_intercept() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::LAST_DITCH_INTERCEPT, 275.0)
             .starting_boost(0.0)
@@ -115,6 +148,7 @@ mod integration_tests {
 
     #[test]
     fn no_infinite_loop() {
+        tags!(Defense);
         let start_loc = Point3::new(517.51996, -3536.02, 17.01);
         let test = TestRunner::new()
             .scenario(TestScenario {
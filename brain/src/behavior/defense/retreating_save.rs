@@ -106,7 +106,7 @@ impl Behavior for RetreatingSave {
         name_of_type!(RetreatingSave)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if let Err(reason) = Self::applicable(ctx) {
             ctx.eeg.log(self.name(), reason);
             return Action::Abort;
@@ -436,6 +436,7 @@ mod integration_tests {
 
     #[test]
     fn catching_up_to_the_play() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::CATCHING_UP_TO_THE_PLAY, 217.5)
             .starting_boost(15.0)
@@ -457,6 +458,7 @@ mod integration_tests {
 
     #[test]
     fn landing_awkwardly_close_to_the_ball() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::LANDING_AWKWARDLY_CLOSE_TO_THE_BALL, 218.5)
             .starting_boost(15.0)
@@ -478,6 +480,7 @@ mod integration_tests {
 
     #[test]
     fn driving_alongside_rolling_ball() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2184.45, -2221.26, 93.15),
@@ -506,6 +509,7 @@ mod integration_tests {
 
     #[test]
     fn anticipate_shot() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::ANTICIPATE_SHOT, 321.0)
             .starting_boost(0.0)
@@ -527,6 +531,7 @@ mod integration_tests {
 
     #[test]
     fn facing_slightly_away() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(672.2, -2429.41, 93.15),
@@ -556,6 +561,7 @@ mod integration_tests {
 
     #[test]
     fn switch_sides_to_goalside() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1243.13, -730.49, 93.15),
@@ -588,6 +594,7 @@ mod integration_tests {
 
     #[test]
     fn slow_dribble_behind_us() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::SLOW_DRIBBLE_BEHIND_US, 154.0)
             .starting_boost(70.0)
@@ -610,6 +617,7 @@ mod integration_tests {
 
     #[test]
     fn turn_for_bouncing_ball() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::TURN_FOR_BOUNCING_BALL, 30.0)
             .starting_boost(70.0)
@@ -632,6 +640,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "oops, this got broken at some point")]
     fn waiting_awkward_close_to_goal() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::WAITING_AWKWARD_CLOSE_TO_GOAL, 372.0)
             .starting_boost(70.0)
@@ -652,6 +661,7 @@ mod integration_tests {
 
     #[test]
     fn save_falling_ball() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2795.05, 401.83, 1006.6),
@@ -687,6 +697,7 @@ mod integration_tests {
 
     #[test]
     fn retreating_save_patience() {
+        tags!(Defense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::RETREATING_SAVE_PATIENCE, 262.0)
             .starting_boost(60.0)
@@ -708,6 +719,7 @@ mod integration_tests {
 
     #[test]
     fn make_save_after_turning() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2794.06, -4137.35, 162.48999),
@@ -738,6 +750,7 @@ mod integration_tests {
 
     #[test]
     fn make_save_without_turning() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2492.3499, -4248.4, 295.04),
@@ -768,6 +781,7 @@ mod integration_tests {
 
     #[test]
     fn make_bouncing_save() {
+        tags!(Defense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2597.49, 425.9, 1656.75),
@@ -0,0 +1,123 @@
+use crate::{
+    behavior::{defense::Defense, movement::simple_yaw_diff},
+    eeg::{Drawable, Event},
+    helpers::drive::arrive_on_time,
+    strategy::{Action, Behavior, Context, Scenario},
+};
+use common::prelude::*;
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+
+/// When the enemy has the ball, stay goal-side of it at a distance instead of
+/// diving in: match the ball's lateral position, face upfield, and back off as
+/// they come forward. This keeps us between them and the net until they make a
+/// mistake or the ball comes loose.
+pub struct Shadow {
+    distance: f32,
+}
+
+impl Shadow {
+    /// Shadow when the enemy will reach the ball at least this many seconds
+    /// before us.
+    pub const MIN_DEFICIT: f32 = 1.5;
+    /// How far to stay goal-side of the ball, unless told otherwise.
+    const DEFAULT_DISTANCE: f32 = 2000.0;
+    /// Stay at least this many seconds of the ball's travel goal-side of it,
+    /// so a fast break gets more room.
+    const TIME_GAP: f32 = 1.0;
+    /// Never hold closer to our goal line than this.
+    const MIN_GOAL_DIST: f32 = 500.0;
+    /// Stay this far from the side walls.
+    const WALL_MARGIN: f32 = 800.0;
+    /// Within this distance of the spot, stop driving to it and start tracking
+    /// the ball.
+    const TRACK_DIST: f32 = 300.0;
+    /// Aim to be in position this far in the future.
+    const HORIZON: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self {
+            distance: Self::DEFAULT_DISTANCE,
+        }
+    }
+
+    /// How far to stay goal-side of the ball.
+    pub fn distance(mut self, distance: f32) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    /// Where to be while the ball is at `ball_loc`, moving at `ball_vel`.
+    fn shadow_loc(
+        &self,
+        ctx: &mut Context<'_>,
+        ball_loc: Point2<f32>,
+        ball_vel: Vector2<f32>,
+    ) -> Point2<f32> {
+        let goal = ctx.game.own_goal();
+        let max_x = ctx.game.field_max_x() - Self::WALL_MARGIN;
+        let ball_speed = ball_vel.dot(&-goal.normal_2d).max(0.0);
+        let distance = self.distance.max(ball_speed * Self::TIME_GAP);
+        let upfield = (ball_loc - goal.center_2d).dot(&goal.normal_2d);
+        let along = (upfield - distance).max(Self::MIN_GOAL_DIST);
+        let y = (goal.center_2d + goal.normal_2d.into_inner() * along).y;
+        Point2::new(ball_loc.x.max(-max_x).min(max_x), y)
+    }
+}
+
+impl Behavior for Shadow {
+    fn name(&self) -> &str {
+        name_of_type!(Shadow)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::Shadow);
+
+        if ctx.risk.all_out_offense() {
+            ctx.eeg.log(self.name(), "going all in");
+            return Action::Return;
+        }
+        if ctx.scenario.possession() >= -Scenario::POSSESSION_CONTESTABLE {
+            ctx.eeg.log(self.name(), "the ball is up for grabs");
+            return Action::Return;
+        }
+        if !Defense::is_between_ball_and_own_goal(ctx.game, ctx.me(), ctx.scenario) {
+            ctx.eeg.log(self.name(), "got beat");
+            return Action::Return;
+        }
+
+        // Drive to where we'll want to be once we get there, not where we want
+        // to be now.
+        let ball = ctx
+            .scenario
+            .ball_prediction()
+            .at_time_or_last(Self::HORIZON);
+        let (future_ball_loc, future_ball_vel) = (ball.loc.to_2d(), ball.vel.to_2d());
+        let future_loc = self.shadow_loc(ctx, future_ball_loc, future_ball_vel);
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let ball_vel = ctx.packet.GameBall.Physics.vel_2d();
+        let target_loc = self.shadow_loc(ctx, ball_loc, ball_vel);
+        let me = ctx.me();
+        ctx.eeg
+            .draw(Drawable::ghost_car_ground(future_loc, me.Physics.rot()));
+
+        let to_target = target_loc - me.Physics.loc_2d();
+        if to_target.norm() >= Self::TRACK_DIST {
+            return Action::Yield(arrive_on_time(ctx, future_loc, Self::HORIZON));
+        }
+
+        // In position. Face upfield, and move with the ball so we stay the same
+        // distance from it.
+        let upfield = ctx.game.own_goal().normal_2d;
+        let ball_speed = ball_vel.dot(&upfield);
+        let target_speed = ball_speed + to_target.dot(&upfield) * 2.0;
+        let speed = me.Physics.vel_2d().dot(&upfield);
+        let face_loc = me.Physics.loc_2d() + upfield.into_inner() * 1000.0;
+        let steer = simple_yaw_diff(&me.Physics, face_loc) * 2.0;
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: ((target_speed - speed) / 500.0).max(-1.0).min(1.0),
+            Steer: steer.max(-1.0).min(1.0),
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    behavior::movement::drive_towards,
+    eeg::{Drawable, Event},
+    helpers::drive::{arrive_on_time, hold_position},
+    strategy::{Action, Behavior, Context, Goal, Role},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// Back up a teammate who's going for the ball.
+///
+/// Hang back on the line from the ball to our goal, drifting towards the
+/// middle of the field, so we're ready to pick up a loose ball or cover the
+/// net if the first man gets beat.
+pub struct Support;
+
+impl Support {
+    /// How far goal-side of the ball to stay.
+    const DISTANCE: f32 = 2500.0;
+    /// How far to drift from the ball's side of the field towards the middle,
+    /// as a fraction of the way there.
+    const CENTER_BIAS: f32 = 0.5;
+    /// Never hold closer to our goal line than this.
+    const MIN_GOAL_DIST: f32 = 1000.0;
+    /// Within this distance of the spot, stop driving to it and face the ball.
+    const ARRIVE_DIST: f32 = 300.0;
+    /// Aim to be in position this far in the future.
+    const HORIZON: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Support
+    }
+}
+
+impl Behavior for Support {
+    fn name(&self) -> &str {
+        name_of_type!(Support)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::Support);
+
+        if ctx.scenario.team().my_role() != Role::SecondMan {
+            ctx.eeg.log(self.name(), "our role changed");
+            return Action::Return;
+        }
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let spot = support_spot(ctx.game.own_goal(), ball_loc);
+        ctx.eeg.draw(Drawable::Crosshair(spot));
+
+        let me = ctx.me();
+        if (spot - me.Physics.loc_2d()).norm() >= Self::ARRIVE_DIST {
+            return Action::Yield(arrive_on_time(ctx, spot, Self::HORIZON));
+        }
+
+        // Hold the spot, facing the ball.
+        Action::Yield(hold_position(me, ball_loc))
+    }
+}
+
+/// Where to wait in front of `goal` while a teammate plays the ball at
+/// `ball_loc`.
+fn support_spot(goal: &Goal, ball_loc: Point2<f32>) -> Point2<f32> {
+    let normal = goal.normal_2d.into_inner();
+    let upfield = (ball_loc - goal.center_2d).dot(&normal);
+    let along = (upfield - Support::DISTANCE).max(Support::MIN_GOAL_DIST);
+    let y = (goal.center_2d + normal * along).y;
+    let x = ball_loc.x + (goal.center_2d.x - ball_loc.x) * Support::CENTER_BIAS;
+    Point2::new(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::defense::support::support_spot, strategy::SOCCAR_GOAL_BLUE};
+    use nalgebra::Point2;
+
+    #[test]
+    fn hangs_back_towards_the_middle() {
+        let goal = &*SOCCAR_GOAL_BLUE;
+        let spot = support_spot(goal, Point2::new(2000.0, 1000.0));
+        assert!((spot.x - 1000.0).abs() < 1.0);
+        assert!((spot.y - (1000.0 - 2500.0)).abs() < 1.0);
+    }
+
+    #[test]
+    fn stays_out_of_the_net() {
+        let goal = &*SOCCAR_GOAL_BLUE;
+        let spot = support_spot(goal, Point2::new(0.0, goal.center_2d.y + 500.0));
+        assert!((spot.y - (goal.center_2d.y + 1000.0)).abs() < 1.0);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{drive_towards, GetToFlatGround, Yielder},
+    },
+    eeg::{Drawable, Event},
+    helpers::{
+        ball::BallTrajectory,
+        drive::{hold_position, rough_time_drive_to_loc},
+    },
+    strategy::{Action, Behavior, Context, Goal, Mutators, Priority},
+};
+use common::{prelude::*, rl, Coordinate};
+use nalgebra::Point3;
+use nameof::name_of_type;
+use simulate::car_double_jump;
+use vec_box::vec_box;
+
+/// Block a shot that's going in too high to stop from the ground.
+///
+/// Stand where the ball will cross the goal mouth, and time the jump (or fast
+/// double jump) so the car's center is level with the ball's center when it
+/// arrives. That puts the whole body of the car in the ball's path, instead of
+/// just a corner of the roof.
+pub struct TimedJumpSave;
+
+/// When and how to jump to block a shot.
+#[derive(Clone, Debug)]
+pub struct JumpSavePlan {
+    /// Where the car should be when the ball reaches it.
+    pub block_loc: Point3<f32>,
+    /// Seconds from now until the ball reaches `block_loc`.
+    pub block_time: f32,
+    /// Seconds from pressing jump until the car reaches `block_loc.z`.
+    pub jump_time: f32,
+    /// Whether it takes a double jump to get that high.
+    pub double_jump: bool,
+}
+
+impl JumpSavePlan {
+    /// Seconds from now until we need to press jump.
+    pub fn release_time(&self) -> f32 {
+        self.block_time - self.jump_time
+    }
+}
+
+impl TimedJumpSave {
+    /// Block the ball this far in front of the goal line.
+    const BLOCK_DEPTH: f32 = 150.0;
+    /// Balls lower than this we can block without leaving the ground.
+    const MIN_BALL_Z: f32 = 150.0;
+    /// Keep blocking for a bit after the ball should reach us.
+    const FOLLOW_THROUGH_TIME: f32 = 0.25;
+
+    pub fn new() -> Self {
+        TimedJumpSave
+    }
+
+    /// Plan a jump to block the ball where it passes through the plane
+    /// `BLOCK_DEPTH` in front of `goal`, or `None` if it's low enough to block
+    /// from the ground, or too high to reach.
+    pub fn plan(
+        mutators: &Mutators,
+        goal: &Goal,
+        ball_prediction: &BallTrajectory,
+    ) -> Option<JumpSavePlan> {
+        let normal = goal.normal_2d.into_inner();
+        let ball = ball_prediction
+            .iter()
+            .find(|ball| (ball.loc.to_2d() - goal.center_2d).dot(&normal) <= Self::BLOCK_DEPTH)?;
+        if ball.loc.z < Self::MIN_BALL_Z {
+            return None;
+        }
+
+        // Prefer a single jump; it's quicker to recover from.
+        let (jump_time, double_jump) = match mutators.jump_time_to_z(ball.loc.z) {
+            Some(t) => (t, false),
+            None => (mutators.double_jump_time_to_z(ball.loc.z)?, true),
+        };
+        if ball.t < jump_time {
+            return None;
+        }
+
+        // Stand right on the plane, even if the prediction overshoots it a bit.
+        let depth = (ball.loc.to_2d() - goal.center_2d).dot(&normal);
+        let block_loc = ball.loc.to_2d() + normal * (Self::BLOCK_DEPTH - depth);
+        Some(JumpSavePlan {
+            block_loc: block_loc.to_3d(ball.loc.z),
+            block_time: ball.t,
+            jump_time,
+            double_jump,
+        })
+    }
+
+    /// Returns `true` if a shot is going in high, and we can get in front of
+    /// it in time to jump.
+    pub fn applicable(ctx: &Context<'_>) -> bool {
+        Self::feasible_plan(ctx).is_some()
+    }
+
+    fn feasible_plan(ctx: &Context<'_>) -> Option<JumpSavePlan> {
+        ctx.scenario.impending_concede()?;
+        let plan = Self::plan(
+            ctx.game.mutators(),
+            ctx.game.own_goal(),
+            ctx.scenario.ball_prediction(),
+        )?;
+        if rough_time_drive_to_loc(ctx.me(), plan.block_loc.to_2d()) > plan.release_time() {
+            return None;
+        }
+        Some(plan)
+    }
+
+    fn jump(plan: &JumpSavePlan) -> Chain {
+        let jump = common::halfway_house::PlayerInput {
+            Jump: true,
+            ..Default::default()
+        };
+        let hold_time = plan.jump_time + Self::FOLLOW_THROUGH_TIME;
+        if !plan.double_jump {
+            return Chain::new(
+                Priority::Save,
+                vec_box![
+                    Yielder::new(rl::CAR_JUMP_FORCE_TIME, jump),
+                    Yielder::new(
+                        (hold_time - rl::CAR_JUMP_FORCE_TIME).max(0.0),
+                        Default::default(),
+                    ),
+                ],
+            );
+        }
+        Chain::new(
+            Priority::Save,
+            vec_box![
+                Yielder::new(rl::CAR_JUMP_FORCE_TIME, jump),
+                Yielder::new(car_double_jump::RELEASE_TIME, Default::default()),
+                Yielder::new(rl::PHYSICS_DT * 2.0, jump),
+                Yielder::new(
+                    (hold_time - car_double_jump::SECOND_JUMP_TIME).max(0.0),
+                    Default::default(),
+                ),
+            ],
+        )
+    }
+}
+
+impl Behavior for TimedJumpSave {
+    fn name(&self) -> &str {
+        name_of_type!(TimedJumpSave)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Save
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::TimedJumpSave);
+
+        if !GetToFlatGround::on_flat_ground(ctx.me()) {
+            ctx.eeg.log(self.name(), "not on flat ground");
+            return Action::Abort;
+        }
+
+        let plan = some_or_else!(Self::feasible_plan(ctx), {
+            ctx.eeg.log(self.name(), "no shot we can jump for");
+            return Action::Abort;
+        });
+
+        ctx.eeg.draw(Drawable::ghost_ball(plan.block_loc));
+        ctx.eeg.print_time("block_time", plan.block_time);
+        ctx.eeg.print_time("release_time", plan.release_time());
+        ctx.eeg
+            .print_value("block_loc_z", Coordinate(plan.block_loc.z));
+
+        if plan.release_time() <= rl::PHYSICS_DT {
+            ctx.eeg.log(self.name(), "jump");
+            return Action::tail_call(Self::jump(&plan));
+        }
+
+        let block_loc = plan.block_loc.to_2d();
+        let me = ctx.me();
+        if (block_loc - me.Physics.loc_2d()).norm() >= rl::BALL_RADIUS {
+            return Action::Yield(drive_towards(ctx, block_loc));
+        }
+
+        // Settle in place so the jump goes straight up.
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        Action::Yield(hold_position(me, ball_loc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::defense::TimedJumpSave,
+        helpers::ball::BallTrajectory,
+        strategy::{Mutators, SOCCAR_GOAL_BLUE},
+    };
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    /// A ball flying level at height `z`, straight into the blue goal.
+    fn shot(z: f32) -> BallTrajectory {
+        let start = Point3::new(300.0, -3000.0, z);
+        let vel = Vector3::new(0.0, -2000.0, 0.0);
+        BallTrajectory::from_samples((0..240).map(|i| {
+            let t = i as f32 * rl::PHYSICS_DT;
+            (t, start + vel * t, vel)
+        }))
+    }
+
+    #[test]
+    fn low_shots_need_no_jump() {
+        let plan = TimedJumpSave::plan(&Mutators::standard(), &SOCCAR_GOAL_BLUE, &shot(100.0));
+        assert!(plan.is_none());
+    }
+
+    #[test]
+    fn single_jump_timing() {
+        let mutators = Mutators::standard();
+        let plan = TimedJumpSave::plan(&mutators, &SOCCAR_GOAL_BLUE, &shot(200.0)).unwrap();
+        assert!(!plan.double_jump);
+        assert_eq!(plan.jump_time, mutators.jump_time_to_z(200.0).unwrap());
+        assert!((plan.block_loc.x - 300.0).abs() < 1.0);
+        assert!((plan.block_loc.y - (SOCCAR_GOAL_BLUE.center_2d.y + 150.0)).abs() < 1.0);
+        // The ball covers the ~1970 uu to the block plane at 2000 uu/s.
+        assert!((plan.block_time - 0.985).abs() < 0.01);
+        assert!(plan.release_time() > 0.0);
+    }
+
+    #[test]
+    fn double_jump_when_out_of_reach() {
+        let plan =
+            TimedJumpSave::plan(&Mutators::standard(), &SOCCAR_GOAL_BLUE, &shot(350.0)).unwrap();
+        assert!(plan.double_jump);
+        assert!(plan.jump_time > 0.0);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    eeg::{color, Drawable},
-    strategy::{Action, Behavior, Context, Priority},
+    eeg::{color, Drawable, NodeStatus},
+    strategy::{Action, Behavior, Child, Context, Priority},
 };
 use itertools::Itertools;
 use nameof::name_of_type;
@@ -53,7 +53,13 @@ impl Behavior for Chain {
         self.priority
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        for child in &self.children {
+            visit(Child::Behavior(&**child));
+        }
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.draw(Drawable::print(
             self.children
                 .iter()
@@ -69,7 +75,7 @@ impl Behavior for Chain {
         };
         ctx.eeg.draw(Drawable::print(front.blurb(), color::YELLOW));
 
-        match front.execute_old(ctx) {
+        match front.execute(ctx) {
             Action::Yield(x) => Action::Yield(x),
             Action::TailCall(b) => {
                 let front = mem::replace(&mut self.children[0], b);
@@ -78,22 +84,24 @@ impl Behavior for Chain {
                     self.name(),
                     format!("TailCall from {}; becoming {}", front.name(), self.blurb),
                 );
-                self.execute_old(ctx)
+                self.execute(ctx)
             }
             Action::RootCall(x) => Action::RootCall(x),
             Action::Return => {
                 let front = self.children.pop_front().unwrap();
+                ctx.eeg.track_outcome(front.name(), NodeStatus::Succeeded);
                 self.blurb = Self::blurb(self.children.iter());
                 ctx.eeg.log(
                     self.name(),
                     format!("Return from {}; becoming {}", front.name(), self.blurb),
                 );
-                self.execute_old(ctx)
+                self.execute(ctx)
             }
             Action::Abort => {
                 let front = self.children.front().unwrap();
                 ctx.eeg
                     .log(self.name(), format!("Abort from {}", front.name()));
+                ctx.eeg.track_outcome(front.name(), NodeStatus::Aborted);
                 Action::Abort
             }
         }
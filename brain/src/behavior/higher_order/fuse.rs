@@ -1,4 +1,4 @@
-use crate::strategy::{Action, Behavior, Context};
+use crate::strategy::{Action, Behavior, Child, Context};
 use nameof::name_of_type;
 
 /// Run `child` until it returns, then do nothing forever.
@@ -17,7 +17,13 @@ impl Behavior for Fuse {
         name_of_type!(Fuse)
     }
 
-    fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        if let Some(child) = &self.child {
+            visit(Child::Behavior(&**child));
+        }
+    }
+
+    fn execute(&mut self, _ctx: &mut Context<'_>) -> Action {
         // `take()` leaves a None behind, so this can only match `Some` once.
         match self.child.take() {
             Some(b) => Action::TailCall(b),
@@ -14,7 +14,7 @@ impl Behavior for NullBehavior {
         name_of_type!(NullBehavior)
     }
 
-    fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, _ctx: &mut Context<'_>) -> Action {
         Action::Yield(common::halfway_house::PlayerInput::default())
     }
 }
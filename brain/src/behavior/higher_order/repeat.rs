@@ -1,6 +1,6 @@
 use crate::{
     eeg::{color, Drawable},
-    strategy::{Action, Behavior, Context},
+    strategy::{Action, Behavior, Child, Context},
 };
 
 /// Run `behavior` forever
@@ -33,10 +33,14 @@ where
         stringify!(Repeat)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        visit(Child::Behavior(&self.current));
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg
             .draw(Drawable::print(self.current.blurb(), color::YELLOW));
-        match self.current.execute_old(ctx) {
+        match self.current.execute(ctx) {
             Action::Yield(i) => Action::Yield(i),
             Action::TailCall(b) => Action::TailCall(b),
             Action::RootCall(i) => Action::RootCall(i),
@@ -1,6 +1,6 @@
 use crate::{
     eeg::{color, Drawable},
-    strategy::{Action, Behavior, Context, Priority},
+    strategy::{Action, Behavior, Child, Context, Priority},
 };
 
 /// Run `child` while `predicate` holds true.
@@ -41,7 +41,11 @@ where
         self.child.priority()
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        visit(Child::Behavior(&*self.child));
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if !self.predicate.evaluate(ctx) {
             ctx.eeg.log(self.name(), "terminating");
             return Action::Return;
@@ -52,12 +56,12 @@ where
         ctx.eeg
             .draw(Drawable::print(self.child.blurb(), color::YELLOW));
 
-        match self.child.execute_old(ctx) {
+        match self.child.execute(ctx) {
             Action::Yield(i) => Action::Yield(i),
             Action::TailCall(b) => {
                 // The tail-called behavior should not escape the predicate.
                 self.child = b;
-                self.execute_old(ctx)
+                self.execute(ctx)
             }
             Action::RootCall(b) => Action::RootCall(b),
             Action::Return => Action::Return,
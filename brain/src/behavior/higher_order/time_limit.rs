@@ -1,4 +1,4 @@
-use crate::strategy::{Action, Behavior, Context};
+use crate::strategy::{Action, Behavior, Child, Context};
 use nameof::name_of_type;
 
 /// Execute `child` for at most `limit` seconds, then return.
@@ -23,14 +23,18 @@ impl Behavior for TimeLimit {
         name_of_type!(TimeLimit)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        visit(Child::Behavior(&*self.child));
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let now = ctx.packet.GameInfo.TimeSeconds;
         let start = *self.start.get_or_insert(now);
         let elapsed = now - start;
         if elapsed >= self.limit {
             Action::Abort
         } else {
-            self.child.execute_old(ctx)
+            self.child.execute(ctx)
         }
     }
 }
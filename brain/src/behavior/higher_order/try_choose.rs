@@ -1,6 +1,6 @@
 use crate::{
     eeg::{color, Drawable},
-    strategy::{Action, Behavior, Context, Priority},
+    strategy::{Action, Behavior, Child, Context, Priority},
 };
 use itertools::Itertools;
 use nameof::name_of_type;
@@ -46,7 +46,18 @@ impl Behavior for TryChoose {
         self.priority
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        match self.chosen_index {
+            Some(index) => visit(Child::Behavior(&*self.choices[index])),
+            None => {
+                for choice in &self.choices {
+                    visit(Child::Behavior(&**choice));
+                }
+            }
+        }
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg
             .draw(Drawable::print(self.choice_names.as_str(), color::GREEN));
 
@@ -54,7 +65,7 @@ impl Behavior for TryChoose {
             let behavior = &mut self.choices[chosen_index];
             ctx.eeg
                 .draw(Drawable::print(behavior.name(), color::YELLOW));
-            return behavior.execute_old(ctx);
+            return behavior.execute(ctx);
         }
 
         // If we get here, we need to choose a child behavior. This will only happen on
@@ -83,7 +94,7 @@ impl TryChoose {
     }
 
     fn try_index(&mut self, ctx: &mut Context<'_>, index: usize) -> Option<Action> {
-        match self.choices[index].execute_old(ctx) {
+        match self.choices[index].execute(ctx) {
             Action::Yield(input) => {
                 ctx.eeg.log(
                     self.name(),
@@ -1,6 +1,6 @@
 use crate::{
     eeg::{color, Drawable},
-    strategy::{Action, Behavior, Context, Priority},
+    strategy::{Action, Behavior, Child, Context, Priority},
 };
 
 pub struct WithDraw<B: Behavior> {
@@ -23,7 +23,11 @@ impl<B: Behavior> Behavior for WithDraw<B> {
         self.behavior.priority()
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        visit(Child::Behavior(&self.behavior));
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         for d in self.draw.iter() {
             ctx.eeg.draw(d.clone());
         }
@@ -31,6 +35,6 @@ impl<B: Behavior> Behavior for WithDraw<B> {
         ctx.eeg
             .draw(Drawable::print(self.behavior.blurb(), color::YELLOW));
 
-        self.behavior.execute_old(ctx)
+        self.behavior.execute(ctx)
     }
 }
@@ -1,8 +1,12 @@
 use crate::{
     behavior::{
+        defense::Shadow,
         higher_order::{Chain, TimeLimit, While},
-        movement::{drive_towards, QuickJumpAndDodge, Yielder},
+        movement::{
+            drive_towards, simple_steer_towards, simple_yaw_diff, Land, QuickJumpAndDodge, Yielder,
+        },
     },
+    helpers::drive::hold_position,
     routing::{
         behavior::FollowRoute,
         models::RoutePlanner,
@@ -10,20 +14,28 @@ use crate::{
         recover::RoundIsNotActive,
         StraightMode,
     },
-    strategy::{Action, Behavior, Context, Priority},
+    strategy::{Action, Behavior, Context, Game, Priority, RiskMode, RiskProfile},
 };
 use common::{prelude::*, rl};
 use derive_new::new;
 use nalgebra::Point2;
 use nameof::name_of_type;
-use std::f32::consts::PI;
+use std::{f32::consts::PI, ptr};
 use vec_box::vec_box;
 
-pub struct PreKickoff;
+pub struct PreKickoff {
+    /// If `None`, pick one from `KICKOFF_TABLE` once we're at the spawn.
+    plan: Option<KickoffPlan>,
+}
 
 impl PreKickoff {
     pub fn new() -> Self {
-        Self
+        Self { plan: None }
+    }
+
+    /// Like `new`, but skip the selection and go straight to `plan`.
+    pub fn with_plan(plan: KickoffPlan) -> Self {
+        Self { plan: Some(plan) }
     }
 
     pub fn is_kickoff(ball: &common::halfway_house::BallInfo) -> bool {
@@ -36,20 +48,246 @@ impl Behavior for PreKickoff {
         name_of_type!(PreKickoff)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         // Disable quick chat for now since sometimes it chats before the round
         // countdown starts, and it seems out of place.
         if false {
             kickoff_quick_chat(ctx);
         }
 
-        Action::tail_call(Chain::new(Priority::Idle, vec_box![
-            wait_for_round_to_begin(),
-            Kickoff::new(),
+        let plan = match self.plan {
+            Some(plan) => plan,
+            None => choose_plan(ctx),
+        };
+        ctx.eeg.log(self.name(), format!("plan: {:?}", plan));
+
+        let kickoff: Box<dyn Behavior> = match plan {
+            KickoffPlan::Standard => Box::new(Kickoff::new()),
+            KickoffPlan::Speedflip => Box::new(SpeedflipKickoff::new()),
+            KickoffPlan::Fake => Box::new(FakeKickoff::new()),
+            KickoffPlan::CheatUp => Box::new(WaitOutKickoff::new(cheat_up_loc(ctx.game))),
+        };
+        Action::tail_call(Chain::new(Priority::Idle, vec![
+            Box::new(wait_for_round_to_begin()),
+            kickoff,
         ]))
     }
 }
 
+/// The ways we know how to play a kickoff.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KickoffPlan {
+    /// Drive at the ball and go for the 50/50, with `Kickoff`.
+    Standard,
+    /// Like `Standard`, but open with a speedflip to get there sooner.
+    Speedflip,
+    /// Let the enemy have the first touch, and be ready for it, with
+    /// `FakeKickoff`.
+    Fake,
+    /// A teammate is taking the kickoff. Move up to pick up whatever comes out
+    /// of it.
+    CheatUp,
+}
+
+/// Who does what on a kickoff, when we have teammates.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum KickoffRole {
+    /// We're closest to the ball, so it's ours.
+    Taker,
+    /// We're next in line after the taker.
+    Support,
+    /// Everyone else stays home.
+    Back,
+}
+
+/// The score and the clock, boiled down to what matters for a kickoff.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Stakes {
+    Normal,
+    /// Ahead, with little time left.
+    ProtectingLead,
+    /// Behind, with little time left.
+    Chasing,
+}
+
+impl Stakes {
+    /// The same call the rest of the bot makes, so the kickoff doesn't play it
+    /// safe while everything after it goes all in.
+    fn of(risk: &RiskProfile) -> Self {
+        match risk.mode() {
+            RiskMode::Normal => Stakes::Normal,
+            RiskMode::ProtectLead => Stakes::ProtectingLead,
+            RiskMode::AllIn => Stakes::Chasing,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct KickoffSituation {
+    spawn: Option<Spawn>,
+    role: KickoffRole,
+    stakes: Stakes,
+    /// Whether the difficulty and our boost allow a speedflip.
+    can_speedflip: bool,
+    /// Whether the enemy has been winning our kickoffs. See `KickoffStats`.
+    losing_kickoffs: bool,
+}
+
+impl KickoffSituation {
+    fn plan(&self) -> KickoffPlan {
+        let plan = KICKOFF_TABLE
+            .iter()
+            .find(|rule| rule.matches(self))
+            .map_or(KickoffPlan::Standard, |rule| rule.plan);
+        if plan == KickoffPlan::Speedflip && !self.can_speedflip {
+            return KickoffPlan::Standard;
+        }
+        plan
+    }
+}
+
+struct KickoffRule {
+    /// `None` matches any spawn, including unknown ones.
+    spawn: Option<Spawn>,
+    role: KickoffRole,
+    /// `None` matches any stakes.
+    stakes: Option<Stakes>,
+    /// `None` matches either way.
+    losing_kickoffs: Option<bool>,
+    plan: KickoffPlan,
+}
+
+impl KickoffRule {
+    fn matches(&self, situation: &KickoffSituation) -> bool {
+        self.spawn
+            .map_or(true, |spawn| situation.spawn == Some(spawn))
+            && self.role == situation.role
+            && self
+                .stakes
+                .map_or(true, |stakes| situation.stakes == stakes)
+            && self
+                .losing_kickoffs
+                .map_or(true, |losing| situation.losing_kickoffs == losing)
+    }
+}
+
+/// The first matching rule wins. If nothing matches, take a `Standard`
+/// kickoff.
+const KICKOFF_TABLE: &[KickoffRule] = &[
+    // When chasing the game, everyone goes forward.
+    KickoffRule {
+        spawn: None,
+        role: KickoffRole::Support,
+        stakes: Some(Stakes::Chasing),
+        losing_kickoffs: None,
+        plan: KickoffPlan::CheatUp,
+    },
+    // Otherwise, the car in the center spawn is the last line of defense, so
+    // it stays home even if it's next in line.
+    KickoffRule {
+        spawn: Some(Spawn::Center),
+        role: KickoffRole::Support,
+        stakes: None,
+        losing_kickoffs: None,
+        plan: KickoffPlan::Fake,
+    },
+    KickoffRule {
+        spawn: None,
+        role: KickoffRole::Support,
+        stakes: None,
+        losing_kickoffs: None,
+        plan: KickoffPlan::CheatUp,
+    },
+    KickoffRule {
+        spawn: None,
+        role: KickoffRole::Back,
+        stakes: None,
+        losing_kickoffs: None,
+        plan: KickoffPlan::Fake,
+    },
+    // We need the ball, even if the enemy usually wins the kickoff.
+    KickoffRule {
+        spawn: None,
+        role: KickoffRole::Taker,
+        stakes: Some(Stakes::Chasing),
+        losing_kickoffs: None,
+        plan: KickoffPlan::Speedflip,
+    },
+    // If the enemy keeps winning the 50/50, stop giving it to them.
+    KickoffRule {
+        spawn: None,
+        role: KickoffRole::Taker,
+        stakes: None,
+        losing_kickoffs: Some(true),
+        plan: KickoffPlan::Fake,
+    },
+    // A speedflip that goes wrong gives up a breakaway. Don't risk it with
+    // the game almost won.
+    KickoffRule {
+        spawn: None,
+        role: KickoffRole::Taker,
+        stakes: Some(Stakes::ProtectingLead),
+        losing_kickoffs: None,
+        plan: KickoffPlan::Standard,
+    },
+    KickoffRule {
+        spawn: None,
+        role: KickoffRole::Taker,
+        stakes: None,
+        losing_kickoffs: None,
+        plan: KickoffPlan::Speedflip,
+    },
+];
+
+fn choose_plan(ctx: &mut Context<'_>) -> KickoffPlan {
+    let me = ctx.me();
+    let situation = KickoffSituation {
+        spawn: Spawn::of(me.Physics.loc_2d()),
+        role: kickoff_role(ctx.game),
+        stakes: Stakes::of(&ctx.risk),
+        can_speedflip: ctx.difficulty.speedflips && SpeedflipKickoff::applicable(me),
+        losing_kickoffs: ctx
+            .match_memory
+            .kickoff_stats()
+            .should_fake(ctx.time_based_random()),
+    };
+    ctx.eeg
+        .log(name_of_type!(PreKickoff), format!("{:?}", situation));
+    situation.plan()
+}
+
+/// The car closest to the ball takes the kickoff. On a tie, the one on the
+/// left (from behind our own goal) goes, same as the usual human convention.
+/// Every bot on the team reaches the same answer, so nobody needs to talk.
+///
+/// This doesn't use `TeamCoordination`, since mirrored spawns tie exactly on
+/// time to the ball, and its tiebreak isn't one human teammates would expect.
+fn kickoff_role(game: &Game<'_>) -> KickoffRole {
+    let left = -game.own_goal().center_2d.y.signum();
+    // Round the distance, so mirrored spawns compare equal.
+    let key = |car: &common::halfway_house::PlayerInfo| {
+        let loc = car.Physics.loc_2d();
+        let dist = ((loc - Point2::origin()).norm() / 10.0).round();
+        (dist, loc.x * left)
+    };
+    let mut cars: Vec<_> = game.cars(game.team).collect();
+    cars.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap());
+    match cars.iter().position(|&car| ptr::eq(car, game.me())) {
+        Some(0) | None => KickoffRole::Taker,
+        Some(1) => KickoffRole::Support,
+        Some(_) => KickoffRole::Back,
+    }
+}
+
+/// Up the field from where we spawned, on our side of the kickoff.
+fn cheat_up_loc(game: &Game<'_>) -> Point2<f32> {
+    let me_loc = game.me().Physics.loc_2d();
+    Point2::new(
+        me_loc.x * 0.5,
+        game.own_goal().center_2d.y.signum() * WaitOutKickoff::CHEAT_UP_Y,
+    )
+}
+
 fn kickoff_quick_chat(ctx: &mut Context<'_>) {
     let quick_chat = if ctx.time_based_random() < 0.1 {
         // I'm so funny
@@ -87,7 +325,7 @@ impl Behavior for Kickoff {
         name_of_type!(Kickoff)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
             ctx.eeg.log(self.name(), "not a kickoff");
             return Action::Abort;
@@ -99,7 +337,10 @@ impl Behavior for Kickoff {
         let rand1 = rand1 * 2.0 - 1.0;
         let rand2 = rand2 * 2.0 - 1.0;
         let rand3 = rand3 * 2.0 - 1.0;
-        let rand4 = rand4 * 2.0 - 1.0;
+
+        let variant = ctx.match_memory.choose_kickoff_variant(rand4);
+        ctx.match_memory.kickoff_started(variant);
+        ctx.eeg.log(self.name(), format!("variant: {:?}", variant));
 
         let x_signum = ctx.me().Physics.loc().x.signum();
         let y_signum = ctx.me().Physics.loc().y.signum();
@@ -132,7 +373,7 @@ impl Behavior for Kickoff {
 
         Action::tail_call(Chain::new(Priority::Idle, vec![
             Box::new(FollowRoute::new_boxed(approach)),
-            Box::new(KickoffStrike::new(rand4 * 25.0)),
+            Box::new(KickoffStrike::new(variant.commit_offset())),
         ]))
     }
 }
@@ -147,6 +388,147 @@ fn is_diagonal_kickoff(ctx: &mut Context<'_>) -> bool {
     car_x.abs() >= 1000.0
 }
 
+/// The standard kickoff spawns, on either side of the field.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Spawn {
+    Center,
+    OffCenter,
+    Diagonal,
+}
+
+impl Spawn {
+    /// How far a car can be from a spawn point and still count as there.
+    const TOLERANCE: f32 = 50.0;
+
+    fn of(car_loc: Point2<f32>) -> Option<Self> {
+        let near = |x: f32, y: f32| {
+            (car_loc.x.abs() - x).abs() < Self::TOLERANCE
+                && (car_loc.y.abs() - y).abs() < Self::TOLERANCE
+        };
+        if near(0.0, 4608.0) {
+            Some(Spawn::Center)
+        } else if near(256.0, 3840.0) {
+            Some(Spawn::OffCenter)
+        } else if near(2048.0, 2560.0) {
+            Some(Spawn::Diagonal)
+        } else {
+            None
+        }
+    }
+
+    /// `None` for the spawns the speedflip hasn't been tuned for. Those take
+    /// the standard kickoff.
+    fn speedflip_timing(self) -> Option<SpeedflipTiming> {
+        match self {
+            Spawn::Diagonal => Some(SpeedflipTiming {
+                drive: 0.10,
+                steer: 0.4,
+                jump: 0.08,
+            }),
+            Spawn::Center | Spawn::OffCenter => None,
+        }
+    }
+}
+
+struct SpeedflipTiming {
+    /// Seconds to drive before jumping.
+    drive: f32,
+    /// How hard to steer away from the side we'll flip towards, so the flip
+    /// carries us back onto the line to the ball.
+    steer: f32,
+    /// How long to hold the first jump.
+    jump: f32,
+}
+
+/// A kickoff that opens with a speedflip: a diagonal dodge, cancelled right
+/// away, so we get the dodge's burst of speed without the tumble.
+///
+/// It's only tuned for the diagonal spawns (see `speedflip_beats_standard`),
+/// and it boosts the whole way, so it needs the full kickoff allotment of
+/// boost.
+pub struct SpeedflipKickoff;
+
+impl SpeedflipKickoff {
+    /// The boost every car starts a kickoff with.
+    const MIN_BOOST: i32 = 33;
+    /// Keep pulling back this long to cancel the dodge.
+    const CANCEL_TIME: f32 = 0.6;
+
+    // This shouldn't be used without `PreKickoff` coming first, so make it private.
+    fn new() -> Self {
+        Self
+    }
+
+    /// Returns `true` if we're lined up on a spawn we know the timings for,
+    /// with enough boost to see it through.
+    pub fn applicable(car: &common::halfway_house::PlayerInfo) -> bool {
+        car.Boost >= Self::MIN_BOOST && Self::timing(car).is_some()
+    }
+
+    fn timing(car: &common::halfway_house::PlayerInfo) -> Option<SpeedflipTiming> {
+        Spawn::of(car.Physics.loc_2d()).and_then(Spawn::speedflip_timing)
+    }
+}
+
+impl Behavior for SpeedflipKickoff {
+    fn name(&self) -> &str {
+        name_of_type!(SpeedflipKickoff)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
+            ctx.eeg.log(self.name(), "not a kickoff");
+            return Action::Abort;
+        }
+
+        let timing = some_or_else!(Self::timing(ctx.me()), {
+            ctx.eeg.log(self.name(), "no speedflip for this spawn");
+            return Action::tail_call(Kickoff::new());
+        });
+
+        let variant = ctx
+            .match_memory
+            .choose_kickoff_variant(ctx.time_based_random());
+        ctx.match_memory.kickoff_started(variant);
+        ctx.eeg.log(self.name(), format!("variant: {:?}", variant));
+
+        // Flip towards the side the ball is on. We only speedflip from the
+        // diagonal spawns, where that's never in doubt, but if we're somehow
+        // lined up straight at the ball, either side will do.
+        let yaw_diff = simple_yaw_diff(&ctx.me().Physics, Point2::origin());
+        let side = if yaw_diff.abs() < 0.05 {
+            1.0
+        } else {
+            yaw_diff.signum()
+        };
+        let steer = -side * timing.steer;
+
+        let input = |steer, pitch, yaw, roll, jump| common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Steer: steer,
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+            Jump: jump,
+            Boost: true,
+            ..Default::default()
+        };
+
+        Action::tail_call(Chain::new(Priority::Idle, vec_box![
+            Yielder::new(timing.drive, input(steer, 0.0, 0.0, 0.0, false)),
+            // Jump
+            Yielder::new(timing.jump, input(0.0, 0.0, 0.0, 0.0, true)),
+            Yielder::new(0.05, input(0.0, 0.0, 0.0, 0.0, false)),
+            // Dodge diagonally forwards
+            Yielder::new(0.05, input(0.0, -1.0, side, 0.0, true)),
+            // Cancel the dodge, and roll level for the landing
+            Yielder::new(Self::CANCEL_TIME, input(0.0, 1.0, 0.0, -side, false)),
+            Land::new(),
+            KickoffStrike::new(variant.commit_offset()),
+        ]))
+    }
+}
+
 struct KickoffStrike {
     commit_offset: f32,
 }
@@ -154,12 +536,10 @@ struct KickoffStrike {
 impl KickoffStrike {
     const JUMP_TIME: f32 = 0.1;
 
-    /// `slop` is a random component added to the dodge distance, to keep things
-    /// unpredictable.
-    pub fn new(slop: f32) -> Self {
-        Self {
-            commit_offset: slop,
-        }
+    /// `commit_offset` is added to the dodge distance. It comes from the
+    /// chosen `KickoffVariant`.
+    pub fn new(commit_offset: f32) -> Self {
+        Self { commit_offset }
     }
 }
 
@@ -168,7 +548,7 @@ impl Behavior for KickoffStrike {
         name_of_type!(KickoffStrike)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
             ctx.eeg.log(self.name(), "not a kickoff");
             return Action::Abort;
@@ -248,7 +628,7 @@ impl Behavior for RoughAngledChip {
         name_of_type!(RoughAngledChip)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
             return Action::Return;
         }
@@ -263,10 +643,198 @@ enum CommitAction {
     Chip,
 }
 
+/// Hang back and let the enemy have the kickoff. Grab the corner boost on our
+/// side while they go for the ball, then shadow whatever they do with it.
+pub struct FakeKickoff;
+
+impl FakeKickoff {
+    /// Once we're this close to the boost, we have it (or someone else does).
+    const BOOST_DIST: f32 = 200.0;
+
+    pub fn new() -> Self {
+        FakeKickoff
+    }
+
+    /// The big boost in the back corner on our spawn's side.
+    fn corner_boost(game: &Game<'_>) -> Point2<f32> {
+        let me_loc = game.me().Physics.loc_2d();
+        let own_goal_y = game.own_goal().center_2d.y;
+        game.boost_dollars()
+            .iter()
+            .map(|pickup| pickup.loc)
+            // Strictly on our side, so the midfield pads don't count.
+            .filter(|loc| loc.y * own_goal_y > 0.0)
+            .min_by(|a, b| {
+                let dist = |loc: &Point2<f32>| (*loc - me_loc).norm();
+                dist(a).partial_cmp(&dist(b)).unwrap()
+            })
+            .unwrap_or_else(|| Point2::new(me_loc.x, own_goal_y))
+    }
+}
+
+impl Behavior for FakeKickoff {
+    fn name(&self) -> &str {
+        name_of_type!(FakeKickoff)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
+            ctx.eeg.log(self.name(), "the ball is in play; shadowing");
+            return Action::tail_call(Shadow::new());
+        }
+
+        let me = ctx.me();
+        let boost_loc = Self::corner_boost(ctx.game);
+        if me.Boost < 100 && (boost_loc - me.Physics.loc_2d()).norm() >= Self::BOOST_DIST {
+            return Action::Yield(drive_towards(ctx, boost_loc));
+        }
+
+        // Wait for the enemy to make their move, facing the ball.
+        Action::Yield(hold_position(me, Point2::origin()))
+    }
+}
+
+/// For the cars that aren't taking the kickoff: go to `target_loc` and wait
+/// there, facing the ball, until somebody touches it.
+struct WaitOutKickoff {
+    target_loc: Point2<f32>,
+}
+
+impl WaitOutKickoff {
+    /// How far up the field to cheat, measured from midfield.
+    const CHEAT_UP_Y: f32 = 1500.0;
+    /// Stop once we're this close to the spot.
+    const ARRIVE_DIST: f32 = 100.0;
+
+    fn new(target_loc: Point2<f32>) -> Self {
+        Self { target_loc }
+    }
+}
+
+impl Behavior for WaitOutKickoff {
+    fn name(&self) -> &str {
+        name_of_type!(WaitOutKickoff)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !PreKickoff::is_kickoff(&ctx.packet.GameBall) {
+            return Action::Return;
+        }
+
+        let me = ctx.me();
+        if (self.target_loc - me.Physics.loc_2d()).norm() >= Self::ARRIVE_DIST {
+            return Action::Yield(drive_towards(ctx, self.target_loc));
+        }
+
+        Action::Yield(hold_position(me, Point2::origin()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::kickoff::{KickoffPlan, KickoffRole, KickoffSituation, Spawn, Stakes},
+        strategy::RiskProfile,
+    };
+    use nalgebra::Point2;
+
+    #[test]
+    fn spawns() {
+        let cases = [
+            (Point2::new(0.0, -4608.0), Some(Spawn::Center)),
+            (Point2::new(-256.0, -3840.0), Some(Spawn::OffCenter)),
+            (Point2::new(256.0, 3840.0), Some(Spawn::OffCenter)),
+            (Point2::new(-2048.0, -2560.0), Some(Spawn::Diagonal)),
+            (Point2::new(2048.0, 2560.0), Some(Spawn::Diagonal)),
+            (Point2::new(1000.0, -2000.0), None),
+        ];
+        for &(loc, expected) in cases.iter() {
+            assert_eq!(Spawn::of(loc), expected);
+        }
+    }
+
+    #[test]
+    fn stakes() {
+        let stakes = |margin, seconds| Stakes::of(&RiskProfile::new(margin, seconds));
+        assert_eq!(stakes(1, 30.0), Stakes::ProtectingLead);
+        assert_eq!(stakes(-2, 30.0), Stakes::Chasing);
+        assert_eq!(stakes(0, 30.0), Stakes::Normal);
+        assert_eq!(stakes(1, 200.0), Stakes::Normal);
+        // A lead is worth protecting for the last minute and a half.
+        assert_eq!(stakes(1, 75.0), Stakes::ProtectingLead);
+    }
+
+    #[test]
+    fn speedflip_only_where_tuned() {
+        assert!(Spawn::Diagonal.speedflip_timing().is_some());
+        assert!(Spawn::Center.speedflip_timing().is_none());
+        assert!(Spawn::OffCenter.speedflip_timing().is_none());
+    }
+
+    #[test]
+    fn plan_table() {
+        let plan = |spawn, role, stakes| {
+            KickoffSituation {
+                spawn: Some(spawn),
+                role,
+                stakes,
+                can_speedflip: true,
+                losing_kickoffs: false,
+            }
+            .plan()
+        };
+        assert_eq!(
+            plan(Spawn::Diagonal, KickoffRole::Taker, Stakes::Normal),
+            KickoffPlan::Speedflip,
+        );
+        assert_eq!(
+            plan(Spawn::Diagonal, KickoffRole::Taker, Stakes::ProtectingLead),
+            KickoffPlan::Standard,
+        );
+        assert_eq!(
+            plan(Spawn::Diagonal, KickoffRole::Support, Stakes::Normal),
+            KickoffPlan::CheatUp,
+        );
+        assert_eq!(
+            plan(Spawn::Center, KickoffRole::Support, Stakes::Normal),
+            KickoffPlan::Fake,
+        );
+        assert_eq!(
+            plan(Spawn::Center, KickoffRole::Support, Stakes::Chasing),
+            KickoffPlan::CheatUp,
+        );
+        assert_eq!(
+            plan(Spawn::Center, KickoffRole::Back, Stakes::Chasing),
+            KickoffPlan::Fake,
+        );
+
+        let no_speedflip = KickoffSituation {
+            spawn: None,
+            role: KickoffRole::Taker,
+            stakes: Stakes::Normal,
+            can_speedflip: false,
+            losing_kickoffs: false,
+        };
+        assert_eq!(no_speedflip.plan(), KickoffPlan::Standard);
+
+        let losing = KickoffSituation {
+            losing_kickoffs: true,
+            ..no_speedflip
+        };
+        assert_eq!(losing.plan(), KickoffPlan::Fake);
+        let losing_and_chasing = KickoffSituation {
+            stakes: Stakes::Chasing,
+            can_speedflip: true,
+            ..losing
+        };
+        assert_eq!(losing_and_chasing.plan(), KickoffPlan::Speedflip);
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
-        behavior::PreKickoff,
+        behavior::{KickoffPlan, PreKickoff},
         integration_tests::{TestRunner, TestScenario},
     };
     use brain_test_data::recordings;
@@ -276,6 +844,7 @@ mod integration_tests {
 
     #[test]
     fn kickoff_center() {
+        tags!(Kickoff);
         let test = TestRunner::new()
             .one_v_one(&*recordings::KICKOFF_CENTER, 107.0)
             .starting_boost(33.0)
@@ -290,6 +859,7 @@ mod integration_tests {
 
     #[test]
     fn kickoff_off_center() {
+        tags!(Kickoff);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 car_loc: Point3::new(-256.0, -3840.0, 17.01),
@@ -306,6 +876,7 @@ mod integration_tests {
 
     #[test]
     fn kickoff_diagonal() {
+        tags!(Kickoff);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 car_loc: Point3::new(-1952.0, -2464.0, 17.01),
@@ -324,6 +895,43 @@ mod integration_tests {
         assert!(!PreKickoff::is_kickoff(&packet.GameBall));
     }
 
+    #[test]
+    fn speedflip_beats_standard() {
+        tags!(Kickoff);
+        let standard = diagonal_first_touch(KickoffPlan::Standard);
+        let speedflip = diagonal_first_touch(KickoffPlan::Speedflip);
+        assert!(
+            speedflip < standard - 0.1,
+            "standard = {}, speedflip = {}",
+            standard,
+            speedflip,
+        );
+    }
+
+    /// How long it takes to touch the ball from the diagonal spawn.
+    fn diagonal_first_touch(plan: KickoffPlan) -> f32 {
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                car_loc: Point3::new(-2048.0, -2560.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, 0.25 * PI, 0.0),
+                ..Default::default()
+            })
+            .starting_boost(33.0)
+            .behavior(PreKickoff::with_plan(plan))
+            .run();
+
+        let start = test.sniff_packet().GameInfo.TimeSeconds;
+        loop {
+            let packet = test.sniff_packet();
+            let elapsed = packet.GameInfo.TimeSeconds - start;
+            if !PreKickoff::is_kickoff(&packet.GameBall) {
+                return elapsed;
+            }
+            assert!(elapsed < 5.0, "never touched the ball");
+            test.sleep_millis(10);
+        }
+    }
+
     fn extrapolate_ball(
         packet: &common::halfway_house::LiveDataPacket,
         seconds: f32,
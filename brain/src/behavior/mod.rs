@@ -1,4 +1,4 @@
-pub use self::kickoff::PreKickoff;
+pub use self::kickoff::{FakeKickoff, KickoffPlan, PreKickoff, SpeedflipKickoff};
 
 pub mod defense;
 #[macro_use]
@@ -6,5 +6,6 @@ pub mod higher_order;
 mod kickoff;
 pub mod movement;
 pub mod offense;
+pub mod registry;
 pub mod strike;
 pub mod taunt;
@@ -7,6 +7,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn simple() {
+        tags!(Movement, Aerial);
         let expected_loc = Point3::new(400.0, 1100.0, 600.0);
         let test = TestRunner::new()
             .scenario(TestScenario {
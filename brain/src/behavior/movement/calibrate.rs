@@ -0,0 +1,237 @@
+use crate::{
+    eeg::Event,
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, UnitComplex};
+use nameof::name_of_type;
+use simulate::{car_single_jump::JUMP_MAX_Z, car_turn, Car1D};
+
+/// Run a few quick maneuvers and compare the results against our models in
+/// `simulate`. If the game's physics have drifted from our tables (mutators, a
+/// different car body, a game update, etc.), this will complain loudly.
+///
+/// This wastes a few seconds, so it's meant to be run on demand (e.g., at the
+/// start of a test match), not during a real game.
+pub struct Calibrate {
+    phase: Phase,
+    phase_start: Option<PhaseStart>,
+    drifted: bool,
+}
+
+#[derive(Copy, Clone)]
+enum Phase {
+    Settle,
+    Throttle,
+    Turn,
+    Jump,
+}
+
+#[derive(Copy, Clone)]
+struct PhaseStart {
+    time: f32,
+    loc: Point2<f32>,
+    speed: f32,
+    boost: f32,
+    last_loc: Point2<f32>,
+    last_rot: UnitComplex<f32>,
+    traveled: f32,
+    turned: f32,
+    max_z: f32,
+}
+
+impl Calibrate {
+    const SETTLE_TIME: f32 = 0.5;
+    const SETTLE_TIMEOUT: f32 = 3.0;
+    const THROTTLE_TIME: f32 = 1.0;
+    const TURN_TIME: f32 = 1.0;
+    const JUMP_TIMEOUT: f32 = 2.0;
+
+    /// Allowed relative error in distance traveled under full throttle.
+    const THROTTLE_TOLERANCE: f32 = 0.1;
+    /// Allowed relative error in turn curvature.
+    const TURN_TOLERANCE: f32 = 0.15;
+    /// Allowed absolute error in jump apex height.
+    const JUMP_TOLERANCE: f32 = 15.0;
+
+    pub fn new() -> Self {
+        Self {
+            phase: Phase::Settle,
+            phase_start: None,
+            drifted: false,
+        }
+    }
+}
+
+impl Behavior for Calibrate {
+    fn name(&self) -> &str {
+        name_of_type!(Calibrate)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Force
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let loc = me.Physics.loc_2d();
+        let rot = me.Physics.quat().to_2d();
+
+        let start = self.phase_start.get_or_insert_with(|| PhaseStart {
+            time: now,
+            loc,
+            speed: me.Physics.vel().dot(&me.Physics.forward_axis()).max(0.0),
+            boost: me.Boost as f32,
+            last_loc: loc,
+            last_rot: rot,
+            traveled: 0.0,
+            turned: 0.0,
+            max_z: me.Physics.loc().z,
+        });
+        start.traveled += (loc - start.last_loc).norm();
+        start.turned += start.last_rot.rotation_to(&rot).angle();
+        start.last_loc = loc;
+        start.last_rot = rot;
+        start.max_z = start.max_z.max(me.Physics.loc().z);
+        let start = *start;
+        let elapsed = now - start.time;
+
+        match self.phase {
+            Phase::Settle => {
+                let speed = me.Physics.vel().norm();
+                if (me.OnGround && speed < 10.0 && elapsed >= Self::SETTLE_TIME)
+                    || elapsed >= Self::SETTLE_TIMEOUT
+                {
+                    self.next_phase(Phase::Throttle);
+                    return self.execute(ctx);
+                }
+                let forward_speed = me.Physics.vel().dot(&me.Physics.forward_axis());
+                Action::Yield(common::halfway_house::PlayerInput {
+                    Throttle: -forward_speed.signum() * (forward_speed.abs() / 500.0).min(1.0),
+                    ..Default::default()
+                })
+            }
+            Phase::Throttle => {
+                if elapsed < Self::THROTTLE_TIME {
+                    return Action::Yield(common::halfway_house::PlayerInput {
+                        Throttle: 1.0,
+                        Boost: true,
+                        ..Default::default()
+                    });
+                }
+
+                let mut sim = Car1D::new()
+                    .with_speed(start.speed.min(rl::CAR_MAX_SPEED))
+                    .with_boost(start.boost);
+                sim.advance(elapsed, 1.0, true);
+                let actual = (loc - start.loc).norm();
+                let error = (actual - sim.distance()) / sim.distance();
+                ctx.eeg.log(
+                    self.name(),
+                    format!(
+                        "throttle: traveled {:.0}, expected {:.0}",
+                        actual,
+                        sim.distance(),
+                    ),
+                );
+                self.check(ctx, "throttle", error.abs() <= Self::THROTTLE_TOLERANCE);
+                self.next_phase(Phase::Turn);
+                Action::Yield(Default::default())
+            }
+            Phase::Turn => {
+                if elapsed < Self::TURN_TIME {
+                    return Action::Yield(common::halfway_house::PlayerInput {
+                        Throttle: 1.0,
+                        Steer: 1.0,
+                        ..Default::default()
+                    });
+                }
+
+                let average_speed = start.traveled / elapsed;
+                let actual = start.turned.abs() / start.traveled;
+                let expected = car_turn::max_curvature(average_speed);
+                let error = (actual - expected) / expected;
+                ctx.eeg.log(
+                    self.name(),
+                    format!(
+                        "turn: curvature {:.5}, expected {:.5} at speed {:.0}",
+                        actual, expected, average_speed,
+                    ),
+                );
+                self.check(ctx, "turn", error.abs() <= Self::TURN_TOLERANCE);
+                self.next_phase(Phase::Jump);
+                Action::Yield(Default::default())
+            }
+            Phase::Jump => {
+                let landed = elapsed >= rl::CAR_JUMP_FORCE_TIME * 2.0 && me.OnGround;
+                if !landed && elapsed < Self::JUMP_TIMEOUT {
+                    return Action::Yield(common::halfway_house::PlayerInput {
+                        Jump: elapsed < rl::CAR_JUMP_FORCE_TIME,
+                        ..Default::default()
+                    });
+                }
+
+                ctx.eeg.log(
+                    self.name(),
+                    format!("jump: apex {:.0}, expected {:.0}", start.max_z, JUMP_MAX_Z),
+                );
+                let error = start.max_z - JUMP_MAX_Z;
+                self.check(ctx, "jump", error.abs() <= Self::JUMP_TOLERANCE);
+
+                if self.drifted {
+                    ctx.eeg
+                        .log(self.name(), "physics differ from our models; beware!");
+                } else {
+                    ctx.eeg.log(self.name(), "physics match our models");
+                }
+                Action::Return
+            }
+        }
+    }
+}
+
+impl Calibrate {
+    fn next_phase(&mut self, phase: Phase) {
+        self.phase = phase;
+        self.phase_start = None;
+    }
+
+    fn check(&mut self, ctx: &mut Context<'_>, maneuver: &str, ok: bool) {
+        if !ok {
+            ctx.eeg.log(
+                self.name(),
+                format!("{} does not match the model", maneuver),
+            );
+            ctx.eeg.track(Event::CalibrationDrift);
+            self.drifted = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::movement::Calibrate,
+        eeg::Event,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use nalgebra::Point3;
+
+    #[test]
+    fn physics_match_our_models() {
+        tags!(Movement);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(3000.0, 3000.0, 92.74),
+                car_loc: Point3::new(0.0, -2000.0, 17.01),
+                ..Default::default()
+            })
+            .behavior(Calibrate::new())
+            .run_for_millis(5000);
+
+        test.examine_events(|events| {
+            assert!(!events.contains(&Event::CalibrationDrift));
+        });
+    }
+}
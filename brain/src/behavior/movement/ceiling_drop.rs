@@ -0,0 +1,107 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{Land, Yielder},
+    },
+    eeg::{color, Drawable},
+    strategy::{Action, Behavior, Context},
+};
+use common::{kinematics::kinematic_time, prelude::*, rl};
+use nalgebra::{Point2, Vector3};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+use vec_box::vec_box;
+
+/// Get down from the ceiling without wasting the fall.
+///
+/// Drive along the ceiling to a spot above where we want to land, then jump
+/// off and let `Land` roll us back onto our wheels. The planners all assume
+/// gravity points at our wheels, so none of them should be running up here.
+pub struct CeilingDrop;
+
+impl CeilingDrop {
+    /// Wheels this close to the ceiling count as being on it.
+    const CEILING_MARGIN: f32 = 100.0;
+    /// Drop once we're this close to the release point.
+    const RELEASE_DIST: f32 = 300.0;
+    /// Aim to land this far on our side of the ball.
+    const GOALSIDE_DIST: f32 = 1000.0;
+    /// Stay this far away from the walls, so we don't land on one.
+    const WALL_MARGIN: f32 = 500.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn on_ceiling(car: &common::halfway_house::PlayerInfo) -> bool {
+        car.OnGround
+            && car.Physics.loc().z >= rl::FIELD_MAX_Z - Self::CEILING_MARGIN
+            && car.Physics.roof_axis().angle(&-Vector3::z_axis()) < PI / 6.0
+    }
+
+    /// Where the ball will be when we hit the floor, backed off towards our
+    /// goal.
+    fn release_loc(ctx: &mut Context<'_>) -> Point2<f32> {
+        let me = ctx.me();
+        let fall_time =
+            kinematic_time(-me.Physics.loc().z + rl::OCTANE_NEUTRAL_Z, 0.0, rl::GRAVITY)
+                .unwrap_or(0.0);
+        let ball_loc = ctx
+            .scenario
+            .ball_prediction()
+            .at_time_or_last(fall_time)
+            .loc
+            .to_2d();
+        let to_goal = (ctx.game.own_goal().center_2d - ball_loc).normalize();
+        let target = ball_loc + to_goal * Self::GOALSIDE_DIST;
+
+        let max_x = ctx.game.field_max_x() - Self::WALL_MARGIN;
+        let max_y = ctx.game.field_max_y() - Self::WALL_MARGIN;
+        Point2::new(
+            target.x.max(-max_x).min(max_x),
+            target.y.max(-max_y).min(max_y),
+        )
+    }
+}
+
+impl Behavior for CeilingDrop {
+    fn name(&self) -> &str {
+        name_of_type!(CeilingDrop)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !Self::on_ceiling(ctx.me()) {
+            ctx.eeg.log(self.name(), "not on the ceiling");
+            return Action::Return;
+        }
+
+        let target_loc = Self::release_loc(ctx);
+        let me = ctx.me();
+        ctx.eeg
+            .draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
+
+        let to_target = target_loc - me.Physics.loc_2d();
+        if to_target.norm() < Self::RELEASE_DIST {
+            ctx.eeg.log(self.name(), "dropping");
+            return Action::tail_call(Chain::new(self.priority(), vec_box![
+                Yielder::new(0.1, common::halfway_house::PlayerInput {
+                    Jump: true,
+                    ..Default::default()
+                }),
+                Land::new(),
+            ]));
+        }
+
+        ctx.eeg
+            .draw(Drawable::print("driving on the ceiling", color::GREEN));
+
+        // Upside down, steering right turns us left, as seen from above.
+        let forward = me.Physics.forward_axis().into_inner().to_2d();
+        let steer = forward.angle_to(&to_target) * 2.0;
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Steer: steer.max(-1.0).min(1.0),
+            ..Default::default()
+        })
+    }
+}
@@ -53,11 +53,15 @@ impl Behavior for Dodge {
         name_of_type!(Dodge)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if ctx.me().OnGround {
             ctx.eeg.log(self.name(), "can't dodge while on ground");
             return Action::Abort;
         }
+        if !ctx.jump.has_flip() {
+            ctx.eeg.log(self.name(), "flip is no longer available");
+            return Action::Abort;
+        }
 
         let (pitch, yaw) = match self.direction {
             Direction::Angle(angle) => (-angle.cos_angle(), angle.sin_angle()),
@@ -102,6 +106,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "this is a slow test, and unlikely to break")]
     fn all_directions() {
+        tags!(Movement);
         let angles = [-3.0, -1.5, 0.0, 1.5, 3.0];
         let targets = [
             Point2::new(1000.0, 0.0),
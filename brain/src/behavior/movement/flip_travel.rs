@@ -0,0 +1,32 @@
+use crate::{
+    routing::{behavior::FollowRoute, plan::StraightWithFlips},
+    strategy::{Action, Behavior, Context},
+};
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// Drive to a location without boost, front flipping whenever there's room.
+/// On an empty tank, chaining flips is the fastest way across the field.
+pub struct FlipTravel {
+    target_loc: Point2<f32>,
+}
+
+impl FlipTravel {
+    pub fn new(target_loc: Point2<f32>) -> Self {
+        Self { target_loc }
+    }
+}
+
+impl Behavior for FlipTravel {
+    fn name(&self) -> &str {
+        name_of_type!(FlipTravel)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.log(self.name(), "flipping the whole way");
+        Action::tail_call(FollowRoute::new(StraightWithFlips::new(
+            self.target_loc,
+            0.0,
+        )))
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     behavior::{
         higher_order::Chain,
-        movement::{dodge::Dodge, drive_towards::drive_towards, land::Land},
+        movement::{
+            ceiling_drop::CeilingDrop, dodge::Dodge, drive_towards::drive_towards, land::Land,
+        },
     },
     eeg::{color, Drawable},
     helpers::telepathy::predict_enemy_hit_direction,
@@ -32,7 +34,7 @@ impl Behavior for GetToFlatGround {
         name_of_type!(GetToFlatGround)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if Self::on_flat_ground(ctx.me()) {
             return Action::Return;
         }
@@ -43,6 +45,10 @@ impl Behavior for GetToFlatGround {
             return Action::tail_call(Land::new());
         }
 
+        if CeilingDrop::on_ceiling(me) {
+            return Action::tail_call(CeilingDrop::new());
+        }
+
         if me.Physics.roof_axis().angle(&-Vector3::z_axis()) < PI / 10.0 {
             // We're probably upside down under the ceiling of a goal
             ctx.eeg.log(self.name(), "jumping while upside-down");
@@ -221,7 +227,7 @@ impl Behavior for YieldAndMaybePanicBoost {
         name_of_type!(GetToFlatGround)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let now = ctx.packet.GameInfo.TimeSeconds;
         let start = *self.start.get_or_insert(now);
         let elapsed = now - start;
@@ -0,0 +1,81 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{Land, Yielder},
+    },
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use std::f32::consts::PI;
+use vec_box::vec_box;
+
+/// Turn around in place: backflip, cancel the flip halfway through, and roll
+/// back onto our wheels facing the other way.
+///
+/// It's much faster than a three-point turn from a standstill, but it spends
+/// the flip, so it's only worth it when the target really is behind us.
+pub struct HalfFlip;
+
+impl HalfFlip {
+    /// The target must be at least this far off our nose.
+    const MIN_ANGLE: f32 = PI * 3.0 / 4.0;
+    /// Any faster forwards and it's quicker to just turn.
+    const MAX_FORWARD_SPEED: f32 = 500.0;
+    /// Closer than this, just back up.
+    const MIN_DIST: f32 = 1000.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns `true` if a half-flip is the fastest way to start heading to
+    /// `target_loc`.
+    pub fn applicable(car: &common::halfway_house::PlayerInfo, target_loc: Point2<f32>) -> bool {
+        let forward = car.Physics.forward_axis_2d();
+        let to_target = target_loc - car.Physics.loc_2d();
+        car.OnGround
+            && to_target.norm() >= Self::MIN_DIST
+            && forward.angle_to(&to_target).abs() >= Self::MIN_ANGLE
+            && car.Physics.vel_2d().dot(&forward) < Self::MAX_FORWARD_SPEED
+    }
+}
+
+impl Behavior for HalfFlip {
+    fn name(&self) -> &str {
+        name_of_type!(HalfFlip)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Force
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !ctx.me().OnGround {
+            ctx.eeg.log(self.name(), "wheels must be on ground");
+            return Action::Abort;
+        }
+
+        let input = |pitch, roll, jump| common::halfway_house::PlayerInput {
+            Throttle: -1.0,
+            Pitch: pitch,
+            Roll: roll,
+            Jump: jump,
+            ..Default::default()
+        };
+
+        Action::tail_call(Chain::new(self.priority(), vec_box![
+            // Jump
+            Yielder::new(0.05, input(0.0, 0.0, true)),
+            Yielder::new(0.05, input(0.0, 0.0, false)),
+            // Backflip
+            Yielder::new(0.05, input(1.0, 0.0, true)),
+            // Cancel the flip once the nose is pointing up
+            Yielder::new(0.2, input(-1.0, 0.0, false)),
+            // Roll back onto our wheels
+            Yielder::new(0.3, input(-1.0, 1.0, false)),
+            Land::new(),
+        ]))
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
+    behavior::movement::WaveDash,
     eeg::{color, Drawable},
     routing::{models::CarState, plan::avoid_goal_wall_waypoint},
     strategy::{Action, Behavior, Context},
     utils::geometry::Plane,
 };
-use common::{kinematics::kinematic, prelude::*, rl};
+use common::{prelude::*, rl};
 use derive_new::new;
 use nalgebra::{Unit, Vector2, Vector3};
 use nameof::name_of_type;
@@ -21,7 +22,7 @@ impl Behavior for Land {
         name_of_type!(Land)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let me = ctx.me();
 
         if me.OnGround {
@@ -57,6 +58,11 @@ impl Behavior for Land {
             });
         }
 
+        // If we still have a flip, spend it on the landing instead of skidding.
+        if WaveDash::applicable(ctx) {
+            return Action::tail_call(WaveDash::new());
+        }
+
         ctx.eeg.draw(Drawable::print("air rolling", color::GREEN));
 
         let (plane, landing_time) = find_landing_plane(ctx);
@@ -102,7 +108,7 @@ impl Behavior for Land {
             Roll: roll,
             Boost: (boost_down || panic_boost)
                 && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED,
-            Handbrake: will_be_skidding_on_landing(ctx, plane),
+            Handbrake: will_be_skidding_on_landing(ctx, &plane),
             ..Default::default()
         })
     }
@@ -160,33 +166,13 @@ fn face_the_ball(ctx: &mut Context<'_>) -> Vector2<f32> {
     target_loc - me.Physics.loc_2d()
 }
 
-/// Simulate car freefall for increasing time intervals and try to find the
-/// first wall we will penetrate.
-fn find_landing_plane<'ctx>(ctx: &mut Context<'ctx>) -> (&'ctx Plane, f32) {
-    // This routine assumes the field is fully convex (or concave I guess, since
-    // we're inside it?)
-
-    let start_loc = ctx.me().Physics.loc();
-    let start_vel = ctx.me().Physics.vel();
-
-    // If we're outside the field, we're probably inside a goal. The goals are not
-    // convex(/convace?) so the below check won't work. Just orient to the ground
-    // instead since that seems to produce the best results.
-    if !ctx.game.is_inside_field(start_loc.to_2d()) {
-        return (ctx.game.pitch().ground(), 0.0);
+/// Find the surface we're going to land on, and how long until we get there.
+fn find_landing_plane(ctx: &mut Context<'_>) -> (Plane, f32) {
+    match ctx.scenario.me_landing() {
+        Some(landing) => (landing.plane, landing.time),
+        // Fallback
+        None => (*ctx.game.pitch().ground(), 2.0),
     }
-
-    for time in (0..40).into_iter().map(|x| x as f32 / 20.0) {
-        let (loc, _vel) = kinematic(start_vel, Vector3::z() * rl::GRAVITY, time);
-        let loc = start_loc + loc;
-        let plane = ctx.game.pitch().closest_plane(&loc);
-        if plane.distance_to_point(&loc) < 50.0 {
-            return (plane, time);
-        }
-    }
-
-    // Fallback
-    (ctx.game.pitch().ground(), 2.0)
 }
 
 impl Land {
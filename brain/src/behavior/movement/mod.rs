@@ -1,22 +1,31 @@
 pub use self::{
     blitz_to_location::BlitzToLocation,
+    calibrate::Calibrate,
+    ceiling_drop::CeilingDrop,
     dodge::Dodge,
     drive_towards::{drive_towards, DriveTowards},
+    flip_travel::FlipTravel,
     get_to_flat_ground::GetToFlatGround,
+    half_flip::HalfFlip,
     jump_and_turn::JumpAndTurn,
     land::Land,
     quick_jump_and_dodge::QuickJumpAndDodge,
     simple_steer_towards::{simple_steer_towards, simple_yaw_diff},
     skid_recover::SkidRecover,
+    wave_dash::WaveDash,
     yielder::Yielder,
 };
 
 #[cfg(test)]
 mod aerial_loc_time;
 mod blitz_to_location;
+mod calibrate;
+mod ceiling_drop;
 mod dodge;
 mod drive_towards;
+mod flip_travel;
 mod get_to_flat_ground;
+mod half_flip;
 mod jump_and_turn;
 mod land;
 mod quick_jump_and_dodge;
@@ -24,4 +33,5 @@ mod simple_steer_towards;
 mod skid_recover;
 #[cfg(test)]
 mod wall_drive;
+mod wave_dash;
 mod yielder;
@@ -61,7 +61,7 @@ impl Behavior for QuickJumpAndDodge {
         Priority::Force
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let start_time = *self
             .start_time
             .get_or_insert(ctx.packet.GameInfo.TimeSeconds);
@@ -96,10 +96,14 @@ impl Behavior for QuickJumpAndDodge {
                 ctx.eeg.log(self.name(), "goomba stomped?");
                 return Action::Abort;
             }
+            if !ctx.jump.has_flip() {
+                ctx.eeg.log(self.name(), "flip is no longer available");
+                return Action::Abort;
+            }
 
             self.phase = Phase::FollowThrough;
 
-            self.dodge.execute_old(ctx)
+            self.dodge.execute(ctx)
         } else if self.phase == Phase::FollowThrough
             || elapsed < self.dodge_time + Self::FOLLOW_THROUGH_TIME
         {
@@ -29,7 +29,7 @@ mod integration_tests {
             name_of_type!(SimpleSteerTowardsBall)
         }
 
-        fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+        fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
             let me = ctx.me();
             let ball = ctx.packet.GameBall;
             Action::Yield(common::halfway_house::PlayerInput {
@@ -42,6 +42,7 @@ mod integration_tests {
 
     #[test]
     fn steer_direction() {
+        tags!(Movement);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2800.0, -2800.0, 0.0),
@@ -18,7 +18,7 @@ impl Behavior for WallDrive {
         name_of_type!(WallDrive)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let me = ctx.me();
         let me_forward = me.Physics.forward_axis();
 
@@ -37,7 +37,9 @@ impl Behavior for WallDrive {
         }
 
         let steer_target_loc = if current_plane.normal.dot(&target_plane.normal) < 0.95 {
-            let unfold = some_or_else!(target_plane.unfold(current_plane).ok(), {
+            let pitch = ctx.game.pitch();
+            let unfold = pitch.unfold(target_plane, current_plane, &self.target_loc);
+            let unfold = some_or_else!(unfold.ok(), {
                 ctx.eeg.log(self.name(), "can't unfold wall");
                 return Action::Abort;
             });
@@ -69,6 +71,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn simple() {
+        tags!(Movement, Wall);
         TestRunner::new()
             .scenario(TestScenario {
                 car_loc: Point3::new(3000.0, 0.0, 17.01),
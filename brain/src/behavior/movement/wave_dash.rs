@@ -0,0 +1,116 @@
+use crate::{
+    behavior::movement::Land,
+    eeg::{color, Drawable},
+    strategy::{Action, Behavior, Context},
+};
+use common::prelude::*;
+use nalgebra::{Unit, UnitQuaternion, Vector3};
+use nameof::name_of_type;
+
+/// Dodge forward the instant before touching down.
+///
+/// The dodge's impulse gets applied while the wheels are on the floor, so
+/// instead of sending us tumbling, it turns into a burst of speed, and the
+/// landing doesn't cost us the momentum we came down with.
+pub struct WaveDash {
+    dodged: bool,
+}
+
+impl WaveDash {
+    /// Land with the nose tilted up this much, so the dodge tips us forward
+    /// onto all four wheels instead of into the floor.
+    const NOSE_UP_ANGLE: f32 = 0.25;
+    /// Start lining up this long before touchdown.
+    pub const LEAD_TIME: f32 = 0.5;
+    /// Press jump when we're this close to touchdown.
+    const TRIGGER_TIME: f32 = 2.0 / 60.0;
+    /// Slower than this, there's no momentum worth saving.
+    const MIN_SPEED: f32 = 500.0;
+
+    pub fn new() -> Self {
+        Self { dodged: false }
+    }
+
+    /// Returns `true` if we'll come down on the floor soon, with a flip left
+    /// and enough speed to be worth keeping.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        if !ctx.jump.has_flip() {
+            return false;
+        }
+        let flip_window = ctx.jump.flip_window_remaining();
+        let landing = match ctx.scenario.me_landing() {
+            Some(landing) => landing,
+            None => return false,
+        };
+        landing.is_on_ground()
+            && landing.time < Self::LEAD_TIME
+            && flip_window.map(|t| t > landing.time).unwrap_or(true)
+            && landing.surface_vel().norm() >= Self::MIN_SPEED
+    }
+}
+
+impl Behavior for WaveDash {
+    fn name(&self) -> &str {
+        name_of_type!(WaveDash)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if ctx.me().OnGround {
+            if !self.dodged {
+                ctx.eeg.log(self.name(), "touched down before the dodge");
+            }
+            return Action::Return;
+        }
+
+        if self.dodged {
+            // Wait for the wheels to touch down.
+            return Action::Yield(Default::default());
+        }
+
+        if !ctx.jump.has_flip() {
+            ctx.eeg.log(self.name(), "no flip left");
+            return Action::tail_call(Land::new());
+        }
+
+        let landing = match ctx.scenario.me_landing() {
+            Some(landing) => *landing,
+            None => {
+                ctx.eeg.log(self.name(), "not landing");
+                return Action::tail_call(Land::new());
+            }
+        };
+
+        if landing.time < Self::TRIGGER_TIME {
+            ctx.eeg.log(self.name(), "dodging");
+            self.dodged = true;
+            return Action::Yield(common::halfway_house::PlayerInput {
+                Throttle: 1.0,
+                Pitch: -1.0,
+                Jump: true,
+                ..Default::default()
+            });
+        }
+
+        ctx.eeg.draw(Drawable::print("lining up", color::GREEN));
+        ctx.eeg.print_time("landing_time", landing.time);
+
+        // Face along our momentum, then tip the nose up from there.
+        let me = ctx.me();
+        let up = landing.plane.normal;
+        let forward = Unit::try_new(landing.plane.project_vector(&landing.vel), 1e-3)
+            .unwrap_or_else(|| me.Physics.forward_axis());
+        let tilt_axis = Unit::new_normalize(forward.cross(&up));
+        let tilt = UnitQuaternion::from_axis_angle(&tilt_axis, Self::NOSE_UP_ANGLE);
+        let target_forward = tilt * forward;
+        let target_up: Unit<Vector3<f32>> = tilt * up;
+
+        let (pitch, yaw, roll) = dom::get_pitch_yaw_roll(me, target_forward, target_up);
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Pitch: pitch,
+            Yaw: yaw,
+            Roll: roll,
+            ..Default::default()
+        })
+    }
+}
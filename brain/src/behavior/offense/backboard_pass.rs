@@ -0,0 +1,272 @@
+use crate::{
+    behavior::{
+        offense::Shoot,
+        strike::{GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
+    },
+    eeg::{Drawable, Event},
+    helpers::{
+        ball::BallFrame,
+        hit_outcome::{ball_vel_after_hit, goal_line_crossing_z},
+        intercept::{naive_ground_intercept_2, NaiveIntercept},
+    },
+    routing::{
+        behavior::FollowRoute,
+        plan::{GroundDrive, GroundIntercept},
+    },
+    strategy::{Action, Behavior, Context, Game, Goal, Priority, Scenario},
+};
+use common::{prelude::*, rl, Coordinate};
+use nalgebra::{Point2, Point3, Vector3};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Hit the ball over the crossbar and into the enemy backboard, softly enough
+/// that it drops back out in front of the goal, then follow it in and shoot
+/// the rebound. Useful when there's no direct shot but we have time on the
+/// ball.
+pub struct BackboardPass;
+
+impl BackboardPass {
+    /// Only try this from the attacking third.
+    const MAX_GOAL_DIST: f32 = 4000.0;
+    /// The car needs to be headed roughly towards the goal already.
+    const MAX_APPROACH_ANGLE: f32 = PI / 6.0;
+    /// Hit the backboard at least this far above the crossbar, so a small
+    /// misjudgment doesn't turn into a shot that hits the bar,
+    const MIN_BACKBOARD_Z: f32 = rl::CROSSBAR_Z + 150.0;
+    /// and no higher than this, so the rebound doesn't hang in the air forever.
+    const MAX_BACKBOARD_Z: f32 = 1400.0;
+    /// The share of its speed into the wall that the ball keeps after the
+    /// bounce. This is a rough estimate; the wall also soaks up some spin.
+    const WALL_RESTITUTION: f32 = 0.6;
+    /// A rebound is worth following if it lands this far from the back wall,
+    const MIN_REBOUND_DIST: f32 = 600.0;
+    const MAX_REBOUND_DIST: f32 = 2500.0;
+    /// and no further than this outside the posts.
+    const MAX_REBOUND_WIDTH: f32 = 500.0;
+    /// Wait for the rebound this far behind where it lands, so we meet it
+    /// facing the goal.
+    const FOLLOW_BACK: f32 = 500.0;
+    /// When jumping, the nose is pitched up, so the car meets the ball below
+    /// its center. (Same as in `Shoot`.)
+    const JUMP_CONTACT_BELOW: f32 = 50.0;
+
+    pub fn new() -> Self {
+        BackboardPass
+    }
+
+    /// Returns `true` if we have time on the ball and can hit it into a
+    /// rebound that we can follow.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        if ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE {
+            return false;
+        }
+        let intercept = some_or_else!(Self::intercept(ctx.game, &ctx.scenario, ctx.me()), {
+            return false;
+        });
+        Self::plan(ctx.game, &intercept).is_some()
+    }
+
+    fn intercept(
+        game: &Game<'_>,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<NaiveIntercept<()>> {
+        naive_ground_intercept_2(&car.into(), scenario.ball_prediction(), |ball| {
+            Self::in_range(game, ball)
+        })
+    }
+
+    fn in_range(game: &Game<'_>, ball: &BallFrame) -> Option<()> {
+        let goal = game.enemy_goal();
+        if ball.loc.z >= GroundedHitTarget::MAX_BALL_Z
+            || (ball.loc.to_2d() - goal.center_2d).norm() >= Self::MAX_GOAL_DIST
+        {
+            return None;
+        }
+        Some(())
+    }
+
+    /// Figure out how to hit the ball at `intercept` so that it comes off the
+    /// backboard into a followable rebound. Prefer the chip, and fall back to
+    /// jumping, which makes contact lower on the ball.
+    fn plan(game: &Game<'_>, intercept: &NaiveIntercept<()>) -> Option<Pass> {
+        let goal = game.enemy_goal();
+        let ball_loc = intercept.ball_loc;
+        let forward = (ball_loc - intercept.car_loc).to_2d().to_axis();
+        let ball_to_goal = goal.center_2d - ball_loc.to_2d();
+        if forward.angle_to(&ball_to_goal).abs() >= Self::MAX_APPROACH_ANGLE {
+            return None;
+        }
+
+        let contact_dist = game.ball_radius() + game.me_vehicle().pivot_to_front_dist();
+        [false, true].iter().find_map(|&jump| {
+            let car_z = if jump {
+                ball_loc.z - Self::JUMP_CONTACT_BELOW
+            } else {
+                rl::OCTANE_NEUTRAL_Z
+            };
+            let car_loc = (ball_loc.to_2d() - forward.as_ref() * contact_dist).to_3d(car_z);
+            let car_forward = forward.to_3d();
+            let car_vel = car_forward.into_inner() * intercept.car_speed;
+            let ball_vel =
+                ball_vel_after_hit(car_loc, car_vel, &car_forward, ball_loc, intercept.ball_vel);
+            let rebound = rebound(goal, ball_loc, ball_vel)?;
+            if !followable(goal, rebound.land_loc) {
+                return None;
+            }
+            Some(Pass { jump, rebound })
+        })
+    }
+
+    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        let intercept = Self::intercept(ctx.game, ctx.scenario, ctx.car).ok_or(())?;
+        let pass = Self::plan(ctx.game, &intercept).ok_or_else(|| {
+            ctx.eeg
+                .log(name_of_type!(BackboardPass), "the rebound is no good");
+        })?;
+        ctx.eeg
+            .print_value("backboard_z", Coordinate(pass.rebound.wall_z));
+
+        let aim_loc = ctx.game.enemy_goal().center_2d;
+        Ok(
+            GroundedHitTarget::new(intercept.time, GroundedHitTargetAdjust::RoughAim, aim_loc)
+                .jump(pass.jump)
+                .dodge(false),
+        )
+    }
+}
+
+struct Pass {
+    jump: bool,
+    rebound: Rebound,
+}
+
+#[derive(Debug)]
+struct Rebound {
+    /// How high the ball hits the back wall.
+    wall_z: f32,
+    /// Where the ball first touches the floor after the bounce.
+    land_loc: Point2<f32>,
+    /// How long from the hit until then.
+    time: f32,
+}
+
+impl Behavior for BackboardPass {
+    fn name(&self) -> &str {
+        name_of_type!(BackboardPass)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::BackboardPass);
+
+        let intercept = some_or_else!(Self::intercept(ctx.game, &ctx.scenario, ctx.me()), {
+            ctx.eeg.log(self.name(), "no intercept");
+            return Action::Abort;
+        });
+        let pass = some_or_else!(Self::plan(ctx.game, &intercept), {
+            ctx.eeg.log(self.name(), "no followable rebound");
+            return Action::Abort;
+        });
+
+        let goal = ctx.game.enemy_goal();
+        let land_loc = pass.rebound.land_loc;
+        ctx.eeg
+            .draw(Drawable::ghost_ball(land_loc.to_3d(rl::BALL_RADIUS)));
+        ctx.eeg.log(
+            self.name(),
+            format!(
+                "backboard at z={:.0}, rebound in {:.2}s",
+                pass.rebound.wall_z, pass.rebound.time,
+            ),
+        );
+
+        // Be in place behind the rebound, facing the goal, by the time it lands.
+        let follow_loc = land_loc + goal.normal_2d.as_ref() * Self::FOLLOW_BACK;
+        Action::tail_call(chain!(
+            Priority::Strike,
+            [
+                FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
+                GroundedHit::hit_towards(Self::aim),
+                FollowRoute::new(GroundDrive::new(follow_loc).end_chop(0.5)),
+                Shoot::new(),
+            ]
+        ))
+    }
+}
+
+/// Follow the ball from `ball_loc` into the back wall behind `goal` and back
+/// down to the floor. Returns `None` if it doesn't hit the backboard in the
+/// usable range.
+fn rebound(goal: &Goal, ball_loc: Point3<f32>, ball_vel: Vector3<f32>) -> Option<Rebound> {
+    let wall_z = goal_line_crossing_z(goal, ball_loc, ball_vel)?;
+    if wall_z < BackboardPass::MIN_BACKBOARD_Z || wall_z > BackboardPass::MAX_BACKBOARD_Z {
+        return None;
+    }
+
+    let normal = goal.normal_2d.into_inner();
+    let speed_in = ball_vel.to_2d().dot(&-normal);
+    let dist = (ball_loc.to_2d() - goal.center_2d).dot(&normal);
+    let wall_time = dist / speed_in;
+    let wall_loc = ball_loc.to_2d() + ball_vel.to_2d() * wall_time;
+    let wall_vel_z = ball_vel.z + rl::GRAVITY * wall_time;
+
+    // Reverse the part of the velocity going into the wall, minus what the
+    // bounce soaks up.
+    let tangential = ball_vel.to_2d() + normal * speed_in;
+    let vel_out = tangential + normal * speed_in * BackboardPass::WALL_RESTITUTION;
+
+    // Solve wall_z + wall_vel_z * t + GRAVITY / 2 * t^2 = BALL_RADIUS.
+    let a = 0.5 * rl::GRAVITY;
+    let b = wall_vel_z;
+    let c = wall_z - rl::BALL_RADIUS;
+    let fall_time = (-b - (b * b - 4.0 * a * c).sqrt()) / (2.0 * a);
+
+    Some(Rebound {
+        wall_z,
+        land_loc: wall_loc + vel_out * fall_time,
+        time: wall_time + fall_time,
+    })
+}
+
+fn followable(goal: &Goal, land_loc: Point2<f32>) -> bool {
+    let dist = (land_loc - goal.center_2d).dot(&goal.normal_2d);
+    dist >= BackboardPass::MIN_REBOUND_DIST
+        && dist < BackboardPass::MAX_REBOUND_DIST
+        && (land_loc.x - goal.center_2d.x).abs() < goal.max_x + BackboardPass::MAX_REBOUND_WIDTH
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::offense::backboard_pass::{followable, rebound},
+        strategy::SOCCAR_GOAL_ORANGE,
+    };
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn soft_lob_comes_back_out() {
+        let goal = &*SOCCAR_GOAL_ORANGE;
+        let rebound = rebound(
+            goal,
+            Point3::new(0.0, 3500.0, 93.0),
+            Vector3::new(0.0, 1200.0, 1300.0),
+        )
+        .unwrap();
+        assert!(rebound.wall_z > rl::CROSSBAR_Z);
+        assert!(rebound.land_loc.y < goal.center_2d.y - 1500.0);
+        assert!(rebound.land_loc.y > goal.center_2d.y - 2500.0);
+        assert!(followable(goal, rebound.land_loc));
+    }
+
+    #[test]
+    fn low_shot_misses_the_backboard() {
+        let goal = &*SOCCAR_GOAL_ORANGE;
+        // This goes in the net instead.
+        let ball_loc = Point3::new(0.0, 3500.0, 93.0);
+        assert!(rebound(goal, ball_loc, Vector3::new(0.0, 2000.0, 300.0)).is_none());
+        // This never gets there at all.
+        assert!(rebound(goal, ball_loc, Vector3::new(0.0, -1000.0, 300.0)).is_none());
+    }
+}
@@ -7,6 +7,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO (this could be either a bounce dribble or normal dribble)")]
     fn full_field() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1977.3411, -3995.6826, 93.15),
@@ -0,0 +1,133 @@
+use crate::{
+    behavior::{movement::simple_steer_towards, offense::Dribble},
+    eeg::Drawable,
+    helpers::{
+        ball::{BallFrame, BallTrajectory},
+        drive::{arrival_throttle, rough_time_drive_to_loc},
+    },
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::prelude::*;
+use nameof::name_of_type;
+
+/// Catch a ball that's dropping gently, so it settles on the roof, then carry
+/// it with `Dribble`.
+///
+/// Drive to where the ball comes down through roof height, arriving when it
+/// does. In the last moments, match its speed instead, so it lands on the car
+/// instead of bouncing off.
+pub struct Catch;
+
+impl Catch {
+    /// The height of the ball's center when it rests on the roof.
+    const CATCH_Z: f32 = 150.0;
+    /// With more horizontal speed than this, the ball rolls right off.
+    const MAX_BALL_SPEED: f32 = 500.0;
+    /// Give up if we'd get there this much later than the ball.
+    const MAX_LATENESS: f32 = 0.1;
+    /// Once the ball is this close to landing, stop worrying about where and
+    /// match its speed.
+    const MATCH_TIME: f32 = 0.25;
+    const MATCH_GAIN: f32 = 0.01;
+
+    pub fn new() -> Self {
+        Catch
+    }
+
+    /// Where and when the ball will come down onto the roof, if it's in the air
+    /// now and coming down gently enough to catch.
+    pub fn landing(ball_prediction: &BallTrajectory) -> Option<&BallFrame> {
+        if ball_prediction.start().loc.z <= Self::CATCH_Z {
+            return None;
+        }
+        let landing = ball_prediction.landing_spot(Self::CATCH_Z)?;
+        if landing.vel.to_2d().norm() >= Self::MAX_BALL_SPEED {
+            return None;
+        }
+        Some(landing)
+    }
+
+    /// Returns `true` if the ball is catchable and we can get under it in time.
+    pub fn feasible(ctx: &Context<'_>) -> bool {
+        let landing = some_or_else!(Self::landing(ctx.scenario.ball_prediction()), {
+            return false;
+        });
+        rough_time_drive_to_loc(ctx.me(), landing.loc.to_2d()) <= landing.t + Self::MAX_LATENESS
+    }
+}
+
+impl Behavior for Catch {
+    fn name(&self) -> &str {
+        name_of_type!(Catch)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+        if Dribble::can_carry(me, &ctx.packet.GameBall) {
+            ctx.eeg.log(self.name(), "caught it");
+            return Action::tail_call(Dribble::new());
+        }
+
+        let landing = some_or_else!(Self::landing(ctx.scenario.ball_prediction()).cloned(), {
+            ctx.eeg.log(self.name(), "nothing to catch");
+            return Action::Abort;
+        });
+        let landing_loc = landing.loc.to_2d();
+        if rough_time_drive_to_loc(me, landing_loc) > landing.t + Self::MAX_LATENESS {
+            ctx.eeg.log(self.name(), "can't get under it in time");
+            return Action::Abort;
+        }
+
+        ctx.eeg.draw(Drawable::ghost_ball(landing.loc));
+        ctx.eeg.print_time("landing", landing.t);
+
+        let (throttle, boost) = if landing.t > Self::MATCH_TIME {
+            let dist = (landing_loc - me.Physics.loc_2d()).norm();
+            arrival_throttle(me, dist, landing.t)
+        } else {
+            let forward = me.Physics.forward_axis_2d();
+            let speed_diff = landing.vel.to_2d().dot(&forward) - me.Physics.vel_2d().dot(&forward);
+            ((speed_diff * Self::MATCH_GAIN).max(-1.0).min(1.0), false)
+        };
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: throttle,
+            Steer: simple_steer_towards(&me.Physics, landing_loc),
+            Boost: boost && me.Boost > 0,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::offense::Catch, helpers::ball::BallTrajectory};
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    fn dropping(horizontal_speed: f32) -> BallTrajectory {
+        let start = Point3::new(0.0, 0.0, 800.0);
+        BallTrajectory::from_samples((0..240).map(|i| {
+            let t = i as f32 * rl::PHYSICS_DT;
+            let vel = Vector3::new(horizontal_speed, 0.0, rl::GRAVITY * t);
+            let loc = start + Vector3::new(horizontal_speed * t, 0.0, 0.5 * rl::GRAVITY * t * t);
+            (t, loc, vel)
+        }))
+    }
+
+    #[test]
+    fn catch_a_gentle_drop() {
+        let landing = Catch::landing(&dropping(200.0)).unwrap();
+        assert!(landing.loc.z <= Catch::CATCH_Z);
+        assert!(landing.t > 1.0);
+    }
+
+    #[test]
+    fn too_fast_to_catch() {
+        assert!(Catch::landing(&dropping(1000.0)).is_none());
+    }
+}
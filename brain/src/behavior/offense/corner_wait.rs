@@ -0,0 +1,132 @@
+use crate::{
+    behavior::movement::drive_towards,
+    eeg::{Drawable, Event},
+    helpers::drive::hold_position,
+    strategy::{Action, Behavior, Context, Goal, Scenario},
+    utils::WallRayCalculator,
+};
+use common::{prelude::*, rl};
+use nalgebra::Point2;
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// When the ball is stuck in the enemy's corner and we won't get there first,
+/// chasing it in (like `TepidHit` would) gains nothing and takes us out of the
+/// play. Instead, wait by the far post for whatever comes out of the corner.
+pub struct CornerWait;
+
+impl CornerWait {
+    /// The corner is the part of the field within this distance of the back
+    /// wall,
+    const CORNER_DEPTH: f32 = 1500.0;
+    /// and at least this far outside the posts.
+    const CORNER_WIDTH: f32 = 1100.0;
+    /// The ball is pinned if it's moving slower than this.
+    const PINNED_SPEED: f32 = 500.0;
+    /// Shots from an angle steeper than this are hopeless anyway.
+    const MIN_SHOT_ANGLE: f32 = PI / 3.0;
+    /// How far a ball that caroms off the back wall travels before we meet it.
+    const REBOUND_DIST: f32 = 800.0;
+    /// Don't wait closer than this to the back wall, where we'd be in the way.
+    const MIN_BACK_WALL_GAP: f32 = 1000.0;
+    /// Stop once we're this close to the spot.
+    const ARRIVE_DIST: f32 = 250.0;
+
+    pub fn new() -> Self {
+        CornerWait
+    }
+
+    /// Returns `true` if the ball is pinned in the enemy corner, with no shot
+    /// and nothing to gain by challenging for it.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        let ball = &ctx.packet.GameBall.Physics;
+        let goal = ctx.game.enemy_goal();
+        in_corner(goal, ball.loc_2d())
+            && ball.vel_2d().norm() < Self::PINNED_SPEED
+            && goal.shot_angle_2d(ball.loc_2d()) >= Self::MIN_SHOT_ANGLE
+            && ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE
+    }
+}
+
+impl Behavior for CornerWait {
+    fn name(&self) -> &str {
+        name_of_type!(CornerWait)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::CornerWait);
+
+        if !Self::applicable(ctx) {
+            ctx.eeg.log(self.name(), "the ball is out of the corner");
+            return Action::Return;
+        }
+
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let spot = rebound_spot(ctx.game.enemy_goal(), ball_loc);
+        ctx.eeg.draw(Drawable::Crosshair(spot));
+
+        let me = ctx.me();
+        if (spot - me.Physics.loc_2d()).norm() >= Self::ARRIVE_DIST {
+            return Action::Yield(drive_towards(ctx, spot));
+        }
+
+        // Hold the spot, facing the ball.
+        Action::Yield(hold_position(me, ball_loc))
+    }
+}
+
+fn in_corner(goal: &Goal, ball_loc: Point2<f32>) -> bool {
+    goal.is_y_within_range(ball_loc.y, ..CornerWait::CORNER_DEPTH)
+        && (ball_loc.x - goal.center_2d.x).abs() >= goal.max_x + CornerWait::CORNER_WIDTH
+}
+
+/// Where a ball sent from the corner towards the goal ends up after it
+/// caroms off the back wall, which is on the far post side.
+fn rebound_spot(goal: &Goal, ball_loc: Point2<f32>) -> Point2<f32> {
+    let wall_loc = WallRayCalculator::calculate(ball_loc, goal.center_2d);
+    let incoming = (wall_loc - ball_loc).normalize();
+    let normal = goal.normal_2d.into_inner();
+    let outgoing = incoming - normal * (2.0 * incoming.dot(&normal));
+    let spot = wall_loc + outgoing * CornerWait::REBOUND_DIST;
+
+    // Keep out of the goal mouth and off the side wall.
+    let depth = (spot - goal.center_2d).dot(&normal);
+    let spot = spot + normal * (CornerWait::MIN_BACK_WALL_GAP - depth).max(0.0);
+    let max_x = rl::FIELD_MAX_X - CornerWait::MIN_BACK_WALL_GAP;
+    Point2::new(spot.x.max(-max_x).min(max_x), spot.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::offense::corner_wait::{in_corner, rebound_spot},
+        strategy::SOCCAR_GOAL_ORANGE,
+    };
+    use common::rl;
+    use nalgebra::Point2;
+
+    #[test]
+    fn corner() {
+        let goal = &*SOCCAR_GOAL_ORANGE;
+        assert!(in_corner(goal, Point2::new(3500.0, 4700.0)));
+        assert!(in_corner(goal, Point2::new(-3500.0, 4700.0)));
+        // In front of the goal.
+        assert!(!in_corner(goal, Point2::new(500.0, 4700.0)));
+        // Upfield.
+        assert!(!in_corner(goal, Point2::new(3500.0, 2000.0)));
+    }
+
+    #[test]
+    fn wait_at_the_far_post() {
+        let goal = &*SOCCAR_GOAL_ORANGE;
+        let spot = rebound_spot(goal, Point2::new(3500.0, 4700.0));
+        // On the far side of the goal, a ways out from the back wall.
+        assert!(spot.x < 0.0);
+        assert!(spot.x > -goal.max_x - 1000.0);
+        assert!(spot.y <= rl::FIELD_MAX_Y - 1000.0 + 1.0);
+        assert!(spot.y > rl::FIELD_MAX_Y - 1500.0);
+
+        let mirrored = rebound_spot(goal, Point2::new(-3500.0, 4700.0));
+        assert!((mirrored.x + spot.x).abs() < 1.0);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    behavior::movement::drive_towards,
+    eeg::{color, Drawable, Event},
+    strategy::{Action, Behavior, Context, Game},
+};
+use common::prelude::*;
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+use simulate::Car1D;
+
+/// Blow up the enemy car.
+///
+/// When we can't get to the ball anyway and the enemy is stuck somewhere out
+/// of the way, taking them out of the play for a few seconds is worth more
+/// than a hopeless chase.
+pub struct DemoEnemy;
+
+/// Where and when we would hit the enemy car, assuming it keeps going the way
+/// it's going.
+struct DemoIntercept {
+    time: f32,
+    enemy_loc: Point2<f32>,
+    /// Our speed at contact. Only a supersonic hit demolishes.
+    speed: f32,
+}
+
+impl DemoEnemy {
+    /// The speed a car needs to demolish another on contact.
+    const SUPERSONIC_SPEED: f32 = 2200.0;
+    /// Don't bother if we'd get there with less than this.
+    const MIN_BOOST: i32 = 30;
+    /// The enemy is out of position this close to a side wall.
+    const WALL_DIST: f32 = 1000.0;
+    /// The enemy is also out of position this close to their own goal.
+    const GOAL_DIST: f32 = 2000.0;
+    /// The ball is out of reach if we're at least this far behind in the race
+    /// for it.
+    const MIN_DEFICIT: f32 = 1.0;
+    /// Don't plan runs longer than this. The enemy won't drive in a straight
+    /// line forever.
+    const MAX_TIME: f32 = 3.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns `true` if the ball is out of reach, the enemy is out of
+    /// position, and we can hit them at supersonic speed.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        let me = ctx.me();
+        if me.Boost < Self::MIN_BOOST {
+            return false;
+        }
+        let ball_reachable = ctx.scenario.me_intercept().is_some()
+            && ctx.scenario.possession() >= -Self::MIN_DEFICIT;
+        if ball_reachable {
+            return false;
+        }
+        let enemy = some_or_else!(ctx.scenario.primary_enemy(), {
+            return false;
+        });
+        if enemy.Demolished || !enemy.OnGround || !out_of_position(ctx.game, enemy) {
+            return false;
+        }
+        demo_intercept(
+            me.Physics.loc_2d(),
+            me.Physics.vel_2d().norm(),
+            me.Boost as f32,
+            enemy.Physics.loc_2d(),
+            enemy.Physics.vel_2d(),
+        )
+        .is_some()
+    }
+}
+
+impl Behavior for DemoEnemy {
+    fn name(&self) -> &str {
+        name_of_type!(DemoEnemy)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::DemoEnemy);
+
+        let enemy = some_or_else!(ctx.scenario.primary_enemy(), {
+            ctx.eeg.log(self.name(), "no enemy");
+            return Action::Return;
+        });
+        if enemy.Demolished {
+            ctx.eeg.log(self.name(), "got 'em");
+            return Action::Return;
+        }
+
+        let me = ctx.me();
+        let intercept = some_or_else!(
+            demo_intercept(
+                me.Physics.loc_2d(),
+                me.Physics.vel_2d().norm(),
+                me.Boost as f32,
+                enemy.Physics.loc_2d(),
+                enemy.Physics.vel_2d(),
+            ),
+            {
+                ctx.eeg.log(self.name(), "can't hit them supersonic");
+                return Action::Return;
+            }
+        );
+
+        ctx.eeg.print_time("demo_time", intercept.time);
+        ctx.eeg.draw(Drawable::Crosshair(intercept.enemy_loc));
+        ctx.eeg.draw(Drawable::Line(
+            me.Physics.loc_2d(),
+            intercept.enemy_loc,
+            color::RED,
+        ));
+
+        let mut input = drive_towards(ctx, intercept.enemy_loc);
+        input.Boost = !input.Handbrake && me.Boost > 0;
+        Action::Yield(input)
+    }
+}
+
+/// Is `enemy` somewhere they can't easily get back into the play from?
+fn out_of_position(game: &Game<'_>, enemy: &common::halfway_house::PlayerInfo) -> bool {
+    let loc = enemy.Physics.loc_2d();
+    let near_wall = loc.x.abs() >= game.field_max_x() - DemoEnemy::WALL_DIST;
+    let near_goal = (loc - game.enemy_goal().center_2d).norm() < DemoEnemy::GOAL_DIST;
+    near_wall || near_goal
+}
+
+/// Like `naive_ground_intercept`, but the target is the enemy car, which is
+/// assumed to keep its current velocity. Returns `None` unless we'd be
+/// supersonic at contact.
+fn demo_intercept(
+    me_loc: Point2<f32>,
+    me_speed: f32,
+    me_boost: f32,
+    enemy_loc: Point2<f32>,
+    enemy_vel: Vector2<f32>,
+) -> Option<DemoIntercept> {
+    // Roughly the distance between the centers of two cars at contact.
+    const CAR_LENGTH: f32 = 120.0;
+    const DT: f32 = 1.0 / 60.0;
+
+    let mut sim = Car1D::new().with_speed(me_speed).with_boost(me_boost);
+    while sim.time() < DemoEnemy::MAX_TIME {
+        sim.advance(DT, 1.0, true);
+        let enemy_loc = enemy_loc + enemy_vel * sim.time();
+        if sim.distance() >= (enemy_loc - me_loc).norm() - CAR_LENGTH {
+            if sim.speed() < DemoEnemy::SUPERSONIC_SPEED {
+                return None;
+            }
+            return Some(DemoIntercept {
+                time: sim.time(),
+                enemy_loc,
+                speed: sim.speed(),
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::offense::demo_enemy::demo_intercept;
+    use nalgebra::{Point2, Vector2};
+
+    #[test]
+    fn long_run_reaches_supersonic() {
+        let intercept = demo_intercept(
+            Point2::new(0.0, 0.0),
+            1400.0,
+            100.0,
+            Point2::new(0.0, 3000.0),
+            Vector2::zeros(),
+        )
+        .unwrap();
+        assert!(intercept.speed >= 2200.0);
+        assert!((intercept.enemy_loc.y - 3000.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn short_run_is_too_slow() {
+        let intercept = demo_intercept(
+            Point2::new(0.0, 0.0),
+            0.0,
+            100.0,
+            Point2::new(0.0, 500.0),
+            Vector2::zeros(),
+        );
+        assert!(intercept.is_none());
+    }
+
+    #[test]
+    fn leads_a_moving_target() {
+        let intercept = demo_intercept(
+            Point2::new(0.0, 0.0),
+            1400.0,
+            100.0,
+            Point2::new(2000.0, 3000.0),
+            Vector2::new(0.0, -500.0),
+        )
+        .unwrap();
+        assert!(intercept.enemy_loc.y < 3000.0);
+    }
+}
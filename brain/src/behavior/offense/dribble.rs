@@ -0,0 +1,170 @@
+use crate::{
+    behavior::movement::{simple_yaw_diff, QuickJumpAndDodge},
+    strategy::{Action, Behavior, Context, Priority},
+};
+use common::{prelude::*, Distance};
+use nalgebra::Vector2;
+use nameof::name_of_type;
+
+/// Carry the ball on the car's roof towards the enemy goal, and flick it when
+/// an enemy comes to challenge.
+///
+/// The balancing is a PD controller on the ball's position and velocity
+/// relative to the car. Front-to-back, it picks a speed that keeps the car
+/// under the ball, and throttles towards it. It coasts rather than brakes to
+/// shed a little speed, since braking throws the ball off the hood. Side to
+/// side, steering keeps the ball over the roof. Once it's balanced, a little
+/// extra steering turns the whole carry towards the goal.
+pub struct Dribble;
+
+impl Dribble {
+    /// Hold the ball this far in front of the car's center, so it rolls
+    /// forward and we keep driving instead of stalling.
+    const TARGET_AHEAD: f32 = 10.0;
+    /// How much faster than the ball to drive, per uu the ball is ahead of
+    /// where we want it.
+    const SPEED_P: f32 = 4.0;
+    /// Throttle per uu/s of speed error.
+    const THROTTLE_GAIN: f32 = 0.02;
+    /// Only brake when we're this much too fast. Otherwise, coast.
+    const BRAKE_SPEED_ERROR: f32 = 150.0;
+    const LATERAL_P: f32 = 0.015;
+    const LATERAL_D: f32 = 0.01;
+    const HEADING_GAIN: f32 = 0.3;
+    /// Only turn towards the goal while the ball is this close to centered.
+    const HEADING_MAX_LATERAL: f32 = 20.0;
+    /// Boost when we're this much too slow.
+    const BOOST_SPEED_ERROR: f32 = 200.0;
+
+    /// Bounds on the ball's height above the car's center while it's carried.
+    const ROOF_MIN_Z: f32 = 100.0;
+    const ROOF_MAX_Z: f32 = 200.0;
+    /// The ball is off the roof if it's further than this from the car's
+    /// center, horizontally.
+    const ROOF_MAX_DIST: f32 = 120.0;
+    /// The ball can't be caught if it's moving this fast relative to the car.
+    const MAX_REL_SPEED: f32 = 300.0;
+
+    /// Only enemies this close are a threat.
+    const CHALLENGE_DIST: f32 = 1000.0;
+    /// Flick when an enemy would arrive within this many seconds.
+    const FLICK_LEAD: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns `true` if the ball is sitting on the car's roof, so a carry is
+    /// possible.
+    pub fn can_carry(
+        car: &common::halfway_house::PlayerInfo,
+        ball: &common::halfway_house::BallInfo,
+    ) -> bool {
+        let offset = ball.Physics.loc() - car.Physics.loc();
+        let rel_vel = ball.Physics.vel() - car.Physics.vel();
+        car.OnGround
+            && offset.z >= Self::ROOF_MIN_Z
+            && offset.z < Self::ROOF_MAX_Z
+            && offset.to_2d().norm() < Self::ROOF_MAX_DIST
+            && rel_vel.to_2d().norm() < Self::MAX_REL_SPEED
+    }
+
+    /// Returns `true` if an enemy is about to challenge for the ball.
+    fn challenged(ctx: &Context<'_>) -> bool {
+        let me = ctx.me();
+        ctx.enemy_cars().any(|enemy| {
+            let to_me = me.Physics.loc_2d() - enemy.Physics.loc_2d();
+            let dist = to_me.norm();
+            let closing_speed = (enemy.Physics.vel_2d() - me.Physics.vel_2d()).dot(&to_me) / dist;
+            dist < Self::CHALLENGE_DIST
+                && closing_speed > 0.0
+                && dist / closing_speed < Self::FLICK_LEAD
+        })
+    }
+}
+
+impl Behavior for Dribble {
+    fn name(&self) -> &str {
+        name_of_type!(Dribble)
+    }
+
+    fn priority(&self) -> Priority {
+        Priority::Strike
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        let me = ctx.me();
+        let ball = &ctx.packet.GameBall;
+
+        if !Self::can_carry(me, ball) {
+            ctx.eeg.log(self.name(), "lost the ball");
+            return Action::Abort;
+        }
+
+        if Self::challenged(ctx) {
+            ctx.eeg.log(self.name(), "challenged; flicking");
+            return Action::tail_call(QuickJumpAndDodge::new());
+        }
+
+        let forward = me.Physics.forward_axis_2d();
+        let right = me.Physics.right_axis().into_inner().to_2d();
+        let offset = ball.Physics.loc_2d() - me.Physics.loc_2d();
+        let rel_vel: Vector2<f32> = ball.Physics.vel_2d() - me.Physics.vel_2d();
+
+        let ahead = offset.dot(&forward) - Self::TARGET_AHEAD;
+        let lateral = offset.dot(&right);
+        ctx.eeg.print_value("dribble_ahead", Distance(ahead));
+        ctx.eeg.print_value("dribble_lateral", Distance(lateral));
+
+        let car_speed = me.Physics.vel_2d().dot(&forward);
+        let ball_speed = ball.Physics.vel_2d().dot(&forward);
+        let speed_error = ball_speed + Self::SPEED_P * ahead - car_speed;
+        let throttle = if speed_error >= -Self::BRAKE_SPEED_ERROR {
+            (speed_error * Self::THROTTLE_GAIN).max(0.0)
+        } else {
+            -1.0
+        };
+
+        let mut steer = Self::LATERAL_P * lateral + Self::LATERAL_D * rel_vel.dot(&right);
+        if lateral.abs() < Self::HEADING_MAX_LATERAL {
+            let heading = simple_yaw_diff(&me.Physics, ctx.game.enemy_goal().center_2d);
+            steer += heading * Self::HEADING_GAIN;
+        }
+
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: throttle.min(1.0),
+            Steer: steer.max(-1.0).min(1.0),
+            Boost: speed_error >= Self::BOOST_SPEED_ERROR && me.Boost > 0,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::offense::Dribble,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn carry_upfield() {
+        tags!(Offense);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, -2000.0, 160.0),
+                ball_vel: Vector3::new(0.0, 500.0, 0.0),
+                car_loc: Point3::new(0.0, -2010.0, 17.01),
+                car_vel: Vector3::new(0.0, 500.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(Dribble::new())
+            .run_for_millis(3000);
+
+        let packet = test.sniff_packet();
+        let car = packet.GameCars[0];
+        assert!(Dribble::can_carry(&car, &packet.GameBall));
+        assert!(packet.GameBall.Physics.loc().y >= 0.0);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{
+    behavior::{
+        movement::drive_towards,
+        offense::{Shoot, TepidHit},
+    },
+    eeg::{color, Drawable, Event},
+    helpers::intercept::naive_ground_intercept_2,
+    strategy::{Action, Behavior, Context},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Vector2};
+use nameof::name_of_type;
+
+/// Line up a shot as usual, but when the keeper comes charging out to meet it,
+/// brake and let them fly by, then shoot past them.
+pub struct FakeShot {
+    /// When we hit the brakes, once we have.
+    fake_start: Option<f32>,
+}
+
+impl FakeShot {
+    /// The keeper is charging if they're closing on the ball at least this
+    /// fast,
+    const MIN_CHARGE_SPEED: f32 = 1000.0;
+    /// and they'll get there within this long of us.
+    const ARRIVAL_WINDOW: f32 = 0.5;
+    /// Hit the brakes once the keeper is this close to the ball, or as soon
+    /// as they leave the ground.
+    const FAKE_DIST: f32 = 800.0;
+    /// Don't wait around for longer than this.
+    const MAX_FAKE_TIME: f32 = 0.75;
+    /// The fake costs us time, so nobody but the keeper may be able to get to
+    /// the ball within this long of us.
+    const DELAY_BUDGET: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self { fake_start: None }
+    }
+
+    /// Returns `true` if the keeper is coming out to challenge our shot early,
+    /// and we can afford to wait for them to go by.
+    ///
+    /// Only keepers the `OpponentModel` has seen challenge early are worth it.
+    /// Against anyone else, faking just gives up a good shot.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        if !ctx.scenario.opponent().early_challenger() {
+            return false;
+        }
+        let me_intercept = some_or_else!(ctx.scenario.me_intercept(), {
+            return false;
+        });
+        let &(keeper, ref keeper_intercept) = some_or_else!(ctx.scenario.enemy_intercept(), {
+            return false;
+        });
+        if (keeper_intercept.time - me_intercept.time).abs() >= Self::ARRIVAL_WINDOW {
+            return false;
+        }
+
+        let contact_loc = me_intercept.ball_loc.to_2d();
+        if !charging(
+            keeper.Physics.loc_2d(),
+            keeper.Physics.vel_2d(),
+            contact_loc,
+            ctx.game.enemy_goal().center_2d,
+        ) {
+            return false;
+        }
+
+        // This is rough, but the others aren't the ones we're faking out.
+        let budget_dist = (me_intercept.time + Self::DELAY_BUDGET) * rl::CAR_MAX_SPEED;
+        ctx.enemy_cars()
+            .filter(|enemy| !std::ptr::eq(*enemy, keeper))
+            .all(|enemy| (enemy.Physics.loc_2d() - contact_loc).norm() >= budget_dist)
+    }
+}
+
+impl Behavior for FakeShot {
+    fn name(&self) -> &str {
+        name_of_type!(FakeShot)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::FakeShot);
+
+        let me = ctx.me();
+        if !me.OnGround {
+            ctx.eeg.log(self.name(), "not on ground");
+            return Action::Abort;
+        }
+
+        let keeper = some_or_else!(ctx.scenario.primary_enemy(), {
+            ctx.eeg.log(self.name(), "no keeper; just shoot");
+            return Action::tail_call(Shoot::new());
+        });
+        let ball_loc = ctx.packet.GameBall.Physics.loc_2d();
+        let keeper_loc = keeper.Physics.loc_2d();
+        let keeper_vel = keeper.Physics.vel_2d();
+        let now = ctx.packet.GameInfo.TimeSeconds;
+
+        ctx.eeg
+            .draw(Drawable::Line(keeper_loc, ball_loc, color::RED));
+
+        let fake_start = match self.fake_start {
+            Some(t) => t,
+            None => {
+                let committed =
+                    !keeper.OnGround || (ball_loc - keeper_loc).norm() < Self::FAKE_DIST;
+                if !committed {
+                    if !Self::applicable(ctx) {
+                        ctx.eeg
+                            .log(self.name(), "the keeper backed off; just shoot");
+                        return Action::tail_call(Shoot::new());
+                    }
+                    let target_loc = ctx.scenario.me_intercept().unwrap().ball_loc.to_2d();
+                    return Action::Yield(drive_towards(ctx, target_loc));
+                }
+                ctx.eeg.log(self.name(), "the keeper committed; faking");
+                self.fake_start = Some(now);
+                now
+            }
+        };
+
+        ctx.eeg.print_time("fake_time", now - fake_start);
+        if flown_by(keeper_loc, keeper_vel, ball_loc) || now - fake_start >= Self::MAX_FAKE_TIME {
+            return finish(ctx);
+        }
+
+        // Brake, and turn away from the keeper's path so they don't clip us on
+        // the way by.
+        let forward = me.Physics.forward_axis_2d();
+        let keeper_side = forward.rotation_to(&(keeper_loc - me.Physics.loc_2d()).to_axis());
+        let forward_speed = me.Physics.vel_2d().dot(&forward);
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: if forward_speed > 0.0 { -1.0 } else { 0.0 },
+            Steer: -keeper_side.angle().signum(),
+            ..Default::default()
+        })
+    }
+}
+
+/// The keeper went by, so take the open shot if there is one. Otherwise push
+/// the ball past them.
+fn finish(ctx: &mut Context<'_>) -> Action {
+    let me = ctx.me();
+    let shot = naive_ground_intercept_2(
+        &me.into(),
+        ctx.scenario.ball_prediction().iter_step_by(0.125),
+        |ball| Shoot::viable_shot(ctx.game, me.Physics.loc(), ball),
+    );
+    if shot.is_some() {
+        ctx.eeg.log(name_of_type!(FakeShot), "the net is open");
+        Action::tail_call(Shoot::new())
+    } else {
+        ctx.eeg
+            .log(name_of_type!(FakeShot), "no shot; pushing past");
+        Action::tail_call(TepidHit::new())
+    }
+}
+
+/// Is the keeper between the ball and their goal, coming out to the ball?
+fn charging(
+    keeper_loc: Point2<f32>,
+    keeper_vel: Vector2<f32>,
+    ball_loc: Point2<f32>,
+    goal_loc: Point2<f32>,
+) -> bool {
+    let goal_side = (keeper_loc - goal_loc).norm() < (ball_loc - goal_loc).norm();
+    let to_ball = ball_loc - keeper_loc;
+    let closing_speed = keeper_vel.dot(&to_ball) / to_ball.norm();
+    goal_side && closing_speed >= FakeShot::MIN_CHARGE_SPEED
+}
+
+/// Has the keeper gone past the ball, or given up on it?
+fn flown_by(keeper_loc: Point2<f32>, keeper_vel: Vector2<f32>, ball_loc: Point2<f32>) -> bool {
+    keeper_vel.dot(&(ball_loc - keeper_loc)) <= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::offense::fake_shot::{charging, flown_by};
+    use nalgebra::{Point2, Vector2};
+
+    #[test]
+    fn keeper_charging_out_of_goal() {
+        let goal = Point2::new(0.0, 5120.0);
+        let ball = Point2::new(0.0, 3000.0);
+        let keeper = Point2::new(200.0, 4500.0);
+        assert!(charging(keeper, Vector2::new(0.0, -1500.0), ball, goal));
+        // Sitting in net isn't charging.
+        assert!(!charging(keeper, Vector2::zeros(), ball, goal));
+        // Neither is coming from upfield.
+        let upfield = Point2::new(200.0, 1000.0);
+        assert!(!charging(upfield, Vector2::new(0.0, 1500.0), ball, goal));
+    }
+
+    #[test]
+    fn keeper_flew_by() {
+        let ball = Point2::new(0.0, 3000.0);
+        let vel = Vector2::new(0.0, -1500.0);
+        assert!(!flown_by(Point2::new(0.0, 3500.0), vel, ball));
+        assert!(flown_by(Point2::new(0.0, 2800.0), vel, ball));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    behavior::movement::drive_towards,
+    eeg::{Drawable, Event},
+    helpers::{ball::BallFrame, drive::hold_position},
+    strategy::{Action, Behavior, Context, Goal},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, UnitComplex, Vector2};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Right after we shoot, stay up for the rebound instead of retreating.
+///
+/// Whether the keeper saves it or it caroms off the backboard, the ball comes
+/// back out of their goal in a cone centered on a plain bounce off the goal
+/// plane. Wait in the middle of that cone, far enough out to react to
+/// wherever it actually goes.
+pub struct FollowShot;
+
+impl FollowShot {
+    /// Follow up for this long after our touch.
+    const FOLLOW_TIME: f32 = 1.5;
+    /// Only count a shot that reaches their goal within this many seconds.
+    const SHOT_HORIZON: f32 = 3.0;
+    /// The ball reaches their goal (and the keeper, or the backboard) once
+    /// it's this close to the goal mouth.
+    const GOAL_DIST: f32 = 300.0;
+    /// Wait this far out from where the ball comes back.
+    const REBOUND_DIST: f32 = 1500.0;
+    /// Saves and caroms come out at most this far either side of straight out
+    /// of the goal.
+    const CONE_HALF_ANGLE: f32 = PI / 4.0;
+    /// Don't wait closer than this to a side wall.
+    const SIDE_WALL_GAP: f32 = 1000.0;
+    /// Stop once we're this close to the spot.
+    const ARRIVE_DIST: f32 = 250.0;
+
+    pub fn new() -> Self {
+        FollowShot
+    }
+
+    /// Returns `true` if we just sent the ball at their goal.
+    pub fn applicable(ctx: &Context<'_>) -> bool {
+        Self::deflection(ctx).is_some()
+    }
+
+    /// If we just shot, where and when the ball meets their goal.
+    fn deflection(ctx: &Context<'_>) -> Option<BallFrame> {
+        let touch = ctx.match_memory.last_own_touch()?;
+        if !ctx.match_memory.last_touch_ours()
+            || ctx.packet.GameInfo.TimeSeconds - touch >= Self::FOLLOW_TIME
+        {
+            return None;
+        }
+
+        let goal = ctx.game.enemy_goal();
+        ctx.scenario
+            .ball_prediction()
+            .iter()
+            .take_while(|ball| ball.t < Self::SHOT_HORIZON)
+            .find(|ball| {
+                let ball_loc = ball.loc.to_2d();
+                (goal.closest_point(ball_loc) - ball_loc).norm() < Self::GOAL_DIST
+            })
+            .cloned()
+    }
+}
+
+impl Behavior for FollowShot {
+    fn name(&self) -> &str {
+        name_of_type!(FollowShot)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::FollowShot);
+
+        let deflection = some_or_else!(Self::deflection(ctx), {
+            ctx.eeg.log(self.name(), "the shot is over");
+            return Action::Return;
+        });
+        let deflection_loc = deflection.loc.to_2d();
+        let spot = rebound_spot(
+            ctx.game.enemy_goal(),
+            deflection_loc,
+            deflection.vel.to_2d(),
+        );
+        ctx.eeg.draw(Drawable::ghost_ball(deflection.loc));
+        ctx.eeg.draw(Drawable::Crosshair(spot));
+
+        let me = ctx.me();
+        if (spot - me.Physics.loc_2d()).norm() >= Self::ARRIVE_DIST {
+            return Action::Yield(drive_towards(ctx, spot));
+        }
+
+        // Hold the spot, facing where the ball comes out.
+        Action::Yield(hold_position(me, deflection_loc))
+    }
+}
+
+/// Where to wait for a ball that meets the goal at `loc`, moving at `vel`.
+fn rebound_spot(goal: &Goal, loc: Point2<f32>, vel: Vector2<f32>) -> Point2<f32> {
+    let normal = goal.normal_2d.into_inner();
+    let incoming = vel.try_normalize(1e-3).unwrap_or(-normal);
+    let bounce = incoming - normal * (2.0 * incoming.dot(&normal));
+    let angle = goal.normal_2d.rotation_to(&bounce.to_axis()).angle();
+    let angle = angle
+        .max(-FollowShot::CONE_HALF_ANGLE)
+        .min(FollowShot::CONE_HALF_ANGLE);
+    let outgoing = UnitComplex::new(angle) * normal;
+
+    let spot = loc + outgoing * FollowShot::REBOUND_DIST;
+    let max_x = rl::FIELD_MAX_X - FollowShot::SIDE_WALL_GAP;
+    Point2::new(spot.x.max(-max_x).min(max_x), spot.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::offense::follow_shot::rebound_spot, strategy::SOCCAR_GOAL_ORANGE};
+    use common::rl;
+    use nalgebra::{Point2, Vector2};
+
+    #[test]
+    fn straight_shot_comes_straight_out() {
+        let goal = &*SOCCAR_GOAL_ORANGE;
+        let loc = Point2::new(0.0, rl::FIELD_MAX_Y - 200.0);
+        let spot = rebound_spot(goal, loc, Vector2::new(0.0, 2000.0));
+        assert!(spot.x.abs() < 1.0);
+        assert!((spot.y - (loc.y - 1500.0)).abs() < 1.0);
+    }
+
+    #[test]
+    fn angled_shot_comes_out_the_other_side() {
+        let goal = &*SOCCAR_GOAL_ORANGE;
+        let loc = Point2::new(0.0, rl::FIELD_MAX_Y - 200.0);
+        let spot = rebound_spot(goal, loc, Vector2::new(1000.0, 2000.0));
+        assert!(spot.x > 0.0);
+        assert!(spot.y < loc.y - 1000.0);
+
+        // Even a shot along the goal line doesn't come out flat.
+        let spot = rebound_spot(goal, loc, Vector2::new(2000.0, 1.0));
+        assert!(loc.y - spot.y >= 1500.0 * (std::f32::consts::PI / 4.0).cos() - 1.0);
+    }
+}
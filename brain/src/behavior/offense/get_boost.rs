@@ -0,0 +1,92 @@
+use crate::{
+    behavior::higher_order::{Predicate, While},
+    eeg::{Drawable, Event},
+    routing::{behavior::FollowRoute, plan::GetDollar},
+    strategy::{Action, Behavior, Context},
+};
+use common::{prelude::*, rl};
+use nalgebra::Point2;
+use nameof::name_of_type;
+
+/// When we have the ball to ourselves for a while, go grab the nearest big pad
+/// that's up instead of idling.
+pub struct GetBoost;
+
+impl GetBoost {
+    /// Only leave the ball alone if we'll beat the enemy to it by this much,
+    const COMFORTABLE_POSSESSION: f32 = 2.0;
+    /// and the trip to the pad leaves at least this much of that to spare.
+    const SPARE_TIME: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns `true` if there's a pad up that we can get to well before the
+    /// enemy gets to the ball.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        let possession = ctx.scenario.possession();
+        if possession < Self::COMFORTABLE_POSSESSION {
+            return false;
+        }
+
+        let me_loc = ctx.me().Physics.loc_2d();
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let (_, pad) = some_or_else!(ctx.boost.nearest_dollar(me_loc, now), {
+            return false;
+        });
+        let travel_time = (pad.loc - me_loc).norm() / rl::CAR_NORMAL_SPEED;
+        travel_time < possession - Self::SPARE_TIME
+    }
+}
+
+impl Behavior for GetBoost {
+    fn name(&self) -> &str {
+        name_of_type!(GetBoost)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::GetBoost);
+
+        let me_loc = ctx.me().Physics.loc_2d();
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let (index, pad) = some_or_else!(ctx.boost.nearest_dollar(me_loc, now), {
+            ctx.eeg.log(self.name(), "no pads are up");
+            return Action::Abort;
+        });
+
+        ctx.eeg.log_pretty(self.name(), "pad", pad.loc);
+        let ball_loc = ctx.scenario.ball_prediction().at_time_or_last(2.0).loc;
+        let route = GetDollar::new(ball_loc.to_2d()).pickup(&pad.pickup());
+        Action::tail_call(While::new(
+            PadIsUp::new(index, pad.loc),
+            FollowRoute::new(route),
+        ))
+    }
+}
+
+/// Returns `false` once the pad goes on cooldown, whether because we took it
+/// or somebody beat us to it.
+struct PadIsUp {
+    index: usize,
+    loc: Point2<f32>,
+}
+
+impl PadIsUp {
+    fn new(index: usize, loc: Point2<f32>) -> Self {
+        Self { index, loc }
+    }
+}
+
+impl Predicate for PadIsUp {
+    fn name(&self) -> &str {
+        name_of_type!(PadIsUp)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let fastest = (self.loc - ctx.me().Physics.loc_2d()).norm() / rl::CAR_MAX_SPEED;
+        ctx.eeg.draw(Drawable::Crosshair(self.loc));
+        ctx.boost.time_until_up(self.index, now) <= fastest
+    }
+}
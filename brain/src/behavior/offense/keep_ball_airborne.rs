@@ -0,0 +1,133 @@
+use crate::{
+    behavior::strike::{
+        DoubleJumpAerial, GroundedHit, GroundedHitAimContext, GroundedHitTarget,
+        GroundedHitTargetAdjust,
+    },
+    eeg::{Drawable, Event},
+    helpers::{
+        ball::{BallFrame, BallTrajectory},
+        intercept::{naive_ground_intercept_2, NaiveIntercept},
+    },
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{Action, Behavior, Context, Priority, Scenario},
+};
+use common::{prelude::*, rl};
+use nameof::name_of_type;
+
+/// At 0:00, the game goes on until the ball touches the ground. If we still
+/// need a goal, get a touch in before it lands, and pop it up towards the enemy
+/// goal to buy another chance.
+pub struct KeepBallAirborne;
+
+impl KeepBallAirborne {
+    /// The ball counts as landed once its bottom is this close to the floor.
+    const LANDING_MARGIN: f32 = 10.0;
+
+    pub fn new() -> Self {
+        KeepBallAirborne
+    }
+
+    /// When the ball will next touch the ground, if it does so within the
+    /// prediction.
+    pub fn landing_time(ball_prediction: &BallTrajectory) -> Option<f32> {
+        ball_prediction
+            .iter()
+            .find(|ball| ball.loc.z < rl::BALL_RADIUS + Self::LANDING_MARGIN)
+            .map(|ball| ball.t)
+    }
+
+    fn intercept(
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<NaiveIntercept<()>> {
+        let landing_time = Self::landing_time(scenario.ball_prediction());
+        naive_ground_intercept_2(&car.into(), scenario.ball_prediction(), |ball| {
+            Self::before_landing(landing_time, ball)
+        })
+    }
+
+    fn before_landing(landing_time: Option<f32>, ball: &BallFrame) -> Option<()> {
+        if ball.loc.z >= GroundedHitTarget::MAX_BALL_Z
+            || landing_time.map_or(false, |t| ball.t >= t)
+        {
+            return None;
+        }
+        Some(())
+    }
+
+    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        let intercept = Self::intercept(ctx.scenario, ctx.car).ok_or_else(|| {
+            ctx.eeg
+                .log(name_of_type!(KeepBallAirborne), "it lands first");
+        })?;
+
+        // Jumping makes contact below the ball's center, which sends it up.
+        let aim_loc = ctx.game.enemy_goal().center_2d;
+        Ok(
+            GroundedHitTarget::new(intercept.time, GroundedHitTargetAdjust::RoughAim, aim_loc)
+                .jump(true)
+                .dodge(false),
+        )
+    }
+}
+
+impl Behavior for KeepBallAirborne {
+    fn name(&self) -> &str {
+        name_of_type!(KeepBallAirborne)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::KeepBallAirborne);
+
+        let landing_time = Self::landing_time(ctx.scenario.ball_prediction());
+        if let Some(landing_time) = landing_time {
+            ctx.eeg.print_time("landing_time", landing_time);
+        }
+
+        if let Some(intercept) = Self::intercept(&ctx.scenario, ctx.me()) {
+            ctx.eeg.draw(Drawable::ghost_ball(intercept.ball_loc));
+            return Action::tail_call(chain!(
+                Priority::Strike,
+                [
+                    FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
+                    GroundedHit::hit_towards(Self::aim),
+                ]
+            ));
+        }
+
+        // Too high to reach from the ground. This is the last chance anyway, so
+        // take the aerial even if it's a long shot.
+        if ctx.difficulty.aerials {
+            let intercept =
+                DoubleJumpAerial::intercept(ctx.game, ctx.me(), ctx.scenario.ball_prediction());
+            let reachable = intercept.map_or(false, |i| landing_time.map_or(true, |t| i.time < t));
+            if reachable {
+                return Action::tail_call(DoubleJumpAerial::new());
+            }
+        }
+
+        ctx.eeg
+            .log(self.name(), "the ball lands before we get there");
+        Action::Abort
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{behavior::offense::KeepBallAirborne, helpers::ball::BallTrajectory};
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn landing_time() {
+        // Falling from 500uu at a constant 1000uu/s lands after about 0.4s.
+        let start = Point3::new(0.0, 3000.0, 500.0);
+        let vel = Vector3::new(0.0, 0.0, -1000.0);
+        let trajectory = BallTrajectory::from_samples((0..120).map(|i| {
+            let t = i as f32 * rl::PHYSICS_DT;
+            (t, start + vel * t, vel)
+        }));
+        let t = KeepBallAirborne::landing_time(&trajectory).unwrap();
+        assert!((t - (500.0 - rl::BALL_RADIUS) / 1000.0).abs() < 0.02);
+    }
+}
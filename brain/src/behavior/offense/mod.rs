@@ -1,8 +1,20 @@
 pub use self::{
-    offense::Offense, reset_behind_ball::ResetBehindBall, shoot::Shoot, tepid_hit::TepidHit,
+    backboard_pass::BackboardPass, catch::Catch, corner_wait::CornerWait, demo_enemy::DemoEnemy,
+    dribble::Dribble, fake_shot::FakeShot, follow_shot::FollowShot, get_boost::GetBoost,
+    keep_ball_airborne::KeepBallAirborne, offense::Offense, reset_behind_ball::ResetBehindBall,
+    shoot::Shoot, tepid_hit::TepidHit,
 };
 
+mod backboard_pass;
 mod bounce_dribble;
+mod catch;
+mod corner_wait;
+mod demo_enemy;
+mod dribble;
+mod fake_shot;
+mod follow_shot;
+mod get_boost;
+mod keep_ball_airborne;
 #[allow(clippy::module_inception)]
 mod offense;
 mod regroup;
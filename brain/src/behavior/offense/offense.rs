@@ -1,8 +1,15 @@
 use crate::{
-    behavior::offense::{ResetBehindBall, Shoot, TepidHit},
+    behavior::{
+        defense::Defense,
+        offense::{
+            BackboardPass, CornerWait, DemoEnemy, FakeShot, GetBoost, ResetBehindBall, Shoot,
+            TepidHit,
+        },
+        strike::Redirect,
+    },
     eeg::Event,
     helpers::{ball::BallFrame, intercept::naive_ground_intercept_2},
-    routing::{behavior::FollowRoute, models::CarState, plan::GetDollar},
+    routing::{behavior::FollowRoute, plan::GetDollar},
     strategy::{Action, Behavior, Context, Game, Scenario},
     utils::geometry::RayCoordinateSystem,
 };
@@ -25,10 +32,20 @@ impl Behavior for Offense {
         name_of_type!(Offense)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         ctx.eeg.track(Event::Offense);
 
+        if !ctx.scenario.team().may_commit() {
+            ctx.eeg.log(self.name(), "a teammate has the ball");
+            return Action::tail_call(Defense::new());
+        }
+
         if can_we_shoot(ctx) {
+            if FakeShot::applicable(ctx) {
+                ctx.eeg
+                    .log(self.name(), "the keeper is coming out; faking the shot");
+                return Action::tail_call(FakeShot::new());
+            }
             ctx.eeg.log(self.name(), "taking the shot!");
             ctx.quick_chat(0.05, &[
                 rlbot::flat::QuickChatSelection::Information_Incoming,
@@ -36,6 +53,30 @@ impl Behavior for Offense {
             return Action::tail_call(Shoot::new());
         }
 
+        // A ball crossing their box doesn't give us time to line up a normal shot,
+        // but it might be good for a touch on goal.
+        if Redirect::applicable(ctx) {
+            ctx.eeg
+                .log(self.name(), "ball is crossing their box; redirecting");
+            return Action::tail_call(Redirect::new());
+        }
+
+        // If we can't get to the ball anyway, take the enemy out of the play.
+        if DemoEnemy::applicable(ctx) {
+            ctx.eeg
+                .log(self.name(), "ball is out of reach; going for a demo");
+            return Action::tail_call(DemoEnemy::new());
+        }
+
+        // Don't chase the ball into their corner for nothing.
+        if CornerWait::applicable(ctx) {
+            ctx.eeg.log(
+                self.name(),
+                "ball is pinned in their corner; waiting it out",
+            );
+            return Action::tail_call(CornerWait::new());
+        }
+
         // TODO: if angle is almost good, slightly adjust path such that good_angle
         // becomes true
 
@@ -59,6 +100,15 @@ impl Behavior for Offense {
             return action;
         }
 
+        // With no shot but time on the ball, set up our own rebound.
+        if BackboardPass::applicable(ctx) {
+            ctx.eeg.log(
+                self.name(),
+                "no shot; passing to ourselves off the backboard",
+            );
+            return Action::tail_call(BackboardPass::new());
+        }
+
         ctx.eeg
             .log(self.name(), "no good hit; going for a tepid hit");
         Action::tail_call(TepidHit::new())
@@ -89,7 +139,7 @@ fn can_we_shoot(ctx: &mut Context<'_>) -> bool {
     let shoot_intercept = naive_ground_intercept_2(
         &me.into(),
         ctx.scenario.ball_prediction().iter_step_by(0.125),
-        |ball| Shoot::viable_shot(ctx.game, me.Physics.loc(), ball.loc),
+        |ball| Shoot::viable_shot(ctx.game, me.Physics.loc(), ball),
     );
 
     let shoot_intercept = some_or_else!(shoot_intercept, {
@@ -143,17 +193,23 @@ fn flat_and_defensive(ctx: &mut Context<'_>) -> bool {
         && (ball_loc - car_loc).norm() < min_dist
 }
 
-fn slow_play(ctx: &mut Context<'_>) -> Option<Action> {
-    // Only slow play if we have enough time.
-    if ctx.scenario.possession() < 2.0 {
-        ctx.eeg
-            .log(name_of_type!(Offense), "slow_play: need possession");
-        return None;
-    }
+/// How long we need the ball to ourselves to bother setting up a slow shot.
+const SLOW_PLAY_PEACE: f32 = 1.5;
 
+fn slow_play(ctx: &mut Context<'_>) -> Option<Action> {
     let intercept = ctx.scenario.me_intercept()?;
     let ball_loc = intercept.ball_loc.to_2d();
 
+    // Only slow play if we'll have the ball to ourselves for a while.
+    let peace = ctx.scenario.time_to_pressure() - intercept.time;
+    if ctx.scenario.possession() < 2.0 || peace < SLOW_PLAY_PEACE {
+        ctx.eeg.log(
+            name_of_type!(Offense),
+            format!("slow_play: need possession and peace ({:.2})", peace),
+        );
+        return None;
+    }
+
     if let Some(adjust) = readjust_for_shot(ctx, intercept.time) {
         return Some(adjust);
     }
@@ -176,7 +232,7 @@ fn slow_play(ctx: &mut Context<'_>) -> Option<Action> {
     let ball = ctx.scenario.ball_prediction().at_time_or_last(2.5);
     let ball_loc = ball.loc.to_2d();
 
-    if ctx.me().Boost < 50 {
+    if ctx.me().Boost < ctx.personality.boost_reserve {
         ctx.eeg.log(
             name_of_type!(Offense),
             "slow_play: getting boost conveniently behind the ball",
@@ -230,7 +286,19 @@ fn readjust_for_shot(ctx: &mut Context<'_>, intercept_time: f32) -> Option<Actio
 }
 
 fn get_boost(ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
-    if ctx.me().Boost > 50 {
+    if !ctx.game.mutators().boost_pads_matter() {
+        ctx.eeg
+            .log(name_of_type!(Offense), "get_boost: boost is unlimited");
+        return None;
+    }
+
+    // If the meter refills by itself, only detour when it's nearly empty.
+    let reserve = if ctx.game.mutators().boost_recharge {
+        ctx.personality.boost_reserve / 2
+    } else {
+        ctx.personality.boost_reserve
+    };
+    if ctx.me().Boost > reserve {
         ctx.eeg
             .log(name_of_type!(Offense), "get_boost: already have enough");
         return None;
@@ -259,22 +327,12 @@ fn get_boost(ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
         return Some(Box::new(FollowRoute::new(GetDollar::smart(&ctx, eeg))));
     }
 
-    if ctx.scenario.possession() >= 2.0 {
-        let ball = ctx.scenario.ball_prediction().at_time_or_last(2.0);
-        if let Some(pickup) = GetDollar::choose_pickup(
-            ctx.game.boost_dollars(),
-            &CarState::from(ctx.me()).to_2d_assume(),
-            ball.loc.to_2d(),
-            ctx.game.enemy_goal(),
-        ) {
-            if (pickup.loc - ctx.me().Physics.loc_2d()).norm() < 1000.0 {
-                ctx.eeg.log(
-                    name_of_type!(Offense),
-                    "get_boost: we're close and we have time",
-                );
-                return Some(Box::new(FollowRoute::new(GetDollar::new(ball.loc.to_2d()))));
-            }
-        }
+    if GetBoost::applicable(ctx) {
+        ctx.eeg.log(
+            name_of_type!(Offense),
+            "get_boost: we have time and there's a pad up",
+        );
+        return Some(Box::new(GetBoost::new()));
     }
 
     ctx.eeg.log(name_of_type!(Offense), "get_boost: let's not");
@@ -368,6 +426,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn wait_for_curl_around_lip_near_post() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2972.6848, 1018.38824, 101.33544),
@@ -386,6 +445,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn in_corner_barely_cant_reach() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2230.9802, 2748.329, 93.14),
@@ -403,6 +463,7 @@ mod integration_tests {
 
     #[test]
     fn wait_for_ball_to_fall() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3987.7068, -2086.639, 329.19128),
@@ -422,6 +483,7 @@ mod integration_tests {
 
     #[test]
     fn shoot_across_the_goal() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1438.1412, 4697.7017, 92.71),
@@ -439,6 +501,7 @@ mod integration_tests {
 
     #[test]
     fn awkward_breakaway_shot() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1463.9786, -1842.5327, 93.15),
@@ -457,6 +520,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn juicy_bouncing() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3811.8657, 1580.2241, 1172.8545),
@@ -473,6 +537,7 @@ mod integration_tests {
 
     #[test]
     fn tepid_hit_from_own_goal() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2869.6829, -4145.095, 97.65185),
@@ -494,6 +559,7 @@ mod integration_tests {
 
     #[test]
     fn slow_play_get_boost() {
+        tags!(Offense);
         // The setup: we have possession and low boost, and we're on the wrong side of
         // the ball to hit it towards the enemy goal.
         let test = TestRunner::new()
@@ -516,6 +582,7 @@ mod integration_tests {
 
     #[test]
     fn swing_around_and_shoot() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-480.59, -2596.8699, 200.25),
@@ -535,6 +602,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "oops, this got broken at some point")]
     fn swing_around_while_retreating_but_not_too_far() {
+        tags!(Offense);
         let test = TestRunner::new()
             .one_v_one(
                 &*recordings::SWING_AROUND_WHILE_RETREATING_BUT_NOT_TOO_FAR,
@@ -554,6 +622,7 @@ mod integration_tests {
 
     #[test]
     fn dont_delay_shot_without_possession() {
+        tags!(Offense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::DONT_DELAY_SHOT_WITHOUT_POSSESSION, 27.0)
             .starting_boost(0.0)
@@ -568,6 +637,7 @@ mod integration_tests {
 
     #[test]
     fn dont_try_to_save_if_we_can_shoot_instead() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2188.9, 2587.98, 709.94),
@@ -6,6 +6,7 @@ mod integration_tests {
 
     #[test]
     fn choose_a_side() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1042.4299, 1818.3915, 93.14645),
@@ -27,6 +28,7 @@ mod integration_tests {
 
     #[test]
     fn fast_push_to_corner() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3231.1572, 1764.513, 92.159996),
@@ -1,7 +1,7 @@
 use crate::{
     routing::{
         behavior::FollowRoute,
-        plan::{GetDollar, GroundDrive},
+        plan::{BoostAwarePlanner, GetDollar, GroundDrive},
     },
     strategy::{Action, Behavior, BoostPickup, Context},
 };
@@ -36,7 +36,7 @@ impl Behavior for ResetBehindBall {
         name_of_type!(ResetBehindBall)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let target_loc = self.get_sane_drive_loc(ctx);
         if let Some(pickup) = self.snap_to_boost_if_close(ctx, target_loc) {
             return Action::tail_call(FollowRoute::new(GetDollar::new(self.loc).pickup(pickup)));
@@ -46,7 +46,7 @@ impl Behavior for ResetBehindBall {
             .end_chop(0.5)
             .always_prefer_dodge(true);
         Action::tail_call(
-            FollowRoute::new(straight)
+            FollowRoute::new(BoostAwarePlanner::new(straight))
                 .same_ball_trajectory(true)
                 .never_recover(self.never_recover),
         )
@@ -123,10 +123,11 @@ impl ResetBehindBall {
             return None;
         }
         let distance = linear_interpolate(&[0.0, 50.0], &[1000.0, 250.0], ctx.me().Boost as f32);
+        let me_loc = ctx.me().Physics.loc_2d();
         for pickup in ctx.game.boost_dollars() {
             if (loc - pickup.loc).norm() < distance
-                && (pickup.loc - ctx.me().Physics.loc_2d()).norm()
-                    >= (loc - ctx.me().Physics.loc_2d()).norm()
+                && (pickup.loc - me_loc).norm() >= (loc - me_loc).norm()
+                && ctx.boost.pad_up_in_time(pickup.loc, me_loc)
             {
                 ctx.eeg
                     .log(name_of_type!(ResetBehindBall), "snapping to boost");
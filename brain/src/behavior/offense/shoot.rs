@@ -6,66 +6,139 @@ use crate::{
             GroundedHitTargetAdjust,
         },
     },
-    helpers::intercept::{naive_ground_intercept_2, NaiveIntercept},
+    helpers::{
+        ball::BallFrame,
+        hit_outcome::{ball_vel_after_hit, goal_line_crossing_z, is_over_crossbar},
+        intercept::{naive_ground_intercept_2, NaiveIntercept},
+        line_of_sight::Corridor,
+    },
     routing::{behavior::FollowRoute, plan::GroundIntercept},
     strategy::{Action, Behavior, Context, Game, Priority, Scenario},
 };
-use common::{prelude::*, Speed};
+use common::{prelude::*, rl, Coordinate, Speed};
 use nalgebra::{Point2, Point3};
 use nameof::name_of_type;
 use simulate::linear_interpolate;
-use std::f32::consts::PI;
+use std::{f32::consts::PI, iter};
 
 pub struct Shoot;
 
 impl Shoot {
+    /// When jumping without a dodge, the nose is pitched up, so the car meets the
+    /// ball below its center.
+    const JUMP_CONTACT_BELOW: f32 = 50.0;
+    /// The dodge rotates the nose down into the ball, so contact is closer to
+    /// the center.
+    const DODGE_CONTACT_BELOW: f32 = 20.0;
+
     pub fn new() -> Self {
         Self
     }
 
-    pub fn viable_shot(
-        game: &Game<'_>,
-        car_loc: Point3<f32>,
-        ball_loc: Point3<f32>,
-    ) -> Option<Shot> {
+    pub fn viable_shot(game: &Game<'_>, car_loc: Point3<f32>, ball: &BallFrame) -> Option<Shot> {
         // Aerials are not ready for prime-time yet
-        if ball_loc.z >= GroundedHitTarget::MAX_BALL_Z {
+        if ball.loc.z >= GroundedHitTarget::MAX_BALL_Z {
             return None;
         }
 
         let goal = game.enemy_goal();
-        let aim_loc = BounceShot::aim_loc(goal, car_loc.to_2d(), ball_loc.to_2d());
+        let ball_loc = ball.loc.to_2d();
+
+        // Prefer the usual aim, but if a defender is in the way, try either side
+        // of the goal instead.
+        let preferred = BounceShot::aim_loc(goal, car_loc.to_2d(), ball_loc);
+        let alternates = [-1.0, 1.0]
+            .iter()
+            .map(|&side| Point2::new(side * goal.max_x * 0.6, goal.center_2d.y));
+        let aim_loc = iter::once(preferred)
+            .chain(alternates)
+            .filter(|&aim_loc| Self::shot_angle_ok(game, car_loc, ball_loc, aim_loc))
+            .find(|&aim_loc| {
+                let corridor = Corridor::new(ball_loc, aim_loc).start_time(ball.t);
+                corridor.first_blocker(game.cars(game.enemy_team)).is_none()
+            })?;
+
+        Some(Shot { aim_loc })
+    }
 
+    fn shot_angle_ok(
+        game: &Game<'_>,
+        car_loc: Point3<f32>,
+        ball_loc: Point2<f32>,
+        aim_loc: Point2<f32>,
+    ) -> bool {
         if aim_loc.x.abs() >= 900.0 {
-            return None;
+            return false;
         }
 
-        let ball_to_goal = aim_loc - ball_loc.to_2d();
-        let car_to_ball = ball_loc.to_2d() - car_loc.to_2d();
+        let ball_to_goal = aim_loc - ball_loc;
+        let car_to_ball = ball_loc - car_loc.to_2d();
         if car_to_ball.angle_to(&ball_to_goal).abs() >= 45.0_f32.to_radians() {
-            return None;
+            return false;
         }
 
-        let goal_angle = (ball_loc.to_2d() - aim_loc)
+        let goal_angle = (ball_loc - aim_loc)
             .to_axis()
-            .angle_to(&goal.normal_2d);
-        if goal_angle.abs() >= PI * (5.0 / 12.0) {
-            return None;
-        }
-
-        Some(Shot { aim_loc })
+            .angle_to(&game.enemy_goal().normal_2d);
+        goal_angle.abs() < PI * (5.0 / 12.0)
     }
 
     fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
-        match Self::aim_calc(ctx.game, ctx.scenario, ctx.car) {
-            Some(i) => Ok(GroundedHitTarget::new(
-                i.time,
-                GroundedHitTargetAdjust::RoughAim,
-                i.data.aim_loc,
-            )
-            .jump(!Self::is_chippable(ctx, &i))),
-            None => Err(()),
+        let i = Self::aim_calc(ctx.game, ctx.scenario, ctx.car).ok_or(())?;
+
+        // Try each kind of hit in order of preference, and take the first one that
+        // doesn't sail over the crossbar. The fallbacks make contact lower on the
+        // ball, or hit it more softly.
+        let styles: &[HitStyle] = if Self::is_chippable(ctx, &i) {
+            &[HitStyle::Chip, HitStyle::JumpAndDodge, HitStyle::Jump]
+        } else {
+            &[HitStyle::JumpAndDodge, HitStyle::Jump]
+        };
+        let style = styles
+            .iter()
+            .cloned()
+            .find(|&style| {
+                let entry_z = Self::goal_entry_z(ctx.game, &i, style);
+                !entry_z.map(is_over_crossbar).unwrap_or(false)
+            })
+            .ok_or_else(|| {
+                ctx.eeg
+                    .log(name_of_type!(Shoot), "every shot goes over the crossbar")
+            })?;
+        if let Some(entry_z) = Self::goal_entry_z(ctx.game, &i, style) {
+            ctx.eeg.print_value("entry_z", Coordinate(entry_z));
         }
+
+        Ok(
+            GroundedHitTarget::new(i.time, GroundedHitTargetAdjust::RoughAim, i.data.aim_loc)
+                .jump(style != HitStyle::Chip)
+                .dodge(style == HitStyle::JumpAndDodge),
+        )
+    }
+
+    /// Estimate the height at which the shot would reach the goal line.
+    fn goal_entry_z(
+        game: &Game<'_>,
+        intercept: &NaiveIntercept<Shot>,
+        style: HitStyle,
+    ) -> Option<f32> {
+        let ball_loc = intercept.ball_loc;
+        let forward = (ball_loc - intercept.car_loc).to_2d().to_axis();
+        let contact_dist = game.ball_radius() + game.me_vehicle().pivot_to_front_dist();
+        let (car_z, car_speed) = match style {
+            HitStyle::Chip => (rl::OCTANE_NEUTRAL_Z, intercept.car_speed),
+            HitStyle::Jump => (ball_loc.z - Self::JUMP_CONTACT_BELOW, intercept.car_speed),
+            HitStyle::JumpAndDodge => (
+                ball_loc.z - Self::DODGE_CONTACT_BELOW,
+                intercept.car_speed + rl::DODGE_FORWARD_IMPULSE,
+            ),
+        };
+        let car_loc = (ball_loc.to_2d() - forward.as_ref() * contact_dist).to_3d(car_z);
+        let car_forward = forward.to_3d();
+        let car_vel = car_forward.into_inner() * car_speed;
+        let ball_vel =
+            ball_vel_after_hit(car_loc, car_vel, &car_forward, ball_loc, intercept.ball_vel);
+        goal_line_crossing_z(game.enemy_goal(), ball_loc, ball_vel)
     }
 
     pub fn is_chippable(
@@ -158,7 +231,7 @@ impl Shoot {
         car: &common::halfway_house::PlayerInfo,
     ) -> Option<NaiveIntercept<Shot>> {
         naive_ground_intercept_2(&car.into(), scenario.ball_prediction(), |ball| {
-            Self::viable_shot(game, car.Physics.loc(), ball.loc)
+            Self::viable_shot(game, car.Physics.loc(), ball)
         })
     }
 }
@@ -167,12 +240,20 @@ pub struct Shot {
     aim_loc: Point2<f32>,
 }
 
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum HitStyle {
+    /// Drive through the ball without jumping.
+    Chip,
+    Jump,
+    JumpAndDodge,
+}
+
 impl Behavior for Shoot {
     fn name(&self) -> &str {
         name_of_type!(Shoot)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let intercept = Self::aim_calc(ctx.game, &ctx.scenario, ctx.me());
         if intercept.is_none() {
             ctx.eeg.log(self.name(), "no viable shot");
@@ -197,6 +278,7 @@ mod integration_tests {
 
     #[test]
     fn awkwardly_angled_breakaway() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(61.44401, -2595.4697, 94.76237),
@@ -215,6 +297,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "this usually works, but only by coincidence")]
     fn awkward_breakaway_2() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2944.1208, -2035.1736, 309.80853),
@@ -232,6 +315,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "I don't even")]
     fn awkward_bouncing_breakaway() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3083.084, -3752.2727, 567.4716),
@@ -249,6 +333,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "The great bankruptcy of 2018")]
     fn fast_falling_ball() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3862.044, 1163.3925, 1456.4243),
@@ -273,6 +358,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "oops, this got broken at some point")]
     fn speedy_angle_adjust() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(856.57855, 2725.1563, 93.15),
@@ -289,6 +375,7 @@ mod integration_tests {
 
     #[test]
     fn close_fast_rolling_redirect() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(99.87652, 1684.7692, 93.14),
@@ -306,6 +393,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn far_rolling_along_side_wall() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3827.3035, -2371.3047, 103.192085),
@@ -323,6 +411,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn awkward_corner_angle() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3074.1807, 4219.743, 506.9326),
@@ -339,6 +428,7 @@ mod integration_tests {
 
     #[test]
     fn low_boost_shot() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1847.84, 1641.52, 503.91998),
@@ -358,6 +448,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "works, but is flaky")]
     fn high_lobbed_shot() {
+        tags!(Offense, Flaky);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2966.91, 2058.1199, 1604.51),
@@ -375,6 +466,7 @@ mod integration_tests {
 
     #[test]
     fn another_forward_shot() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2723.44, -441.38, 105.6),
@@ -392,6 +484,7 @@ mod integration_tests {
 
     #[test]
     fn chip_it_over_reliefbots_head() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1504.64, 2860.74, 98.34),
@@ -413,6 +506,7 @@ mod integration_tests {
 
     #[test]
     fn chip_it_over_reliefbots_head_2() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2401.8298, 1984.36, 93.14),
@@ -434,6 +528,7 @@ mod integration_tests {
 
     #[test]
     fn chip_it_over_reliefbots_head_3() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(141.26, 1363.0499, 93.15),
@@ -455,6 +550,7 @@ mod integration_tests {
 
     #[test]
     fn dont_abandon_shot() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1672.57, 2171.4, 342.32),
@@ -476,6 +572,7 @@ mod integration_tests {
 
     #[test]
     fn dont_miss_a_slightly_bouncing_ball() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2244.3599, 2427.03, 220.15),
@@ -494,6 +591,7 @@ mod integration_tests {
 
     #[test]
     fn dont_give_up_when_ball_prediction_has_a_blip() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2550.02, 2887.8499, 1467.25),
@@ -512,6 +610,7 @@ mod integration_tests {
 
     #[test]
     fn wait_for_ball_to_cross_net() {
+        tags!(Offense);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3343.14, 2892.47, 139.55),
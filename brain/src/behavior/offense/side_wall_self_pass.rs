@@ -7,6 +7,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn basic() {
+        tags!(Offense, Wall);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2655.6062, 1005.91815, 102.159805),
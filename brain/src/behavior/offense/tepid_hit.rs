@@ -1,14 +1,20 @@
 use crate::{
     behavior::{
         defense::Defense,
+        offense::{Catch, Dribble},
         strike::{
-            GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust, WallHit,
+            DoubleJumpAerial, GroundedHit, GroundedHitAimContext, GroundedHitTarget,
+            GroundedHitTargetAdjust, WallHit,
         },
     },
     eeg::{color, Drawable, Event, EEG},
-    helpers::hit_angle::{blocking_angle, feasible_hit_angle_away, feasible_hit_angle_toward},
+    helpers::{
+        hit_angle::{blocking_angle, feasible_hit_angle_away, feasible_hit_angle_toward},
+        line_of_sight::Corridor,
+    },
     routing::{
         behavior::FollowRoute,
+        fallback::FallbackChain,
         plan::{GetDollar, GroundIntercept, WallIntercept},
     },
     strategy::{Action, Behavior, Context, Context2, Priority, Scenario},
@@ -34,12 +40,39 @@ impl Behavior for TepidHit {
         name_of_type!(TepidHit)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if !ctx.scenario.team().may_commit() {
+            ctx.eeg.log(self.name(), "a teammate has the ball");
+            return Action::Abort;
+        }
+
+        // A carry keeps possession, where any hit would give it away.
+        let dribbles = ctx.difficulty.dribbles;
+        if dribbles && Dribble::can_carry(ctx.me(), &ctx.packet.GameBall) {
+            ctx.eeg.log(self.name(), "ball is on the roof; carrying");
+            return Action::tail_call(Dribble::new());
+        }
+
+        // So does catching a ball that's dropping gently.
+        if dribbles && Catch::feasible(ctx) {
+            ctx.eeg
+                .log(self.name(), "ball is dropping gently; catching");
+            return Action::tail_call(Catch::new());
+        }
+
+        let aerials = ctx.difficulty.aerials;
+        let all_in = ctx.risk.all_out_offense();
         let (ctx, eeg) = ctx.split();
 
         let mut hits = ArrayVec::<[_; 4]>::new();
         hits.push(ground(&ctx, eeg));
         hits.push(wall(&ctx, eeg));
+        // The ball might just be too high for either. When we're desperate for a
+        // goal, an aerial is worth a try even if we could wait for the ball to
+        // come down.
+        if aerials && (all_in || hits.iter().all(Option::is_none)) {
+            hits.push(double_jump(&ctx, eeg));
+        }
 
         let hit = hits
             .into_iter()
@@ -63,9 +96,12 @@ impl Behavior for TepidHit {
                 WallHit::new(),
             ])),
             Some((_, HitType::Ground)) => Action::tail_call(chain!(Priority::Strike, [
-                FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
+                FollowRoute::new(GroundIntercept::new())
+                    .replan_on_new_trajectory(true)
+                    .fallbacks(FallbackChain::ground_intercept()),
                 GroundedHit::hit_towards(time_wasting_hit),
             ])),
+            Some((_, HitType::DoubleJump)) => Action::tail_call(DoubleJumpAerial::new()),
             None => Action::tail_call(FollowRoute::new(GetDollar::smart(&ctx, eeg))),
         }
     }
@@ -118,10 +154,25 @@ fn wall<'ball>(ctx: &Context2<'_, 'ball>, eeg: &mut EEG) -> Option<(f32, HitType
     Some((intercept.t, HitType::Wall))
 }
 
+fn double_jump(ctx: &Context2<'_, '_>, eeg: &mut EEG) -> Option<(f32, HitType)> {
+    let intercept = DoubleJumpAerial::intercept(ctx.game, ctx.me(), ctx.scenario.ball_prediction());
+    let intercept = some_or_else!(intercept, {
+        eeg.log(name_of_type!(TepidHit), "double jump: out of reach");
+        return None;
+    });
+    eeg.log(
+        name_of_type!(TepidHit),
+        format!("double jump: intercept is {}", intercept.ball_loc.pretty()),
+    );
+
+    Some((intercept.time, HitType::DoubleJump))
+}
+
 #[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
 enum HitType {
     Ground,
     Wall,
+    DoubleJump,
 }
 
 fn time_wasting_hit(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
@@ -226,13 +277,25 @@ fn offensive_aim(
     ctx.eeg.print_angle("rot_progress", rot_progress.angle());
     ctx.eeg.print_angle("rot_easy", rot_easy.angle());
 
-    // If the enemy isn't near the better aim, hit it there
-    if rot_progress.angle_to(&rot_to_enemy).abs() >= PI / 6.0 {
+    let (game, intercept_time) = (ctx.game, ctx.intercept_time);
+    let open = |aim_loc| {
+        Corridor::new(ball_loc, aim_loc)
+            .start_time(intercept_time)
+            .first_blocker(game.cars(game.enemy_team))
+            .is_none()
+    };
+
+    // If nobody will be in the way of the better aim, hit it there
+    if open(progress) {
         ctx.eeg.draw(Drawable::print("ideal", color::GREEN));
         return (progress, GroundedHitTargetAdjust::RoughAim);
     }
+    if open(easy) {
+        ctx.eeg.draw(Drawable::print("easy (open)", color::GREEN));
+        return (easy, GroundedHitTargetAdjust::RoughAim);
+    }
 
-    // If the enemy is there, choose the spot where the enemy is not.
+    // If both are covered, choose the spot further from the enemy.
     if rot_progress.angle_to(&rot_to_enemy).abs() < rot_easy.angle_to(&rot_to_enemy).abs() {
         ctx.eeg.draw(Drawable::print("easy", color::GREEN));
         (easy, GroundedHitTargetAdjust::RoughAim)
@@ -288,6 +351,7 @@ mod integration_tests {
 
     #[test]
     fn tepid_save() {
+        tags!(Offense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::TEPID_SAVE, 187.5)
             .soccar()
@@ -305,6 +369,7 @@ mod integration_tests {
 
     #[test]
     fn dont_pass_to_opponent() {
+        tags!(Offense);
         let test = TestRunner::new()
             .one_v_one(&*recordings::DONT_PASS_TO_OPPONENT, 459.0)
             .starting_boost(0.0)
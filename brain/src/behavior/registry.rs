@@ -0,0 +1,104 @@
+use crate::{
+    behavior::{
+        defense::{
+            AvoidOwnGoal, BackwallClear, ContainSlowPlay, ContainWallCarry, Defense, FakeChallenge,
+            Goalkeeper, HitToOwnCorner, PanicDefense, PushToOwnCorner, Retreat, Shadow, Support,
+            TimedJumpSave,
+        },
+        movement::{
+            Calibrate, CeilingDrop, Dodge, GetToFlatGround, HalfFlip, QuickJumpAndDodge, WaveDash,
+        },
+        offense::{
+            BackboardPass, Catch, CornerWait, DemoEnemy, Dribble, FakeShot, FollowShot, GetBoost,
+            KeepBallAirborne, Offense, Shoot, TepidHit,
+        },
+        strike::{DoubleJumpAerial, FiftyFifty, Redirect, WallHit},
+        taunt::{PodiumBlastoff, PodiumSpew, PodiumStare, SaltWhileDemolished, TurtleSpin},
+        FakeKickoff, PreKickoff,
+    },
+    strategy::Behavior,
+};
+
+macro_rules! registry {
+    ($($name:ident => $ctor:expr,)*) => {
+        /// The names accepted by `behavior_by_name`.
+        pub const BEHAVIOR_NAMES: &[&str] = &[$(stringify!($name)),*];
+
+        /// Construct a behavior given the name it reports from `Behavior::name`,
+        /// so tests and tools can refer to behaviors in config, e.g.,
+        /// `behavior = "WallHit"`.
+        ///
+        /// Only behaviors which can be built without arguments are listed.
+        pub fn behavior_by_name(name: &str) -> Option<Box<dyn Behavior>> {
+            match name {
+                $(stringify!($name) => Some(Box::new($ctor)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+registry! {
+    AvoidOwnGoal => AvoidOwnGoal::new(),
+    BackboardPass => BackboardPass::new(),
+    BackwallClear => BackwallClear::new(),
+    Calibrate => Calibrate::new(),
+    Catch => Catch::new(),
+    CeilingDrop => CeilingDrop::new(),
+    ContainSlowPlay => ContainSlowPlay::new(),
+    ContainWallCarry => ContainWallCarry::new(),
+    CornerWait => CornerWait::new(),
+    Defense => Defense::new(),
+    DemoEnemy => DemoEnemy::new(),
+    Dodge => Dodge::new(),
+    DoubleJumpAerial => DoubleJumpAerial::new(),
+    Dribble => Dribble::new(),
+    FakeChallenge => FakeChallenge::new(),
+    FakeKickoff => FakeKickoff::new(),
+    FakeShot => FakeShot::new(),
+    FiftyFifty => FiftyFifty::new(),
+    FollowShot => FollowShot::new(),
+    GetBoost => GetBoost::new(),
+    GetToFlatGround => GetToFlatGround::new(),
+    Goalkeeper => Goalkeeper::new(),
+    HalfFlip => HalfFlip::new(),
+    HitToOwnCorner => HitToOwnCorner::new(),
+    KeepBallAirborne => KeepBallAirborne::new(),
+    Offense => Offense::new(),
+    PanicDefense => PanicDefense::new(),
+    PodiumBlastoff => PodiumBlastoff::new(),
+    PodiumSpew => PodiumSpew::new(),
+    PodiumStare => PodiumStare::new(),
+    PreKickoff => PreKickoff::new(),
+    PushToOwnCorner => PushToOwnCorner::new(),
+    QuickJumpAndDodge => QuickJumpAndDodge::new(),
+    Redirect => Redirect::new(),
+    Retreat => Retreat::new(),
+    SaltWhileDemolished => SaltWhileDemolished::new(),
+    Shadow => Shadow::new(),
+    Shoot => Shoot::new(),
+    Support => Support::new(),
+    TepidHit => TepidHit::new(),
+    TimedJumpSave => TimedJumpSave::new(),
+    TurtleSpin => TurtleSpin::new(),
+    WallHit => WallHit::new(),
+    WaveDash => WaveDash::new(),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::registry::{behavior_by_name, BEHAVIOR_NAMES};
+
+    #[test]
+    fn names_round_trip() {
+        for &name in BEHAVIOR_NAMES {
+            let behavior = behavior_by_name(name).unwrap();
+            assert_eq!(behavior.name(), name);
+        }
+    }
+
+    #[test]
+    fn unknown_name() {
+        assert!(behavior_by_name("Telekinesis").is_none());
+    }
+}
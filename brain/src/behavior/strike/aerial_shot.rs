@@ -7,6 +7,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn easy_in_front_of_goal() {
+        tags!(Strike, Aerial);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2947.987, 2573.8042, 954.9597),
@@ -129,6 +129,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn normal() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2000.0, 2000.0, 500.0),
@@ -145,6 +146,7 @@ mod integration_tests {
 
     #[test]
     fn slow_no_boost() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2000.0, 2000.0, 1000.0),
@@ -163,6 +165,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn face_target_before_estimating_approach() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(866.92804, -4290.7188, 353.78827),
@@ -182,6 +185,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn long_high_bouncing_save() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(90.25211, -340.07803, 1487.03),
@@ -0,0 +1,216 @@
+use crate::{
+    behavior::{
+        higher_order::Chain,
+        movement::{simple_steer_towards, Yielder},
+    },
+    eeg::{Drawable, Event},
+    helpers::{
+        ball::BallTrajectory,
+        drive::arrival_control,
+        intercept::{naive_ground_intercept, NaiveIntercept},
+        intercept_predicate::{BelowGroundedReach, BelowZ, InterceptPredicate},
+    },
+    routing::recover::{IsSkidding, NotOnFlatGround},
+    strategy::{Action, Behavior, Context, Game, Mutators, Priority},
+};
+use common::{prelude::*, rl, Coordinate};
+use nalgebra::{Point2, Point3};
+use nameof::name_of_type;
+use simulate::{car_double_jump, Car1D};
+use std::f32::consts::PI;
+use vec_box::vec_box;
+
+/// Hit a ball that's too high for `GroundedHit` by driving underneath it and
+/// jumping twice. Up to a point, that's quicker than boosting up to it.
+pub struct DoubleJumpAerial {
+    contact_planned: bool,
+}
+
+impl DoubleJumpAerial {
+    /// Meet the ball with the car this far below its center, so the roof and
+    /// nose do the hitting.
+    const CONTACT_Z_OFFSET: f32 = -70.0;
+    /// How long to hold the second jump, so the game doesn't miss it.
+    const SECOND_JUMP_HOLD: f32 = 2.0 / 120.0;
+    /// Keep flying at the ball for a bit after we expect to touch it.
+    const FOLLOW_THROUGH_TIME: f32 = 0.25;
+
+    pub fn new() -> Self {
+        Self {
+            contact_planned: false,
+        }
+    }
+
+    /// The highest ball we can reach with a double jump.
+    pub fn max_ball_z(mutators: &Mutators) -> f32 {
+        mutators.double_jump_max_z() - Self::CONTACT_Z_OFFSET
+    }
+
+    /// The first ball we can get underneath that's too high for `GroundedHit`
+    /// but low enough for a double jump.
+    pub fn intercept(
+        game: &Game<'_>,
+        car: &common::halfway_house::PlayerInfo,
+        ball_prediction: &BallTrajectory,
+    ) -> Option<NaiveIntercept> {
+        let predicate = BelowZ(Self::max_ball_z(game.mutators())).and(BelowGroundedReach.not());
+        naive_ground_intercept(
+            ball_prediction.iter(),
+            car.Physics.loc(),
+            car.Physics.vel(),
+            car.Boost as f32,
+            &predicate,
+        )
+    }
+}
+
+impl Behavior for DoubleJumpAerial {
+    fn name(&self) -> &str {
+        name_of_type!(DoubleJumpAerial)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::DoubleJumpAerial);
+
+        let me = ctx.me();
+        if IsSkidding.evaluate(&me.into()) {
+            ctx.eeg.log(self.name(), name_of_type!(IsSkidding));
+            return Action::Abort;
+        }
+        if NotOnFlatGround.evaluate(&me.into()) {
+            ctx.eeg.log(self.name(), name_of_type!(NotOnFlatGround));
+            return Action::Abort;
+        }
+
+        let intercept = Self::intercept(ctx.game, me, ctx.scenario.ball_prediction());
+        let intercept = some_or_else!(intercept, {
+            ctx.eeg
+                .log(self.name(), "no intercept within double jump reach");
+            return Action::Abort;
+        });
+        let target_loc = contact_loc(ctx.game, me.Physics.loc_2d(), intercept.ball_loc);
+        let jump_time = some_or_else!(ctx.game.mutators().double_jump_time_to_z(target_loc.z), {
+            ctx.eeg.log(self.name(), "can't jump that high");
+            return Action::Abort;
+        });
+
+        if !self.contact_planned {
+            let now = ctx.packet.GameInfo.TimeSeconds;
+            ctx.segment_accuracy.expect_contact(
+                now,
+                now + intercept.time,
+                intercept.ball_loc,
+                jump_time,
+            );
+            self.contact_planned = true;
+        }
+
+        ctx.eeg.print_time("intercept_time", intercept.time);
+        ctx.eeg.print_time("jump_time", jump_time);
+        ctx.eeg
+            .print_value("intercept_loc_z", Coordinate(intercept.ball_loc.z));
+        ctx.eeg.draw(Drawable::ghost_ball(intercept.ball_loc));
+        ctx.eeg
+            .draw(Drawable::GhostCar(target_loc, me.Physics.rot()));
+
+        let drive_time = intercept.time - jump_time;
+        if drive_time <= 0.0 {
+            return Action::tail_call(Self::jump(jump_time));
+        }
+
+        let me_forward = me.Physics.forward_axis_2d();
+        let steer = me_forward.angle_to(&(target_loc.to_2d() - me.Physics.loc_2d()).to_axis());
+        if steer.abs() >= PI / 3.0 {
+            ctx.eeg.log(self.name(), "not facing the target");
+            return Action::Abort;
+        }
+
+        let (throttle, boost) = arrival(ctx, target_loc.to_2d(), drive_time, jump_time);
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: throttle,
+            Steer: simple_steer_towards(&me.Physics, target_loc.to_2d()),
+            Boost: boost && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED,
+            ..Default::default()
+        })
+    }
+}
+
+impl DoubleJumpAerial {
+    fn jump(jump_time: f32) -> Chain {
+        let jump = common::halfway_house::PlayerInput {
+            Jump: true,
+            ..Default::default()
+        };
+        let coast_time = jump_time - car_double_jump::SECOND_JUMP_TIME - Self::SECOND_JUMP_HOLD;
+        Chain::new(Priority::Strike, vec_box![
+            Yielder::new(rl::CAR_JUMP_FORCE_TIME, jump),
+            Yielder::new(car_double_jump::RELEASE_TIME, Default::default()),
+            Yielder::new(Self::SECOND_JUMP_HOLD, jump),
+            Yielder::new(
+                coast_time.max(0.0) + Self::FOLLOW_THROUGH_TIME,
+                Default::default(),
+            ),
+        ])
+    }
+}
+
+/// Where the car should be when it touches the ball: just under it, on the
+/// side we're coming from.
+fn contact_loc(game: &Game<'_>, car_loc: Point2<f32>, ball_loc: Point3<f32>) -> Point3<f32> {
+    let approach = (ball_loc.to_2d() - car_loc).to_axis();
+    let loc = ball_loc.to_2d() - approach.as_ref() * game.ball_radius();
+    loc.to_3d(ball_loc.z + DoubleJumpAerial::CONTACT_Z_OFFSET)
+}
+
+/// Choose a throttle and boost that put us under the ball right as the jump
+/// peaks.
+fn arrival(
+    ctx: &Context<'_>,
+    target_loc: Point2<f32>,
+    drive_time: f32,
+    jump_time: f32,
+) -> (f32, bool) {
+    let me = ctx.me();
+    let target_dist = (target_loc - me.Physics.loc_2d()).norm();
+    let would_reach = |throttle, boost| {
+        let mut drive = Car1D::new()
+            .with_speed(me.Physics.vel().norm())
+            .with_boost(me.Boost as f32);
+        drive.advance(drive_time, throttle, boost);
+        // Our xy-velocity holds steady through the jump.
+        drive.distance() + drive.speed() * jump_time - target_dist
+    };
+    arrival_control(
+        would_reach(0.0, false),
+        would_reach(1.0, false),
+        would_reach(1.0, true),
+    )
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use crate::{
+        behavior::strike::DoubleJumpAerial,
+        integration_tests::{TestRunner, TestScenario},
+    };
+    use common::prelude::*;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn popped_up_ball() {
+        tags!(Strike, Aerial);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, 0.0, 300.0),
+                ball_vel: Vector3::new(0.0, 0.0, 600.0),
+                car_loc: Point3::new(0.0, -1500.0, 17.01),
+                car_vel: Vector3::new(0.0, 1000.0, 0.0),
+                ..Default::default()
+            })
+            .behavior(DoubleJumpAerial::new())
+            .run_for_millis(2500);
+
+        let packet = test.sniff_packet();
+        assert!(packet.GameBall.Physics.vel().y > 500.0);
+    }
+}
@@ -2,11 +2,13 @@ use crate::{
     behavior::{
         defense::defensive_hit,
         higher_order::{Chain, While},
-        strike::GroundedHit,
+        strike::{GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
     },
     routing::{behavior::FollowRoute, plan::GroundIntercept, recover::WeDontWinTheRace},
     strategy::{Action, Behavior, Context, Priority},
+    utils::WallRayCalculator,
 };
+use common::prelude::*;
 use nameof::name_of_type;
 
 pub struct FiftyFifty;
@@ -22,13 +24,29 @@ impl Behavior for FiftyFifty {
         name_of_type!(FiftyFifty)
     }
 
-    fn execute_old(&mut self, _ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, _ctx: &mut Context<'_>) -> Action {
         Action::tail_call(While::new(
             WeDontWinTheRace,
             Chain::new(Priority::Idle, vec![
                 Box::new(FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true)),
-                Box::new(GroundedHit::hit_towards(defensive_hit)),
+                Box::new(GroundedHit::hit_towards(contest_hit)),
             ]),
         ))
     }
 }
+
+/// Drive through the ball along the approach that wins the 50/50, if we know
+/// it, or else just keep it away from our goal.
+fn contest_hit(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+    let approach = match ctx.scenario.contest() {
+        Some(contest) => contest.approach,
+        None => return defensive_hit(ctx),
+    };
+    let ball_loc = ctx.intercept_ball_loc.to_2d();
+    let aim_loc = WallRayCalculator::calculate(ball_loc, ball_loc + approach.into_inner() * 4000.0);
+    Ok(GroundedHitTarget::new(
+        ctx.intercept_time,
+        GroundedHitTargetAdjust::RoughAim,
+        aim_loc,
+    ))
+}
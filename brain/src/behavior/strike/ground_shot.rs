@@ -6,6 +6,7 @@ mod integration_tests {
 
     #[test]
     fn crossing_the_midfield() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1794.4557, -681.9332, 99.93823),
@@ -23,6 +24,7 @@ mod integration_tests {
 
     #[test]
     fn crossing_the_box() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-726.1142, -673.77716, 118.28892),
@@ -39,6 +41,7 @@ mod integration_tests {
 
     #[test]
     fn high_bouncer() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1725.8822, 4719.4307, 93.15),
@@ -55,6 +58,7 @@ mod integration_tests {
 
     #[test]
     fn easy_open_net() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(999.651, 3636.9731, 93.14),
@@ -73,6 +77,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "when the angle is too tight")]
     fn tight_angle_needs_correction() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2618.1267, 4567.453, 93.14),
@@ -89,6 +94,7 @@ mod integration_tests {
 
     #[test]
     fn corner_shot() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2616.377, 4173.1816, 122.709236),
@@ -107,6 +113,7 @@ mod integration_tests {
 
     #[test]
     fn rolling_to_corner_of_goal() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(151.63426, 4371.35, 93.15),
@@ -124,6 +131,7 @@ mod integration_tests {
 
     #[test]
     fn tight_angle_with_no_boost() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2815.55, 4677.98, 233.65),
@@ -143,6 +151,7 @@ mod integration_tests {
 
     #[test]
     fn tight_angle_with_boost() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2815.55, 4677.98, 233.65),
@@ -5,19 +5,20 @@ use crate::{
         strike::BounceShot,
     },
     eeg::{color, Drawable, EEG},
-    helpers::intercept::{naive_ground_intercept, NaiveIntercept},
+    helpers::{
+        drive::arrival_control,
+        intercept::{naive_ground_intercept, NaiveIntercept},
+        intercept_predicate::{BelowGroundedReach, BelowZ},
+    },
     routing::recover::{IsSkidding, NotOnFlatGround},
-    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+    strategy::{Action, Behavior, Context, Game, Mutators, Priority, Scenario},
     utils::intercept_memory::{InterceptMemory, InterceptMemoryResult},
 };
 use common::{physics, prelude::*, rl, Coordinate, Distance};
 use derive_new::new;
-use nalgebra::{Point2, Point3, UnitQuaternion, Vector3};
+use nalgebra::{Point2, Point3, Rotation2, Unit, UnitQuaternion, Vector2, Vector3};
 use nameof::name_of_type;
-use simulate::{
-    car_single_jump::{time_to_z, JUMP_MAX_Z},
-    linear_interpolate, Car1D,
-};
+use simulate::{linear_interpolate, Car1D};
 use std::f32::consts::PI;
 
 pub struct GroundedHit<Aim>
@@ -26,6 +27,7 @@ where
 {
     aim: Aim,
     intercept: InterceptMemory,
+    contact_planned: bool,
 }
 
 impl<Aim> GroundedHit<Aim>
@@ -36,6 +38,7 @@ where
         Self {
             aim,
             intercept: InterceptMemory::new(),
+            contact_planned: false,
         }
     }
 }
@@ -62,7 +65,7 @@ where
         stringify!(GroundedHit)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let me = ctx.me();
 
         if IsSkidding.evaluate(&me.into()) {
@@ -97,6 +100,21 @@ where
             }
         };
 
+        if !self.contact_planned {
+            let jump_duration = if plan.jump {
+                Self::jump_duration(ctx.game.mutators(), plan.target_loc.z)
+            } else {
+                0.0
+            };
+            ctx.segment_accuracy.expect_contact(
+                now,
+                now + plan.intercept_time,
+                intercept.ball_loc,
+                jump_duration,
+            );
+            self.contact_planned = true;
+        }
+
         let me_forward = me.Physics.forward_axis_2d();
         let steer = me_forward.angle_to(&(plan.target_loc - me.Physics.loc()).to_2d().to_axis());
         if steer.abs() >= PI / 3.0 {
@@ -112,12 +130,12 @@ where
         {
             ctx.eeg
                 .log(self.name(), "likely stuck out of position; jumping early");
-            return self.jump(&plan);
+            return self.jump(ctx.game.mutators(), &plan);
         }
 
         match self.estimate_approach(ctx, &plan) {
             Do::Drive(throttle, boost) => self.drive(ctx, &plan, throttle, boost),
-            Do::Jump => self.jump(&plan),
+            Do::Jump => self.jump(ctx.game.mutators(), &plan),
         }
     }
 }
@@ -135,10 +153,13 @@ where
             me.Physics.loc(),
             me.Physics.vel(),
             me.Boost as f32,
-            |ball| ball.loc.z < GroundedHit::MAX_BALL_Z,
+            &BelowGroundedReach,
         );
         let intercept = some_or_else!(intercept, {
-            ctx.eeg.log(self.name(), "can't find intercept");
+            ctx.eeg.log(
+                self.name(),
+                format!("can't find intercept {}", BelowGroundedReach),
+            );
             return Err(());
         });
 
@@ -178,17 +199,21 @@ where
         let target = (self.aim)(&mut aim_context)
             .map_err(|_| ctx.eeg.log(self.name(), "error getting aim location"))?;
         let (target_loc, _target_rot) = Self::preliminary_target(ctx, &intercept, &target);
-        let ball_max_z = JUMP_MAX_Z + (intercept.ball_loc.z - target_loc.z);
+        let jump_max_z = ctx.game.mutators().jump_max_z();
+        let predicate = BelowZ(jump_max_z + (intercept.ball_loc.z - target_loc.z));
 
         let intercept = naive_ground_intercept(
             ctx.scenario.ball_prediction().iter(),
             me.Physics.loc(),
             me.Physics.vel(),
             me.Boost as f32,
-            |ball| ball.loc.z < ball_max_z,
+            &predicate,
         );
         let intercept = some_or_else!(intercept, {
-            ctx.eeg.log(self.name(), "can't find phase-two intercept");
+            ctx.eeg.log(
+                self.name(),
+                format!("can't find phase-two intercept {}", predicate),
+            );
             return Err(());
         });
         ctx.eeg.draw(Drawable::print("two-phase", color::GREEN));
@@ -209,6 +234,16 @@ where
         let target = (self.aim)(&mut aim_context)?;
 
         let (target_loc, target_rot) = Self::preliminary_target(ctx, intercept, &target);
+        let dodge = if target.dodge {
+            Some(PlannedDodge::new(
+                target.dodge_direction,
+                me.Physics.loc_2d(),
+                intercept.ball_loc,
+                target.aim_loc,
+            ))
+        } else {
+            None
+        };
 
         // TODO: iteratively find contact point which hits the ball towards aim_loc
 
@@ -225,7 +260,7 @@ where
             target_loc,
             target_rot,
             jump: target.jump,
-            dodge: target.dodge,
+            dodge,
         })
     }
 
@@ -247,19 +282,25 @@ where
         // Take the more extreme of the two.
         let pitch = pitch_from_distance.max(pitch_from_ball_vel);
 
+        let car_reference_loc = match target.adjust {
+            // Come in along the normal, not from wherever the car happens to be.
+            GroundedHitTargetAdjust::ContactNormal(normal) => {
+                (intercept.ball_loc.to_2d() - normal.as_ref() * 1000.0).to_3d(0.0)
+            }
+            _ => ctx.me().Physics.loc(),
+        };
+
         // Just do something hacky for now
-        let (naive_target_loc, target_rot) = car_ball_contact_with_pitch(
-            ctx.game,
-            intercept.ball_loc,
-            ctx.me().Physics.loc(),
-            pitch,
-        );
+        let (naive_target_loc, target_rot) =
+            car_ball_contact_with_pitch(ctx.game, intercept.ball_loc, car_reference_loc, pitch);
         let mut target_loc = match target.adjust {
             GroundedHitTargetAdjust::RoughAim => {
                 let rough = BounceShot::rough_shooting_spot(intercept, target.aim_loc);
                 rough.to_3d(naive_target_loc.z)
             }
-            GroundedHitTargetAdjust::StraightOn => naive_target_loc,
+            GroundedHitTargetAdjust::StraightOn | GroundedHitTargetAdjust::ContactNormal(_) => {
+                naive_target_loc
+            }
         };
         // Don't get too far underneath the ball, since we might end up hitting it way
         // up in the air (which we never want to do).
@@ -267,10 +308,9 @@ where
         (target_loc, target_rot)
     }
 
-    #[allow(clippy::if_same_then_else)]
     fn estimate_approach(&mut self, ctx: &mut Context<'_>, plan: &Plan) -> Do {
         let total_time = plan.intercept_time;
-        let jump_duration = Self::jump_duration(plan.target_loc.z);
+        let jump_duration = Self::jump_duration(ctx.game.mutators(), plan.target_loc.z);
         let drive_time = total_time - jump_duration;
 
         if drive_time < 0.0 {
@@ -302,17 +342,11 @@ where
         let throttle_offset = would_reach(1.0, false);
         let blitz_offset = would_reach(1.0, true);
 
-        let (throttle, boost) = if coast_offset > target_offset + 100.0 {
-            (-1.0, false) // We're _really_ overshooting…
-        } else if coast_offset > target_offset {
-            (0.0, false) // We're overshooting…
-        } else if throttle_offset > target_offset {
-            (0.0, false)
-        } else if blitz_offset > target_offset {
-            (1.0, false)
-        } else {
-            (1.0, true)
-        };
+        let (throttle, boost) = arrival_control(
+            coast_offset - target_offset,
+            throttle_offset - target_offset,
+            blitz_offset - target_offset,
+        );
 
         ctx.eeg.print_value("target", plan.target_loc);
         ctx.eeg.print_time("drive_time", drive_time);
@@ -336,9 +370,9 @@ where
         })
     }
 
-    fn jump(&self, plan: &Plan) -> Action {
+    fn jump(&self, mutators: &Mutators, plan: &Plan) -> Action {
         // Simulate the jump to predict our exact location at the peak.
-        let jump_time = Self::jump_duration(plan.target_loc.z);
+        let jump_time = Self::jump_duration(mutators, plan.target_loc.z);
 
         if !plan.jump {
             // Don't jump, but at least follow-through on the hit so we don't prematurely
@@ -350,15 +384,30 @@ where
             }));
         }
 
+        // The pre-roll comes out of the jump, so the flip still starts at
+        // `jump_time`, when the car reaches the ball.
+        let pre_roll_time = plan.dodge.as_ref().map_or(0.0, PlannedDodge::pre_roll_time);
+
         let mut steps = Vec::<Box<dyn Behavior>>::new();
         steps.push(Box::new(JumpAndTurn::new(
-            jump_time - 0.05,
-            jump_time,
+            jump_time - 0.05 - pre_roll_time,
+            jump_time - pre_roll_time,
             plan.target_rot,
         )));
         steps.push(Box::new(AbortIfNotNearBall::new()));
-        if plan.dodge {
-            steps.push(Box::new(Dodge::new().towards_ball()));
+        if let Some(ref dodge) = plan.dodge {
+            if pre_roll_time > 0.0 {
+                // Lean into the flip, so it starts out already tilted toward the side
+                // it's going to.
+                steps.push(Box::new(Yielder::new(
+                    pre_roll_time,
+                    common::halfway_house::PlayerInput {
+                        Roll: dodge.roll(),
+                        ..Default::default()
+                    },
+                )));
+            }
+            steps.push(Box::new(Dodge::new().towards(dodge.target_loc)));
         } else {
             // If we're not dodging, force pushing the nose down, since sometimes the air
             // recovery does wonky things here.
@@ -374,15 +423,19 @@ where
         Action::tail_call(Chain::new(Priority::Strike, steps))
     }
 
-    fn jump_duration(z: f32) -> f32 {
-        // Avoid a panic in `time_to_z()` from trying to jump too high. Assert that the
+    fn jump_duration(mutators: &Mutators, z: f32) -> f32 {
+        // Avoid a panic in `jump_time_to_z()` from trying to jump too high. Assert that the
         // error is small before we clamp the value.
         let leeway = 30.0;
-        assert!(z < JUMP_MAX_Z + leeway, "{} {} {}", z, JUMP_MAX_Z, leeway);
-        let clamped = z.min(JUMP_MAX_Z);
+        let jump_max_z = mutators.jump_max_z();
+        assert!(z < jump_max_z + leeway, "{} {} {}", z, jump_max_z, leeway);
+        let clamped = z.min(jump_max_z);
 
         // Always leave at least enough time for the jump before the dodge.
-        time_to_z(clamped).unwrap().max(JumpAndTurn::MIN_DURATION)
+        mutators
+            .jump_time_to_z(clamped)
+            .unwrap()
+            .max(JumpAndTurn::MIN_DURATION)
     }
 }
 
@@ -415,7 +468,7 @@ impl Behavior for AbortIfNotNearBall {
         stringify!(AbortIfNotNearBall)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let ball_loc = ctx.packet.GameBall.Physics.loc();
         let car_loc = ctx.me().Physics.loc();
         let distance = (ball_loc - car_loc).norm();
@@ -450,6 +503,8 @@ pub struct GroundedHitTarget {
     jump: bool,
     #[new(value = "true")]
     dodge: bool,
+    #[new(value = "DodgeDirection::Forward")]
+    dodge_direction: DodgeDirection,
 }
 
 impl GroundedHitTarget {
@@ -464,11 +519,113 @@ impl GroundedHitTarget {
         self.dodge = dodge;
         self
     }
+
+    pub fn dodge_direction(mut self, dodge_direction: DodgeDirection) -> Self {
+        self.dodge_direction = dodge_direction;
+        self
+    }
+}
+
+/// Which way to flip into the ball. A forward flip only pushes the ball along
+/// our line of approach; flipping to one side steers the shot toward that side.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DodgeDirection {
+    /// Choose from the rest, going by the approach angle and ball height.
+    Auto,
+    Forward,
+    Diagonal,
+    Sideways,
+}
+
+impl DodgeDirection {
+    /// Aim a diagonal flip once the aim is this far off our line to the ball,
+    const DIAGONAL_MIN_ANGLE: f32 = PI / 9.0;
+    /// and a sideways flip once it's this far off.
+    const SIDEWAYS_MIN_ANGLE: f32 = PI / 3.0;
+    /// Past this, no flip can save the shot, so just hit it hard.
+    const MAX_ANGLE: f32 = 2.0 * PI / 3.0;
+    /// A sideways flip swings the car's side at the ball, which is too low to
+    /// reach anything much higher than this.
+    const SIDEWAYS_MAX_BALL_Z: f32 = 160.0;
+
+    fn resolve(self, aim_angle: f32, ball_z: f32) -> Self {
+        if self != DodgeDirection::Auto {
+            return self;
+        }
+        let aim_angle = aim_angle.abs();
+        if aim_angle < Self::DIAGONAL_MIN_ANGLE || aim_angle >= Self::MAX_ANGLE {
+            DodgeDirection::Forward
+        } else if aim_angle < Self::SIDEWAYS_MIN_ANGLE || ball_z > Self::SIDEWAYS_MAX_BALL_Z {
+            DodgeDirection::Diagonal
+        } else {
+            DodgeDirection::Sideways
+        }
+    }
+
+    /// How far from straight ahead this flip can send the ball.
+    fn max_turn(self) -> f32 {
+        match self {
+            DodgeDirection::Auto | DodgeDirection::Forward => 0.0,
+            DodgeDirection::Diagonal => PI / 4.0,
+            DodgeDirection::Sideways => PI / 2.0,
+        }
+    }
 }
 
+struct PlannedDodge {
+    /// Never `Auto`.
+    direction: DodgeDirection,
+    /// Flip towards this location.
+    target_loc: Point2<f32>,
+    /// The angle from straight at the ball to the flip. It's measured the same
+    /// way `Dodge` measures it, so its sign matches the flip's yaw.
+    turn: f32,
+}
+
+impl PlannedDodge {
+    /// How long to air roll before flipping sideways or diagonally.
+    const PRE_ROLL_TIME: f32 = 0.05;
+
+    fn new(
+        direction: DodgeDirection,
+        car_loc: Point2<f32>,
+        ball_loc: Point3<f32>,
+        aim_loc: Point2<f32>,
+    ) -> Self {
+        let approach = (ball_loc.to_2d() - car_loc).to_axis();
+        let aim_angle = approach.angle_to(&(aim_loc - ball_loc.to_2d()).to_axis());
+        let direction = direction.resolve(aim_angle, ball_loc.z);
+        let max_turn = direction.max_turn();
+        let turn = aim_angle.max(-max_turn).min(max_turn);
+        let dodge_axis = Rotation2::new(turn) * approach.into_inner();
+        Self {
+            direction,
+            target_loc: ball_loc.to_2d() + dodge_axis * 1000.0,
+            turn,
+        }
+    }
+
+    fn roll(&self) -> f32 {
+        self.turn.signum()
+    }
+
+    /// How long to air roll before the flip.
+    fn pre_roll_time(&self) -> f32 {
+        if self.direction == DodgeDirection::Forward {
+            0.0
+        } else {
+            Self::PRE_ROLL_TIME
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
 pub enum GroundedHitTargetAdjust {
     StraightOn,
     RoughAim,
+    /// Meet the ball so the car pushes it along this direction. For redirects,
+    /// where the ball's own velocity matters as much as the car's.
+    ContactNormal(Unit<Vector2<f32>>),
 }
 
 struct Plan {
@@ -476,7 +633,7 @@ struct Plan {
     target_loc: Point3<f32>,
     target_rot: UnitQuaternion<f32>,
     jump: bool,
-    dodge: bool,
+    dodge: Option<PlannedDodge>,
 }
 
 enum Do {
@@ -485,6 +642,76 @@ enum Do {
     Jump,
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::behavior::strike::grounded_hit::{
+        DodgeDirection, GroundedHitTarget, GroundedHitTargetAdjust, PlannedDodge,
+    };
+    use nalgebra::{Point2, Point3};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn choose_direction() {
+        let resolve = |angle: f32, z| DodgeDirection::Auto.resolve(angle, z);
+        assert_eq!(resolve(0.0, 100.0), DodgeDirection::Forward);
+        assert_eq!(resolve(-PI / 6.0, 100.0), DodgeDirection::Diagonal);
+        assert_eq!(resolve(PI / 2.0, 100.0), DodgeDirection::Sideways);
+        // Too high to hit with the side of the car.
+        assert_eq!(resolve(PI / 2.0, 250.0), DodgeDirection::Diagonal);
+        // Forcing a direction overrides all that.
+        assert_eq!(
+            DodgeDirection::Forward.resolve(PI / 2.0, 100.0),
+            DodgeDirection::Forward,
+        );
+    }
+
+    #[test]
+    fn flip_toward_the_aim() {
+        let car = Point2::new(0.0, -1000.0);
+        let ball = Point3::new(0.0, 0.0, 100.0);
+
+        let straight = PlannedDodge::new(DodgeDirection::Auto, car, ball, Point2::new(0.0, 5000.0));
+        assert_eq!(straight.direction, DodgeDirection::Forward);
+        assert!(straight.target_loc.x.abs() < 1e-3);
+
+        // The aim is well off to one side, but a diagonal flip can only turn the
+        // ball so far.
+        let aim = Point2::new(3000.0, 1000.0);
+        let diagonal = PlannedDodge::new(DodgeDirection::Diagonal, car, ball, aim);
+        assert!((diagonal.turn.abs() - PI / 4.0).abs() < 1e-3);
+        assert!(diagonal.target_loc.x > 0.0);
+        assert_eq!(diagonal.roll(), diagonal.turn.signum());
+    }
+
+    #[test]
+    fn auto_follows_the_aim() {
+        let car = Point2::new(0.0, -1000.0);
+        let ball = Point3::new(0.0, 0.0, 100.0);
+
+        // A little off to the side: diagonal, turned all the way.
+        let aim = Point2::new(3000.0, 3000.0);
+        let dodge = PlannedDodge::new(DodgeDirection::Auto, car, ball, aim);
+        assert_eq!(dodge.direction, DodgeDirection::Diagonal);
+        assert!((dodge.turn.abs() - PI / 4.0).abs() < 1e-3);
+        assert!(dodge.target_loc.x > 0.0);
+        assert_eq!(dodge.pre_roll_time(), PlannedDodge::PRE_ROLL_TIME);
+
+        // Square to the side: sideways, and turned only as far as the aim.
+        let aim = Point2::new(-3000.0, 0.0);
+        let dodge = PlannedDodge::new(DodgeDirection::Auto, car, ball, aim);
+        assert_eq!(dodge.direction, DodgeDirection::Sideways);
+        assert!((dodge.turn.abs() - PI / 2.0).abs() < 1e-3);
+        assert!(dodge.target_loc.x < 0.0);
+    }
+
+    #[test]
+    fn targets_flip_forward_by_default() {
+        let target =
+            GroundedHitTarget::new(1.0, GroundedHitTargetAdjust::RoughAim, Point2::origin());
+        assert_eq!(target.dodge_direction, DodgeDirection::Forward);
+    }
+}
+
 #[cfg(test)]
 mod integration_tests {
     use crate::{
@@ -497,6 +724,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "The great bankruptcy of 2018")]
     fn normal_shoot() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-2000.0, 2000.0, 500.0),
@@ -517,6 +745,7 @@ mod integration_tests {
 
     #[test]
     fn rolling_hit() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3962.02, -1981.12, 152.39),
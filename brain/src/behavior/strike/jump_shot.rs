@@ -6,6 +6,7 @@ mod integration_tests {
 
     #[test]
     fn far_corner_falling() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(2790.6147, 4609.1733, 101.71101),
@@ -25,6 +26,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn sideways_jump() {
+        tags!(Strike);
         let _test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3882.8804, 1246.1459, 145.22563),
@@ -43,6 +45,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "This is a tough shot and not a high priority to fix.")]
     fn correct_mispredicted_bounce() {
+        tags!(Strike);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3350.3652, 2287.6494, 537.7215),
@@ -1,16 +1,21 @@
 pub use self::{
     bounce_shot::BounceShot,
+    double_jump_aerial::DoubleJumpAerial,
     fifty_fifty::FiftyFifty,
     grounded_hit::{
-        GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust,
+        DodgeDirection, GroundedHit, GroundedHitAimContext, GroundedHitTarget,
+        GroundedHitTargetAdjust,
     },
+    redirect::Redirect,
     wall_hit::WallHit,
 };
 
 mod aerial_shot;
 mod bounce_shot;
+mod double_jump_aerial;
 mod fifty_fifty;
 mod ground_shot;
 mod grounded_hit;
 mod jump_shot;
+mod redirect;
 mod wall_hit;
@@ -0,0 +1,235 @@
+use crate::{
+    behavior::strike::{
+        GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust,
+    },
+    eeg::{color, Drawable, Event},
+    helpers::{
+        ball::BallFrame,
+        hit_outcome::ball_vel_after_hit,
+        intercept::{naive_ground_intercept_2, NaiveIntercept},
+    },
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{Action, Behavior, Context, Game, Priority, Scenario},
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3, Rotation2, Unit, Vector2, Vector3};
+use nameof::name_of_type;
+use std::f32::consts::PI;
+
+/// Deflect a ball that's crossing in front of the enemy goal (a pass, or a
+/// clear that didn't go far enough) into the net.
+///
+/// `Shoot` hits the ball about as if it were sitting still. That's fine for a
+/// ball rolling towards us, but a fast cross carries most of its speed through
+/// the hit, so this works out the contact normal from the ball's incoming
+/// velocity and the outgoing velocity we want instead.
+pub struct Redirect;
+
+impl Redirect {
+    /// The ball has to be crossing the box: within this distance of the goal
+    /// line,
+    const BOX_DEPTH: f32 = 2500.0;
+    /// and this far to either side of the goal.
+    const BOX_WIDTH: f32 = 2000.0;
+    /// Balls slower than this aren't crossing, they're sitting there. Leave
+    /// them to `Shoot`.
+    const MIN_CROSSING_SPEED: f32 = 600.0;
+    /// Don't try to turn the ball more sharply than this from the direction
+    /// we're pushing it, since the outcome is too sensitive to the contact.
+    const MAX_DEFLECTION: f32 = PI / 3.0;
+    /// The car needs to be roughly behind the contact normal already.
+    const MAX_APPROACH_ANGLE: f32 = PI / 3.0;
+    /// Close enough for a shot on goal.
+    const MAX_AIM_ERROR: f32 = PI / 36.0;
+    const NORMAL_ITERATIONS: usize = 4;
+    /// Above this, jump to make contact. (Same cutoff as `Shoot`'s chip.)
+    const MAX_CHIP_BALL_Z: f32 = 110.0;
+
+    pub fn new() -> Self {
+        Redirect
+    }
+
+    /// Returns `true` if the ball is crossing the enemy box and we can turn it
+    /// into the goal.
+    pub fn applicable(ctx: &mut Context<'_>) -> bool {
+        let intercept = some_or_else!(Self::intercept(ctx.game, &ctx.scenario, ctx.me()), {
+            return false;
+        });
+        Self::plan(ctx.game, ctx.me().Physics.loc(), &intercept).is_some()
+    }
+
+    fn intercept(
+        game: &Game<'_>,
+        scenario: &Scenario<'_>,
+        car: &common::halfway_house::PlayerInfo,
+    ) -> Option<NaiveIntercept<()>> {
+        naive_ground_intercept_2(&car.into(), scenario.ball_prediction(), |ball| {
+            Self::crossing_box(game, ball)
+        })
+    }
+
+    fn crossing_box(game: &Game<'_>, ball: &BallFrame) -> Option<()> {
+        let goal = game.enemy_goal();
+        let ball_loc = ball.loc.to_2d();
+        let depth = (ball_loc - goal.center_2d).dot(&goal.normal_2d);
+        let width = (ball_loc.x - goal.center_2d.x).abs();
+        let crossing_speed = ball.vel.to_2d().dot(&goal.normal_2d.ortho()).abs();
+        if ball.loc.z >= GroundedHitTarget::MAX_BALL_Z
+            || depth >= Self::BOX_DEPTH
+            || width >= Self::BOX_WIDTH
+            || crossing_speed < Self::MIN_CROSSING_SPEED
+        {
+            return None;
+        }
+        Some(())
+    }
+
+    /// The contact normal for a redirect at `intercept`, if there is a
+    /// reasonable one.
+    fn plan(
+        game: &Game<'_>,
+        car_loc: Point3<f32>,
+        intercept: &NaiveIntercept<()>,
+    ) -> Option<Unit<Vector2<f32>>> {
+        let ball_loc = intercept.ball_loc;
+        let aim_loc = game.enemy_goal().center_2d;
+        let contact_dist = game.ball_radius() + game.me_vehicle().pivot_to_front_dist();
+        let normal = contact_normal(
+            contact_dist,
+            intercept.car_speed,
+            ball_loc,
+            intercept.ball_vel,
+            aim_loc,
+        )?;
+
+        let approach = (ball_loc - car_loc).to_2d();
+        if approach.angle_to(&normal).abs() >= Self::MAX_APPROACH_ANGLE {
+            return None;
+        }
+        Some(normal)
+    }
+
+    fn aim(ctx: &mut GroundedHitAimContext<'_, '_>) -> Result<GroundedHitTarget, ()> {
+        let intercept = Self::intercept(ctx.game, ctx.scenario, ctx.car).ok_or(())?;
+        let normal = Self::plan(ctx.game, ctx.car.Physics.loc(), &intercept).ok_or_else(|| {
+            ctx.eeg
+                .log(name_of_type!(Redirect), "no contact normal works");
+        })?;
+
+        let ball_loc = intercept.ball_loc.to_2d();
+        ctx.eeg.draw(Drawable::Line(
+            ball_loc - normal.as_ref() * 500.0,
+            ball_loc,
+            color::YELLOW,
+        ));
+
+        let aim_loc = ctx.game.enemy_goal().center_2d;
+        Ok(GroundedHitTarget::new(
+            intercept.time,
+            GroundedHitTargetAdjust::ContactNormal(normal),
+            aim_loc,
+        )
+        .jump(intercept.ball_loc.z >= Self::MAX_CHIP_BALL_Z)
+        .dodge(false))
+    }
+}
+
+impl Behavior for Redirect {
+    fn name(&self) -> &str {
+        name_of_type!(Redirect)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        ctx.eeg.track(Event::Redirect);
+
+        if !Self::applicable(ctx) {
+            ctx.eeg.log(self.name(), "no redirect");
+            return Action::Abort;
+        }
+
+        Action::tail_call(chain!(
+            Priority::Strike,
+            [
+                FollowRoute::new(GroundIntercept::new()).same_ball_trajectory(true),
+                GroundedHit::hit_towards(Self::aim),
+            ]
+        ))
+    }
+}
+
+/// Find the direction to push a ball moving at `ball_vel` so it leaves
+/// towards `aim_loc`.
+///
+/// Start from the impulse that turns the incoming velocity into the outgoing
+/// one, assuming the ball leaves at about the car's speed. The hit adds more
+/// than that, so then turn the normal by however far the predicted outcome
+/// misses, a few times over.
+fn contact_normal(
+    contact_dist: f32,
+    car_speed: f32,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    aim_loc: Point2<f32>,
+) -> Option<Unit<Vector2<f32>>> {
+    let desired = (aim_loc - ball_loc.to_2d()).normalize();
+    let mut normal = (desired * car_speed - ball_vel.to_2d()).to_axis();
+    for _ in 0..Redirect::NORMAL_ITERATIONS {
+        let outcome = outcome(contact_dist, car_speed, ball_loc, ball_vel, &normal);
+        let error = outcome.angle_to(&desired);
+        normal = Unit::new_normalize(Rotation2::new(error) * normal.into_inner());
+    }
+
+    let outcome = outcome(contact_dist, car_speed, ball_loc, ball_vel, &normal);
+    if outcome.angle_to(&desired).abs() >= Redirect::MAX_AIM_ERROR
+        || normal.angle_to(&desired).abs() >= Redirect::MAX_DEFLECTION
+    {
+        return None;
+    }
+    Some(normal)
+}
+
+/// The ball's velocity after a car driving along `normal` hits it.
+fn outcome(
+    contact_dist: f32,
+    car_speed: f32,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    normal: &Unit<Vector2<f32>>,
+) -> Vector2<f32> {
+    let car_loc = (ball_loc.to_2d() - normal.as_ref() * contact_dist).to_3d(rl::OCTANE_NEUTRAL_Z);
+    let car_forward = normal.to_3d();
+    let car_vel = car_forward.into_inner() * car_speed;
+    ball_vel_after_hit(car_loc, car_vel, &car_forward, ball_loc, ball_vel).to_2d()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::behavior::strike::redirect::{contact_normal, outcome};
+    use common::prelude::*;
+    use nalgebra::{Point2, Point3, Vector3};
+
+    #[test]
+    fn turn_a_cross_into_the_goal() {
+        let ball_loc = Point3::new(1000.0, 4000.0, 93.0);
+        let ball_vel = Vector3::new(-1500.0, 0.0, 0.0);
+        let aim_loc = Point2::new(0.0, 5120.0);
+        let normal = contact_normal(150.0, 1400.0, ball_loc, ball_vel, aim_loc).unwrap();
+
+        let desired = aim_loc - ball_loc.to_2d();
+        let result = outcome(150.0, 1400.0, ball_loc, ball_vel, &normal);
+        assert!(result.angle_to(&desired).abs() < 5.0_f32.to_radians());
+        // A power hit straight at the goal would send the ball wide, since the
+        // ball keeps its sideways speed. The normal has to lean against it.
+        assert!(normal.angle_to(&desired) > 5.0_f32.to_radians());
+    }
+
+    #[test]
+    fn too_fast_to_turn() {
+        // All we can do to a cross this fast is nudge it. Getting it on goal
+        // would take more of a deflection than we trust.
+        let ball_loc = Point3::new(1000.0, 4000.0, 93.0);
+        let ball_vel = Vector3::new(-5000.0, 0.0, 0.0);
+        let aim_loc = Point2::new(0.0, 5120.0);
+        assert!(contact_normal(150.0, 0.0, ball_loc, ball_vel, aim_loc).is_none());
+    }
+}
@@ -46,7 +46,7 @@ impl Behavior for WallHit {
         Priority::Strike
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let (ref ctx, ref mut eeg) = ctx.split();
 
         if !ctx.me().OnGround {
@@ -107,8 +107,9 @@ fn check_intercept(ctx: &Context2<'_, '_>, ball: &BallFrame) -> Result<(), ()> {
         return Err(());
     }
 
-    let me_to_ground = me_surface.unfold(&ground)?;
-    let target_to_me = target_surface.unfold(&me_surface)?;
+    let pitch = ctx.game.pitch();
+    let me_to_ground = pitch.unfold(me_surface, ground, &me.Physics.loc())?;
+    let target_to_me = pitch.unfold(target_surface, me_surface, &target)?;
     let target_to_ground = me_to_ground * target_to_me;
 
     let me_to_flat = Flattener::new(me_to_ground);
@@ -152,8 +153,9 @@ fn flat_target(
     }
 
     // Build the origami structure
-    let me_to_ground = me_surface.unfold(&ground)?;
-    let intercept_to_me = intercept_surface.unfold(&me_surface)?;
+    let pitch = ctx.game.pitch();
+    let me_to_ground = pitch.unfold(me_surface, ground, &me.Physics.loc())?;
+    let intercept_to_me = pitch.unfold(intercept_surface, me_surface, intercept_ball_loc)?;
     let intercept_to_ground = me_to_ground * intercept_to_me;
     let ground_to_intercept = intercept_to_ground.inverse();
 
@@ -421,6 +423,7 @@ mod integration_tests {
 
     #[test]
     fn side_wall_high() {
+        tags!(Strike, Wall);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3000.0, 0.0, 90.0),
@@ -436,8 +439,27 @@ mod integration_tests {
         assert!(packet.GameBall.Physics.loc().y >= 1000.0);
     }
 
+    #[test]
+    fn side_wall_high_by_name() {
+        tags!(Strike, Wall);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(-3000.0, 0.0, 90.0),
+                ball_vel: Vector3::new(-2000.0, 0.0, 0.0),
+                car_loc: Point3::new(-3000.0, -2000.0, 17.01),
+                car_rot: Rotation3::from_unreal_angles(0.0, PI * 0.75, 0.0),
+                ..Default::default()
+            })
+            .behavior_named("WallHit")
+            .run_for_millis(3000);
+
+        let packet = test.sniff_packet();
+        assert!(packet.GameBall.Physics.loc().y >= 1000.0);
+    }
+
     #[test]
     fn side_wall_low() {
+        tags!(Strike, Wall);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3782.89, 563.18, 93.14),
@@ -459,6 +481,7 @@ mod integration_tests {
 
     #[test]
     fn side_wall_easy_angle() {
+        tags!(Strike, Wall);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(3971.1199, -1644.1699, 1230.4299),
@@ -477,6 +500,7 @@ mod integration_tests {
 
     #[test]
     fn from_the_corner() {
+        tags!(Strike, Wall);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3135.25, -3098.04, 106.81),
@@ -495,6 +519,7 @@ mod integration_tests {
 
     #[test]
     fn angle_check_dont_bail() {
+        tags!(Strike, Wall);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3500.0, 1600.0, 98.0),
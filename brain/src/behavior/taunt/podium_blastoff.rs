@@ -37,7 +37,7 @@ impl Behavior for PodiumBlastoff {
         Priority::Taunt
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let elapsed = self.time_tracker.update(ctx);
 
         self.chat.run(ctx);
@@ -55,14 +55,14 @@ impl Behavior for PodiumBlastoff {
         }
 
         let blastoff = elapsed >= 3.75;
-        Twirl::new(blastoff).execute_old(ctx)
+        Twirl::new(blastoff).execute(ctx)
     }
 }
 
 impl PodiumBlastoff {
     fn run_child(&mut self, ctx: &mut Context<'_>) -> Option<Action> {
         if let Some(child) = self.child.as_mut() {
-            match child.execute_old(ctx) {
+            match child.execute(ctx) {
                 Action::Yield(i) => {
                     return Some(Action::Yield(i));
                 }
@@ -89,7 +89,7 @@ impl PodiumBlastoff {
                 ..Default::default()
             })
         ]));
-        self.child.as_mut().unwrap().execute_old(ctx)
+        self.child.as_mut().unwrap().execute(ctx)
     }
 }
 
@@ -36,7 +36,7 @@ impl Behavior for PodiumSpew {
         Priority::Taunt
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let elapsed = self.time_tracker.update(ctx);
 
         self.chat.run(ctx);
@@ -57,7 +57,7 @@ impl Behavior for PodiumSpew {
                 Yaw: 1.0,
                 ..Default::default()
             }));
-            return self.execute_old(ctx); // Recurse into `run_child`.
+            return self.execute(ctx); // Recurse into `run_child`.
         }
 
         if !me.OnGround {
@@ -95,7 +95,7 @@ impl Behavior for PodiumSpew {
 impl PodiumSpew {
     fn run_child(&mut self, ctx: &mut Context<'_>) -> Option<Action> {
         if let Some(child) = self.child.as_mut() {
-            match child.execute_old(ctx) {
+            match child.execute(ctx) {
                 Action::Yield(i) => {
                     return Some(Action::Yield(i));
                 }
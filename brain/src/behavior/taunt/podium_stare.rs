@@ -31,7 +31,7 @@ impl Behavior for PodiumStare {
         Priority::Taunt
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let elapsed = self.time_tracker.update(ctx);
 
         self.chat.run(ctx);
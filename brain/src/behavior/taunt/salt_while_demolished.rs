@@ -18,7 +18,7 @@ impl Behavior for SaltWhileDemolished {
         Priority::Taunt
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         if !ctx.me().Demolished {
             return Action::Return;
         }
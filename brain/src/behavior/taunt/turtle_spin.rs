@@ -39,7 +39,7 @@ impl Behavior for TurtleSpin {
         Priority::Taunt
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         self.quick_chat(ctx);
 
         if ctx.me().OnGround {
@@ -191,6 +191,7 @@ mod integration_tests {
 
     #[test]
     fn bask() {
+        tags!();
         let test = TestRunner::new()
             .scenario(TestScenario {
                 car_loc: Point3::new(2000.0, 0.0, 50.0),
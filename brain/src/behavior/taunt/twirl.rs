@@ -23,7 +23,7 @@ impl Behavior for Twirl {
         Priority::Taunt
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         let car = ctx.me();
         let car_forward_axis = car.Physics.forward_axis();
 
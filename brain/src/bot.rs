@@ -0,0 +1,171 @@
+use crate::{
+    brain::Brain,
+    eeg::EEG,
+    strategy::{Difficulty, Personality, Team},
+};
+use common::halfway_house::{LiveDataPacket, PlayerInput};
+
+/// The whole bot behind one call per frame, for other RLBot harnesses that
+/// want to embed it without wiring up a `Brain` and an `EEG` themselves.
+///
+/// This plays soccar on a standard field. Start from `Bot::builder()`.
+pub struct Bot {
+    brain: Brain,
+    eeg: EEG,
+    team: Team,
+    player_index: Option<usize>,
+    warned_ambiguous: bool,
+}
+
+impl Bot {
+    pub fn builder() -> BotBuilder {
+        BotBuilder::new()
+    }
+
+    /// Decide what to do this frame.
+    ///
+    /// Until we know which car is ours, this returns neutral inputs. That
+    /// includes when no car in the packet is on our team, and when more than
+    /// one is and no `player_index` was given.
+    pub fn tick(&mut self, packet: &LiveDataPacket) -> PlayerInput {
+        let player_index = match self.player_index {
+            Some(i) => i,
+            None => match find_player(packet, self.team) {
+                Ok(Some(i)) => i,
+                Ok(None) => return Default::default(),
+                Err(()) => {
+                    if !self.warned_ambiguous {
+                        log::warn!("several cars on our team; set player_index to pick one");
+                        self.warned_ambiguous = true;
+                    }
+                    return Default::default();
+                }
+            },
+        };
+        if self.player_index.is_none() {
+            self.player_index = Some(player_index);
+            self.brain.set_player_index(player_index as i32);
+        }
+
+        self.eeg.begin(packet);
+        let input = self.brain.tick_offline(packet, &mut self.eeg);
+        self.eeg.show(packet);
+        input
+    }
+}
+
+/// Settings for a `Bot`.
+pub struct BotBuilder {
+    team: Team,
+    player_index: Option<usize>,
+    difficulty: Difficulty,
+    personality: Personality,
+    eeg: bool,
+}
+
+impl BotBuilder {
+    fn new() -> Self {
+        Self {
+            team: Team::Blue,
+            player_index: None,
+            difficulty: Difficulty::default(),
+            personality: Personality::default(),
+            eeg: false,
+        }
+    }
+
+    /// Play for this team, as its only car in the packet. Defaults to blue.
+    /// If the team has more than one car, set `player_index` instead.
+    pub fn team(mut self, team: Team) -> Self {
+        self.team = team;
+        self
+    }
+
+    /// Play as the car at this index in the packet. This takes precedence
+    /// over `team`.
+    pub fn player_index(mut self, player_index: usize) -> Self {
+        self.player_index = Some(player_index);
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
+    pub fn personality(mut self, personality: Personality) -> Self {
+        self.personality = personality;
+        self
+    }
+
+    /// Whether to open the EEG window, to watch what the bot is thinking.
+    /// Defaults to off.
+    pub fn eeg(mut self, enabled: bool) -> Self {
+        self.eeg = enabled;
+        self
+    }
+
+    pub fn build(self) -> Bot {
+        let mut brain = Brain::soccar();
+        brain.set_difficulty(self.difficulty);
+        brain.set_personality(self.personality);
+        if let Some(i) = self.player_index {
+            brain.set_player_index(i as i32);
+        }
+
+        let mut eeg = EEG::new();
+        if self.eeg {
+            eeg.show_window();
+        }
+
+        Bot {
+            brain,
+            eeg,
+            team: self.team,
+            player_index: self.player_index,
+            warned_ambiguous: false,
+        }
+    }
+}
+
+/// The index of the only car in the packet on `team`, if there is one. If
+/// there are several, any of them could be ours, so that's an error.
+fn find_player(packet: &LiveDataPacket, team: Team) -> Result<Option<usize>, ()> {
+    let mut cars = (packet.cars().enumerate())
+        .filter(|(_, car)| car.Team == team.to_ffi())
+        .map(|(i, _)| i);
+    match (cars.next(), cars.next()) {
+        (first, None) => Ok(first),
+        (_, Some(_)) => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{bot::find_player, strategy::Team};
+    use common::halfway_house::{LiveDataPacket, PlayerInfo};
+
+    fn packet(teams: &[u8]) -> LiveDataPacket {
+        LiveDataPacket::default().with_cars(teams.iter().map(|&team| PlayerInfo {
+            Team: team,
+            ..Default::default()
+        }))
+    }
+
+    #[test]
+    fn finds_only_car_on_team() {
+        let packet = packet(&[1, 0]);
+        assert_eq!(find_player(&packet, Team::Blue), Ok(Some(1)));
+        assert_eq!(find_player(&packet, Team::Orange), Ok(Some(0)));
+    }
+
+    #[test]
+    fn no_car_on_team() {
+        assert_eq!(find_player(&packet(&[0, 0]), Team::Orange), Ok(None));
+    }
+
+    #[test]
+    fn several_cars_on_team() {
+        assert_eq!(find_player(&packet(&[0, 1, 0, 1]), Team::Blue), Err(()));
+    }
+}
@@ -1,63 +1,143 @@
 #[cfg(test)]
 use crate::strategy::Behavior;
 use crate::{
-    eeg::{color, Drawable, EEG},
-    helpers::ball::{BallPredictor, ChipBallPrediction, FrameworkBallPrediction},
-    strategy::{infer_game_mode, Context, Dropshot, Game, Runner, Scenario, Soccar},
-    utils::FPSCounter,
+    behavior::{
+        movement::Calibrate,
+        registry::{behavior_by_name, BEHAVIOR_NAMES},
+    },
+    eeg::{color, Drawable, Event, EEG},
+    helpers::ball::{BallPredictor, ChipBallPrediction, FrameworkBallPrediction, PuckPrediction},
+    routing::{accuracy::SegmentAccuracy, budget::PlanningBudget, models::PlanCache},
+    strategy::{
+        infer_game_mode, BallChargeTracker, BoostTracker, Context, Difficulty, Game, MatchMemory,
+        OpponentModel, Personality, RiskProfile, Rumble, Runner, Scenario, ScoutingReport, Soccar,
+    },
+    telemetry::{TelemetryBody, TelemetryCar},
+    utils::{BallPredictionAccuracy, FPSCounter, GameClock, JumpTracker, MutatorTracker},
 };
-use common::{prelude::*, ControllerInput, ExtendDuration};
+use common::{prelude::*, ControllerInput, ExtendDuration, GoalDetector};
 use nalgebra::{clamp, Point3};
 use nameof::name_of_type;
 use std::time::Instant;
 
 pub struct Brain {
+    mode: rlbot::GameMode,
     runner: Runner,
     ball_predictor: Box<dyn BallPredictor>,
     player_index: Option<i32>,
     fps_counter: FPSCounter,
+    clock: GameClock,
+    jump_tracker: JumpTracker,
+    mutator_tracker: MutatorTracker,
+    boost_tracker: BoostTracker,
+    ball_charge_tracker: BallChargeTracker,
+    ball_prediction_accuracy: BallPredictionAccuracy,
+    segment_accuracy: SegmentAccuracy,
+    plan_cache: PlanCache,
+    planning_budget: PlanningBudget,
+    goal_detector: GoalDetector,
+    personality: Personality,
+    difficulty: Difficulty,
+    match_memory: MatchMemory,
+    opponent_model: OpponentModel,
+    /// Which of our team's cars was first man last frame.
+    first_man: Option<usize>,
     /// This is not automated or enforced in any way, it's just a convenient
     /// memory slot for optional use in behaviors.
     last_quick_chat: f32,
 }
 
 impl Brain {
-    fn new(runner: Runner, ball_predictor: impl BallPredictor + 'static) -> Self {
+    fn new(
+        mode: rlbot::GameMode,
+        runner: Runner,
+        ball_predictor: impl BallPredictor + 'static,
+    ) -> Self {
         Self {
+            mode,
             runner,
             ball_predictor: Box::new(ball_predictor),
             player_index: None,
             fps_counter: FPSCounter::new(),
+            clock: GameClock::new(),
+            jump_tracker: JumpTracker::new(),
+            mutator_tracker: MutatorTracker::new(),
+            boost_tracker: BoostTracker::new(),
+            ball_charge_tracker: BallChargeTracker::new(),
+            ball_prediction_accuracy: BallPredictionAccuracy::new(),
+            segment_accuracy: SegmentAccuracy::new(),
+            plan_cache: PlanCache::new(),
+            planning_budget: PlanningBudget::default(),
+            goal_detector: GoalDetector::new(),
+            personality: Personality::default(),
+            difficulty: Difficulty::default(),
+            match_memory: MatchMemory::new(),
+            opponent_model: OpponentModel::new(),
+            first_man: None,
             last_quick_chat: 0.0,
         }
     }
 
     // This is just here so it's exported from the crate since I'm lazy
-    pub fn infer_game_mode(field_info: rlbot::flat::FieldInfo<'_>) -> rlbot::GameMode {
-        infer_game_mode(field_info)
+    pub fn infer_game_mode(
+        field_info: rlbot::flat::FieldInfo<'_>,
+        ball: &common::halfway_house::BallInfo,
+    ) -> Option<rlbot::GameMode> {
+        infer_game_mode(field_info, ball)
     }
 
     pub fn soccar() -> Self {
-        Self::new(Runner::new(Soccar::new()), ChipBallPrediction::new())
+        Self::new(
+            rlbot::GameMode::Soccer,
+            Runner::new(Soccar::new()),
+            ChipBallPrediction::new(),
+        )
     }
 
     pub fn dropshot(rlbot: &'static rlbot::RLBot) -> Self {
         Self::new(
-            Runner::new(Dropshot::new()),
+            rlbot::GameMode::Dropshot,
+            Runner::dropshot(),
             FrameworkBallPrediction::new(rlbot),
         )
     }
 
+    /// Rumble can't be told apart from soccar by the field, so whoever starts
+    /// the bot has to ask for it.
+    pub fn rumble() -> Self {
+        Self::new(
+            rlbot::GameMode::Soccer,
+            Runner::new(Rumble::new()),
+            ChipBallPrediction::new(),
+        )
+    }
+
     pub fn hoops(rlbot: &'static rlbot::RLBot) -> Self {
         Self::new(
+            rlbot::GameMode::Hoops,
             Runner::new(Soccar::new()),
             FrameworkBallPrediction::new(rlbot),
         )
     }
 
+    /// Snow Day plays like soccar, but with a puck.
+    pub fn snow_day() -> Self {
+        Self::new(
+            rlbot::GameMode::Hockey,
+            Runner::new(Soccar::new()),
+            PuckPrediction::new(),
+        )
+    }
+
     #[cfg(test)]
     pub fn with_behavior(behavior: impl Behavior + 'static) -> Self {
-        Self::new(Runner::with_current(behavior), ChipBallPrediction::new())
+        let mut brain = Self::new(
+            rlbot::GameMode::Soccer,
+            Runner::with_current(behavior),
+            ChipBallPrediction::new(),
+        );
+        brain.unlimited_planning();
+        brain
     }
 
     #[cfg(test)]
@@ -66,6 +146,53 @@ impl Brain {
         self.runner = Runner::with_current(behavior);
     }
 
+    /// Before doing anything else, run through a few maneuvers and check that
+    /// the game's physics match our models.
+    pub fn calibrate(&mut self) {
+        self.runner.set_current(Box::new(Calibrate::new()));
+    }
+
+    /// Start by running a single behavior, looked up by name, e.g., to
+    /// practice one mechanic. The strategy takes over again once it returns.
+    pub fn start_behavior(&mut self, name: &str) -> Result<(), String> {
+        let behavior = behavior_by_name(name).ok_or_else(|| {
+            format!(
+                "unknown behavior {:?}; expected one of {}",
+                name,
+                BEHAVIOR_NAMES.join(", "),
+            )
+        })?;
+        self.runner.set_current(behavior);
+        Ok(())
+    }
+
+    pub fn set_personality(&mut self, personality: Personality) {
+        self.personality = personality;
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+    }
+
+    /// Let route planning take as long as it needs. This makes decisions
+    /// reproducible, at the cost of the occasional slow frame.
+    pub fn unlimited_planning(&mut self) {
+        self.planning_budget = PlanningBudget::unlimited();
+    }
+
+    /// Start the match knowing what we learned about this opponent last time.
+    pub fn learn_from(&mut self, report: ScoutingReport) {
+        self.opponent_model.learn_from(report.tendencies());
+        self.match_memory.learn_from(report);
+    }
+
+    /// What we've learned about the opponent, including anything passed to
+    /// `learn_from`, to save for the next match.
+    pub fn scouting_report(&self) -> ScoutingReport {
+        self.match_memory
+            .scouting_report(self.opponent_model.tendencies())
+    }
+
     pub fn set_player_index(&mut self, player_index: i32) {
         self.player_index = Some(player_index);
     }
@@ -75,6 +202,30 @@ impl Brain {
         field_info: rlbot::flat::FieldInfo<'_>,
         packet: &common::halfway_house::LiveDataPacket,
         eeg: &mut EEG,
+    ) -> common::halfway_house::PlayerInput {
+        let player_index = self.player_index.unwrap() as usize;
+        let game = Game::new(field_info, self.mode, packet, player_index)
+            .with_mutators(self.mutator_tracker.mutators(&packet.GameInfo));
+        self.tick_game(&game, packet, eeg)
+    }
+
+    /// Like `tick`, but without a running game to ask about the field. This
+    /// assumes a standard soccar field.
+    pub fn tick_offline(
+        &mut self,
+        packet: &common::halfway_house::LiveDataPacket,
+        eeg: &mut EEG,
+    ) -> common::halfway_house::PlayerInput {
+        let game = Game::soccar_offline(packet, self.player_index.unwrap() as usize)
+            .with_mutators(self.mutator_tracker.mutators(&packet.GameInfo));
+        self.tick_game(&game, packet, eeg)
+    }
+
+    fn tick_game(
+        &mut self,
+        game: &Game<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        eeg: &mut EEG,
     ) -> common::halfway_house::PlayerInput {
         self.fps_counter.tick(packet.GameInfo.TimeSeconds);
 
@@ -86,7 +237,7 @@ impl Brain {
         eeg.print_value("p1 vel", Point3::from(packet.GameCars[0].Physics.vel()));
         eeg.draw(Drawable::print("-----------------------", color::GREEN));
 
-        let mut result = self.determine_controls(field_info, packet, eeg);
+        let mut result = self.determine_controls(game, packet, eeg);
 
         result.Throttle = clamp(result.Throttle, -1.0, 1.0);
         result.Steer = clamp(result.Steer, -1.0, 1.0);
@@ -103,25 +254,117 @@ impl Brain {
         eeg.print_value("jump", result.Jump);
         eeg.print_value("boost", result.Boost);
         eeg.print_value("handbrake", result.Handbrake);
+        eeg.record_input(self.runner.current_blurb().unwrap_or("none"), &result);
 
         result
     }
 
     fn determine_controls(
         &mut self,
-        field_info: rlbot::flat::FieldInfo<'_>,
+        game: &Game<'_>,
         packet: &common::halfway_house::LiveDataPacket,
         eeg: &mut EEG,
     ) -> common::halfway_house::PlayerInput {
         let start = Instant::now();
+        self.planning_budget.begin_tick();
+
+        if self.clock.update(packet.GameInfo.TimeSeconds) {
+            // Whatever we were doing was planned against a clock that no longer
+            // exists. Start over.
+            eeg.log(name_of_type!(Brain), "game time jumped; replanning");
+            self.runner.invalidate();
+            self.segment_accuracy.abandon();
+            self.plan_cache.clear();
+        }
+        self.jump_tracker
+            .update(packet.GameInfo.TimeSeconds, game.me());
+        self.mutator_tracker.update(game.me());
+        self.boost_tracker.update(game, packet);
+
+        self.opponent_model
+            .update(game, packet, self.match_memory.last_own_touch());
 
-        let game = Game::new(field_info, packet, self.player_index.unwrap() as usize);
-        let scenario = Scenario::new(&game, &*self.ball_predictor, packet);
-        let mut ctx = Context::new(&game, packet, &scenario, eeg, &mut self.last_quick_chat);
+        let scenario = Scenario::new(
+            game,
+            &*self.ball_predictor,
+            &self.opponent_model,
+            self.first_man,
+            packet,
+        );
+        self.ball_prediction_accuracy.update(
+            packet.GameInfo.TimeSeconds,
+            scenario.ball_prediction(),
+            packet.GameBall.Physics.loc(),
+            game.mutators().gravity,
+            eeg,
+        );
+
+        self.segment_accuracy.report(eeg);
+
+        if let Some(goal) = self.goal_detector.update(packet) {
+            if goal.scoring_team == game.team.to_ffi() {
+                eeg.log(name_of_type!(Brain), "goal scored");
+                eeg.track(Event::GoalScored);
+            } else {
+                eeg.log(name_of_type!(Brain), "goal conceded");
+                eeg.track(Event::GoalConceded);
+                if self.match_memory.last_touch_ours() {
+                    eeg.clip("own_goal");
+                } else {
+                    eeg.clip("goal_conceded");
+                }
+            }
+        }
+
+        self.match_memory.update(game, packet, &scenario, eeg);
+        self.ball_charge_tracker
+            .update(packet, self.match_memory.last_touch());
+        self.segment_accuracy.observe_contact(
+            packet.GameInfo.TimeSeconds,
+            game.me().Physics.loc(),
+            game.me().OnGround,
+            packet.GameBall.Physics.loc(),
+            self.match_memory.last_own_touch(),
+            eeg,
+        );
+
+        let mut ctx = Context::new(
+            game,
+            packet,
+            &scenario,
+            &self.clock,
+            &self.jump_tracker,
+            &self.boost_tracker,
+            &self.ball_charge_tracker,
+            eeg,
+            &mut self.last_quick_chat,
+            &mut self.segment_accuracy,
+            &mut self.plan_cache,
+            &mut self.planning_budget,
+            &self.goal_detector,
+            &self.personality,
+            &self.difficulty,
+            &mut self.match_memory,
+            RiskProfile::new(game.score_margin(), game.seconds_remaining()),
+        );
 
         ctx.eeg.print_time("possession", ctx.scenario.possession());
+        ctx.eeg
+            .print_time("enemy delay", self.opponent_model.challenge_delay());
+        ctx.eeg.print_value(
+            "enemy aerials/min",
+            format!("{:.1}", self.opponent_model.aerials_per_minute()),
+        );
+        ctx.eeg
+            .print_value("risk", format!("{:?}", ctx.risk.mode()));
+        ctx.eeg
+            .print_time("time to pressure", ctx.scenario.time_to_pressure());
 
-        let result = self.runner.execute_old(&mut ctx);
+        let result = self.runner.next_input(&mut ctx);
+        ctx.eeg.print_value(
+            "planning",
+            format!("{:.2}ms", ctx.planning_budget.spent_ms()),
+        );
 
         let stop = Instant::now();
         let duration = stop - start;
@@ -134,6 +377,24 @@ impl Brain {
             );
         }
 
+        let behavior = self.runner.current_blurb().unwrap_or("none").to_string();
+        let possession = ctx.scenario.possession();
+        let frame_ms =
+            duration.as_secs() as f32 * 1000.0 + duration.subsec_micros() as f32 / 1000.0;
+        ctx.eeg.update_status(|status| {
+            status.time = packet.GameInfo.TimeSeconds;
+            status.ball = TelemetryBody::from_physics(&packet.GameBall.Physics);
+            status.car = TelemetryCar::from_player(game.me());
+            status.behavior = behavior;
+            status.possession = possession;
+            status.frame_timing.record(frame_ms);
+        });
+
+        // Every frame, even if nothing asked, so every copy of the bot on our
+        // team remembers the same first man.
+        self.first_man = scenario.team().first_man();
+        self.jump_tracker.record_input(&result);
+        self.mutator_tracker.record_input(&result);
         result
     }
 }
@@ -0,0 +1,183 @@
+use crate::{
+    eeg::{color, Drawable},
+    strategy::{Behavior, Child},
+};
+use graphics::types::Color;
+use std::collections::VecDeque;
+
+/// Shows everything the `Runner` is running, nested the way the behaviors are,
+/// instead of just the name at the top. Behaviors that finished recently stay
+/// on the panel for a moment, along with how they finished.
+pub struct BehaviorTreePanel {
+    finished: VecDeque<FinishedNode>,
+}
+
+struct FinishedNode {
+    time: f32,
+    name: String,
+    status: NodeStatus,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NodeStatus {
+    Running,
+    /// Queued up behind a sibling, e.g., a later step in a `Chain`.
+    Pending,
+    Succeeded,
+    Aborted,
+}
+
+impl NodeStatus {
+    fn label(self) -> &'static str {
+        match self {
+            NodeStatus::Running => "running",
+            NodeStatus::Pending => "pending",
+            NodeStatus::Succeeded => "succeeded",
+            NodeStatus::Aborted => "aborted",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            NodeStatus::Running => color::YELLOW,
+            NodeStatus::Pending => color::BLUE,
+            NodeStatus::Succeeded => color::GREEN,
+            NodeStatus::Aborted => color::RED,
+        }
+    }
+}
+
+impl BehaviorTreePanel {
+    /// How long to keep showing a behavior after it finishes.
+    const LINGER: f32 = 1.0;
+
+    pub fn new() -> Self {
+        Self {
+            finished: VecDeque::new(),
+        }
+    }
+
+    pub fn finish(&mut self, time: f32, name: &str, status: NodeStatus) {
+        self.finished.push_back(FinishedNode {
+            time,
+            name: name.to_string(),
+            status,
+        });
+    }
+
+    /// The panel's lines for this frame, starting from `root`.
+    pub fn drawables(&mut self, time: f32, root: &dyn Behavior) -> Vec<Drawable> {
+        while self
+            .finished
+            .front()
+            .map(|node| time - node.time >= Self::LINGER || node.time > time)
+            .unwrap_or(false)
+        {
+            self.finished.pop_front();
+        }
+
+        let mut drawables = vec![Drawable::print("behaviors:", color::WHITE)];
+        for row in rows(root) {
+            drawables.push(line(row.depth + 1, &row.name, row.status));
+        }
+        for node in &self.finished {
+            drawables.push(line(1, &node.name, node.status));
+        }
+        drawables
+    }
+}
+
+fn line(depth: usize, name: &str, status: NodeStatus) -> Drawable {
+    Drawable::print(
+        format!("{}{} [{}]", "  ".repeat(depth), name, status.label()),
+        status.color(),
+    )
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Row {
+    pub depth: usize,
+    pub name: String,
+    pub status: NodeStatus,
+}
+
+/// Flatten the tree under `root`, parents before their children.
+pub fn rows(root: &dyn Behavior) -> Vec<Row> {
+    let mut rows = Vec::new();
+    walk(Child::Behavior(root), 0, NodeStatus::Running, &mut rows);
+    rows
+}
+
+fn walk(node: Child<'_>, depth: usize, status: NodeStatus, rows: &mut Vec<Row>) {
+    match node {
+        Child::Behavior(behavior) => {
+            rows.push(Row {
+                depth,
+                name: behavior.name().to_string(),
+                status,
+            });
+            let mut first = true;
+            behavior.visit_children(&mut |child| {
+                // Only the first child is running, and only if its parent is.
+                let child_status = if first && status == NodeStatus::Running {
+                    NodeStatus::Running
+                } else {
+                    NodeStatus::Pending
+                };
+                first = false;
+                walk(child, depth + 1, child_status, rows);
+            });
+        }
+        Child::Segment(name) => rows.push(Row {
+            depth,
+            name: name.to_string(),
+            status,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        behavior::higher_order::{NullBehavior, Predicate, While},
+        eeg::behavior_tree::{rows, NodeStatus, Row},
+        strategy::{Context, Priority},
+    };
+
+    struct Always;
+
+    impl Predicate for Always {
+        fn name(&self) -> &str {
+            "Always"
+        }
+
+        fn evaluate(&mut self, _ctx: &mut Context<'_>) -> bool {
+            true
+        }
+    }
+
+    fn row(depth: usize, name: &str, status: NodeStatus) -> Row {
+        Row {
+            depth,
+            name: name.to_string(),
+            status,
+        }
+    }
+
+    #[test]
+    fn nested() {
+        let tree = chain!(
+            Priority::Idle,
+            [While::new(Always, NullBehavior::new()), NullBehavior::new(),]
+        );
+        assert_eq!(
+            rows(&tree),
+            vec![
+                row(0, "Chain", NodeStatus::Running),
+                row(1, "While", NodeStatus::Running),
+                row(2, "NullBehavior", NodeStatus::Running),
+                row(1, "NullBehavior", NodeStatus::Pending),
+            ]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use collect::{RecordingPlayerTick, RecordingRigidBodyState, RecordingTick};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{self, File},
+    io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Keeps the last few seconds of the game, and when something notable happens,
+/// saves a clip around it as a recording. Post-match triage can then start by
+/// replaying what went wrong instead of scrolling through logs.
+///
+/// Clips use the same format as `collect`, so they can be fed to
+/// `play --replay`.
+pub struct ClipRecorder {
+    dir: PathBuf,
+    history: VecDeque<RecordingTick>,
+    pending: Vec<PendingClip>,
+    last_trigger: HashMap<String, f32>,
+}
+
+struct PendingClip {
+    time: f32,
+    reason: String,
+}
+
+impl ClipRecorder {
+    /// How much of the game before the event to save.
+    const BEFORE: f32 = 5.0;
+    /// How much of the game after the event to save.
+    const AFTER: f32 = 2.0;
+
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            history: VecDeque::new(),
+            pending: Vec::new(),
+            last_trigger: HashMap::new(),
+        }
+    }
+
+    /// Call this once per frame.
+    pub fn record(&mut self, packet: &common::halfway_house::LiveDataPacket) {
+        let now = packet.GameInfo.TimeSeconds;
+        self.history.push_back(RecordingTick {
+            time: now,
+            ball: RecordingRigidBodyState::from_physics(&packet.GameBall.Physics),
+            players: packet
+                .cars()
+                .map(|car| RecordingPlayerTick {
+                    state: RecordingRigidBodyState::from_physics(&car.Physics),
+                    // Inputs aren't in the packet.
+                    input: Default::default(),
+                })
+                .collect(),
+        });
+        let forget_before = now - Self::BEFORE - Self::AFTER;
+        self.history.retain(|tick| tick.time >= forget_before);
+
+        let (ready, pending) = self
+            .pending
+            .drain(..)
+            .partition(|clip| now >= clip.time + Self::AFTER);
+        self.pending = pending;
+        for clip in ready {
+            if let Err(err) = self.save(&clip) {
+                log::warn!("could not save clip {:?}: {}", clip.reason, err);
+            }
+        }
+    }
+
+    /// Save a clip around the given time, once enough of the aftermath has been
+    /// recorded.
+    ///
+    /// A condition that lasts (like a stuck behavior tree) triggers every
+    /// frame, so a reason that already triggered a clip that would overlap
+    /// this one is ignored. Returns whether a clip will be saved.
+    pub fn trigger(&mut self, time: f32, reason: &str) -> bool {
+        if let Some(&last) = self.last_trigger.get(reason) {
+            if time >= last && time < last + Self::BEFORE + Self::AFTER {
+                return false;
+            }
+        }
+        self.last_trigger.insert(reason.to_string(), time);
+        self.pending.push(PendingClip {
+            time,
+            reason: reason.to_string(),
+        });
+        true
+    }
+
+    fn save(&self, clip: &PendingClip) -> io::Result<()> {
+        let ticks: Vec<_> = self
+            .history
+            .iter()
+            .filter(|tick| tick.time >= clip.time - Self::BEFORE)
+            .cloned()
+            .collect();
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!(
+            "{}-{:.1}-{}.csv",
+            timestamp, clip.time, clip.reason
+        ));
+        let file = File::create(path)?;
+        RecordingTick::write(&ticks, file).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eeg::clip::ClipRecorder;
+
+    #[test]
+    fn repeated_triggers_make_one_clip() {
+        let mut clips = ClipRecorder::new("unused");
+        assert!(clips.trigger(10.0, "watchdog"));
+        for i in 1..60 {
+            assert!(!clips.trigger(10.0 + i as f32 / 60.0, "watchdog"));
+        }
+        assert!(clips.trigger(10.5, "goal_conceded"));
+        assert_eq!(clips.pending.len(), 2);
+
+        // Once the first clip is over, the same reason can trigger again.
+        assert!(clips.trigger(20.0, "watchdog"));
+    }
+}
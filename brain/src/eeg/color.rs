@@ -4,6 +4,7 @@ use graphics::types::Color;
 pub const BLACK: Color = [0.0, 0.0, 0.0, 1.0];
 pub const WHITE: Color = [1.0, 1.0, 1.0, 1.0];
 pub const PITCH: Color = [0.0, 0.2, 0.0, 1.0];
+pub const WALL: Color = [0.0, 0.12, 0.0, 1.0];
 pub const RED: Color = [1.0, 0.0, 0.0, 1.0];
 pub const ORANGE: Color = [1.0, 0.5, 0.0, 1.0];
 pub const ORANGE_DARK: Color = [0.5, 0.25, 0.0, 1.0];
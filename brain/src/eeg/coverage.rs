@@ -0,0 +1,174 @@
+use crate::eeg::Event;
+use std::{collections::BTreeMap, fmt, fs, io, path::Path, str::FromStr};
+
+/// Counts of which behaviors the `Runner` activated, which transitions it took
+/// between them, and which events fired.
+///
+/// This can be merged across many runs (the test suite, self-play) and saved
+/// to a text file, so that a report can point out what never happens.
+#[derive(Default, PartialEq, Debug)]
+pub struct Coverage {
+    behaviors: BTreeMap<String, u32>,
+    edges: BTreeMap<(String, String), u32>,
+    events: BTreeMap<String, u32>,
+}
+
+impl Coverage {
+    /// The `from` of an edge that starts from no behavior at all.
+    const ROOT: &'static str = "(root)";
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Record that `to` became the current behavior. `from` is the behavior it
+    /// replaced, if any.
+    pub fn activate(&mut self, from: Option<&str>, to: &str) {
+        *self.behaviors.entry(to.to_string()).or_insert(0) += 1;
+        let from = from.unwrap_or(Self::ROOT).to_string();
+        *self.edges.entry((from, to.to_string())).or_insert(0) += 1;
+    }
+
+    pub fn event(&mut self, event: &Event) {
+        *self.events.entry(format!("{:?}", event)).or_insert(0) += 1;
+    }
+
+    pub fn merge(&mut self, other: &Coverage) {
+        for (k, v) in &other.behaviors {
+            *self.behaviors.entry(k.clone()).or_insert(0) += v;
+        }
+        for (k, v) in &other.edges {
+            *self.edges.entry(k.clone()).or_insert(0) += v;
+        }
+        for (k, v) in &other.events {
+            *self.events.entry(k.clone()).or_insert(0) += v;
+        }
+    }
+
+    /// Add this run's counts to the ones saved at `path`, creating it if need
+    /// be.
+    pub fn merge_into_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut total = if path.exists() {
+            fs::read_to_string(path)?
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        } else {
+            Self::new()
+        };
+        total.merge(self);
+        fs::write(path, total.to_string())
+    }
+
+    /// List what never fired. Events are checked against every known event.
+    /// Behaviors and edges can't be enumerated, so they're checked against a
+    /// `baseline` from an earlier run, e.g., from before a refactor.
+    pub fn report(&self, baseline: Option<&Coverage>) -> String {
+        let mut result = String::new();
+        for event in Event::ALL {
+            let name = format!("{:?}", event);
+            if !self.events.contains_key(&name) {
+                result.push_str(&format!("event never fired: {}\n", name));
+            }
+        }
+        if let Some(baseline) = baseline {
+            for name in baseline.behaviors.keys() {
+                if !self.behaviors.contains_key(name) {
+                    result.push_str(&format!("behavior never activated: {}\n", name));
+                }
+            }
+            for (from, to) in baseline.edges.keys() {
+                if !self.edges.contains_key(&(from.clone(), to.clone())) {
+                    result.push_str(&format!("edge never taken: {} -> {}\n", from, to));
+                }
+            }
+        }
+        result
+    }
+}
+
+/// One line per entry, tab-separated, in the same format `FromStr` reads.
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, count) in &self.behaviors {
+            writeln!(f, "behavior\t{}\t{}", name, count)?;
+        }
+        for ((from, to), count) in &self.edges {
+            writeln!(f, "edge\t{}\t{}\t{}", from, to, count)?;
+        }
+        for (name, count) in &self.events {
+            writeln!(f, "event\t{}\t{}", name, count)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Coverage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::new();
+        for (index, line) in s.lines().enumerate() {
+            let bad_line = || format!("bad coverage line {}: {:?}", index + 1, line);
+            let fields: Vec<_> = line.split('\t').collect();
+            let count = fields
+                .last()
+                .and_then(|c| c.parse::<u32>().ok())
+                .ok_or_else(bad_line)?;
+            match &fields[..] {
+                ["behavior", name, _] => {
+                    result.behaviors.insert(name.to_string(), count);
+                }
+                ["edge", from, to, _] => {
+                    result
+                        .edges
+                        .insert((from.to_string(), to.to_string()), count);
+                }
+                ["event", name, _] => {
+                    result.events.insert(name.to_string(), count);
+                }
+                _ if line.trim().is_empty() => {}
+                _ => return Err(bad_line()),
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eeg::{Coverage, Event};
+
+    #[test]
+    fn round_trip_and_merge() {
+        let mut coverage = Coverage::new();
+        coverage.activate(None, "Offense");
+        coverage.activate(Some("Offense"), "Shoot");
+        coverage.event(&Event::Offense);
+
+        let parsed: Coverage = coverage.to_string().parse().unwrap();
+        assert_eq!(parsed, coverage);
+
+        let mut merged = Coverage::new();
+        merged.merge(&coverage);
+        merged.merge(&parsed);
+        assert!(merged.to_string().contains("behavior\tOffense\t2\n"));
+    }
+
+    #[test]
+    fn report() {
+        let mut baseline = Coverage::new();
+        baseline.activate(None, "Offense");
+        baseline.activate(Some("Offense"), "Shoot");
+
+        let mut coverage = Coverage::new();
+        coverage.activate(None, "Offense");
+        coverage.event(&Event::Offense);
+
+        let report = coverage.report(Some(&baseline));
+        assert!(report.contains("behavior never activated: Shoot\n"));
+        assert!(report.contains("edge never taken: Offense -> Shoot\n"));
+        assert!(report.contains("event never fired: Defense\n"));
+        assert!(!report.contains("event never fired: Offense\n"));
+    }
+}
@@ -1,36 +1,139 @@
-use crate::eeg::{color, window::Window};
+use crate::{
+    eeg::{
+        color, window::Window, BehaviorTreePanel, ClipRecorder, Coverage, EEGStream, GameRenderer,
+        NodeStatus,
+    },
+    status::BotStatus,
+    strategy::Behavior,
+    telemetry::TelemetryRecorder,
+};
 use common::{prelude::*, rl, Angle, Distance, PrettyPrint, Time};
 use graphics::types::Color;
 use nalgebra::{Point2, Point3, Rotation3};
-use std::{collections::HashSet, mem};
+use std::{
+    collections::HashSet,
+    io, mem,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
 pub struct EEG {
     log_to_stdout: bool,
     window: Option<Window>,
+    stream: Option<EEGStream>,
+    /// This frame's log lines, kept only while streaming.
+    stream_logs: Vec<String>,
+    game_renderer: Option<GameRenderer>,
+    view: View,
     current_packet_time: f32,
     draw_list: DrawList,
+    behavior_tree: BehaviorTreePanel,
+    hidden_categories: HashSet<DrawCategory>,
     pub events: Option<HashSet<Event>>,
+    /// Every behavior switch made by the `Runner`, for replay regression.
+    pub decisions: Option<Vec<String>>,
+    /// Every key-value record logged with `log_value`, for tests.
+    pub logs: Option<Vec<LogRecord>>,
+    pub coverage: Option<Coverage>,
+    clips: Option<ClipRecorder>,
+    telemetry: Option<TelemetryRecorder>,
+    status: Option<Arc<Mutex<BotStatus>>>,
     // I added quick-chat here only for convenience before a tournament, but it should really be
     // somewhere else…
     pub quick_chat: Option<rlbot::flat::QuickChatSelection>,
 }
 
-#[derive(Eq, PartialEq, Hash)]
+#[derive(Eq, PartialEq, Hash, Debug)]
 pub enum Event {
     Defense,
     Retreat,
     HitToOwnCorner,
+    BackwallClear,
     PushFromLeftToRight,
     PushFromRightToLeft,
+    ClearToTeammate,
     RetreatingSave,
     RetreatingSaveStopAndWait,
+    Shadow,
+    FakeChallenge,
     Offense,
+    DemoEnemy,
+    FakeShot,
+    CornerWait,
+    BackboardPass,
+    Redirect,
+    SafetyFilterVeto,
+    KeepBallAirborne,
+    FollowShot,
+    GetBoost,
+    Goalkeeper,
+    Support,
+    TimedJumpSave,
     TepidHitTowardEnemyGoal,
     TepidHitBlockAngleToGoal,
     TepidHitAwayFromOwnGoal,
     PanicDefense,
+    DoubleJumpAerial,
     WallHitFinishedWithoutJump,
     WallHitNotFacingTarget,
+    CalibrationDrift,
+    ContainWallCarry,
+    ContainSlowPlay,
+    GoalScored,
+    GoalConceded,
+    AvoidOwnGoal,
+    WhiffArrivedLate,
+    WhiffBallDiverged,
+    WhiffJumpedEarly,
+    WhiffBadAim,
+}
+
+impl Event {
+    /// Every event, for coverage reports. Keep this in sync with the enum.
+    pub const ALL: &'static [Event] = &[
+        Event::Defense,
+        Event::Retreat,
+        Event::HitToOwnCorner,
+        Event::BackwallClear,
+        Event::PushFromLeftToRight,
+        Event::PushFromRightToLeft,
+        Event::ClearToTeammate,
+        Event::RetreatingSave,
+        Event::RetreatingSaveStopAndWait,
+        Event::Shadow,
+        Event::FakeChallenge,
+        Event::Offense,
+        Event::DemoEnemy,
+        Event::FakeShot,
+        Event::CornerWait,
+        Event::BackboardPass,
+        Event::Redirect,
+        Event::SafetyFilterVeto,
+        Event::KeepBallAirborne,
+        Event::FollowShot,
+        Event::GetBoost,
+        Event::Goalkeeper,
+        Event::Support,
+        Event::TimedJumpSave,
+        Event::TepidHitTowardEnemyGoal,
+        Event::TepidHitBlockAngleToGoal,
+        Event::TepidHitAwayFromOwnGoal,
+        Event::PanicDefense,
+        Event::DoubleJumpAerial,
+        Event::WallHitFinishedWithoutJump,
+        Event::WallHitNotFacingTarget,
+        Event::CalibrationDrift,
+        Event::ContainWallCarry,
+        Event::ContainSlowPlay,
+        Event::GoalScored,
+        Event::GoalConceded,
+        Event::AvoidOwnGoal,
+        Event::WhiffArrivedLate,
+        Event::WhiffBallDiverged,
+        Event::WhiffJumpedEarly,
+        Event::WhiffBadAim,
+    ];
 }
 
 impl EEG {
@@ -39,9 +142,21 @@ impl EEG {
         EEG {
             log_to_stdout: false,
             window: None,
+            stream: None,
+            stream_logs: Vec::new(),
+            game_renderer: None,
+            view: View::TopDown,
             current_packet_time: 0.0,
             draw_list: DrawList::new(),
+            behavior_tree: BehaviorTreePanel::new(),
+            hidden_categories: HashSet::new(),
             events: None,
+            decisions: None,
+            logs: None,
+            coverage: None,
+            clips: None,
+            telemetry: None,
+            status: None,
             quick_chat: None,
         }
     }
@@ -54,9 +169,64 @@ impl EEG {
         self.window = Some(Window::new());
     }
 
+    /// Send everything that would be drawn, and the log, to clients that
+    /// connect to `addr`. This works with or without the window, so the bot can
+    /// run headless and be watched from another machine.
+    pub fn stream_to(&mut self, addr: &str) -> io::Result<()> {
+        self.stream = Some(EEGStream::bind(addr)?);
+        Ok(())
+    }
+
+    /// Draw into the game itself through RLBot's render API. Like the stream,
+    /// this works with or without the window.
+    pub fn render_in_game(&mut self, rlbot: &'static rlbot::RLBot) {
+        self.game_renderer = Some(GameRenderer::new(rlbot));
+    }
+
+    /// Change how the window lays out the arena.
+    pub fn set_view(&mut self, view: View) {
+        self.view = view;
+    }
+
     pub fn track_events(&mut self) {
         self.events = Some(HashSet::new());
     }
+
+    pub fn track_decisions(&mut self) {
+        self.decisions = Some(Vec::new());
+    }
+
+    pub fn track_logs(&mut self) {
+        self.logs = Some(Vec::new());
+    }
+
+    pub fn track_coverage(&mut self) {
+        self.coverage = Some(Coverage::new());
+    }
+
+    /// Save a clip of the game to `dir` whenever something notable happens.
+    pub fn save_clips_to(&mut self, dir: impl Into<PathBuf>) {
+        self.clips = Some(ClipRecorder::new(dir));
+    }
+
+    /// Record every frame of the match to a telemetry log at `path`.
+    pub fn record_telemetry_to(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.telemetry = Some(TelemetryRecorder::create(path)?);
+        Ok(())
+    }
+
+    /// Keep a `BotStatus` up to date from now on, for anyone outside the bot
+    /// who wants to know what it's doing.
+    pub fn publish_status(&mut self) -> Arc<Mutex<BotStatus>> {
+        let status = Arc::new(Mutex::new(BotStatus::default()));
+        self.status = Some(status.clone());
+        status
+    }
+
+    /// Stop drawing anything in the given category.
+    pub fn hide(&mut self, category: DrawCategory) {
+        self.hidden_categories.insert(category);
+    }
 }
 
 impl EEG {
@@ -65,75 +235,227 @@ impl EEG {
         self.current_packet_time = packet.GameInfo.TimeSeconds;
         assert!(self.draw_list.drawables.is_empty());
         self.quick_chat = None;
+        if let Some(clips) = &mut self.clips {
+            clips.record(packet);
+        }
+        if let Some(telemetry) = &mut self.telemetry {
+            telemetry.begin(packet);
+        }
     }
 
     /// Call this at the end of each frame.
     pub fn show(&mut self, packet: &common::halfway_house::LiveDataPacket) {
-        let drawables = mem::replace(&mut self.draw_list.drawables, Vec::new());
-        if let Some(window) = &self.window {
-            window.draw(packet.clone(), drawables);
+        let mut drawables = mem::replace(&mut self.draw_list.drawables, Vec::new());
+        let over_budget = mem::replace(&mut self.draw_list.over_budget, 0);
+        if over_budget > 0 {
+            drawables.push(Drawable::print(
+                format!("over budget: {} drawables", over_budget),
+                color::RED,
+            ));
+        }
+        if let Some(stream) = &mut self.stream {
+            stream.send(packet, &drawables, &self.stream_logs);
+            self.stream_logs.clear();
+        }
+        if let Some(renderer) = &self.game_renderer {
+            renderer.draw(&drawables);
+        }
+        if let Some(window) = &mut self.window {
+            window.draw(packet, drawables, self.view);
+        }
+        if let Some(telemetry) = &mut self.telemetry {
+            if let Err(err) = telemetry.finish() {
+                log::warn!("could not write telemetry; giving up: {}", err);
+                self.telemetry = None;
+            }
         }
     }
 
+    /// Returns `true` if drawables in the given category will actually end up
+    /// on screen. Check this before doing work that only exists to be drawn.
+    pub fn is_drawing(&self, category: DrawCategory) -> bool {
+        (self.window.is_some() || self.stream.is_some() || self.game_renderer.is_some())
+            && !self.hidden_categories.contains(&category)
+    }
+
     pub fn quick_chat(&mut self, selection: rlbot::flat::QuickChatSelection) {
         self.quick_chat = Some(selection);
     }
 
     pub fn draw(&mut self, drawable: Drawable) {
-        self.draw_list.draw(drawable);
+        if self.is_drawing(drawable.category()) {
+            self.draw_list.draw(drawable);
+        }
+    }
+
+    /// Show everything the `Runner` is running, starting from `root`.
+    pub fn draw_behavior_tree(&mut self, root: &dyn Behavior) {
+        if !self.is_drawing(DrawCategory::Text) {
+            return;
+        }
+        for drawable in self.behavior_tree.drawables(self.current_packet_time, root) {
+            self.draw_list.draw(drawable);
+        }
+    }
+
+    /// Note that a behavior finished, so the behavior panel can show how.
+    pub fn track_outcome(&mut self, name: &str, status: NodeStatus) {
+        if self.is_drawing(DrawCategory::Text) {
+            self.behavior_tree
+                .finish(self.current_packet_time, name, status);
+        }
     }
 
     pub fn print_value(&mut self, label: &str, value: impl PrettyPrint) {
-        self.draw_list.print_value(label, value);
+        // Skip the formatting, too.
+        if self.is_drawing(DrawCategory::Text) {
+            self.draw_list.print_value(label, value);
+        }
     }
 
     pub fn print_time(&mut self, label: &str, time: f32) {
-        self.draw_list.print_value(label, Time(time));
+        self.print_value(label, Time(time));
     }
 
     pub fn print_angle(&mut self, label: &str, angle: f32) {
-        self.draw_list.print_value(label, Angle(angle));
+        self.print_value(label, Angle(angle));
     }
 
     pub fn print_distance(&mut self, label: &str, distance: f32) {
-        self.draw_list.print_value(label, Distance(distance));
+        self.print_value(label, Distance(distance));
     }
 
     pub fn log(&mut self, tag: &str, message: impl Into<String>) {
-        if !self.log_to_stdout {
+        if !self.log_to_stdout && self.stream.is_none() {
             return;
         }
-        println!(
+        let line = format!(
             "{:>8.3} [{}] {}",
             self.current_packet_time,
             tag,
             message.into()
         );
+        if self.log_to_stdout {
+            println!("{}", line);
+        }
+        if self.stream.is_some() {
+            self.stream_logs.push(line);
+        }
     }
 
     pub fn log_pretty(&mut self, tag: &str, name: &str, value: impl PrettyPrint) {
         self.log(tag, format!("{} = {}", name, value.pretty()))
     }
 
+    /// Log a key-value pair. Unlike a plain `log`, these are kept as records
+    /// if logs are being tracked, so tests can check them without parsing
+    /// strings.
+    pub fn log_value(&mut self, tag: &str, key: &str, value: impl ToString) {
+        let value = value.to_string();
+        self.log(tag, format!("{} = {}", key, value));
+        if let Some(ref mut logs) = self.logs {
+            logs.push(LogRecord {
+                time: self.current_packet_time,
+                tag: tag.to_string(),
+                key: key.to_string(),
+                value,
+            });
+        }
+    }
+
+    /// Note that something notable happened. If clips are being saved, this
+    /// saves one around the current time.
+    pub fn clip(&mut self, reason: &str) {
+        let triggered = match &mut self.clips {
+            Some(clips) => clips.trigger(self.current_packet_time, reason),
+            None => false,
+        };
+        if triggered {
+            self.log("clip", reason);
+        }
+    }
+
+    /// Log a change of behavior. These are also kept in the decision trace, if
+    /// it's being tracked.
+    pub fn log_decision(&mut self, tag: &str, message: impl Into<String>) {
+        let message = message.into();
+        let line = format!("{:.3} [{}] {}", self.current_packet_time, tag, message);
+        self.update_status(|status| status.push_decision(line.clone()));
+        if let Some(telemetry) = &mut self.telemetry {
+            telemetry.decision(&line);
+        }
+        if let Some(ref mut decisions) = self.decisions {
+            decisions.push(line);
+        }
+        self.log(tag, message);
+    }
+
+    /// Update the published status, if anyone asked for it.
+    pub fn update_status(&mut self, f: impl FnOnce(&mut BotStatus)) {
+        if let Some(status) = &self.status {
+            f(&mut status.lock().unwrap());
+        }
+    }
+
     pub fn track(&mut self, event: Event) {
+        if let Some(telemetry) = &mut self.telemetry {
+            telemetry.event(&event);
+        }
+        if let Some(ref mut coverage) = self.coverage {
+            coverage.event(&event);
+        }
         if let Some(ref mut events) = self.events {
             events.insert(event);
         }
     }
+
+    /// Note the input we settled on for this frame, and the behavior that
+    /// chose it.
+    pub fn record_input(&mut self, behavior: &str, input: &common::halfway_house::PlayerInput) {
+        if let Some(telemetry) = &mut self.telemetry {
+            telemetry.input(behavior, input);
+        }
+    }
+
+    /// Note that the `Runner` switched from one behavior to another.
+    pub fn track_activation(&mut self, from: Option<&str>, to: &str) {
+        if let Some(ref mut coverage) = self.coverage {
+            coverage.activate(from, to);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    pub time: f32,
+    /// Usually the name of the behavior that logged it.
+    pub tag: String,
+    pub key: String,
+    pub value: String,
 }
 
 pub struct DrawList {
     pub drawables: Vec<Drawable>,
+    /// How many drawables were thrown away this frame for exceeding the budget.
+    over_budget: usize,
 }
 
 impl DrawList {
+    /// Past this many drawables per frame, the window can't keep up anyway.
+    const BUDGET: usize = 500;
+
     pub fn new() -> Self {
         Self {
             drawables: Vec::new(),
+            over_budget: 0,
         }
     }
 
     fn draw(&mut self, drawable: Drawable) {
+        if self.drawables.len() >= Self::BUDGET {
+            self.over_budget += 1;
+            return;
+        }
         self.drawables.push(drawable);
     }
 
@@ -145,7 +467,7 @@ impl DrawList {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum Drawable {
     GhostBall(Point3<f32>, Color),
     GhostCar(Point3<f32>, Rotation3<f32>),
@@ -153,6 +475,9 @@ pub enum Drawable {
     Line(Point2<f32>, Point2<f32>, Color),
     Arc(Point2<f32>, f32, f32, f32, Color),
     Print(String, Color),
+    /// Only drawn in the unfolded view. The inner drawable's coordinates are
+    /// already in unfolded space.
+    Unfolded(Box<Drawable>),
 }
 
 impl Drawable {
@@ -167,4 +492,64 @@ impl Drawable {
     pub fn print(text: impl Into<String>, color: Color) -> Self {
         Drawable::Print(text.into(), color)
     }
+
+    pub fn unfolded(drawable: Drawable) -> Self {
+        Drawable::Unfolded(Box::new(drawable))
+    }
+
+    fn category(&self) -> DrawCategory {
+        match self {
+            Drawable::Print(..) => DrawCategory::Text,
+            Drawable::Unfolded(inner) => inner.category(),
+            _ => DrawCategory::Shapes,
+        }
+    }
+}
+
+/// Groups of drawables that can be hidden independently.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DrawCategory {
+    /// Planned routes. Drawing every segment of every plan is the most
+    /// expensive thing we draw.
+    Routes,
+    /// Text in the corner of the window.
+    Text,
+    /// Everything else.
+    Shapes,
+}
+
+impl FromStr for DrawCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "routes" => Ok(DrawCategory::Routes),
+            "text" => Ok(DrawCategory::Text),
+            "shapes" => Ok(DrawCategory::Shapes),
+            _ => Err(format!("unknown draw category {:?}", s)),
+        }
+    }
+}
+
+/// How the window lays out the arena.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum View {
+    /// Looking straight down at the floor. Anything up on a wall is drawn
+    /// wherever it would land if it dropped straight down.
+    TopDown,
+    /// The walls folded out flat around the floor, like a box cut open at the
+    /// corners. Wall routes show up where they actually go.
+    Unfolded,
+}
+
+impl FromStr for View {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top-down" => Ok(View::TopDown),
+            "unfolded" => Ok(View::Unfolded),
+            _ => Err(format!("unknown view {:?}", s)),
+        }
+    }
 }
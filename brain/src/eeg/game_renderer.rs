@@ -0,0 +1,214 @@
+use crate::eeg::{color, Drawable};
+use common::rl;
+use graphics::types::Color;
+use nalgebra::{Point2, Point3, Vector2, Vector3};
+use std::f32::consts::PI;
+
+/// Draws the EEG into the game window through RLBot's render API, so visual
+/// debugging doesn't need the separate 2D window.
+///
+/// The game is 3D and the window is not, so shapes that only have a 2D
+/// location are drawn just above the floor, and unfolded drawables are skipped.
+pub struct GameRenderer {
+    rlbot: &'static rlbot::RLBot,
+}
+
+impl GameRenderer {
+    /// Any id works, as long as nothing else renders with it.
+    const GROUP_ID: i32 = 0x0EE6;
+    /// Draw floor-level shapes this high, so they don't disappear into the
+    /// floor.
+    const FLOOR_Z: f32 = 20.0;
+    const CIRCLE_SEGMENTS: usize = 16;
+    const CROSSHAIR_SIZE: f32 = 100.0;
+    const TEXT_X: f32 = 10.0;
+    const TEXT_Y: f32 = 10.0;
+    const TEXT_LINE_HEIGHT: f32 = 15.0;
+
+    pub fn new(rlbot: &'static rlbot::RLBot) -> Self {
+        Self { rlbot }
+    }
+
+    pub fn draw(&self, drawables: &[Drawable]) {
+        let mut group = self.rlbot.begin_render_group(Self::GROUP_ID);
+        let mut text_y = Self::TEXT_Y;
+        for primitive in primitives(drawables) {
+            match primitive {
+                Primitive::Line(start, end, c) => {
+                    let (a, r, g, b) = argb(c);
+                    let c = group.color_argb(a, r, g, b);
+                    group.draw_line_3d((start.x, start.y, start.z), (end.x, end.y, end.z), c);
+                }
+                Primitive::Text(text, c) => {
+                    let (a, r, g, b) = argb(c);
+                    let c = group.color_argb(a, r, g, b);
+                    group.draw_string_2d((Self::TEXT_X, text_y), (1, 1), text, c);
+                    text_y += Self::TEXT_LINE_HEIGHT;
+                }
+            }
+        }
+
+        // Like the window, this is best-effort. Drop the frame on errors.
+        let _ = group.render();
+    }
+}
+
+fn argb(color: Color) -> (u8, u8, u8, u8) {
+    let byte = |x: f32| (x * 255.0).max(0.0).min(255.0) as u8;
+    (
+        byte(color[3]),
+        byte(color[0]),
+        byte(color[1]),
+        byte(color[2]),
+    )
+}
+
+/// The only things the render API needs to know how to draw.
+#[derive(Debug)]
+enum Primitive<'a> {
+    Line(Point3<f32>, Point3<f32>, Color),
+    Text(&'a str, Color),
+}
+
+fn primitives(drawables: &[Drawable]) -> Vec<Primitive<'_>> {
+    let mut result = Vec::new();
+    for drawable in drawables {
+        match drawable {
+            Drawable::GhostBall(loc, color) => {
+                for &(u, v) in &[
+                    (Vector3::x(), Vector3::y()),
+                    (Vector3::x(), Vector3::z()),
+                    (Vector3::y(), Vector3::z()),
+                ] {
+                    let points = circle(*loc, u, v, rl::BALL_RADIUS, 0.0, 2.0 * PI);
+                    polyline(&mut result, &points, *color);
+                }
+            }
+            Drawable::GhostCar(loc, rot) => {
+                let (loc, rot) = (*loc, *rot);
+                let half = Vector3::new(
+                    rl::OCTANE_HITBOX_LENGTH,
+                    rl::OCTANE_HITBOX_WIDTH,
+                    rl::OCTANE_HITBOX_HEIGHT,
+                ) / 2.0;
+                let corner = |x: f32, y: f32, z: f32| {
+                    loc + rot * Vector3::new(x * half.x, y * half.y, z * half.z)
+                };
+                // Each edge of the box runs along one axis, between two corners
+                // that differ only along that axis.
+                for &s in &[-1.0, 1.0] {
+                    for &t in &[-1.0, 1.0] {
+                        let edges = [
+                            (corner(-1.0, s, t), corner(1.0, s, t)),
+                            (corner(s, -1.0, t), corner(s, 1.0, t)),
+                            (corner(s, t, -1.0), corner(s, t, 1.0)),
+                        ];
+                        for &(start, end) in &edges {
+                            result.push(Primitive::Line(start, end, color::WHITE));
+                        }
+                    }
+                }
+            }
+            Drawable::Crosshair(loc) => {
+                let size = GameRenderer::CROSSHAIR_SIZE;
+                for &(dx, dy) in &[(size, size), (size, -size)] {
+                    let offset = Vector2::new(dx, dy);
+                    result.push(Primitive::Line(
+                        floor(*loc - offset),
+                        floor(*loc + offset),
+                        color::YELLOW,
+                    ));
+                }
+            }
+            Drawable::Line(start, end, color) => {
+                result.push(Primitive::Line(floor(*start), floor(*end), *color));
+            }
+            Drawable::Arc(center, radius, start, end, color) => {
+                let center = floor(*center);
+                let points = circle(center, Vector3::x(), Vector3::y(), *radius, *start, *end);
+                polyline(&mut result, &points, *color);
+            }
+            Drawable::Print(text, color) => {
+                result.push(Primitive::Text(text, *color));
+            }
+            // These are laid out for the unfolded walls in the window, which
+            // have no place in the actual arena.
+            Drawable::Unfolded(_) => {}
+        }
+    }
+    result
+}
+
+fn floor(loc: Point2<f32>) -> Point3<f32> {
+    Point3::new(loc.x, loc.y, GameRenderer::FLOOR_Z)
+}
+
+/// Points along an arc around `center`, in the plane spanned by `u` and `v`,
+/// from angle `start` to `end`.
+fn circle(
+    center: Point3<f32>,
+    u: Vector3<f32>,
+    v: Vector3<f32>,
+    radius: f32,
+    start: f32,
+    end: f32,
+) -> Vec<Point3<f32>> {
+    let n = GameRenderer::CIRCLE_SEGMENTS;
+    (0..=n)
+        .map(|i| {
+            let angle = start + (end - start) * i as f32 / n as f32;
+            center + (u * angle.cos() + v * angle.sin()) * radius
+        })
+        .collect()
+}
+
+fn polyline(result: &mut Vec<Primitive<'_>>, points: &[Point3<f32>], color: Color) {
+    for pair in points.windows(2) {
+        result.push(Primitive::Line(pair[0], pair[1], color));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eeg::{
+        color,
+        game_renderer::{primitives, GameRenderer, Primitive},
+        Drawable,
+    };
+    use nalgebra::{Point2, Point3, Rotation3};
+
+    #[test]
+    fn shapes_become_lines() {
+        let n = GameRenderer::CIRCLE_SEGMENTS;
+        let count = |d: Drawable| primitives(&[d]).len();
+        assert_eq!(count(Drawable::ghost_ball(Point3::origin())), 3 * n);
+        assert_eq!(
+            count(Drawable::GhostCar(Point3::origin(), Rotation3::identity())),
+            12,
+        );
+        assert_eq!(count(Drawable::Crosshair(Point2::origin())), 2);
+        assert_eq!(
+            count(Drawable::Arc(Point2::origin(), 100.0, 0.0, 1.0, color::RED)),
+            n,
+        );
+    }
+
+    #[test]
+    fn floor_and_text() {
+        let drawables = [
+            Drawable::Line(Point2::new(0.0, 0.0), Point2::new(100.0, 0.0), color::RED),
+            Drawable::print("Defense", color::YELLOW),
+            Drawable::unfolded(Drawable::print("hidden", color::RED)),
+        ];
+        let primitives = primitives(&drawables);
+        assert_eq!(primitives.len(), 2);
+        match primitives[0] {
+            Primitive::Line(start, _, _) => assert_eq!(start.z, GameRenderer::FLOOR_Z),
+            ref p => panic!("{:?}", p),
+        }
+        match primitives[1] {
+            Primitive::Text(text, _) => assert_eq!(text, "Defense"),
+            ref p => panic!("{:?}", p),
+        }
+    }
+}
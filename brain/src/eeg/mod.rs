@@ -1,8 +1,20 @@
-pub use crate::eeg::eeg::*;
+pub use crate::eeg::{
+    behavior_tree::{BehaviorTreePanel, NodeStatus},
+    clip::ClipRecorder,
+    coverage::Coverage,
+    eeg::*,
+    game_renderer::GameRenderer,
+    stream::EEGStream,
+};
 
+mod behavior_tree;
+mod clip;
 pub mod color;
+mod coverage;
 #[allow(clippy::module_inception)]
 mod eeg;
+mod game_renderer;
 #[allow(dead_code)]
 pub mod recipes;
+mod stream;
 mod window;
@@ -0,0 +1,280 @@
+use crate::eeg::Drawable;
+use common::prelude::*;
+use graphics::types::Color;
+use nalgebra::{Point2, Point3, Rotation3};
+use serde_derive::Serialize;
+use std::{
+    io::{self, Write},
+    mem,
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// Sends everything the EEG would draw, plus the log, to anyone who connects.
+/// That way the overlay can run on a second machine, and the game PC doesn't
+/// spend any time drawing.
+///
+/// Each frame is one line of JSON. See `frame_json` for the format.
+pub struct EEGStream {
+    tx: crossbeam_channel::Sender<String>,
+    dropped_frames: u32,
+}
+
+/// A client that can't take a frame in this long has stopped reading, and
+/// gets dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+impl EEGStream {
+    /// Listen on `addr`, e.g., `0.0.0.0:8322`, from background threads.
+    pub fn bind(addr: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let clients = Arc::new(Mutex::new(Vec::new()));
+
+        let accepted = clients.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.and_then(|stream| {
+                    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+                    Ok(stream)
+                });
+                match stream {
+                    Ok(stream) => accepted.lock().unwrap().push(stream),
+                    Err(e) => log::warn!("could not accept EEG client: {}", e),
+                }
+            }
+        });
+
+        // Like the window, keep at most one frame in flight. A slow client
+        // costs frames, not bot time.
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        thread::spawn(move || send_thread(rx, clients));
+
+        Ok(Self {
+            tx,
+            dropped_frames: 0,
+        })
+    }
+
+    pub fn send(
+        &mut self,
+        packet: &common::halfway_house::LiveDataPacket,
+        drawables: &[Drawable],
+        logs: &[String],
+    ) {
+        // We're the only sender, so nothing can fill the channel between the check
+        // and the send.
+        if self.tx.is_full() {
+            self.dropped_frames += 1;
+            return;
+        }
+        self.tx
+            .send(frame_json(packet, drawables, logs, self.dropped_frames));
+    }
+}
+
+fn send_thread(rx: crossbeam_channel::Receiver<String>, clients: Arc<Mutex<Vec<TcpStream>>>) {
+    while let Some(line) = rx.recv() {
+        send_to_all(&clients, &line);
+    }
+}
+
+/// Send `line` to every client, and forget anyone who hung up or stopped
+/// reading. The writes happen outside the lock, so new clients can connect
+/// meanwhile.
+fn send_to_all(clients: &Mutex<Vec<TcpStream>>, line: &str) {
+    let mut sending = mem::replace(&mut *clients.lock().unwrap(), Vec::new());
+    sending.retain(|mut client: &TcpStream| client.write_all(line.as_bytes()).is_ok());
+    clients.lock().unwrap().extend(sending);
+}
+
+/// One frame, as a line of JSON:
+///
+/// ```text
+/// {"time":12.5,"dropped_frames":0,"ball":[0.0,0.0,92.75],
+///  "cars":[{"team":0,"loc":[0.0,-4608.0,17.0],"rot":[0.0,1.57,0.0]}],
+///  "drawables":[{"type":"print","text":"Defense","color":[1.0,1.0,0.0,1.0]}],
+///  "logs":["  12.500 [Runner] > Defense"]}
+/// ```
+///
+/// Rotations are pitch, yaw and roll. Colors are RGBA from 0 to 1.
+pub fn frame_json(
+    packet: &common::halfway_house::LiveDataPacket,
+    drawables: &[Drawable],
+    logs: &[String],
+    dropped_frames: u32,
+) -> String {
+    let frame = FrameJson {
+        time: packet.GameInfo.TimeSeconds,
+        dropped_frames,
+        ball: packet.GameBall.Physics.loc(),
+        cars: packet
+            .cars()
+            .map(|car| CarJson {
+                team: car.Team,
+                loc: car.Physics.loc(),
+                rot: car.Physics.rot(),
+            })
+            .collect(),
+        drawables: drawables.iter().map(DrawableJson::new).collect(),
+        logs,
+    };
+    let mut line = serde_json::to_string(&frame).unwrap();
+    line.push('\n');
+    line
+}
+
+#[derive(Serialize)]
+struct FrameJson<'a> {
+    time: f32,
+    dropped_frames: u32,
+    #[serde(with = "crate::utils::wire::point3")]
+    ball: Point3<f32>,
+    cars: Vec<CarJson>,
+    drawables: Vec<DrawableJson<'a>>,
+    logs: &'a [String],
+}
+
+#[derive(Serialize)]
+struct CarJson {
+    team: u8,
+    #[serde(with = "crate::utils::wire::point3")]
+    loc: Point3<f32>,
+    #[serde(with = "crate::utils::wire::rotation3")]
+    rot: Rotation3<f32>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum DrawableJson<'a> {
+    GhostBall {
+        #[serde(with = "crate::utils::wire::point3")]
+        loc: Point3<f32>,
+        color: &'a Color,
+    },
+    GhostCar {
+        #[serde(with = "crate::utils::wire::point3")]
+        loc: Point3<f32>,
+        #[serde(with = "crate::utils::wire::rotation3")]
+        rot: Rotation3<f32>,
+    },
+    Crosshair {
+        #[serde(with = "crate::utils::wire::point2")]
+        loc: Point2<f32>,
+    },
+    Line {
+        #[serde(with = "crate::utils::wire::point2")]
+        start: Point2<f32>,
+        #[serde(with = "crate::utils::wire::point2")]
+        end: Point2<f32>,
+        color: &'a Color,
+    },
+    Arc {
+        #[serde(with = "crate::utils::wire::point2")]
+        center: Point2<f32>,
+        radius: f32,
+        start: f32,
+        end: f32,
+        color: &'a Color,
+    },
+    Print {
+        text: &'a str,
+        color: &'a Color,
+    },
+    Unfolded {
+        inner: Box<DrawableJson<'a>>,
+    },
+}
+
+impl<'a> DrawableJson<'a> {
+    fn new(drawable: &'a Drawable) -> Self {
+        match drawable {
+            Drawable::GhostBall(loc, color) => DrawableJson::GhostBall { loc: *loc, color },
+            Drawable::GhostCar(loc, rot) => DrawableJson::GhostCar {
+                loc: *loc,
+                rot: *rot,
+            },
+            Drawable::Crosshair(loc) => DrawableJson::Crosshair { loc: *loc },
+            Drawable::Line(start, end, color) => DrawableJson::Line {
+                start: *start,
+                end: *end,
+                color,
+            },
+            Drawable::Arc(center, radius, start, end, color) => DrawableJson::Arc {
+                center: *center,
+                radius: *radius,
+                start: *start,
+                end: *end,
+                color,
+            },
+            Drawable::Print(text, color) => DrawableJson::Print { text, color },
+            Drawable::Unfolded(inner) => DrawableJson::Unfolded {
+                inner: Box::new(DrawableJson::new(inner)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eeg::{
+        color,
+        stream::{frame_json, send_to_all, WRITE_TIMEOUT},
+        Drawable,
+    };
+    use common::halfway_house::LiveDataPacket;
+    use nalgebra::{Point2, Point3};
+    use std::{
+        net::{TcpListener, TcpStream},
+        sync::Mutex,
+    };
+
+    fn packet() -> LiveDataPacket {
+        let mut packet = LiveDataPacket::default().with_time(12.5);
+        packet.GameBall.Physics.Location.Z = 92.75;
+        packet
+    }
+
+    #[test]
+    fn frame() {
+        let drawables = [
+            Drawable::print("Chain (\"Dodge\")", color::YELLOW),
+            Drawable::unfolded(Drawable::Line(
+                Point2::new(1.0, 2.0),
+                Point2::new(3.0, 4.0),
+                color::RED,
+            )),
+            Drawable::ghost_ball(Point3::new(0.0, 0.0, 100.0)),
+        ];
+        let logs = ["  12.500 [Runner] > Defense".to_string()];
+        assert_eq!(
+            frame_json(&packet(), &drawables, &logs, 3),
+            concat!(
+                r#"{"time":12.5,"dropped_frames":3,"ball":[0.0,0.0,92.75],"cars":[],"#,
+                r#""drawables":[{"type":"print","text":"Chain (\"Dodge\")","#,
+                r#""color":[1.0,1.0,0.0,1.0]},"#,
+                r#"{"type":"unfolded","inner":{"type":"line","start":[1.0,2.0],"end":[3.0,4.0],"#,
+                r#""color":[1.0,0.0,0.0,1.0]}},"#,
+                r#"{"type":"ghost_ball","loc":[0.0,0.0,100.0],"color":[1.0,1.0,1.0,1.0]}],"#,
+                r#""logs":["  12.500 [Runner] > Defense"]}"#,
+                "\n",
+            ),
+        );
+    }
+
+    #[test]
+    fn stalled_client_is_dropped() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        // Connects, but never reads.
+        let _stalled = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        stream.set_write_timeout(Some(WRITE_TIMEOUT)).unwrap();
+        let clients = Mutex::new(vec![stream]);
+
+        // Far more than the socket buffers hold.
+        let line = "x".repeat(64 << 20);
+        send_to_all(&clients, &line);
+        assert!(clients.lock().unwrap().is_empty());
+    }
+}
@@ -1,14 +1,17 @@
 // This file is a hot mess, don't look at it please :)
 
 use crate::{
-    eeg::{color, eeg::Drawable},
-    strategy::Team,
+    eeg::{
+        color,
+        eeg::{Drawable, View},
+    },
+    strategy::{Team, DFH_STADIUM},
 };
 use common::{prelude::*, rl};
 use graphics::{
-    circle_arc, clear, ellipse, line, rectangle, text, Ellipse, Rectangle, Transformed,
+    circle_arc, clear, ellipse, line, polygon, rectangle, text, Ellipse, Rectangle, Transformed,
 };
-use nalgebra::Point2;
+use nalgebra::{Point2, Point3, Rotation3, Vector3};
 use piston_window::{
     AdvancedWindow, Glyphs, OpenGL, PistonWindow, Position, TextureSettings, WindowSettings,
 };
@@ -17,23 +20,43 @@ use std::{path::PathBuf, thread};
 pub struct Window {
     tx: Option<crossbeam_channel::Sender<ThreadMessage>>,
     join_handle: Option<thread::JoinHandle<()>>,
+    dropped_frames: u32,
 }
 
 impl Window {
     pub fn new() -> Self {
-        let (tx, rx) = crossbeam_channel::unbounded();
+        // Keep at most one frame in flight. If the render thread falls behind, it
+        // would only skip to the latest frame anyway.
+        let (tx, rx) = crossbeam_channel::bounded(1);
         let join_handle = thread::spawn(|| thread(rx));
         Self {
             tx: Some(tx),
             join_handle: Some(join_handle),
+            dropped_frames: 0,
         }
     }
 
-    pub fn draw(&self, packet: common::halfway_house::LiveDataPacket, drawables: Vec<Drawable>) {
-        self.tx
-            .as_ref()
-            .unwrap()
-            .send(ThreadMessage::Draw(packet, drawables));
+    /// Send a frame to the render thread. If it's still busy with the last one,
+    /// drop this one instead of blocking the bot.
+    pub fn draw(
+        &mut self,
+        packet: &common::halfway_house::LiveDataPacket,
+        drawables: Vec<Drawable>,
+        view: View,
+    ) {
+        let tx = self.tx.as_ref().unwrap();
+        // We're the only sender, so nothing can fill the channel between the check
+        // and the send.
+        if tx.is_full() {
+            self.dropped_frames += 1;
+            return;
+        }
+        tx.send(ThreadMessage::Draw(
+            packet.clone(),
+            drawables,
+            view,
+            self.dropped_frames,
+        ));
     }
 }
 
@@ -45,7 +68,12 @@ impl Drop for Window {
 }
 
 enum ThreadMessage {
-    Draw(common::halfway_house::LiveDataPacket, Vec<Drawable>),
+    Draw(
+        common::halfway_house::LiveDataPacket,
+        Vec<Drawable>,
+        View,
+        u32,
+    ),
 }
 
 fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
@@ -74,18 +102,39 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
 
         match message {
             None => break, // The channel was closed, so exit the thread.
-            Some(ThreadMessage::Draw(packet, drawables)) => {
+            Some(ThreadMessage::Draw(packet, drawables, view, dropped_frames)) => {
+                let drawables = dedup(drawables);
+                // Where to draw something in the arena, and which way it faces.
+                let place = |loc: Point3<f32>, rot: &Rotation3<f32>| match view {
+                    View::TopDown => (loc.to_2d(), rot.yaw()),
+                    View::Unfolded => {
+                        let flattener = DFH_STADIUM.unfold_to_ground(&loc);
+                        let forward = flattener * (rot * Vector3::x());
+                        (flattener * loc, forward.y.atan2(forward.x))
+                    }
+                };
                 window.draw_2d(&event, |c, g| {
                     const GOAL_DEPTH: f64 = 900.0; // This was just estimated visually.
+                    const GOAL_HEIGHT: f64 = 642.775;
                     let car_rect = rectangle::rectangle_by_corners(-100.0, -50.0, 100.0, 50.0);
                     let ball_rect = ellipse::circle(0.0, 0.0, 92.0);
 
-                    const SCALE: f64 = 0.05;
-                    const OUTLINE_RADIUS: f64 = 0.5 / SCALE;
+                    // Zoom out in the unfolded view to make room for the walls.
+                    let (scale, origin, text_x) = match view {
+                        View::TopDown => (0.05, [4200.0, 6200.0], 420.0),
+                        View::Unfolded => (0.035, [6200.0, 7200.0], 440.0),
+                    };
+                    let outline_radius = 0.5 / scale;
 
                     clear(color::BLACK, g);
 
-                    let transform = c.transform.scale(SCALE, SCALE).trans(4200.0, 6200.0);
+                    let transform = c.transform.scale(scale, scale).trans(origin[0], origin[1]);
+
+                    if view == View::Unfolded {
+                        for wall in &unfolded_walls() {
+                            polygon(color::WALL, wall, transform, g);
+                        }
+                    }
 
                     rectangle(
                         color::PITCH,
@@ -98,13 +147,18 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                         transform,
                         g,
                     );
+                    // In the unfolded view, the goals are holes in the back walls.
+                    let goal_extent = match view {
+                        View::TopDown => GOAL_DEPTH,
+                        View::Unfolded => GOAL_HEIGHT,
+                    };
                     rectangle(
                         color::BLUE_DARK,
                         rectangle::rectangle_by_corners(
                             f64::from(-rl::GOALPOST_X),
                             f64::from(-rl::FIELD_MAX_Y),
                             f64::from(rl::GOALPOST_X),
-                            f64::from(-rl::FIELD_MAX_Y) - GOAL_DEPTH,
+                            f64::from(-rl::FIELD_MAX_Y) - goal_extent,
                         ),
                         transform,
                         g,
@@ -115,7 +169,7 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                             f64::from(-rl::GOALPOST_X),
                             f64::from(rl::FIELD_MAX_Y),
                             f64::from(rl::GOALPOST_X),
-                            f64::from(rl::FIELD_MAX_Y) + GOAL_DEPTH,
+                            f64::from(rl::FIELD_MAX_Y) + goal_extent,
                         ),
                         transform,
                         g,
@@ -138,35 +192,40 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                     }
 
                     for car in packet.cars() {
+                        let (loc, yaw) = place(car.Physics.loc(), &car.Physics.rot());
                         rectangle(
                             color::for_team(Team::from_ffi(car.Team)),
                             car_rect,
                             transform
-                                .trans(
-                                    f64::from(car.Physics.Location.X),
-                                    f64::from(car.Physics.Location.Y),
-                                )
-                                .rot_rad(f64::from(car.Physics.Rotation.Yaw)),
+                                .trans(f64::from(loc.x), f64::from(loc.y))
+                                .rot_rad(f64::from(yaw)),
                             g,
                         );
                     }
 
+                    let (ball_loc, _) =
+                        place(packet.GameBall.Physics.loc(), &Rotation3::identity());
                     ellipse(
                         color::WHITE,
                         ball_rect,
-                        transform.trans(
-                            f64::from(packet.GameBall.Physics.Location.X),
-                            f64::from(packet.GameBall.Physics.Location.Y),
-                        ),
+                        transform.trans(f64::from(ball_loc.x), f64::from(ball_loc.y)),
                         g,
                     );
 
                     let mut prints = Vec::new();
 
-                    for drawable in drawables.into_iter() {
+                    let drawables = drawables.into_iter().filter_map(|d| match d {
+                        Drawable::Unfolded(inner) => match view {
+                            View::TopDown => None,
+                            View::Unfolded => Some(*inner),
+                        },
+                        d => Some(d),
+                    });
+                    for drawable in drawables {
                         match drawable {
                             Drawable::GhostBall(loc, color) => {
-                                Ellipse::new_border(color, OUTLINE_RADIUS).draw(
+                                let (loc, _) = place(loc, &Rotation3::identity());
+                                Ellipse::new_border(color, outline_radius).draw(
                                     ball_rect,
                                     &Default::default(),
                                     transform.trans(f64::from(loc.x), f64::from(loc.y)),
@@ -174,19 +233,20 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                                 );
                             }
                             Drawable::GhostCar(loc, rot) => {
-                                Rectangle::new_border(color::WHITE, OUTLINE_RADIUS).draw(
+                                let (loc, yaw) = place(loc, &rot);
+                                Rectangle::new_border(color::WHITE, outline_radius).draw(
                                     car_rect,
                                     &Default::default(),
                                     transform
                                         .trans(f64::from(loc.x), f64::from(loc.y))
-                                        .rot_rad(f64::from(rot.yaw())),
+                                        .rot_rad(f64::from(yaw)),
                                     g,
                                 );
                             }
                             Drawable::Crosshair(loc) => {
                                 line(
                                     color::YELLOW,
-                                    OUTLINE_RADIUS,
+                                    outline_radius,
                                     [
                                         f64::from(loc.x) - 100.0,
                                         f64::from(loc.y) - 100.0,
@@ -198,7 +258,7 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                                 );
                                 line(
                                     color::YELLOW,
-                                    OUTLINE_RADIUS,
+                                    outline_radius,
                                     [
                                         f64::from(loc.x) - 100.0,
                                         f64::from(loc.y) + 100.0,
@@ -216,12 +276,12 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                                     f64::from(end.x),
                                     f64::from(end.y),
                                 ];
-                                line(color, OUTLINE_RADIUS, pts, transform, g);
+                                line(color, outline_radius, pts, transform, g);
                             }
                             Drawable::Arc(center, radius, start, end, color) => {
                                 circle_arc(
                                     color,
-                                    OUTLINE_RADIUS,
+                                    outline_radius,
                                     f64::from(start),
                                     f64::from(end),
                                     rectangle::centered_square(
@@ -236,12 +296,26 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
                             Drawable::Print(txt, color) => {
                                 prints.push((txt, color));
                             }
+                            // These were unwrapped above. Nesting them does nothing.
+                            Drawable::Unfolded(_) => {}
                         }
                     }
 
+                    if dropped_frames > 0 {
+                        prints.push((format!("dropped frames: {}", dropped_frames), color::RED));
+                    }
+
                     let mut y = 20.0;
                     for (txt, color) in prints.into_iter() {
-                        text(color, 14, &txt, &mut glyphs, c.transform.trans(420.0, y), g).unwrap();
+                        text(
+                            color,
+                            14,
+                            &txt,
+                            &mut glyphs,
+                            c.transform.trans(text_x, y),
+                            g,
+                        )
+                        .unwrap();
                         y += 20.0;
                     }
                 });
@@ -249,3 +323,56 @@ fn thread(rx: crossbeam_channel::Receiver<ThreadMessage>) {
         }
     }
 }
+
+/// The outline of each wall, folded out flat around the floor. These match
+/// `Pitch::unfold_to_ground`.
+fn unfolded_walls() -> Vec<Vec<[f64; 2]>> {
+    // Where each corner wall meets the side and back walls.
+    const CORNER_X: f64 = 2944.0;
+    const CORNER_Y: f64 = 3968.0;
+
+    let x = f64::from(rl::FIELD_MAX_X);
+    let y = f64::from(rl::FIELD_MAX_Y);
+    let z = f64::from(rl::FIELD_MAX_Z);
+    // The corner walls are at 45 degrees, so they fold out diagonally.
+    let d = z / 2.0_f64.sqrt();
+
+    let mut result = Vec::new();
+    for &sx in &[-1.0, 1.0] {
+        result.push(vec![
+            [sx * x, -CORNER_Y],
+            [sx * (x + z), -CORNER_Y],
+            [sx * (x + z), CORNER_Y],
+            [sx * x, CORNER_Y],
+        ]);
+        for &sy in &[-1.0, 1.0] {
+            result.push(vec![
+                [sx * x, sy * CORNER_Y],
+                [sx * CORNER_X, sy * y],
+                [sx * (CORNER_X + d), sy * (y + d)],
+                [sx * (x + d), sy * (CORNER_Y + d)],
+            ]);
+        }
+    }
+    for &sy in &[-1.0, 1.0] {
+        result.push(vec![
+            [-CORNER_X, sy * y],
+            [CORNER_X, sy * y],
+            [CORNER_X, sy * (y + z)],
+            [-CORNER_X, sy * (y + z)],
+        ]);
+    }
+    result
+}
+
+/// Behaviors tend to draw the same things over and over (e.g., when a route
+/// and the behavior following it both draw the target). Only draw each once.
+fn dedup(drawables: Vec<Drawable>) -> Vec<Drawable> {
+    let mut result: Vec<Drawable> = Vec::with_capacity(drawables.len());
+    for drawable in drawables {
+        if !result.contains(&drawable) {
+            result.push(drawable);
+        }
+    }
+    result
+}
@@ -1,8 +1,10 @@
+use crate::strategy::{Pitch, DFH_STADIUM};
 use chip::Ball;
 use common::{math::fractionality, prelude::*, rl, vector_iter};
 use derive_new::new;
 use nalgebra::{Point3, Vector3};
 use ordered_float::OrderedFloat;
+use serde_derive::{Deserialize, Serialize};
 use std::{iter::Cloned, slice::Iter};
 
 const PREDICT_DURATION: f32 = 7.0;
@@ -11,18 +13,35 @@ pub struct BallTrajectory {
     frames: Vec<BallFrame>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct BallFrame {
     pub t: f32,
     dt: f32,
+    #[serde(with = "crate::utils::wire::point3")]
     pub loc: Point3<f32>,
+    #[serde(with = "crate::utils::wire::vector3")]
     pub vel: Vector3<f32>,
+    #[serde(with = "crate::utils::wire::vector3")]
+    pub ang_vel: Vector3<f32>,
 }
 
 impl BallFrame {
     pub fn dt(&self) -> f32 {
         self.dt
     }
+
+    /// Extrapolate the ball's flight `dt` seconds past this frame, ignoring
+    /// bounces, drag and spin. This is only accurate for fractions of a frame.
+    pub fn extrapolate(&self, dt: f32) -> Self {
+        let gravity = Vector3::new(0.0, 0.0, rl::GRAVITY);
+        Self {
+            t: self.t + dt,
+            dt: self.dt,
+            loc: self.loc + self.vel * dt + gravity * (0.5 * dt * dt),
+            vel: self.vel + gravity * dt,
+            ang_vel: self.ang_vel,
+        }
+    }
 }
 
 impl BallTrajectory {
@@ -31,6 +50,31 @@ impl BallTrajectory {
         Self { frames }
     }
 
+    /// Build a trajectory from `(t, loc, vel)` samples taken at a fixed
+    /// interval, e.g., a prediction that was saved to disk. The samples don't
+    /// say how the ball is spinning, so it isn't.
+    pub fn from_samples(
+        samples: impl IntoIterator<Item = (f32, Point3<f32>, Vector3<f32>)>,
+    ) -> Self {
+        let samples: Vec<_> = samples.into_iter().collect();
+        let dt = if samples.len() >= 2 {
+            samples[1].0 - samples[0].0
+        } else {
+            rl::PHYSICS_DT
+        };
+        let frames = samples
+            .into_iter()
+            .map(|(t, loc, vel)| BallFrame {
+                t,
+                dt,
+                loc,
+                vel,
+                ang_vel: Vector3::zeros(),
+            })
+            .collect();
+        Self::new(frames)
+    }
+
     /// Return the starting frame of the prediction (e.g., where the ball is
     /// right now).
     pub fn start(&self) -> &BallFrame {
@@ -59,7 +103,7 @@ impl BallTrajectory {
 
     /// Iterate over the frames, but skip the given number of seconds at the
     /// start.
-    pub fn hacky_expensive_slice(&self, delay: f32) -> Self {
+    pub(crate) fn hacky_expensive_slice(&self, delay: f32) -> Self {
         let frames = match self.frames.iter().position(|f| f.t >= delay) {
             Some(i) => {
                 let start_time = self.frames[i].t;
@@ -94,6 +138,36 @@ impl BallTrajectory {
     pub fn at_time_or_last(&self, t: f32) -> &BallFrame {
         self.at_time(t).unwrap_or_else(|| self.last())
     }
+
+    /// Where and when the ball first comes down through height `z`, e.g., to
+    /// bounce, or to land on a car's roof. Precisely, this is the first frame
+    /// where the ball is falling and below `z`.
+    pub fn landing_spot(&self, z: f32) -> Option<&BallFrame> {
+        self.frames.iter().find(|f| f.vel.z <= 0.0 && f.loc.z <= z)
+    }
+
+    /// Branch the trajectory at time `t`: it stays the same until then, and
+    /// afterwards shows what the ball would do if something touched it and
+    /// left it with velocity `vel` and angular velocity `ang_vel`. The branch
+    /// ends at the same time as the original.
+    pub fn with_touch_at(&self, t: f32, vel: Vector3<f32>, ang_vel: Vector3<f32>) -> Self {
+        let split = self
+            .frames
+            .iter()
+            .position(|f| f.t >= t)
+            .unwrap_or(self.frames.len() - 1);
+        let touch = &self.frames[split];
+
+        let mut ball = Ball::new();
+        ball.set_pos(touch.loc);
+        ball.set_vel(vel);
+        ball.set_omega(ang_vel);
+        let num_frames = ((self.last().t - touch.t) / touch.dt).round() as usize + 1;
+
+        let mut frames = self.frames[..split].to_vec();
+        frames.extend(simulate_chip(ball, touch.t, touch.dt, num_frames));
+        Self::new(frames)
+    }
 }
 
 impl<'a> IntoIterator for &'a BallTrajectory {
@@ -109,6 +183,9 @@ pub trait BallPredictor {
     fn predict(&self, packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory;
 }
 
+/// Predicts the ball with chip's simulation. Bounces, including the friction
+/// from the ball's spin where it meets the ground or a wall, are modeled
+/// there, so it needs to start from the ball's real angular velocity.
 #[derive(new)]
 pub struct ChipBallPrediction;
 
@@ -122,31 +199,125 @@ impl BallPredictor for ChipBallPrediction {
         ball.set_omega(packet.GameBall.Physics.ang_vel());
 
         let num_frames = (PREDICT_DURATION / DT).ceil() as usize;
-        let mut frames = Vec::with_capacity(num_frames);
-        let mut t = 0.0;
+        BallTrajectory::new(simulate_chip(ball, 0.0, DT, num_frames))
+    }
+}
+
+/// Step `ball` forward, starting at time `t`, until there are `num_frames`
+/// frames.
+fn simulate_chip(mut ball: Ball, mut t: f32, dt: f32, num_frames: usize) -> Vec<BallFrame> {
+    let mut frames = Vec::with_capacity(num_frames);
 
-        // Include the initial frame to allow interpolation when the framerate is
-        // faster than `DT`.
+    // Include the initial frame to allow interpolation when the framerate is
+    // faster than `dt`.
+    frames.push(BallFrame {
+        t,
+        dt,
+        loc: ball.pos(),
+        vel: ball.vel(),
+        ang_vel: ball.omega(),
+    });
+
+    while frames.len() < num_frames {
+        t += dt;
+        ball.step(dt);
         frames.push(BallFrame {
             t,
-            dt: DT,
+            dt,
             loc: ball.pos(),
             vel: ball.vel(),
+            ang_vel: ball.omega(),
         });
+    }
 
-        while frames.len() < num_frames {
-            t += DT;
-            ball.step(DT);
-            frames.push(BallFrame {
-                t,
-                dt: DT,
-                loc: ball.pos(),
-                vel: ball.vel(),
-            });
+    frames
+}
+
+/// Predicts the Snow Day puck, which chip doesn't know how to simulate.
+///
+/// The puck mostly slides. When it meets a wall, it doesn't bounce off the way
+/// the ball does; it loses the speed going into the wall and carries on along
+/// it.
+#[derive(new)]
+pub struct PuckPrediction;
+
+impl PuckPrediction {
+    /// How fast the puck slows down while it's touching a surface, in uu/s².
+    const FRICTION: f32 = 60.0;
+}
+
+impl BallPredictor for PuckPrediction {
+    fn predict(&self, packet: &common::halfway_house::LiveDataPacket) -> BallTrajectory {
+        const DT: f32 = rl::PHYSICS_DT;
+
+        let num_frames = (PREDICT_DURATION / DT).ceil() as usize;
+        BallTrajectory::new(simulate_puck(
+            &DFH_STADIUM,
+            packet.GameBall.Physics.loc(),
+            packet.GameBall.Physics.vel(),
+            DT,
+            num_frames,
+        ))
+    }
+}
+
+/// Step the puck forward from time zero until there are `num_frames` frames.
+/// The puck is treated as a box that always lies flat.
+fn simulate_puck(
+    pitch: &Pitch,
+    mut loc: Point3<f32>,
+    mut vel: Vector3<f32>,
+    dt: f32,
+    num_frames: usize,
+) -> Vec<BallFrame> {
+    let gravity = Vector3::new(0.0, 0.0, rl::GRAVITY);
+    let mut frames = Vec::with_capacity(num_frames);
+    let mut t = 0.0;
+
+    frames.push(BallFrame {
+        t,
+        dt,
+        loc,
+        vel,
+        ang_vel: Vector3::zeros(),
+    });
+
+    while frames.len() < num_frames {
+        t += dt;
+        vel += gravity * dt;
+        loc += vel * dt;
+
+        let mut touching = false;
+        for plane in pitch.planes_at(loc.to_2d()) {
+            let n = plane.normal.into_inner();
+            let extent =
+                rl::PUCK_HALF_WIDTH * (n.x.abs() + n.y.abs()) + rl::PUCK_HALF_HEIGHT * n.z.abs();
+            let depth = extent - plane.distance_to_point(&loc);
+            if depth <= 0.0 {
+                continue;
+            }
+            touching = true;
+            loc += n * depth;
+            vel -= n * n.dot(&vel).min(0.0);
         }
 
-        BallTrajectory::new(frames)
+        if touching {
+            let speed = vel.norm();
+            if speed > 0.0 {
+                vel *= (speed - PuckPrediction::FRICTION * dt).max(0.0) / speed;
+            }
+        }
+
+        frames.push(BallFrame {
+            t,
+            dt,
+            loc,
+            vel,
+            ang_vel: Vector3::zeros(),
+        });
     }
+
+    frames
 }
 
 #[derive(new)]
@@ -166,6 +337,7 @@ impl BallPredictor for FrameworkBallPrediction {
                 dt: DT,
                 loc: point3(slice.physics().unwrap().location().unwrap()),
                 vel: vector3(slice.physics().unwrap().velocity().unwrap()),
+                ang_vel: vector3(slice.physics().unwrap().angularVelocity().unwrap()),
             })
             .collect();
         BallTrajectory::new(frames)
@@ -179,3 +351,86 @@ fn point3(v: &rlbot::flat::Vector3) -> Point3<f32> {
 fn vector3(v: &rlbot::flat::Vector3) -> Vector3<f32> {
     Vector3::new(v.x(), v.y(), v.z())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::ball::{simulate_puck, BallTrajectory},
+        strategy::DFH_STADIUM,
+    };
+    use brain_test_data::recordings;
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn puck_does_not_bounce() {
+        let loc = Point3::new(0.0, 0.0, 500.0);
+        let frames = simulate_puck(&DFH_STADIUM, loc, Vector3::zeros(), rl::PHYSICS_DT, 240);
+        let last = frames.last().unwrap();
+        assert!(
+            (last.loc.z - rl::PUCK_HALF_HEIGHT).abs() < 1.0,
+            "{}",
+            last.loc
+        );
+        assert!(last.vel.z.abs() < 1e-3, "{}", last.vel);
+    }
+
+    #[test]
+    fn puck_slides_along_walls() {
+        // Head into the side wall at an angle.
+        let loc = Point3::new(rl::FIELD_MAX_X - 500.0, 0.0, rl::PUCK_HALF_HEIGHT);
+        let vel = Vector3::new(1000.0, 1000.0, 0.0);
+        let frames = simulate_puck(&DFH_STADIUM, loc, vel, rl::PHYSICS_DT, 120);
+        let last = frames.last().unwrap();
+        // It stays against the wall instead of bouncing back toward the middle,
+        assert!(last.loc.x > rl::FIELD_MAX_X - 200.0, "{}", last.loc);
+        // and keeps most of its speed along it.
+        assert!(last.vel.y > 800.0, "{}", last.vel);
+    }
+
+    #[test]
+    fn rolling_ball_keeps_rolling() {
+        // The ball rolls across the floor for a while with nobody near it. If
+        // we forget it's spinning, friction on the floor slows it right down.
+        let scenario = &*recordings::SWING_AROUND_WHILE_RETREATING_BUT_NOT_TOO_FAR;
+        let start = scenario.times.iter().position(|&t| t >= 70.4).unwrap();
+        let end = (scenario.times.iter())
+            .position(|&t| t >= scenario.times[start] + 1.5)
+            .unwrap();
+        let state = &scenario.ball_states[start];
+        let actual = scenario.ball_states[end].loc;
+        let steps = ((scenario.times[end] - scenario.times[start]) / rl::PHYSICS_DT).round();
+
+        let predict = |ang_vel| {
+            let mut ball = simulate::Ball::new(state.loc, state.vel, ang_vel);
+            for _ in 0..steps as usize {
+                ball.step(rl::PHYSICS_DT, rl::GRAVITY);
+            }
+            ball.loc
+        };
+        let with_spin = (predict(state.ang_vel) - actual).norm();
+        let without_spin = (predict(Vector3::zeros()) - actual).norm();
+        assert!(with_spin < 20.0, "{}", with_spin);
+        assert!(without_spin > 100.0, "{}", without_spin);
+    }
+
+    #[test]
+    fn landing_spot_on_the_way_down() {
+        // Thrown up at 500uu/s from 300uu, it peaks, then comes back down
+        // through 300uu after 2 * 500 / 650 seconds.
+        let start = Point3::new(0.0, 0.0, 300.0);
+        let trajectory = BallTrajectory::from_samples((0..240).map(|i| {
+            let t = i as f32 * rl::PHYSICS_DT;
+            let z = start.z + 500.0 * t + 0.5 * rl::GRAVITY * t * t;
+            let vel = Vector3::new(100.0, 0.0, 500.0 + rl::GRAVITY * t);
+            (t, Point3::new(100.0 * t, 0.0, z), vel)
+        }));
+        let landing = trajectory.landing_spot(300.0).unwrap();
+        assert!((landing.t - 1000.0 / -rl::GRAVITY).abs() < 0.02);
+        assert!((landing.loc.x - 100.0 * landing.t).abs() < 1.0);
+        // It never gets up to 1000uu, so it's below that from the moment it
+        // starts falling.
+        let peak = trajectory.landing_spot(1000.0).unwrap();
+        assert!((peak.t - 500.0 / -rl::GRAVITY).abs() < 0.02);
+    }
+}
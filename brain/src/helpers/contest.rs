@@ -0,0 +1,158 @@
+use crate::helpers::{
+    ball::BallTrajectory, hit_outcome::ball_vel_after_hit, intercept::NaiveIntercept,
+};
+use common::prelude::*;
+use nalgebra::{Point2, Point3, Rotation2, Unit, Vector2, Vector3};
+use ordered_float::NotNan;
+use std::f32::consts::PI;
+
+/// The geometry of a 50/50: both cars meet the ball at once, and the ball goes
+/// wherever the two touches add up to.
+///
+/// Arriving first isn't everything. A car that meets the ball square and fast
+/// wins the 50/50 even if it got there a hair later, so this weighs both
+/// contacts instead of only racing.
+pub struct Contest {
+    /// Seconds from now until the cars meet.
+    pub time: f32,
+    pub ball_loc: Point3<f32>,
+    /// Which way we should drive through the ball.
+    pub approach: Unit<Vector2<f32>>,
+    /// The velocity our touch adds to the ball, approaching along `approach`.
+    pub me_contact: Vector3<f32>,
+    /// The velocity the enemy's touch adds to the ball.
+    pub enemy_contact: Vector3<f32>,
+    /// The ball's velocity after both touches.
+    pub outcome: Vector3<f32>,
+    /// How fast `outcome` carries the ball towards the enemy goal. Negative
+    /// means it comes back at us.
+    pub score: f32,
+}
+
+impl Contest {
+    /// Challenge if the ball comes back at us slower than this. A slow loss is
+    /// easy to clean up; a fast one is a shot.
+    const MAX_LOSS: f32 = 500.0;
+
+    pub fn should_challenge(&self) -> bool {
+        self.score >= -Self::MAX_LOSS
+    }
+}
+
+/// The directions we consider driving through the ball, relative to a straight
+/// line from our car.
+const APPROACH_ANGLES: [f32; 3] = [-PI / 6.0, 0.0, PI / 6.0];
+
+/// Evaluate a 50/50 between us and `enemy` at the later of the two intercepts.
+/// `contact_dist` is the distance from a car's pivot to the center of the ball
+/// when the nose touches it.
+pub fn evaluate_contest(
+    ball_prediction: &BallTrajectory,
+    contact_dist: f32,
+    me: &common::halfway_house::PlayerInfo,
+    me_intercept: &NaiveIntercept,
+    enemy: &common::halfway_house::PlayerInfo,
+    enemy_intercept: &NaiveIntercept,
+    enemy_goal: Point2<f32>,
+) -> Contest {
+    let time = me_intercept.time.max(enemy_intercept.time);
+    let ball = ball_prediction.at_time_or_last(time);
+    let mut contest = evaluate(
+        contact_dist,
+        ball.loc,
+        ball.vel,
+        (me.Physics.loc_2d(), me_intercept.car_speed),
+        (enemy.Physics.loc_2d(), enemy_intercept.car_speed),
+        enemy_goal,
+    );
+    contest.time = time;
+    contest
+}
+
+/// Each car is given as its location and its speed at contact.
+fn evaluate(
+    contact_dist: f32,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    (me_loc, me_speed): (Point2<f32>, f32),
+    (enemy_loc, enemy_speed): (Point2<f32>, f32),
+    enemy_goal: Point2<f32>,
+) -> Contest {
+    let to_goal = (enemy_goal - ball_loc.to_2d()).to_axis();
+    let enemy_contact = contact(
+        contact_dist,
+        ball_loc,
+        ball_vel,
+        (ball_loc.to_2d() - enemy_loc).to_axis(),
+        enemy_speed,
+    );
+    let straight = ball_loc.to_2d() - me_loc;
+
+    APPROACH_ANGLES
+        .iter()
+        .map(|&angle| {
+            let approach = (Rotation2::new(angle) * straight).to_axis();
+            let me_contact = contact(contact_dist, ball_loc, ball_vel, approach, me_speed);
+            let outcome = ball_vel + me_contact + enemy_contact;
+            Contest {
+                time: 0.0,
+                ball_loc,
+                approach,
+                me_contact,
+                enemy_contact,
+                outcome,
+                score: outcome.to_2d().dot(&to_goal),
+            }
+        })
+        .max_by_key(|c| NotNan::new(c.score).unwrap())
+        .unwrap()
+}
+
+/// The velocity a car adds to the ball by driving through it along `dir` at
+/// `speed`.
+fn contact(
+    contact_dist: f32,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+    dir: Unit<Vector2<f32>>,
+    speed: f32,
+) -> Vector3<f32> {
+    let forward = dir.to_3d();
+    let car_loc = ball_loc - forward.into_inner() * contact_dist;
+    let car_vel = forward.into_inner() * speed;
+    ball_vel_after_hit(car_loc, car_vel, &forward, ball_loc, ball_vel) - ball_vel
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::contest::evaluate;
+    use common::rl;
+    use nalgebra::{Point2, Point3, Vector3};
+
+    fn head_on(me_speed: f32, enemy_speed: f32) -> crate::helpers::contest::Contest {
+        evaluate(
+            rl::BALL_RADIUS + rl::OCTANE_HITBOX_LENGTH / 2.0,
+            Point3::new(0.0, 0.0, 92.74),
+            Vector3::zeros(),
+            (Point2::new(0.0, -2000.0), me_speed),
+            (Point2::new(0.0, 2000.0), enemy_speed),
+            Point2::new(0.0, 5120.0),
+        )
+    }
+
+    #[test]
+    fn faster_car_wins() {
+        let contest = head_on(2300.0, 500.0);
+        assert!(contest.score > 0.0);
+        assert!(contest.should_challenge());
+        // Straight through is the strongest hit.
+        assert!(contest.approach.x.abs() < 1e-3);
+    }
+
+    #[test]
+    fn slower_car_loses() {
+        let contest = head_on(500.0, 2300.0);
+        assert!(contest.score < 0.0);
+        assert!(!contest.should_challenge());
+    }
+}
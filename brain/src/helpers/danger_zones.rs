@@ -0,0 +1,185 @@
+use crate::{
+    eeg::{color, Drawable, EEG},
+    strategy::Goal,
+};
+use nalgebra::{Point2, Vector2};
+
+/// A region in front of our goal from which the enemy would have a good shot.
+pub struct DangerZone {
+    pub name: &'static str,
+    pub polygon: Vec<Point2<f32>>,
+    /// The spot in the goal that a shot from this zone would go towards.
+    pub aim_loc: Point2<f32>,
+    /// How much we care about this zone, given the current position of the
+    /// ball.
+    pub danger: f32,
+}
+
+impl DangerZone {
+    fn new(
+        goal: &Goal,
+        name: &'static str,
+        corners: &[(f32, f32)],
+        aim_x: f32,
+        base_danger: f32,
+        ball_loc: Point2<f32>,
+    ) -> Self {
+        let polygon: Vec<_> = corners
+            .iter()
+            .map(|&(x, depth)| goal_local(goal, x, depth))
+            .collect();
+        let dist = polygon_distance(&polygon, ball_loc);
+        Self {
+            name,
+            aim_loc: goal_local(goal, aim_x, 0.0),
+            // Zones closer to the ball are easier for the enemy to get a shot
+            // off from.
+            danger: base_danger / (1.0 + dist / PROXIMITY_FALLOFF),
+            polygon,
+        }
+    }
+
+    pub fn contains(&self, point: Point2<f32>) -> bool {
+        polygon_contains(&self.polygon, point)
+    }
+}
+
+/// Danger falls to half at this distance from the ball.
+const PROXIMITY_FALLOFF: f32 = 2000.0;
+
+/// Returns the danger zones around `goal`, given the ball is at `ball_loc`.
+pub fn danger_zones(goal: &Goal, ball_loc: Point2<f32>) -> Vec<DangerZone> {
+    // "Far" is the side of the goal opposite the ball.
+    let far = if ball_loc.x >= 0.0 { -1.0 } else { 1.0 };
+    // Back post tap-ins only happen when the ball comes in from wide.
+    let width = (ball_loc.x.abs() / 1500.0).min(1.5);
+
+    vec![
+        DangerZone::new(
+            goal,
+            "slot",
+            &[
+                (-900.0, 300.0),
+                (900.0, 300.0),
+                (1400.0, 3000.0),
+                (-1400.0, 3000.0),
+            ],
+            0.0,
+            1.0,
+            ball_loc,
+        ),
+        DangerZone::new(
+            goal,
+            "far post",
+            &[
+                (far * 400.0, 0.0),
+                (far * 1600.0, 0.0),
+                (far * 1600.0, 1200.0),
+                (far * 400.0, 1200.0),
+            ],
+            far * goal.max_x * 0.8,
+            width,
+            ball_loc,
+        ),
+    ]
+}
+
+/// Returns the zone we should prioritize denying.
+pub fn most_dangerous_zone(goal: &Goal, ball_loc: Point2<f32>) -> DangerZone {
+    danger_zones(goal, ball_loc)
+        .into_iter()
+        .max_by(|a, b| a.danger.partial_cmp(&b.danger).unwrap())
+        .unwrap()
+}
+
+pub fn draw_danger_zones(eeg: &mut EEG, goal: &Goal, ball_loc: Point2<f32>) {
+    let zones = danger_zones(goal, ball_loc);
+    let worst = most_dangerous_zone(goal, ball_loc);
+    for zone in &zones {
+        let color = if zone.name == worst.name {
+            color::RED
+        } else {
+            color::ORANGE_DARK
+        };
+        let n = zone.polygon.len();
+        for i in 0..n {
+            eeg.draw(Drawable::Line(
+                zone.polygon[i],
+                zone.polygon[(i + 1) % n],
+                color,
+            ));
+        }
+    }
+}
+
+/// Converts coordinates relative to the goal (`x` across the goal mouth,
+/// `depth` out into the field) to world coordinates.
+fn goal_local(goal: &Goal, x: f32, depth: f32) -> Point2<f32> {
+    goal.center_2d + Vector2::new(x, 0.0) + goal.normal_2d.into_inner() * depth
+}
+
+fn polygon_contains(polygon: &[Point2<f32>], point: Point2<f32>) -> bool {
+    // Ray casting: count how many edges a ray towards +x crosses.
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (p, q) = (polygon[i], polygon[j]);
+        if (p.y > point.y) != (q.y > point.y)
+            && point.x < (q.x - p.x) * (point.y - p.y) / (q.y - p.y) + p.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+fn polygon_distance(polygon: &[Point2<f32>], point: Point2<f32>) -> f32 {
+    if polygon_contains(polygon, point) {
+        return 0.0;
+    }
+    let n = polygon.len();
+    (0..n)
+        .map(|i| segment_distance(polygon[i], polygon[(i + 1) % n], point))
+        .min_by(|a, b| a.partial_cmp(b).unwrap())
+        .unwrap()
+}
+
+fn segment_distance(p: Point2<f32>, q: Point2<f32>, point: Point2<f32>) -> f32 {
+    let pq = q - p;
+    let t = ((point - p).dot(&pq) / pq.norm_squared()).max(0.0).min(1.0);
+    (p + pq * t - point).norm()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::danger_zones::{danger_zones, most_dangerous_zone},
+        strategy::SOCCAR_GOAL_BLUE,
+    };
+    use nalgebra::Point2;
+
+    #[test]
+    fn central_ball_threatens_slot() {
+        let zone = most_dangerous_zone(&SOCCAR_GOAL_BLUE, Point2::new(0.0, -1000.0));
+        assert_eq!(zone.name, "slot");
+        assert!(zone.contains(Point2::new(0.0, -3000.0)));
+        assert!(!zone.contains(Point2::new(3000.0, -3000.0)));
+    }
+
+    #[test]
+    fn wide_ball_threatens_far_post() {
+        let zone = most_dangerous_zone(&SOCCAR_GOAL_BLUE, Point2::new(3500.0, -4000.0));
+        assert_eq!(zone.name, "far post");
+        assert!(zone.aim_loc.x < 0.0);
+        assert!(zone.contains(Point2::new(-1000.0, -4500.0)));
+    }
+
+    #[test]
+    fn danger_is_highest_inside_a_zone() {
+        let inside = danger_zones(&SOCCAR_GOAL_BLUE, Point2::new(0.0, -3500.0));
+        let outside = danger_zones(&SOCCAR_GOAL_BLUE, Point2::new(0.0, 0.0));
+        assert!((inside[0].danger - 1.0).abs() < 1e-5);
+        assert!(outside[0].danger < inside[0].danger);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::{behavior::movement::simple_yaw_diff, utils::geometry::ExtendF32};
-use common::prelude::*;
+use crate::{
+    behavior::movement::{drive_towards, simple_steer_towards, simple_yaw_diff},
+    strategy::Context,
+    utils::geometry::ExtendF32,
+};
+use common::{prelude::*, rl, Distance};
 use nalgebra::Point2;
 use simulate::Car1D;
 
@@ -27,3 +31,140 @@ fn steer_penalty(car: &common::halfway_house::PlayerInfo, desired_aim: f32) -> f
     // Literally just guessing here
     turn * 0.5
 }
+
+/// Choose a throttle and boost that will get us `dist` uu further along in
+/// `time` seconds, without getting there early and overrunning the play.
+pub fn arrival_throttle(
+    car: &common::halfway_house::PlayerInfo,
+    dist: f32,
+    time: f32,
+) -> (f32, bool) {
+    let offset = |throttle, boost| {
+        let mut sim_car = Car1D::new()
+            .with_speed(car.Physics.vel().norm())
+            .with_boost(car.Boost as f32);
+        sim_car.advance(time, throttle, boost);
+        sim_car.distance() - dist
+    };
+    arrival_control(offset(0.0, false), offset(1.0, false), offset(1.0, true))
+}
+
+/// Drive towards `target_loc`, timing throttle and boost to get there in
+/// `time` seconds instead of blitzing there and overrunning the play.
+pub fn arrive_on_time(
+    ctx: &mut Context<'_>,
+    target_loc: Point2<f32>,
+    time: f32,
+) -> common::halfway_house::PlayerInput {
+    let me = ctx.me();
+    let dist = (target_loc - me.Physics.loc_2d()).dot(&me.Physics.forward_axis_2d());
+    ctx.eeg.print_value("arrival_dist", Distance(dist));
+    let (throttle, boost) = arrival_throttle(me, dist, time);
+
+    let mut input = drive_towards(ctx, target_loc);
+    input.Throttle = throttle;
+    input.Boost = boost && me.Boost > 0 && !input.Handbrake;
+    input
+}
+
+/// Arriving within this many uu of the planned spot counts as on time.
+pub const ARRIVAL_TOLERANCE: f32 = 10.0;
+
+/// The arrival-time controller. Given how far past the target we would end up
+/// (negative if we'd fall short) when coasting, at full throttle, and at full
+/// boost, choose a throttle and boost that arrive on time.
+///
+/// Our car model only knows full or zero throttle, so between the two, blend
+/// in proportion to the offsets. That's close enough that the error shrinks
+/// each frame instead of bouncing between coasting and flooring it.
+pub fn arrival_control(coast_offset: f32, throttle_offset: f32, blitz_offset: f32) -> (f32, bool) {
+    if coast_offset > 100.0 {
+        (-1.0, false) // We're _really_ overshooting…
+    } else if coast_offset > -ARRIVAL_TOLERANCE {
+        (0.0, false) // We're overshooting…
+    } else if throttle_offset > ARRIVAL_TOLERANCE {
+        let throttle = -coast_offset / (throttle_offset - coast_offset);
+        (throttle.max(0.0).min(1.0), false)
+    } else if blitz_offset > 0.0 || throttle_offset > -ARRIVAL_TOLERANCE {
+        (1.0, false)
+    } else {
+        (1.0, true)
+    }
+}
+
+/// The speed `supersonic_control` holds. It's a little above the supersonic
+/// threshold, so the little we lose to steering doesn't drop us below it.
+pub const SUPERSONIC_HOLD_SPEED: f32 = rl::CAR_SUPERSONIC_SPEED + 25.0;
+
+/// Drive flat out, but boost only as much as it takes to reach supersonic and
+/// stay there. At full throttle the car holds its speed on its own, so past
+/// the threshold, boost only buys the last 100uu/s, which isn't worth the cost.
+/// Once we're there, this taps boost whenever we dip below the hold speed.
+pub fn supersonic_control(speed: f32, boost: i32) -> (f32, bool) {
+    (1.0, boost > 0 && speed < SUPERSONIC_HOLD_SPEED)
+}
+
+/// Brake to a stop where we stand, turning to face `look_at` on the way.
+pub fn hold_position(
+    car: &common::halfway_house::PlayerInfo,
+    look_at: Point2<f32>,
+) -> common::halfway_house::PlayerInput {
+    let forward_speed = car.Physics.vel_2d().dot(&car.Physics.forward_axis_2d());
+    common::halfway_house::PlayerInput {
+        Throttle: (-forward_speed / 500.0).max(-1.0).min(1.0),
+        Steer: simple_steer_towards(&car.Physics, look_at),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::drive::{
+        arrival_control, arrival_throttle, supersonic_control, ARRIVAL_TOLERANCE,
+    };
+    use common::halfway_house::{PlayerInfo, Vector3};
+
+    fn car(speed: f32) -> PlayerInfo {
+        let mut car = PlayerInfo {
+            Boost: 100,
+            ..Default::default()
+        };
+        car.Physics.Velocity = Vector3 {
+            X: speed,
+            Y: 0.0,
+            Z: 0.0,
+        };
+        car
+    }
+
+    #[test]
+    fn arrival_throttle_ladder() {
+        assert_eq!(arrival_throttle(&car(0.0), 5000.0, 1.0), (1.0, true));
+        assert_eq!(arrival_throttle(&car(1000.0), 100.0, 1.0), (-1.0, false));
+
+        // In between coasting and full throttle, ease off partway.
+        let (throttle, boost) = arrival_throttle(&car(1000.0), 1000.0, 1.0);
+        assert!(throttle > 0.0 && throttle < 1.0);
+        assert!(!boost);
+    }
+
+    #[test]
+    fn arrival_control_blend() {
+        assert_eq!(arrival_control(-100.0, 100.0, 500.0), (0.5, false));
+        assert_eq!(arrival_control(-300.0, 100.0, 500.0), (0.75, false));
+        // Close enough to on time at full throttle.
+        assert_eq!(
+            arrival_control(-300.0, ARRIVAL_TOLERANCE / 2.0, 500.0),
+            (1.0, false),
+        );
+        assert_eq!(arrival_control(-300.0, -200.0, -50.0), (1.0, true));
+    }
+    #[test]
+    fn supersonic_control_feathers() {
+        assert_eq!(supersonic_control(1500.0, 50), (1.0, true));
+        assert_eq!(supersonic_control(2210.0, 50), (1.0, true));
+        // Supersonic already. Save the boost.
+        assert_eq!(supersonic_control(2260.0, 50), (1.0, false));
+        assert_eq!(supersonic_control(1500.0, 0), (1.0, false));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{helpers::ball::BallTrajectory, strategy::Goal};
+use common::{prelude::*, rl};
+use nalgebra::{Point2, Point3, Unit, Vector3};
+use simulate::linear_interpolate;
+
+/// Estimate the ball's velocity right after a car touches it.
+///
+/// This is the sum of the physical collision (the ball is much lighter than
+/// the car, so it picks up roughly the closing speed along the contact normal)
+/// and the extra impulse the game adds to every touch, which flattens the
+/// contact normal and scales with the relative speed.
+pub fn ball_vel_after_hit(
+    car_loc: Point3<f32>,
+    car_vel: Vector3<f32>,
+    car_forward: &Unit<Vector3<f32>>,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+) -> Vector3<f32> {
+    let rel_vel = car_vel - ball_vel;
+    let normal = (ball_loc - car_loc).normalize();
+    let closing_speed = rel_vel.dot(&normal).max(0.0);
+
+    let mut hit_dir = ball_loc - car_loc;
+    hit_dir.z *= 0.35;
+    let hit_dir =
+        (hit_dir - car_forward.into_inner() * 0.35 * hit_dir.dot(car_forward)).normalize();
+    let rel_speed = rel_vel.norm().min(4600.0);
+    let scale = linear_interpolate(
+        &[0.0, 500.0, 2300.0, 4600.0],
+        &[0.65, 0.65, 0.55, 0.30],
+        rel_speed,
+    );
+
+    ball_vel + normal * closing_speed + hit_dir * rel_speed * scale
+}
+
+/// The height at which the ball will cross the goal line, if it keeps flying
+/// towards it. This ignores air drag and spin. If the ball lands before it gets
+/// there, it will bounce lower than it started, so call it a ground shot.
+pub fn goal_line_crossing_z(
+    goal: &Goal,
+    ball_loc: Point3<f32>,
+    ball_vel: Vector3<f32>,
+) -> Option<f32> {
+    let speed_towards_goal = ball_vel.to_2d().dot(&-goal.normal_2d);
+    if speed_towards_goal <= 0.0 {
+        return None;
+    }
+    let dist = (ball_loc.to_2d() - goal.center_2d).dot(&goal.normal_2d);
+    let time = dist / speed_towards_goal;
+    let z = ball_loc.z + ball_vel.z * time + 0.5 * rl::GRAVITY * time * time;
+    Some(z.max(rl::BALL_RADIUS))
+}
+
+/// Returns `true` if the ball would clear the crossbar, i.e., miss.
+pub fn is_over_crossbar(entry_z: f32) -> bool {
+    entry_z >= rl::CROSSBAR_Z - rl::BALL_RADIUS
+}
+
+/// Branch `prediction` at `time`, assuming a car arrives at `car_speed` and
+/// drives straight through the ball towards `target`.
+///
+/// This is the "what if they touch it" question. Nobody hits the ball exactly
+/// where they mean to, so treat the result as the worst case, not a forecast.
+pub fn predict_touch_towards(
+    prediction: &BallTrajectory,
+    time: f32,
+    car_speed: f32,
+    target: Point2<f32>,
+) -> BallTrajectory {
+    // Roughly the distance from the center of the car to its nose.
+    const CAR_FRONT: f32 = 70.0;
+
+    let ball = prediction.at_time_or_last(time);
+    let forward = (target - ball.loc.to_2d()).to_axis().to_3d();
+    let car_loc = ball.loc - forward.into_inner() * (rl::BALL_RADIUS + CAR_FRONT);
+    let car_vel = forward.into_inner() * car_speed;
+    let vel = ball_vel_after_hit(car_loc, car_vel, &forward, ball.loc, ball.vel);
+
+    // The car's nose drags the ball's surface along with it, which sets how it
+    // spins, and so how it bounces afterwards.
+    let mut spin = simulate::Ball::new(ball.loc, ball.vel, ball.ang_vel);
+    spin.collide(&forward, car_vel);
+    prediction.with_touch_at(ball.t, vel, spin.omega)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::{
+            ball::BallTrajectory,
+            hit_outcome::{
+                ball_vel_after_hit, goal_line_crossing_z, is_over_crossbar, predict_touch_towards,
+            },
+        },
+        strategy::{SOCCAR_GOAL_BLUE, SOCCAR_GOAL_ORANGE},
+    };
+    use common::{prelude::*, rl};
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn chip_goes_up() {
+        // A car on the ground driving into a rolling ball lifts it.
+        let vel = ball_vel_after_hit(
+            Point3::new(0.0, -150.0, 17.0),
+            Vector3::new(0.0, 1500.0, 0.0),
+            &Vector3::y_axis(),
+            Point3::new(0.0, 0.0, 93.0),
+            Vector3::zeros(),
+        );
+        assert!(vel.y > 1500.0);
+        assert!(vel.z > 300.0);
+    }
+
+    #[test]
+    fn close_chip_sails_over() {
+        let goal = &*SOCCAR_GOAL_ORANGE;
+        let ball_loc = Point3::new(0.0, 4000.0, 93.0);
+        let entry_z = goal_line_crossing_z(goal, ball_loc, Vector3::new(0.0, 2500.0, 1500.0));
+        assert!(is_over_crossbar(entry_z.unwrap()));
+        let entry_z = goal_line_crossing_z(goal, ball_loc, Vector3::new(0.0, 2500.0, 300.0));
+        assert!(!is_over_crossbar(entry_z.unwrap()));
+        assert_eq!(
+            goal_line_crossing_z(goal, ball_loc, Vector3::new(0.0, -100.0, 0.0)),
+            None,
+        );
+    }
+
+    #[test]
+    fn touched_towards_goal() {
+        // A ball sitting still in midfield, until someone hits it.
+        let loc = Point3::new(1000.0, 0.0, rl::BALL_RADIUS);
+        let prediction = BallTrajectory::from_samples(
+            (0..840).map(|i| (i as f32 * rl::PHYSICS_DT, loc, Vector3::zeros())),
+        );
+        let goal = &*SOCCAR_GOAL_BLUE;
+        let touched = predict_touch_towards(&prediction, 1.0, 2000.0, goal.center_2d);
+
+        // Nothing changes before the touch.
+        let before = touched.at_time(0.5).unwrap();
+        assert!((before.loc - loc).norm() < 1e-3);
+
+        // Afterwards the ball heads for the goal, and the branch is as long as
+        // the original.
+        let after = touched.at_time(1.5).unwrap();
+        let to_goal = goal.center_2d - loc.to_2d();
+        assert!(after.vel.to_2d().angle_to(&to_goal).abs() < 0.1);
+        assert!(after.vel.to_2d().norm() > 2000.0);
+        assert!((touched.last().t - prediction.last().t).abs() < 1e-3);
+    }
+}
@@ -1,15 +1,26 @@
-use crate::{helpers::ball::BallFrame, routing::models::CarState};
+use crate::{
+    helpers::{ball::BallFrame, intercept_predicate::InterceptPredicate},
+    routing::models::CarState,
+};
 use common::prelude::*;
-use nalgebra::{Point3, UnitQuaternion, Vector3};
-use simulate::Car1D;
+use nalgebra::{Point2, Point3, UnitQuaternion, Vector3};
+use serde_derive::{Deserialize, Serialize};
+use simulate::{car_turn, Car1DWithDodge};
 use std::borrow::Borrow;
 
+/// We don't want the center of the car to be at the center of the ball – we
+/// want their meshes to barely be touching.
+const RADII: f32 = 240.0;
+/// Below this speed, assume the car speeds up while it turns, so it doesn't
+/// turn any tighter.
+const SLOWEST_TURNING_SPEED: f32 = 900.0;
+
 pub fn naive_ground_intercept<'a>(
     ball: impl Iterator<Item = &'a BallFrame>,
     start_loc: Point3<f32>,
     start_vel: Vector3<f32>,
     start_boost: f32,
-    predicate: impl Fn(&BallFrame) -> bool,
+    predicate: &impl InterceptPredicate,
 ) -> Option<NaiveIntercept> {
     let start = CarState {
         loc: start_loc,
@@ -17,11 +28,7 @@ pub fn naive_ground_intercept<'a>(
         vel: start_vel,
         boost: start_boost,
     };
-    naive_ground_intercept_2(
-        &start,
-        ball,
-        |ball| if predicate(ball) { Some(()) } else { None },
-    )
+    naive_ground_intercept_2(&start, ball, |ball| predicate.matches(ball))
 }
 
 pub fn naive_ground_intercept_2<BF, IID>(
@@ -33,18 +40,15 @@ where
     BF: Borrow<BallFrame>,
     IID: IntoInterceptData,
 {
-    // We don't want the center of the car to be at the center of the ball –
-    // we want their meshes to barely be touching.
-    const RADII: f32 = 240.0;
-
-    let mut sim_car = Car1D::new()
+    // Once the boost runs out, the car front flips to keep its speed up.
+    let mut sim_car = Car1DWithDodge::new()
         .with_speed(start.vel.norm())
         .with_boost(start.boost);
 
     let (sim_ball, data) = ball.into_iter().find_map(|ball| {
         let ball = ball.borrow();
 
-        sim_car.advance(ball.dt(), 1.0, true);
+        sim_car.advance(ball.dt(), true);
 
         let target_dist = (ball.loc - start.loc).to_2d().norm() - RADII;
         if sim_car.distance() >= target_dist {
@@ -68,10 +72,76 @@ where
     Some(intercept)
 }
 
+/// Like `naive_ground_intercept_2`, but the car has to turn to face each ball
+/// location before it can drive there. The turn takes however long it takes
+/// to drive the arc at the car's current turning radius.
+///
+/// The naive version is over-optimistic whenever the car is facing away from
+/// the ball, which is exactly when races for the ball are close.
+pub fn heading_aware_ground_intercept<BF, IID>(
+    start: &CarState,
+    ball: impl IntoIterator<Item = BF>,
+    predicate: impl Fn(&BallFrame) -> IID,
+) -> Option<NaiveIntercept<IID::Data>>
+where
+    BF: Borrow<BallFrame>,
+    IID: IntoInterceptData,
+{
+    let mut sim_car = Car1DWithDodge::new()
+        .with_speed(start.vel.to_2d().norm())
+        .with_boost(start.boost);
+    // `(time, distance, speed)` after each step of the simulation.
+    let mut history = vec![(0.0, 0.0, sim_car.speed())];
+
+    let (sim_ball, data, car_speed) = ball.into_iter().find_map(|ball| {
+        let ball = ball.borrow();
+
+        sim_car.advance(ball.dt(), true);
+        history.push((sim_car.time(), sim_car.distance(), sim_car.speed()));
+
+        let drive_time = ball.t - turn_time(start, ball.loc.to_2d());
+        let &(_, distance, speed) = history.iter().rev().find(|&&(t, _, _)| t <= drive_time)?;
+        let target_dist = (ball.loc - start.loc).to_2d().norm() - RADII;
+        if distance >= target_dist {
+            if let Some(data) = predicate(&ball).into_intercept_data() {
+                return Some((ball.clone(), data, speed));
+            }
+        }
+
+        None
+    })?;
+
+    let intercept_loc = sim_ball.loc - (sim_ball.loc - start.loc).normalize() * RADII;
+    Some(NaiveIntercept {
+        time: sim_ball.t,
+        ball_loc: sim_ball.loc,
+        ball_vel: sim_ball.vel,
+        car_loc: intercept_loc,
+        car_speed,
+        data,
+    })
+}
+
+/// Estimate how long it takes the car to turn until it faces `target_loc`,
+/// going by its turning radius at its current speed.
+pub fn turn_time(car: &CarState, target_loc: Point2<f32>) -> f32 {
+    let speed = car.vel.to_2d().norm().max(SLOWEST_TURNING_SPEED);
+    let radius = car_turn::turn_radius(speed);
+    let angle = car
+        .forward_axis_2d()
+        .angle_to(&(target_loc - car.loc_2d()).to_axis())
+        .abs();
+    radius * angle / speed
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct NaiveIntercept<D = ()> {
     pub time: f32,
+    #[serde(with = "crate::utils::wire::point3")]
     pub ball_loc: Point3<f32>,
+    #[serde(with = "crate::utils::wire::vector3")]
     pub ball_vel: Vector3<f32>,
+    #[serde(with = "crate::utils::wire::point3")]
     pub car_loc: Point3<f32>,
     pub car_speed: f32,
     pub data: D,
@@ -113,3 +183,71 @@ pub fn naive_intercept_penalty(car: &CarState, ball: &BallFrame) -> f32 {
     let fudge = 0.75;
     car_forward.angle_to(&car_to_ball.to_axis()).abs() * fudge
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::{
+            ball::BallTrajectory,
+            intercept::{heading_aware_ground_intercept, naive_ground_intercept_2, turn_time},
+        },
+        routing::models::CarState,
+    };
+    use common::rl;
+    use nalgebra::{Point2, Point3, UnitQuaternion, Vector3};
+    use simulate::Car1D;
+    use std::f32::consts::PI;
+
+    fn car_facing(yaw: f32) -> CarState {
+        let rot = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), yaw);
+        CarState {
+            loc: Point3::new(0.0, 0.0, 17.01),
+            rot,
+            vel: rot * Vector3::new(1000.0, 0.0, 0.0),
+            boost: 0.0,
+        }
+    }
+
+    #[test]
+    fn turning_takes_time() {
+        let target = Point2::new(2000.0, 0.0);
+        assert!(turn_time(&car_facing(0.0), target) < 1e-3);
+        let half = turn_time(&car_facing(PI / 2.0), target);
+        let full = turn_time(&car_facing(PI), target);
+        assert!(half > 0.3);
+        assert!((full - half * 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn facing_away_is_slower() {
+        let ball = BallTrajectory::from_samples((0..840).map(|i| {
+            let loc = Point3::new(2000.0, 0.0, rl::BALL_RADIUS);
+            (i as f32 * rl::PHYSICS_DT, loc, Vector3::zeros())
+        }));
+
+        let facing = car_facing(0.0);
+        let naive = naive_ground_intercept_2(&facing, &ball, |_| true).unwrap();
+        let aware = heading_aware_ground_intercept(&facing, &ball, |_| true).unwrap();
+        assert!((aware.time - naive.time).abs() < 0.05);
+
+        let away = car_facing(PI);
+        let naive = naive_ground_intercept_2(&away, &ball, |_| true).unwrap();
+        let aware = heading_aware_ground_intercept(&away, &ball, |_| true).unwrap();
+        assert!(aware.time > naive.time + 0.5);
+    }
+
+    #[test]
+    fn flips_when_out_of_boost() {
+        let ball_loc = Point3::new(4000.0, 0.0, rl::BALL_RADIUS);
+        let ball = BallTrajectory::from_samples(
+            (0..840).map(|i| (i as f32 * rl::PHYSICS_DT, ball_loc, Vector3::zeros())),
+        );
+
+        let car = car_facing(0.0);
+        let intercept = naive_ground_intercept_2(&car, &ball, |_| true).unwrap();
+
+        let mut driving = Car1D::new().with_speed(1000.0).with_boost(0.0);
+        driving.advance_by_distance(intercept.car_loc.x, 1.0, true);
+        assert!(intercept.time < driving.time() - 0.2);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    behavior::strike::{GroundedHit, WallHit},
+    helpers::ball::BallFrame,
+    utils::geometry::Plane,
+};
+use common::{prelude::*, rl};
+use std::fmt;
+
+/// A named filter over ball frames, for use in intercept searches.
+///
+/// These are preferred over ad-hoc closures so that the same conditions are
+/// checked the same way everywhere, and so that when a search comes up empty,
+/// the EEG can say what we were looking for.
+pub trait InterceptPredicate: fmt::Display {
+    fn matches(&self, ball: &BallFrame) -> bool;
+
+    /// Require both this and `other`.
+    fn and<P: InterceptPredicate>(self, other: P) -> And<Self, P>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Require that this does not match.
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+/// The ball is below the given height.
+pub struct BelowZ(pub f32);
+
+impl InterceptPredicate for BelowZ {
+    fn matches(&self, ball: &BallFrame) -> bool {
+        ball.loc.z < self.0
+    }
+}
+
+impl fmt::Display for BelowZ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "z < {:.0}", self.0)
+    }
+}
+
+/// The ball is low enough to hit from the ground, with a jump if need be.
+pub struct BelowGroundedReach;
+
+impl InterceptPredicate for BelowGroundedReach {
+    fn matches(&self, ball: &BallFrame) -> bool {
+        BelowZ(GroundedHit::MAX_BALL_Z).matches(ball)
+    }
+}
+
+impl fmt::Display for BelowGroundedReach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("within grounded reach")
+    }
+}
+
+/// The ball is on its way up, e.g., just after a bounce.
+pub struct OnBounceRise;
+
+impl InterceptPredicate for OnBounceRise {
+    fn matches(&self, ball: &BallFrame) -> bool {
+        ball.vel.z > 0.0
+    }
+}
+
+impl fmt::Display for OnBounceRise {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("rising")
+    }
+}
+
+/// The ball is close enough to the given surface to hit it from there.
+pub struct OnSurface<'a>(pub &'a Plane);
+
+impl<'a> InterceptPredicate for OnSurface<'a> {
+    fn matches(&self, ball: &BallFrame) -> bool {
+        self.0.distance_to_point(&ball.loc) < WallHit::MAX_BALL_DISTANCE_FROM_SURFACE
+    }
+}
+
+impl<'a> fmt::Display for OnSurface<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "on surface {}", self.0.normal.pretty())
+    }
+}
+
+/// The ball is inside the soccar field, i.e., not in a goal.
+pub struct InsideField;
+
+impl InterceptPredicate for InsideField {
+    fn matches(&self, ball: &BallFrame) -> bool {
+        ball.loc.x.abs() < rl::FIELD_MAX_X && ball.loc.y.abs() < rl::FIELD_MAX_Y
+    }
+}
+
+impl fmt::Display for InsideField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("inside the field")
+    }
+}
+
+pub struct And<A, B>(A, B);
+
+impl<A: InterceptPredicate, B: InterceptPredicate> InterceptPredicate for And<A, B> {
+    fn matches(&self, ball: &BallFrame) -> bool {
+        self.0.matches(ball) && self.1.matches(ball)
+    }
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for And<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} and {}", self.0, self.1)
+    }
+}
+
+pub struct Not<A>(A);
+
+impl<A: InterceptPredicate> InterceptPredicate for Not<A> {
+    fn matches(&self, ball: &BallFrame) -> bool {
+        !self.0.matches(ball)
+    }
+}
+
+impl<A: fmt::Display> fmt::Display for Not<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not ({})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::{
+        ball::BallTrajectory,
+        intercept_predicate::{BelowZ, InsideField, InterceptPredicate, OnBounceRise},
+    };
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn compose() {
+        let balls = BallTrajectory::from_samples(vec![
+            (
+                0.0,
+                Point3::new(0.0, 0.0, 100.0),
+                Vector3::new(0.0, 0.0, -500.0),
+            ),
+            (
+                0.1,
+                Point3::new(0.0, 0.0, 200.0),
+                Vector3::new(0.0, 0.0, 500.0),
+            ),
+            (
+                0.2,
+                Point3::new(0.0, 5200.0, 100.0),
+                Vector3::new(0.0, 0.0, 500.0),
+            ),
+        ]);
+        let predicate = BelowZ(150.0).and(OnBounceRise).and(InsideField.not());
+        let matched: Vec<_> = balls.iter().map(|ball| predicate.matches(ball)).collect();
+        assert_eq!(matched, vec![false, false, true]);
+        assert_eq!(
+            predicate.to_string(),
+            "z < 150 and rising and not (inside the field)",
+        );
+    }
+}
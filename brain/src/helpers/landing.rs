@@ -0,0 +1,78 @@
+use crate::{strategy::Pitch, utils::geometry::Plane};
+use common::{
+    kinematics::{kinematic, kinematic_time},
+    rl,
+};
+use nalgebra::{Point3, Vector3};
+
+/// Where and when a car in freefall will touch down.
+#[derive(Copy, Clone)]
+pub struct Landing {
+    /// Seconds from now.
+    pub time: f32,
+    pub loc: Point3<f32>,
+    /// The velocity the instant before touching down.
+    pub vel: Vector3<f32>,
+    /// The surface we'll land on.
+    pub plane: Plane,
+}
+
+impl Landing {
+    /// The velocity left over after the suspension absorbs the impact.
+    pub fn surface_vel(&self) -> Vector3<f32> {
+        self.plane.project_vector(&self.vel)
+    }
+
+    /// Returns `true` if we'll land on the floor, as opposed to a wall or the
+    /// ceiling.
+    pub fn is_on_ground(&self) -> bool {
+        self.plane.normal.z >= 0.99
+    }
+}
+
+/// How far ahead to simulate. Anything later is not worth planning around.
+const MAX_TIME: f32 = 3.0;
+const STEP: f32 = 1.0 / 60.0;
+
+/// Simulate a car in freefall and find the first surface it will touch. This
+/// ignores boost and air control.
+pub fn predict_landing(
+    pitch: &Pitch,
+    gravity: f32,
+    loc: Point3<f32>,
+    vel: Vector3<f32>,
+) -> Option<Landing> {
+    let accel = Vector3::z() * gravity;
+
+    // The goals are not convex, so the search below doesn't work inside one. The
+    // floor is usually the right answer there anyway.
+    if loc.x.abs() >= rl::FIELD_MAX_X || loc.y.abs() >= rl::FIELD_MAX_Y {
+        let time = kinematic_time(-loc.z + rl::OCTANE_NEUTRAL_Z, vel.z, gravity).unwrap_or(0.0);
+        let (d, vel) = kinematic(vel, accel, time);
+        return Some(Landing {
+            time,
+            loc: loc + d,
+            vel,
+            plane: *pitch.ground(),
+        });
+    }
+
+    // This assumes the field is convex, so the closest plane is the one we'll
+    // hit first.
+    let steps = (MAX_TIME / STEP) as usize;
+    (0..=steps).map(|i| i as f32 * STEP).find_map(|time| {
+        let (d, vel) = kinematic(vel, accel, time);
+        let loc = loc + d;
+        let plane = pitch.closest_plane(&loc);
+        if plane.distance_to_point(&loc) < rl::OCTANE_NEUTRAL_Z {
+            Some(Landing {
+                time,
+                loc,
+                vel,
+                plane: *plane,
+            })
+        } else {
+            None
+        }
+    })
+}
@@ -0,0 +1,109 @@
+use common::{halfway_house::PlayerInfo, prelude::*, rl};
+use nalgebra::{Point2, Vector2};
+
+/// The path the ball takes from a contact point to an aim location, for
+/// checking whether a car will be in the way.
+///
+/// Cars are assumed to keep their current velocity. That's wrong over long
+/// horizons, but it's a lot better than assuming the field is empty.
+pub struct Corridor {
+    start: Point2<f32>,
+    end: Point2<f32>,
+    start_time: f32,
+    speed: f32,
+}
+
+impl Corridor {
+    /// A car closer than this to the ball's center will get a touch on it.
+    pub const BLOCK_RADIUS: f32 = rl::BALL_RADIUS + 120.0;
+    /// A typical speed for a ball coming off a shot or clear.
+    pub const DEFAULT_SPEED: f32 = 2500.0;
+
+    pub fn new(start: Point2<f32>, end: Point2<f32>) -> Self {
+        Self {
+            start,
+            end,
+            start_time: 0.0,
+            speed: Self::DEFAULT_SPEED,
+        }
+    }
+
+    /// Seconds from now until the ball leaves `start`.
+    pub fn start_time(mut self, start_time: f32) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    /// How fast the ball travels from `start` to `end`.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The closest the car will come to the ball while the ball is in the
+    /// corridor.
+    pub fn closest_approach(&self, car_loc: Point2<f32>, car_vel: Vector2<f32>) -> f32 {
+        let length = (self.end - self.start).norm();
+        let ball_vel = (self.end - self.start).normalize() * self.speed;
+        let end_time = self.start_time + length / self.speed;
+
+        // The car's offset from the ball is `offset + rel_vel * t` for `t` in
+        // `[start_time, end_time]`. Find the `t` that minimizes it.
+        let offset = (car_loc - self.start) + ball_vel * self.start_time;
+        let rel_vel = car_vel - ball_vel;
+        let time = if rel_vel.norm_squared() < 1e-3 {
+            self.start_time
+        } else {
+            (-offset.dot(&rel_vel) / rel_vel.norm_squared())
+                .max(self.start_time)
+                .min(end_time)
+        };
+        (offset + rel_vel * time).norm()
+    }
+
+    pub fn is_blocked_by(&self, car_loc: Point2<f32>, car_vel: Vector2<f32>) -> bool {
+        self.closest_approach(car_loc, car_vel) < Self::BLOCK_RADIUS
+    }
+
+    /// Returns the first of `cars` that will be in the ball's way, if any.
+    pub fn first_blocker<'a>(
+        &self,
+        cars: impl IntoIterator<Item = &'a PlayerInfo>,
+    ) -> Option<&'a PlayerInfo> {
+        cars.into_iter().find(|car| {
+            !car.Demolished && self.is_blocked_by(car.Physics.loc_2d(), car.Physics.vel_2d())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::line_of_sight::Corridor;
+    use nalgebra::{Point2, Vector2};
+
+    #[test]
+    fn parked_in_the_way() {
+        let corridor = Corridor::new(Point2::new(0.0, 0.0), Point2::new(0.0, 4000.0));
+        assert!(corridor.is_blocked_by(Point2::new(50.0, 2000.0), Vector2::zeros()));
+        assert!(!corridor.is_blocked_by(Point2::new(500.0, 2000.0), Vector2::zeros()));
+        // Behind the ball's starting point doesn't count.
+        assert!(!corridor.is_blocked_by(Point2::new(0.0, -500.0), Vector2::zeros()));
+    }
+
+    #[test]
+    fn predicted_movement() {
+        let corridor = Corridor::new(Point2::new(0.0, 0.0), Point2::new(0.0, 5000.0));
+
+        // Drives into the path in time to meet the ball at y=2500, one second in.
+        let car_vel = Vector2::new(-1000.0, 0.0);
+        assert!(corridor.is_blocked_by(Point2::new(1000.0, 2500.0), car_vel));
+
+        // The same car is too late if the ball leaves sooner.
+        let corridor = corridor.speed(5000.0);
+        assert!(!corridor.is_blocked_by(Point2::new(1000.0, 2500.0), car_vel));
+
+        // And early enough again if the hit happens later.
+        let corridor = corridor.start_time(0.5);
+        assert!(corridor.is_blocked_by(Point2::new(1000.0, 2500.0), car_vel));
+    }
+}
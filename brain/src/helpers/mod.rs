@@ -1,5 +1,12 @@
 pub mod ball;
+pub mod contest;
+pub mod danger_zones;
 pub mod drive;
 pub mod hit_angle;
+pub mod hit_outcome;
 pub mod intercept;
+pub mod intercept_predicate;
+pub mod landing;
+pub mod line_of_sight;
+pub mod self_contact;
 pub mod telepathy;
@@ -0,0 +1,88 @@
+use crate::{
+    helpers::{
+        ball::BallTrajectory,
+        hit_outcome::{ball_vel_after_hit, goal_line_crossing_z},
+    },
+    strategy::Game,
+};
+use common::{prelude::*, rl};
+use nalgebra::{Point3, Vector3};
+
+/// Predict whether we're about to touch the ball in a way that knocks it
+/// into our own goal, e.g., when retreating and the ball bounces off our car.
+///
+/// This is meant to be cheap enough to run every frame, so it assumes the car
+/// keeps driving at its current velocity.
+pub fn predict_own_goal_contact(
+    game: &Game<'_>,
+    car: &common::halfway_house::PlayerInfo,
+    ball_prediction: &BallTrajectory,
+) -> Option<SelfContact> {
+    predict_own_goal_contact_at(game, car, car.Physics.vel(), ball_prediction)
+}
+
+/// Like `predict_own_goal_contact`, but with the car moving at `car_vel`
+/// instead of its current velocity, e.g., to see what a dodge or braking would
+/// change.
+pub fn predict_own_goal_contact_at(
+    game: &Game<'_>,
+    car: &common::halfway_house::PlayerInfo,
+    car_vel: Vector3<f32>,
+    ball_prediction: &BallTrajectory,
+) -> Option<SelfContact> {
+    let car_loc = car.Physics.loc();
+    let contact_dist = game.ball_radius() + game.me_vehicle().pivot_to_front_dist();
+
+    let ball = ball_prediction
+        .iter_step_by(STEP)
+        .take_while(|ball| ball.t < HORIZON)
+        .find(|ball| (car_loc + car_vel * ball.t - ball.loc).norm() < contact_dist)?;
+
+    let car_loc = car_loc + car_vel * ball.t;
+    let closing_speed = (car_vel - ball.vel).dot(&(ball.loc - car_loc).normalize());
+    if closing_speed <= 0.0 {
+        return None;
+    }
+    let car_forward = car.Physics.forward_axis();
+    let ball_vel = ball_vel_after_hit(car_loc, car_vel, &car_forward, ball.loc, ball.vel);
+
+    let time_to_goal = time_to_goal(game, ball.loc, ball_vel)?;
+    Some(SelfContact {
+        time: ball.t,
+        ball_loc: ball.loc,
+        time_to_goal,
+    })
+}
+
+/// How far ahead to look for a touch.
+const HORIZON: f32 = 0.5;
+const STEP: f32 = 1.0 / 60.0;
+/// Beyond this, the keeper (i.e., us again) has time to fix our mistake.
+const MAX_TIME_TO_GOAL: f32 = 2.0;
+
+pub struct SelfContact {
+    /// Seconds until the touch.
+    pub time: f32,
+    pub ball_loc: Point3<f32>,
+    /// Seconds from the touch until the ball would cross our goal line.
+    pub time_to_goal: f32,
+}
+
+/// If the ball would enter our goal soon, return how long it would take.
+fn time_to_goal(game: &Game<'_>, ball_loc: Point3<f32>, ball_vel: Vector3<f32>) -> Option<f32> {
+    let goal = game.own_goal();
+    let entry_z = goal_line_crossing_z(goal, ball_loc, ball_vel)?;
+    let speed_towards_goal = ball_vel.to_2d().dot(&-goal.normal_2d);
+    let dist = (ball_loc.to_2d() - goal.center_2d).dot(&goal.normal_2d);
+    let time = dist / speed_towards_goal;
+    if time > MAX_TIME_TO_GOAL {
+        return None;
+    }
+
+    let arrival_x = ball_loc.x + ball_vel.x * time;
+    if arrival_x.abs() < goal.max_x + rl::BALL_RADIUS && entry_z < rl::CROSSBAR_Z {
+        Some(time)
+    } else {
+        None
+    }
+}
@@ -22,3 +22,87 @@ pub fn predict_enemy_hit_direction_2(ctx: &Context2<'_, '_>) -> Option<Unit<Vect
     );
     Some((likely_aim - enemy_loc).to_axis())
 }
+
+/// A rough guess at what the enemy is up to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EnemyIntent {
+    /// The enemy is carrying the ball along a side wall towards our corner.
+    /// `side` is the sign of the x coordinate of that wall.
+    WallCarry {
+        side: f32,
+    },
+    /// The enemy is holding the ball near the ground, going slow and keeping
+    /// their car between us and it, waiting for us to overcommit.
+    SlowPlay,
+    Unknown,
+}
+
+pub fn enemy_intent(ctx: &mut Context<'_>) -> EnemyIntent {
+    let (ctx, _eeg) = ctx.split();
+    enemy_intent_2(&ctx)
+}
+
+pub fn enemy_intent_2(ctx: &Context2<'_, '_>) -> EnemyIntent {
+    if is_wall_carry(ctx) {
+        let side = ctx.packet.GameBall.Physics.loc().x.signum();
+        return EnemyIntent::WallCarry { side };
+    }
+    if is_slow_play(ctx) {
+        return EnemyIntent::SlowPlay;
+    }
+    EnemyIntent::Unknown
+}
+
+fn is_wall_carry(ctx: &Context2<'_, '_>) -> bool {
+    // How close to the side wall something has to be to count as "on" it.
+    const WALL_MARGIN: f32 = 300.0;
+    // Carries farther than this from our goal line (a bit past midfield) aren't a
+    // threat yet.
+    const MAX_GOAL_DIST: f32 = 6000.0;
+
+    let goal = ctx.game.own_goal();
+    let ball_loc = ctx.packet.GameBall.Physics.loc();
+    let wall_x = ctx.game.field_max_x() - WALL_MARGIN;
+
+    if ball_loc.x.abs() < wall_x || ball_loc.z < 150.0 {
+        return false;
+    }
+    if !goal.is_y_within_range(ball_loc.y, ..MAX_GOAL_DIST) {
+        return false;
+    }
+
+    let enemy = some_or_else!(ctx.scenario.primary_enemy(), {
+        return false;
+    });
+    let enemy_loc = enemy.Physics.loc();
+    let enemy_vel = enemy.Physics.vel_2d();
+    enemy_loc.x.abs() >= wall_x
+        && enemy_loc.x.signum() == ball_loc.x.signum()
+        && (enemy_loc - ball_loc).norm() < 500.0
+        && enemy_vel.dot(&-goal.normal_2d) >= 300.0
+}
+
+fn is_slow_play(ctx: &Context2<'_, '_>) -> bool {
+    const MAX_BALL_SPEED: f32 = 800.0;
+    const MAX_ENEMY_SPEED: f32 = 1000.0;
+    const MAX_ENEMY_DIST: f32 = 400.0;
+
+    let ball_loc = ctx.packet.GameBall.Physics.loc();
+    let ball_vel = ctx.packet.GameBall.Physics.vel();
+    if ball_vel.norm() >= MAX_BALL_SPEED || ball_loc.z >= 200.0 {
+        return false;
+    }
+
+    let enemy = some_or_else!(ctx.scenario.primary_enemy(), {
+        return false;
+    });
+    let enemy_loc = enemy.Physics.loc_2d();
+    let me_loc = ctx.me().Physics.loc_2d();
+    let ball_loc = ball_loc.to_2d();
+
+    // Shielding means the enemy is on our side of the ball.
+    let shielding = (enemy_loc - ball_loc).dot(&(me_loc - ball_loc)) > 0.0;
+    (enemy_loc - ball_loc).norm() < MAX_ENEMY_DIST
+        && enemy.Physics.vel_2d().norm() < MAX_ENEMY_SPEED
+        && shielding
+}
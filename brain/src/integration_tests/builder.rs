@@ -1,10 +1,11 @@
 use crate::{
-    behavior::higher_order::NullBehavior,
+    behavior::{higher_order::NullBehavior, registry::behavior_by_name},
     integration_tests::{
         playback::{BallRecording, CarRecording},
         running_test::RunningTest,
         scenario::TestScenario,
     },
+    sim::MirrorAttacker,
     strategy::{Behavior, Runner, Soccar},
 };
 use brain_test_data::OneVOneScenario;
@@ -23,6 +24,7 @@ pub struct TestRunner {
     car_inital_state: Option<(RecordingRigidBodyState, f32)>,
     enemy_recording: Option<(Vec<f32>, Vec<RecordingPlayerTick>)>,
     enemy_initial_boost: f32,
+    enemy_attacker: bool,
 }
 
 impl TestRunner {
@@ -35,6 +37,7 @@ impl TestRunner {
             car_inital_state: None,
             enemy_recording: None,
             enemy_initial_boost: Self::DEFAULT_STARTING_BOOST,
+            enemy_attacker: false,
         }
     }
 
@@ -71,6 +74,13 @@ impl TestRunner {
         self
     }
 
+    /// Drive the enemy with a `MirrorAttacker` instead of its recording. The
+    /// recording still decides where it starts.
+    pub fn enemy_attacker(mut self) -> Self {
+        self.enemy_attacker = true;
+        self
+    }
+
     fn enemy(
         mut self,
         times: impl Into<Vec<f32>>,
@@ -116,6 +126,14 @@ impl TestRunner {
         self
     }
 
+    /// Like `behavior`, but looked up by name, e.g., from a scenario file.
+    pub fn behavior_named(mut self, name: &str) -> Self {
+        let mut behavior =
+            Some(behavior_by_name(name).unwrap_or_else(|| panic!("unknown behavior {:?}", name)));
+        self.behavior = Some(Box::new(move |_| behavior.take().unwrap()));
+        self
+    }
+
     pub fn soccar(mut self) -> Self {
         self.behavior = Some(Box::new(|_| Box::new(Runner::new(Soccar::new()))));
         self
@@ -177,6 +195,12 @@ impl TestRunner {
             .behavior
             .unwrap_or_else(|| Box::new(|_| Box::new(NullBehavior::new())));
 
+        let attacker = if self.enemy_attacker {
+            Some(MirrorAttacker::new(1))
+        } else {
+            None
+        };
+
         let ready_wait = Arc::new(Barrier::new(2));
         let ready_wait_send = ready_wait.clone();
         let (messages_tx, messages_rx) = crossbeam_channel::unbounded();
@@ -184,6 +208,7 @@ impl TestRunner {
             ball,
             car,
             enemy,
+            attacker,
             move |p| behavior(p),
             ready_wait_send,
             messages_rx,
@@ -0,0 +1,83 @@
+use crate::eeg::LogRecord;
+
+/// Matches the records logged with `EEG::log_value`. Fields that aren't set
+/// match anything.
+///
+/// ```ignore
+/// test.assert_logged(
+///     LogMatcher::new()
+///         .behavior("Defense")
+///         .key("decision")
+///         .value("ContainSlowPlay"),
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct LogMatcher {
+    behavior: Option<String>,
+    key: Option<String>,
+    value: Option<String>,
+}
+
+impl LogMatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn behavior(mut self, behavior: impl Into<String>) -> Self {
+        self.behavior = Some(behavior.into());
+        self
+    }
+
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let field = |expected: &Option<String>, actual: &str| {
+            expected.as_ref().map(|e| e == actual).unwrap_or(true)
+        };
+        field(&self.behavior, &record.tag)
+            && field(&self.key, &record.key)
+            && field(&self.value, &record.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{eeg::LogRecord, integration_tests::LogMatcher};
+
+    fn record(tag: &str, key: &str, value: &str) -> LogRecord {
+        LogRecord {
+            time: 0.0,
+            tag: tag.to_string(),
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn unset_fields_match_anything() {
+        let record = record("Defense", "decision", "Retreat");
+        assert!(LogMatcher::new().matches(&record));
+        assert!(LogMatcher::new().behavior("Defense").matches(&record));
+        assert!(LogMatcher::new().key("decision").matches(&record));
+    }
+
+    #[test]
+    fn every_set_field_must_match() {
+        let record = record("Defense", "decision", "Retreat");
+        let matcher = LogMatcher::new()
+            .behavior("Defense")
+            .key("decision")
+            .value("Retreat");
+        assert!(matcher.matches(&record));
+        assert!(!matcher.value("TepidHit").matches(&record));
+        assert!(!LogMatcher::new().behavior("Offense").matches(&record));
+    }
+}
@@ -1,8 +1,15 @@
-pub use self::{builder::TestRunner, scenario::TestScenario};
+pub use self::{
+    builder::TestRunner,
+    log_matcher::LogMatcher,
+    scenario::TestScenario,
+    tags::{Tag, TaggedTest},
+};
 
 mod builder;
+mod log_matcher;
 mod playback;
 mod running_test;
 mod scenario;
+mod tags;
 mod templates;
 mod utils;
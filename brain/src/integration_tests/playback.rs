@@ -1,4 +1,4 @@
-use crate::integration_tests::utils::rotator;
+use crate::{integration_tests::utils::rotator, utils::GameClock};
 use collect::{RecordingPlayerTick, RecordingRigidBodyState};
 use common::{halfway_house::translate_player_input, prelude::*};
 use ordered_float::NotNan;
@@ -29,7 +29,7 @@ impl BallRecording {
 
 pub struct BallPlayback {
     scenario: BallRecording,
-    start_time: f32,
+    clock: GameClock,
     frames_since_state_set: i32,
 }
 
@@ -37,19 +37,19 @@ impl BallPlayback {
     pub fn new(scenario: BallRecording, start_time: f32) -> Self {
         Self {
             scenario,
-            start_time,
+            clock: clock_starting_at(start_time),
             frames_since_state_set: 0,
         }
     }
 
     pub fn tick(&mut self, rlbot: &rlbot::RLBot, packet: &common::halfway_house::LiveDataPacket) {
+        self.clock.update(packet.GameInfo.TimeSeconds);
         self.frames_since_state_set += 1;
         if self.frames_since_state_set < STATE_SET_DEBOUNCE {
             return;
         }
 
-        let elapsed = packet.GameInfo.TimeSeconds - self.start_time;
-        let data_time = self.scenario.times[0] + elapsed + LATENCY_COMPENSATION;
+        let data_time = self.scenario.times[0] + self.clock.now() + LATENCY_COMPENSATION;
         let index = match self.scenario.times.binary_search(&data_time) {
             Ok(i) => i,
             Err(0) => 0,
@@ -112,7 +112,7 @@ impl CarRecording {
 pub struct CarPlayback {
     scenario: CarRecording,
     player_index: i32,
-    start_time: f32,
+    clock: GameClock,
     frames_since_state_set: i32,
 }
 
@@ -121,14 +121,14 @@ impl CarPlayback {
         Self {
             scenario,
             player_index,
-            start_time,
+            clock: clock_starting_at(start_time),
             frames_since_state_set: 0,
         }
     }
 
     pub fn tick(&mut self, rlbot: &rlbot::RLBot, packet: &common::halfway_house::LiveDataPacket) {
-        let elapsed = packet.GameInfo.TimeSeconds - self.start_time;
-        let data_time = self.scenario.times[0] + elapsed + LATENCY_COMPENSATION;
+        self.clock.update(packet.GameInfo.TimeSeconds);
+        let data_time = self.scenario.times[0] + self.clock.now() + LATENCY_COMPENSATION;
         let index = match self.scenario.times.binary_search(&data_time) {
             Ok(i) => i,
             Err(0) => 0,
@@ -162,3 +162,11 @@ impl CarPlayback {
         }
     }
 }
+
+/// Playback follows our own clock instead of the game's, so a pause or a jump
+/// in game time doesn't skip or replay part of the recording.
+fn clock_starting_at(start_time: f32) -> GameClock {
+    let mut clock = GameClock::new();
+    clock.update(start_time);
+    clock
+}
@@ -1,19 +1,21 @@
 use crate::{
     behavior::higher_order::{Fuse, NullBehavior},
-    eeg::Event,
+    eeg::{Event, LogRecord},
     integration_tests::{
+        log_matcher::LogMatcher,
         playback::{BallPlayback, BallRecording, CarPlayback, CarRecording},
         utils::rotator,
     },
+    sim::MirrorAttacker,
     strategy::{Behavior, Team},
     Brain, EEG,
 };
 use collect::{get_packet_and_inject_rigid_body_tick, RecordingRigidBodyState};
-use common::{halfway_house::translate_player_input, ExtendRLBot};
+use common::{halfway_house::translate_player_input, ExtendRLBot, GoalDetector};
 use lazy_static::lazy_static;
 use std::{
     collections::HashSet,
-    panic,
+    env, panic,
     sync::{Arc, Barrier, Mutex, MutexGuard},
     thread,
     time::Duration,
@@ -78,10 +80,35 @@ impl RunningTest {
         self.examine_eeg(move |eeg| f(eeg.events.as_ref().unwrap()));
     }
 
+    pub fn examine_logs(&self, f: impl Fn(&[LogRecord]) + Send + 'static) {
+        self.examine_eeg(move |eeg| f(eeg.logs.as_ref().unwrap()));
+    }
+
+    /// Assert that something matching `matcher` was logged.
+    pub fn assert_logged(&self, matcher: LogMatcher) {
+        self.examine_logs(move |logs| {
+            assert!(
+                logs.iter().any(|r| matcher.matches(r)),
+                "nothing logged matching {:?}",
+                matcher,
+            );
+        });
+    }
+
+    /// Assert that nothing matching `matcher` was logged.
+    pub fn assert_not_logged(&self, matcher: LogMatcher) {
+        self.examine_logs(move |logs| {
+            if let Some(record) = logs.iter().find(|r| matcher.matches(r)) {
+                panic!("logged {:?}, which matches {:?}", record, matcher);
+            }
+        });
+    }
+
     pub fn spawn_thread(
         ball_scenario: BallRecording,
         car_scenario: CarRecording,
         enemy_scenario: CarRecording,
+        attacker: Option<MirrorAttacker>,
         behavior: impl FnOnce(&common::halfway_house::LiveDataPacket) -> Box<dyn Behavior>
             + Send
             + 'static,
@@ -93,6 +120,7 @@ impl RunningTest {
                 ball_scenario,
                 car_scenario,
                 enemy_scenario,
+                attacker,
                 behavior,
                 ready_wait,
                 messages,
@@ -112,6 +140,7 @@ pub enum Message {
 
 lazy_static! {
     static ref RLBOT_MUTEX: Mutex<Option<rlbot::RLBot>> = Mutex::new(None);
+    static ref COVERAGE_MUTEX: Mutex<()> = Mutex::new(());
 }
 
 /// RLBot can only be initialized once, so keep a permanent instance around for
@@ -128,6 +157,7 @@ fn test_thread(
     ball_scenario: BallRecording,
     car_scenario: CarRecording,
     enemy_scenario: CarRecording,
+    mut attacker: Option<MirrorAttacker>,
     behavior: impl FnOnce(&common::halfway_house::LiveDataPacket) -> Box<dyn Behavior>,
     ready_wait: Arc<Barrier>,
     messages: crossbeam_channel::Receiver<Message>,
@@ -148,6 +178,8 @@ fn test_thread(
     eeg.log_to_stdout();
     eeg.show_window();
     eeg.track_events();
+    eeg.track_logs();
+    eeg.track_coverage();
 
     let mut brain = Brain::with_behavior(NullBehavior::new());
     brain.set_player_index(0);
@@ -182,6 +214,11 @@ fn test_thread(
     brain.set_behavior(Fuse::new(behavior(&first_packet)), &mut eeg);
     ready_wait.wait();
 
+    // Goal resets are disabled, so the score in the packet never changes. Detect
+    // goals from the ball's location instead.
+    let mut goal_detector = GoalDetector::new();
+    goal_detector.update(&first_packet);
+
     let mut ball = BallPlayback::new(ball_scenario, first_packet.GameInfo.TimeSeconds);
     let mut enemy = CarPlayback::new(enemy_scenario, 1, first_packet.GameInfo.TimeSeconds);
 
@@ -190,7 +227,13 @@ fn test_thread(
         let packet = get_packet_and_inject_rigid_body_tick(rlbot, rigid_body_tick).unwrap();
 
         ball.tick(rlbot, &packet);
-        enemy.tick(rlbot, &packet);
+        match &mut attacker {
+            Some(attacker) => rlbot
+                .update_player_input(1, &translate_player_input(&attacker.input(&packet)))
+                .unwrap(),
+            None => enemy.tick(rlbot, &packet),
+        }
+        goal_detector.update(&packet);
 
         while let Some(message) = messages.try_recv() {
             match message {
@@ -201,14 +244,10 @@ fn test_thread(
                     brain.set_behavior(Fuse::new(behavior), &mut eeg);
                 }
                 Message::HasScored(tx) => {
-                    let first_score = first_packet.Teams[Team::Blue.to_ffi() as usize].Score;
-                    let current_score = packet.Teams[Team::Blue.to_ffi() as usize].Score;
-                    tx.send(current_score > first_score);
+                    tx.send(goal_detector.has_scored(Team::Blue.to_ffi()));
                 }
                 Message::EnemyHasScored(tx) => {
-                    let first_score = first_packet.Teams[Team::Orange.to_ffi() as usize].Score;
-                    let current_score = packet.Teams[Team::Orange.to_ffi() as usize].Score;
-                    tx.send(current_score > first_score);
+                    tx.send(goal_detector.has_scored(Team::Orange.to_ffi()));
                 }
                 Message::ExamineEEG(f) => {
                     f(&eeg);
@@ -232,6 +271,8 @@ fn test_thread(
         }
     }
 
+    save_coverage(&eeg);
+
     // For tidiness, make the cars stop moving when the test is finished.
     for i in 0..match_settings.player_configurations.len() {
         rlbot
@@ -240,6 +281,22 @@ fn test_thread(
     }
 }
 
+/// If `BRAIN_COVERAGE` is set, add this test's behavior coverage to the file it
+/// names.
+fn save_coverage(eeg: &EEG) {
+    let path = match env::var_os("BRAIN_COVERAGE") {
+        Some(path) => path,
+        None => return,
+    };
+    // Tests run in parallel, so don't let them clobber each other's counts.
+    let _guard = COVERAGE_MUTEX.lock().unwrap();
+    eeg.coverage
+        .as_ref()
+        .unwrap()
+        .merge_into_file(path)
+        .expect("Error saving coverage");
+}
+
 fn setup_scenario(
     rlbot: &rlbot::RLBot,
     ball: &RecordingRigidBodyState,
@@ -0,0 +1,258 @@
+use lazy_static::lazy_static;
+use std::{collections::BTreeMap, env, fmt, fs, io, path::Path, str::FromStr, sync::Mutex, thread};
+
+/// A label for slicing up the integration tests, e.g., to iterate on one
+/// subsystem at a time. Tests declare theirs with the `tags!` macro.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Tag {
+    Defense,
+    Offense,
+    Strike,
+    Movement,
+    Routing,
+    Wall,
+    Kickoff,
+    Aerial,
+    /// Passes some of the time.
+    Flaky,
+    /// Needs a running copy of Rocket League. Every test built on `TestRunner`
+    /// does, so `tags!` adds this one itself.
+    RequiresGame,
+}
+
+impl Tag {
+    pub const ALL: &'static [Tag] = &[
+        Tag::Defense,
+        Tag::Offense,
+        Tag::Strike,
+        Tag::Movement,
+        Tag::Routing,
+        Tag::Wall,
+        Tag::Kickoff,
+        Tag::Aerial,
+        Tag::Flaky,
+        Tag::RequiresGame,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Tag::Defense => "defense",
+            Tag::Offense => "offense",
+            Tag::Strike => "strike",
+            Tag::Movement => "movement",
+            Tag::Routing => "routing",
+            Tag::Wall => "wall",
+            Tag::Kickoff => "kickoff",
+            Tag::Aerial => "aerial",
+            Tag::Flaky => "flaky",
+            Tag::RequiresGame => "requires-game",
+        }
+    }
+}
+
+impl FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .cloned()
+            .find(|tag| tag.name() == s)
+            .ok_or_else(|| format!("unknown tag {:?}", s))
+    }
+}
+
+/// Which tests to run, e.g., `wall,defense,!flaky` runs the tests tagged
+/// `wall` or `defense`, except the flaky ones. With no positive tags,
+/// everything not excluded runs.
+#[derive(Default, Debug, PartialEq)]
+pub struct TagFilter {
+    include: Vec<Tag>,
+    exclude: Vec<Tag>,
+}
+
+impl TagFilter {
+    /// Read the filter from `BRAIN_TEST_TAGS`. If it's unset, run everything.
+    pub fn from_env() -> Self {
+        match env::var("BRAIN_TEST_TAGS") {
+            Ok(s) => s
+                .parse()
+                .unwrap_or_else(|e| panic!("BRAIN_TEST_TAGS: {}", e)),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn selects(&self, tags: &[Tag]) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|t| tags.contains(t));
+        let excluded = self.exclude.iter().any(|t| tags.contains(t));
+        included && !excluded
+    }
+}
+
+impl FromStr for TagFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+        for word in s.split(',').map(str::trim).filter(|w| !w.is_empty()) {
+            if word.starts_with('!') {
+                result.exclude.push(word[1..].parse()?);
+            } else {
+                result.include.push(word.parse()?);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// How many tests passed, per tag.
+///
+/// Like `Coverage`, this is merged across runs into a text file. The file
+/// doubles as the report, one tag per line: `wall\t12/14\t86%`.
+#[derive(Default, Debug, PartialEq)]
+pub struct TagReport {
+    /// Passed and total, by tag.
+    counts: BTreeMap<Tag, (u32, u32)>,
+}
+
+impl TagReport {
+    pub fn record(&mut self, tags: &[Tag], passed: bool) {
+        for &tag in tags {
+            let (pass, total) = self.counts.entry(tag).or_insert((0, 0));
+            *pass += passed as u32;
+            *total += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: &TagReport) {
+        for (&tag, &(pass, total)) in &other.counts {
+            let entry = self.counts.entry(tag).or_insert((0, 0));
+            entry.0 += pass;
+            entry.1 += total;
+        }
+    }
+
+    /// Add these results to the ones saved at `path`, creating it if need be.
+    pub fn merge_into_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut total = if path.exists() {
+            fs::read_to_string(path)?
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        } else {
+            Self::default()
+        };
+        total.merge(self);
+        fs::write(path, total.to_string())
+    }
+}
+
+impl fmt::Display for TagReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (tag, &(pass, total)) in &self.counts {
+            let percent = 100.0 * pass as f32 / total as f32;
+            writeln!(f, "{}\t{}/{}\t{:.0}%", tag.name(), pass, total, percent)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TagReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let bad_line = || format!("bad tag report line {}: {:?}", index + 1, line);
+            let mut fields = line.split('\t');
+            let tag: Tag = fields.next().ok_or_else(bad_line)?.parse()?;
+            let mut counts = fields.next().ok_or_else(bad_line)?.split('/');
+            let pass = counts.next().and_then(|c| c.parse().ok());
+            let total = counts.next().and_then(|c| c.parse().ok());
+            match (pass, total) {
+                (Some(pass), Some(total)) => {
+                    result.counts.insert(tag, (pass, total));
+                }
+                _ => return Err(bad_line()),
+            }
+        }
+        Ok(result)
+    }
+}
+
+lazy_static! {
+    static ref REPORT_MUTEX: Mutex<()> = Mutex::new(());
+}
+
+/// Returned by `tags!`. When the test finishes, this records whether it passed
+/// in the file named by `BRAIN_TAG_REPORT`, if it's set.
+pub struct TaggedTest {
+    tags: Vec<Tag>,
+}
+
+impl TaggedTest {
+    /// Returns `None` if `BRAIN_TEST_TAGS` filters out a test with these tags.
+    pub fn start(tags: &[Tag]) -> Option<Self> {
+        let mut tags = tags.to_vec();
+        tags.push(Tag::RequiresGame);
+        if !TagFilter::from_env().selects(&tags) {
+            println!("skipped by BRAIN_TEST_TAGS");
+            return None;
+        }
+        Some(Self { tags })
+    }
+}
+
+impl Drop for TaggedTest {
+    fn drop(&mut self) {
+        let path = match env::var_os("BRAIN_TAG_REPORT") {
+            Some(path) => path,
+            None => return,
+        };
+        let mut report = TagReport::default();
+        report.record(&self.tags, !thread::panicking());
+        // Tests run in parallel, so don't let them clobber each other's counts.
+        let _guard = REPORT_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = report.merge_into_file(path) {
+            eprintln!("Error saving tag report: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::integration_tests::tags::{Tag, TagFilter, TagReport};
+
+    #[test]
+    fn filter() {
+        let filter: TagFilter = "wall, defense,!flaky".parse().unwrap();
+        assert!(filter.selects(&[Tag::Wall]));
+        assert!(filter.selects(&[Tag::Defense, Tag::Aerial]));
+        assert!(!filter.selects(&[Tag::Wall, Tag::Flaky]));
+        assert!(!filter.selects(&[Tag::Kickoff]));
+
+        let everything: TagFilter = "".parse().unwrap();
+        assert!(everything.selects(&[]));
+        assert!("walls".parse::<TagFilter>().is_err());
+    }
+
+    #[test]
+    fn report_round_trip() {
+        let mut report = TagReport::default();
+        report.record(&[Tag::Wall, Tag::RequiresGame], true);
+        report.record(&[Tag::Wall, Tag::RequiresGame], false);
+        report.record(&[Tag::RequiresGame], true);
+        assert_eq!(
+            report.to_string(),
+            "wall\t1/2\t50%\nrequires-game\t2/3\t67%\n"
+        );
+
+        let mut parsed: TagReport = report.to_string().parse().unwrap();
+        assert_eq!(parsed, report);
+        parsed.merge(&report);
+        assert!(parsed.to_string().starts_with("wall\t2/4\t50%\n"));
+    }
+}
@@ -3,7 +3,25 @@
 #![warn(clippy::all)]
 #![allow(clippy::unreadable_literal)]
 
-pub use crate::{brain::Brain, eeg::EEG};
+pub use crate::{
+    bot::{Bot, BotBuilder},
+    brain::Brain,
+    eeg::{Coverage, DrawCategory, View, EEG},
+    replay::{diff_decisions, replay_decisions, DecisionDiff},
+    routing::{
+        batch::{evaluate_batch, BatchCase, BatchMetrics, BatchParams, BatchResult},
+        dry_run::{dry_run, dry_run_json, dry_run_segments, DryRunError, DryRunSegment},
+    },
+    sim::{AttackerMode, MirrorAttacker},
+    status::{BotStatus, FrameTiming},
+    strategy::{Difficulty, Personality, ScoutingReport, Team, Tendencies},
+    telemetry::{
+        export_telemetry_csv, read_telemetry, TelemetryBody, TelemetryCar, TelemetryFrame,
+    },
+};
+
+#[cfg(feature = "status-server")]
+pub use crate::status::serve_status;
 
 macro_rules! return_some {
     ($rule:expr) => {
@@ -22,14 +40,34 @@ macro_rules! some_or_else {
     };
 }
 
+/// Tag an integration test, e.g., `tags!(Defense, Wall);` as the first line
+/// of the test. The test returns right away if `BRAIN_TEST_TAGS` filters it
+/// out. See `integration_tests::Tag`.
+#[cfg(test)]
+macro_rules! tags {
+    ($($tag:ident),* $(,)?) => {
+        let _tagged = match crate::integration_tests::TaggedTest::start(&[
+            $(crate::integration_tests::Tag::$tag),*
+        ]) {
+            Some(tagged) => tagged,
+            None => return,
+        };
+    };
+}
+
 mod behavior;
+mod bot;
 mod brain;
 mod eeg;
 mod helpers;
 #[cfg(test)]
 mod integration_tests;
+pub mod prelude;
+mod replay;
 mod routing;
 mod rules;
 mod sim;
+mod status;
 mod strategy;
+mod telemetry;
 mod utils;
@@ -0,0 +1,51 @@
+//! The parts of the bot that are useful on their own, for authors of other
+//! RLBot bots who want to depend on this crate as a library.
+//!
+//! Everything here works from plain game state, with no EEG, `Runner` or
+//! behaviors involved:
+//!
+//! - Ball prediction: `ChipBallPrediction` or `FrameworkBallPrediction`
+//!   produce a `BallTrajectory`. `predict_touch_towards` branches one to show
+//!   what happens if a car hits the ball somewhere.
+//! - Intercepts: `naive_ground_intercept_2` finds the first frame of a
+//!   trajectory the car can reach. `heading_aware_ground_intercept` does the
+//!   same, but charges for the time it takes to turn towards the ball. To
+//!   search for a particular kind of ball, pass one of the named filters, e.g.
+//!   `|ball| OnBounceRise.and(BelowZ(300.0)).matches(ball)`.
+//! - Routing: build a `PlanningContext` and call `PlanningContext::plan_2`
+//!   with any `RoutePlanner`, then `RoutePlan::provisional_expand_2` for the
+//!   rest of the route and how long it takes. Leave `boost` as `None` unless
+//!   you track which pads are up. The segments themselves are tied to the
+//!   bot's behaviors, so they aren't exported.
+//! - Car models: `Car1D` and friends from the `simulate` crate.
+//!
+//! Units are the game's throughout: distances in uu, times in seconds,
+//! velocities in uu/s, angles in radians, and boost from 0 to 100. Times in a
+//! `BallTrajectory` or a plan are relative to its start.
+
+pub use crate::{
+    helpers::{
+        ball::{
+            BallFrame, BallPredictor, BallTrajectory, ChipBallPrediction, FrameworkBallPrediction,
+        },
+        hit_outcome::predict_touch_towards,
+        intercept::{
+            heading_aware_ground_intercept, naive_ground_intercept_2, IntoInterceptData,
+            NaiveIntercept,
+        },
+        intercept_predicate::{
+            BelowGroundedReach, BelowZ, InsideField, InterceptPredicate, OnBounceRise,
+        },
+    },
+    routing::{
+        cost::RouteCost,
+        models::{
+            CarState, CarState2D, PlanningContext, PlanningDump, ProvisionalPlanExpansion,
+            RoutePlan, RoutePlanError, RoutePlanner,
+        },
+        plan::{GroundIntercept, GroundStraightPlanner, TurnPlanner, WallIntercept},
+        StraightMode,
+    },
+    strategy::{Game, Goal, Pitch, Team, DFH_STADIUM},
+};
+pub use simulate::{Car1D, CarForwardDodge, CarForwardDodge1D};
@@ -0,0 +1,132 @@
+use crate::{brain::Brain, eeg::EEG, strategy::DFH_STADIUM};
+use collect::RecordingTick;
+use common::halfway_house;
+use std::{fmt, io::Read};
+
+/// Run a recorded match through the strategy layer, and return the decisions
+/// it made, one per line.
+///
+/// The recorded cars go wherever the recording says, so our inputs are thrown
+/// away. This only exercises the `Runner` and the strategy behind it, which
+/// makes it fast and deterministic, but the behaviors will be deciding based on
+/// movements they didn't make.
+pub fn replay_decisions(recording: impl Read, player_index: usize) -> String {
+    let mut brain = Brain::soccar();
+    brain.set_player_index(player_index as i32);
+    brain.unlimited_planning();
+
+    let mut eeg = EEG::new();
+    eeg.track_decisions();
+
+    for tick in RecordingTick::parse(recording) {
+        let packet = recorded_packet(&tick);
+        eeg.begin(&packet);
+        brain.tick_offline(&packet, &mut eeg);
+        eeg.show(&packet);
+    }
+
+    let mut result = String::new();
+    for decision in eeg.decisions.take().unwrap() {
+        result.push_str(&decision);
+        result.push('\n');
+    }
+    result
+}
+
+/// Compare a decision trace against a stored baseline. Returns the first line
+/// where they differ.
+pub fn diff_decisions(baseline: &str, actual: &str) -> Option<DecisionDiff> {
+    let mut baseline = baseline.lines();
+    let mut actual = actual.lines();
+    for line in 1.. {
+        let (expected, actual) = (baseline.next(), actual.next());
+        if expected.is_none() && actual.is_none() {
+            return None;
+        }
+        if expected != actual {
+            return Some(DecisionDiff {
+                line,
+                expected: expected.map(str::to_string),
+                actual: actual.map(str::to_string),
+            });
+        }
+    }
+    unreachable!();
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DecisionDiff {
+    pub line: usize,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for DecisionDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_end = |s: &Option<String>| s.clone().unwrap_or_else(|| "<end>".to_string());
+        writeln!(f, "decisions differ at line {}", self.line)?;
+        writeln!(f, "  expected: {}", or_end(&self.expected))?;
+        write!(f, "    actual: {}", or_end(&self.actual))
+    }
+}
+
+fn recorded_packet(tick: &RecordingTick) -> halfway_house::LiveDataPacket {
+    let cars = tick.players.iter().enumerate().map(|(index, player)| {
+        // Wheel contact isn't recorded, so guess based on distance from the
+        // nearest surface.
+        let loc = player.state.loc;
+        let on_ground = DFH_STADIUM.closest_plane(&loc).distance_to_point(&loc) < 25.0;
+        halfway_house::PlayerInfo {
+            Physics: player.state.to_physics(),
+            OnGround: on_ground,
+            // Recordings are of 1v1s, with the bot as the first player.
+            Team: (index % 2) as u8,
+            // Boost isn't recorded either. Pretend we have some, but not a
+            // lot.
+            Boost: 50,
+            ..Default::default()
+        }
+    });
+    halfway_house::LiveDataPacket {
+        GameBall: halfway_house::BallInfo {
+            Physics: tick.ball.to_physics(),
+        },
+        GameInfo: halfway_house::GameInfo {
+            TimeSeconds: tick.time,
+            RoundActive: true,
+            UnlimitedTime: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .with_cars(cars)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::replay::{diff_decisions, replay_decisions, DecisionDiff};
+
+    const RECORDING: &str = include_str!("../../brain-test-data/recordings/anticipate_shot.csv");
+
+    #[test]
+    fn replay_is_deterministic() {
+        let first = replay_decisions(RECORDING.as_bytes(), 0);
+        let second = replay_decisions(RECORDING.as_bytes(), 0);
+        assert!(first.lines().any(|line| line.contains("baseline: ")));
+        assert_eq!(diff_decisions(&first, &second), None);
+    }
+
+    #[test]
+    fn diff() {
+        let baseline = "1.000 [Runner] baseline: Offense\n2.000 [Runner] > Shoot\n";
+        assert_eq!(diff_decisions(baseline, baseline), None);
+        assert_eq!(
+            diff_decisions(baseline, "1.000 [Runner] baseline: Offense\n"),
+            Some(DecisionDiff {
+                line: 2,
+                expected: Some("2.000 [Runner] > Shoot".to_string()),
+                actual: None,
+            }),
+        );
+    }
+}
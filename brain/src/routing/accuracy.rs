@@ -0,0 +1,437 @@
+use crate::{
+    eeg::{Event, EEG},
+    routing::models::{CarState, SegmentPlan},
+};
+use common::{Distance, Speed, Time};
+use nalgebra::Point3;
+use nameof::name_of_type;
+use std::collections::BTreeMap;
+
+/// Records where the planner expected the car to be at the end of each segment,
+/// compares that against where the car actually was, and aggregates the error
+/// by segment type.
+///
+/// This exists to answer the question "did we arrive late and whiff because of
+/// the plan, or because of the execution?"
+///
+/// It also keeps the same kind of score for ball contact: approaches tell it
+/// when they expect to touch the ball, and it records how far off that was.
+/// When the touch never comes (a whiff), it makes a guess at why.
+#[derive(Default)]
+pub struct SegmentAccuracy {
+    current: Option<Expectation>,
+    stats: BTreeMap<String, SegmentErrorStats>,
+    expected_contact: Option<ContactExpectation>,
+    contact_stats: ContactErrorStats,
+}
+
+struct Expectation {
+    name: String,
+    start_time: f32,
+    duration: f32,
+    end: CarState,
+}
+
+struct ContactExpectation {
+    /// When the approach expected to touch the ball.
+    time: f32,
+    /// When the expectation was last updated. Only touches after this count.
+    since: f32,
+    /// Where the approach expected the ball to be at contact.
+    ball_loc: Point3<f32>,
+    /// How long the planned jump takes to reach the ball, or 0 for no jump.
+    jump_duration: f32,
+    /// When the car first left the ground after `since`.
+    left_ground: Option<f32>,
+    /// Where things actually were when contact should have happened.
+    snapshot: Option<ContactSnapshot>,
+}
+
+struct ContactSnapshot {
+    car_loc: Point3<f32>,
+    ball_loc: Point3<f32>,
+}
+
+/// The probable reason an expected contact never happened.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum WhiffCause {
+    /// The car wasn't near the ball yet when contact was due.
+    ArrivedLate,
+    /// The ball wasn't where the approach predicted.
+    BallDiverged,
+    /// The car left the ground sooner than the planned jump needed.
+    JumpedEarly,
+    /// Everything lined up, but the car still missed.
+    BadAim,
+}
+
+impl WhiffCause {
+    pub fn event(self) -> Event {
+        match self {
+            WhiffCause::ArrivedLate => Event::WhiffArrivedLate,
+            WhiffCause::BallDiverged => Event::WhiffBallDiverged,
+            WhiffCause::JumpedEarly => Event::WhiffJumpedEarly,
+            WhiffCause::BadAim => Event::WhiffBadAim,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SegmentErrorStats {
+    pub count: usize,
+    pub failures: usize,
+    pub total_loc_error: f32,
+    pub total_speed_error: f32,
+    /// Positive values mean the segment took longer than planned.
+    pub total_lateness: f32,
+}
+
+impl SegmentErrorStats {
+    pub fn mean_loc_error(&self) -> f32 {
+        self.total_loc_error / self.count as f32
+    }
+
+    pub fn mean_speed_error(&self) -> f32 {
+        self.total_speed_error / self.count as f32
+    }
+
+    pub fn mean_lateness(&self) -> f32 {
+        self.total_lateness / self.count as f32
+    }
+}
+
+#[derive(Default)]
+pub struct ContactErrorStats {
+    pub count: usize,
+    /// Expected contacts that never happened.
+    pub misses: usize,
+    /// `misses`, broken down by probable cause.
+    pub whiffs: BTreeMap<WhiffCause, usize>,
+    /// Positive values mean contact happened later than planned.
+    pub total_error: f32,
+    pub total_abs_error: f32,
+}
+
+impl ContactErrorStats {
+    pub fn mean_error(&self) -> f32 {
+        self.total_error / self.count as f32
+    }
+
+    pub fn mean_abs_error(&self) -> f32 {
+        self.total_abs_error / self.count as f32
+    }
+}
+
+impl SegmentAccuracy {
+    /// How many of the worst segment types to show on screen.
+    const WORST_COUNT: usize = 3;
+    /// Give up on an expected contact this long after it should have happened.
+    const CONTACT_TIMEOUT: f32 = 0.5;
+    /// A whiff with the ball further than this from its predicted location is
+    /// blamed on the prediction.
+    const BALL_DIVERGED_DISTANCE: f32 = 150.0;
+    /// Leaving the ground this much sooner than the plan called for counts as
+    /// jumping early.
+    const JUMP_EARLY_SLACK: f32 = 0.1;
+    /// A whiff with the car further than this from the predicted contact is
+    /// blamed on arriving late.
+    const ARRIVED_LATE_DISTANCE: f32 = 250.0;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call this when a segment starts running.
+    pub fn begin(&mut self, segment: &dyn SegmentPlan, time: f32) {
+        self.current = Some(Expectation {
+            name: segment.name().to_string(),
+            start_time: time,
+            duration: segment.duration(),
+            end: segment.end(),
+        });
+    }
+
+    /// Call this when the segment that was passed to `begin` was thrown away
+    /// without finishing, e.g., after the game clock jumped. It isn't scored.
+    pub fn abandon(&mut self) {
+        self.current = None;
+    }
+
+    /// Call this when the segment that was passed to `begin` stops running.
+    pub fn finish(&mut self, time: f32, actual: &CarState, success: bool, eeg: &mut EEG) {
+        let expected = some_or_else!(self.current.take(), {
+            return;
+        });
+
+        let loc_error = (actual.loc - expected.end.loc).norm();
+        let speed_error = actual.vel.norm() - expected.end.vel.norm();
+        let lateness = (time - expected.start_time) - expected.duration;
+
+        eeg.log(
+            name_of_type!(SegmentAccuracy),
+            format!(
+                "{} ended off by {} (speed {}, late {})",
+                expected.name,
+                Distance(loc_error),
+                Speed(speed_error),
+                Time(lateness),
+            ),
+        );
+
+        let stats = self.stats.entry(expected.name).or_default();
+        stats.count += 1;
+        if !success {
+            stats.failures += 1;
+        }
+        stats.total_loc_error += loc_error;
+        stats.total_speed_error += speed_error.abs();
+        stats.total_lateness += lateness;
+    }
+
+    /// Call this once when a touch is planned, with the time and ball location
+    /// where we expect to touch the ball, and how long the planned jump takes
+    /// (0 if there is none).
+    pub fn expect_contact(
+        &mut self,
+        now: f32,
+        contact_time: f32,
+        ball_loc: Point3<f32>,
+        jump_duration: f32,
+    ) {
+        self.expected_contact = Some(ContactExpectation {
+            time: contact_time,
+            since: now,
+            ball_loc,
+            jump_duration,
+            left_ground: None,
+            snapshot: None,
+        });
+    }
+
+    /// Call this once per frame with the state of our car and the ball, and the
+    /// time of our most recent touch, if any, to score the expected contact.
+    pub fn observe_contact(
+        &mut self,
+        now: f32,
+        car_loc: Point3<f32>,
+        car_on_ground: bool,
+        ball_loc: Point3<f32>,
+        last_own_touch: Option<f32>,
+        eeg: &mut EEG,
+    ) {
+        let expected = some_or_else!(self.expected_contact.as_mut(), {
+            return;
+        });
+
+        if !car_on_ground && expected.left_ground.is_none() {
+            expected.left_ground = Some(now);
+        }
+        if now >= expected.time && expected.snapshot.is_none() {
+            expected.snapshot = Some(ContactSnapshot { car_loc, ball_loc });
+        }
+
+        if let Some(touch) = last_own_touch.filter(|&t| t >= expected.since) {
+            let error = touch - expected.time;
+            eeg.log(
+                name_of_type!(SegmentAccuracy),
+                format!("contact off by {}", Time(error)),
+            );
+            self.contact_stats.count += 1;
+            self.contact_stats.total_error += error;
+            self.contact_stats.total_abs_error += error.abs();
+            self.expected_contact = None;
+        } else if now > expected.time + Self::CONTACT_TIMEOUT {
+            let cause = Self::classify_whiff(expected);
+            eeg.log(
+                name_of_type!(SegmentAccuracy),
+                format!("whiff: {:?}", cause),
+            );
+            eeg.track(cause.event());
+            self.contact_stats.misses += 1;
+            *self.contact_stats.whiffs.entry(cause).or_default() += 1;
+            self.expected_contact = None;
+        }
+    }
+
+    fn classify_whiff(expected: &ContactExpectation) -> WhiffCause {
+        let snapshot = some_or_else!(expected.snapshot.as_ref(), {
+            // We never even saw the contact time come around.
+            return WhiffCause::ArrivedLate;
+        });
+
+        let ball_error = (snapshot.ball_loc - expected.ball_loc).norm();
+        if ball_error >= Self::BALL_DIVERGED_DISTANCE {
+            return WhiffCause::BallDiverged;
+        }
+
+        let planned_takeoff = expected.time - expected.jump_duration;
+        if let Some(left_ground) = expected.left_ground {
+            if left_ground < planned_takeoff - Self::JUMP_EARLY_SLACK {
+                return WhiffCause::JumpedEarly;
+            }
+        }
+
+        let car_error = (snapshot.car_loc - expected.ball_loc).norm();
+        if car_error >= Self::ARRIVED_LATE_DISTANCE {
+            return WhiffCause::ArrivedLate;
+        }
+
+        WhiffCause::BadAim
+    }
+
+    pub fn contact_stats(&self) -> &ContactErrorStats {
+        &self.contact_stats
+    }
+
+    pub fn stats(&self) -> impl Iterator<Item = (&str, &SegmentErrorStats)> {
+        self.stats
+            .iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+    }
+
+    /// The segment types with the highest mean location error, worst first.
+    pub fn worst_offenders(&self) -> Vec<(&str, &SegmentErrorStats)> {
+        let mut result: Vec<_> = self.stats().collect();
+        result
+            .sort_by(|(_, a), (_, b)| b.mean_loc_error().partial_cmp(&a.mean_loc_error()).unwrap());
+        result
+    }
+
+    pub fn report(&self, eeg: &mut EEG) {
+        for (name, stats) in self.worst_offenders().into_iter().take(Self::WORST_COUNT) {
+            eeg.print_value(
+                &format!("seg err {}", name),
+                format!(
+                    "{:.0} ({:.0} spd, {:.2}s late, {}/{} failed)",
+                    stats.mean_loc_error(),
+                    stats.mean_speed_error(),
+                    stats.mean_lateness(),
+                    stats.failures,
+                    stats.count,
+                ),
+            );
+        }
+
+        let contact = &self.contact_stats;
+        if contact.count > 0 {
+            eeg.print_value(
+                "contact err",
+                format!(
+                    "{:.3}s ({:.3}s abs, {} missed)",
+                    contact.mean_error(),
+                    contact.mean_abs_error(),
+                    contact.misses,
+                ),
+            );
+        }
+        if !contact.whiffs.is_empty() {
+            let whiffs = contact
+                .whiffs
+                .iter()
+                .map(|(cause, count)| format!("{:?} {}", cause, count))
+                .collect::<Vec<_>>();
+            eeg.print_value("whiffs", whiffs.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eeg::EEG,
+        routing::{
+            accuracy::{SegmentAccuracy, WhiffCause},
+            models::{CarState, SegmentPlan},
+            segments::Straight,
+            StraightMode,
+        },
+        strategy::DFH_STADIUM,
+    };
+    use nalgebra::{Point2, Point3, UnitQuaternion, Vector3};
+
+    #[test]
+    fn aggregates_by_segment_type() {
+        let start = CarState {
+            loc: Point3::new(0.0, 0.0, 17.0),
+            rot: UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.0),
+            vel: Vector3::new(1000.0, 0.0, 0.0),
+            boost: 0.0,
+        };
+        let segment = Straight::new(
+            &DFH_STADIUM,
+            start.to_2d_assume(),
+            Point2::new(2000.0, 0.0),
+            0.0,
+            StraightMode::Asap,
+            false,
+        );
+
+        let mut eeg = EEG::new();
+        let mut accuracy = SegmentAccuracy::new();
+        accuracy.begin(&segment, 10.0);
+        let mut actual = segment.end();
+        actual.loc.x -= 100.0;
+        accuracy.finish(10.0 + segment.duration() + 0.25, &actual, true, &mut eeg);
+
+        let worst = accuracy.worst_offenders();
+        assert_eq!(worst.len(), 1);
+        let (name, stats) = worst[0];
+        assert_eq!(name, "Straight");
+        assert_eq!(stats.count, 1);
+        assert!((stats.mean_loc_error() - 100.0).abs() < 1.0);
+        assert!((stats.mean_lateness() - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn contact_timing() {
+        let mut eeg = EEG::new();
+        let mut accuracy = SegmentAccuracy::new();
+        let ball = Point3::new(0.0, 0.0, 92.74);
+        let car = Point3::new(0.0, -120.0, 17.0);
+
+        // A touch from before the approach doesn't count.
+        accuracy.expect_contact(10.0, 11.0, ball, 0.0);
+        accuracy.observe_contact(10.5, car, true, ball, Some(9.0), &mut eeg);
+        accuracy.expect_contact(10.5, 11.0, ball, 0.0);
+        accuracy.observe_contact(11.1, car, true, ball, Some(11.1), &mut eeg);
+
+        // Then an approach that whiffs.
+        accuracy.expect_contact(12.0, 13.0, ball, 0.0);
+        accuracy.observe_contact(13.2, car, true, ball, Some(11.1), &mut eeg);
+        accuracy.observe_contact(13.6, car, true, ball, Some(11.1), &mut eeg);
+
+        let stats = accuracy.contact_stats();
+        assert_eq!(stats.count, 1);
+        assert_eq!(stats.misses, 1);
+        assert!((stats.mean_error() - 0.1).abs() < 1e-4);
+    }
+
+    #[test]
+    fn whiff_causes() {
+        let ball = Point3::new(0.0, 0.0, 92.74);
+        let near = Point3::new(0.0, -120.0, 17.0);
+        let far = Point3::new(0.0, -1000.0, 17.0);
+
+        let whiff = |car_loc: Point3<f32>, ball_loc: Point3<f32>, takeoff: f32| {
+            let mut eeg = EEG::new();
+            let mut accuracy = SegmentAccuracy::new();
+            accuracy.expect_contact(10.0, 11.0, ball, 0.2);
+            let mut now = 10.0;
+            while now < 11.6 {
+                let on_ground = now < takeoff;
+                accuracy.observe_contact(now, car_loc, on_ground, ball_loc, None, &mut eeg);
+                now += 0.05;
+            }
+            let stats = accuracy.contact_stats();
+            assert_eq!(stats.misses, 1);
+            assert_eq!(stats.whiffs.values().sum::<usize>(), 1);
+            *stats.whiffs.keys().next().unwrap()
+        };
+
+        let moved = Point3::new(400.0, 0.0, 92.74);
+        assert_eq!(whiff(near, moved, 10.8), WhiffCause::BallDiverged);
+        assert_eq!(whiff(near, ball, 10.3), WhiffCause::JumpedEarly);
+        assert_eq!(whiff(far, ball, 10.8), WhiffCause::ArrivedLate);
+        assert_eq!(whiff(near, ball, 10.8), WhiffCause::BadAim);
+    }
+}
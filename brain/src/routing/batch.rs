@@ -0,0 +1,219 @@
+use crate::routing::{
+    cost::RouteCost,
+    dry_run::{dry_run_segments_with_cost, DryRunError},
+};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+/// One scenario to evaluate, together with the parameters to evaluate it with.
+///
+/// The scenario is given in the same text formats as `dry_run`, so batches can
+/// be built straight from files on disk.
+#[derive(Clone)]
+pub struct BatchCase {
+    /// Passed through to the result, so callers can tell the cases apart.
+    pub name: String,
+    pub planner: String,
+    pub car: String,
+    pub ball_prediction: String,
+    pub params: BatchParams,
+}
+
+/// The knobs a tuning run can turn.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BatchParams {
+    /// If set, plan with this instead of the cost model the game would choose.
+    pub cost: Option<RouteCost>,
+}
+
+pub struct BatchResult {
+    pub name: String,
+    pub metrics: Result<BatchMetrics, DryRunError>,
+}
+
+#[derive(Debug)]
+pub struct BatchMetrics {
+    /// Seconds until the end of the plan.
+    pub duration: f32,
+    pub boost_used: f32,
+    /// The plan's total cost according to the `RouteCost` it was planned with.
+    pub cost: f32,
+    pub segment_count: usize,
+}
+
+/// Evaluate each case across `threads` worker threads, and return the results
+/// in the same order as `cases`. If a case panics, that case fails with
+/// `DryRunError::Panic`, and the rest of the batch carries on.
+///
+/// Every case is planned from scratch from its own text, with no `EEG` and no
+/// shared mutable state, so the results don't depend on how the cases are
+/// split between threads. The only globals involved are the read-only
+/// `lazy_static` tables (the pitch, goals, and car models), which are safe to
+/// share.
+pub fn evaluate_batch(cases: Vec<BatchCase>, threads: usize) -> Vec<BatchResult> {
+    let count = cases.len();
+    let cases = Arc::new(cases);
+    let next = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = mpsc::channel();
+
+    let workers = (0..threads.max(1).min(count))
+        .map(|_| {
+            let cases = cases.clone();
+            let next = next.clone();
+            let tx = tx.clone();
+            thread::spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let case = match cases.get(index) {
+                    Some(case) => case,
+                    None => break,
+                };
+                let result = BatchResult {
+                    name: case.name.clone(),
+                    metrics: catch_panic(|| evaluate_case(case)),
+                };
+                if tx.send((index, result)).is_err() {
+                    break;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let mut results = (0..count).map(|_| None).collect::<Vec<_>>();
+    for (index, result) in rx {
+        results[index] = Some(result);
+    }
+    for worker in workers {
+        worker.join().expect("batch worker panicked");
+    }
+    results.into_iter().map(Option::unwrap).collect()
+}
+
+fn catch_panic(
+    f: impl FnOnce() -> Result<BatchMetrics, DryRunError>,
+) -> Result<BatchMetrics, DryRunError> {
+    // Each case only reads its own inputs, so nothing is left half-updated if
+    // it panics.
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|payload| Err(DryRunError::Panic(panic_message(&*payload))))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn evaluate_case(case: &BatchCase) -> Result<BatchMetrics, DryRunError> {
+    let segments = dry_run_segments_with_cost(
+        &case.planner,
+        &case.car,
+        &case.ball_prediction,
+        case.params.cost,
+    )?;
+    let last = segments.last().expect("plans always have a segment");
+    let start_boost = parse_boost(&case.car);
+    Ok(BatchMetrics {
+        duration: last.start_time + last.duration,
+        boost_used: (start_boost - last.end_boost).max(0.0),
+        cost: segments.iter().map(|s| s.cost).sum(),
+        segment_count: segments.len(),
+    })
+}
+
+/// The car has already been validated by the dry run, so this can't fail.
+fn parse_boost(car: &str) -> f32 {
+    car.trim()
+        .rsplit(',')
+        .next()
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{
+        batch::{catch_panic, evaluate_batch, BatchCase, BatchParams},
+        cost::RouteCost,
+        dry_run::DryRunError,
+    };
+
+    const CAR: &str = "0,-2000,17.01,0,0,0.7071068,0.7071068,0,0,0,0,0,0,100";
+
+    fn stationary_ball(x: f32) -> String {
+        (0..840)
+            .map(|i| format!("{},{},0,92.74,0,0,0\n", i as f32 / 120.0, x))
+            .collect()
+    }
+
+    fn case(name: &str, planner: &str, x: f32, cost: Option<RouteCost>) -> BatchCase {
+        BatchCase {
+            name: name.to_string(),
+            planner: planner.to_string(),
+            car: CAR.to_string(),
+            ball_prediction: stationary_ball(x),
+            params: BatchParams { cost },
+        }
+    }
+
+    #[test]
+    fn same_results_on_any_number_of_threads() {
+        let cases: Vec<_> = (0..6)
+            .map(|i| {
+                let x = i as f32 * 300.0 - 900.0;
+                case(&i.to_string(), "GroundIntercept", x, None)
+            })
+            .collect();
+
+        let serial = evaluate_batch(cases.clone(), 1);
+        let parallel = evaluate_batch(cases, 4);
+        assert_eq!(serial.len(), 6);
+        for (i, (a, b)) in serial.iter().zip(&parallel).enumerate() {
+            assert_eq!(a.name, i.to_string());
+            assert_eq!(b.name, i.to_string());
+            let (a, b) = (a.metrics.as_ref().unwrap(), b.metrics.as_ref().unwrap());
+            assert!(a.duration > 0.0);
+            assert!((a.duration - b.duration).abs() < 1e-5);
+            assert_eq!(a.segment_count, b.segment_count);
+        }
+    }
+
+    #[test]
+    fn errors_stay_with_their_case() {
+        let cases = vec![
+            case(
+                "good",
+                "GroundIntercept",
+                0.0,
+                Some(RouteCost::conservative()),
+            ),
+            case("bad", "Teleport", 0.0, None),
+        ];
+        let results = evaluate_batch(cases, 2);
+        assert!(results[0].metrics.is_ok());
+        match results[1].metrics {
+            Err(DryRunError::UnknownPlanner(_)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn panics_fail_their_case() {
+        let result = catch_panic(|| panic!("planner blew up"));
+        match result {
+            Err(DryRunError::Panic(message)) => assert_eq!(message, "planner blew up"),
+            _ => panic!(),
+        }
+    }
+}
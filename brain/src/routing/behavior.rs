@@ -1,13 +1,18 @@
 use crate::{
-    eeg::{color, Drawable},
-    routing::models::{
-        PlanningContext, ProvisionalPlanExpansion, ProvisionalPlanExpansionTail, RoutePlan,
-        RoutePlanError, RoutePlanner, SegmentRunAction, SegmentRunner,
+    eeg::{color, DrawCategory, Drawable},
+    routing::{
+        fallback::FallbackChain,
+        models::{
+            CarState, PlanningContext, ProvisionalPlanExpansion, ProvisionalPlanExpansionTail,
+            RoutePlan, RoutePlanError, RoutePlanner, SegmentRunAction, SegmentRunner,
+        },
+        plan::AirborneStart,
     },
     rules::SameBallTrajectory,
-    strategy::{Action, Behavior, Context},
+    strategy::{Action, Behavior, Child, Context},
 };
 use nameof::name_of_type;
+use std::time::Instant;
 
 pub struct FollowRoute {
     /// Option dance: This only holds a planner before the first tick.
@@ -15,9 +20,15 @@ pub struct FollowRoute {
     current: Option<Current>,
     never_recover: bool,
     same_ball_trajectory: Option<SameBallTrajectory>,
+    /// When the ball's trajectory changes, replan instead of aborting.
+    replan: bool,
+    fallbacks: FallbackChain,
 }
 
 struct Current {
+    /// The planner that came up with `plan`, kept so the rest of the route can
+    /// be planned again from wherever we are.
+    planner: Box<dyn RoutePlanner>,
     plan: RoutePlan,
     runner: Box<dyn SegmentRunner>,
     provisional_expansion_tail: ProvisionalPlanExpansionTail,
@@ -34,6 +45,8 @@ impl FollowRoute {
             current: None,
             never_recover: false,
             same_ball_trajectory: None,
+            replan: false,
+            fallbacks: FallbackChain::standard(),
         }
     }
 
@@ -44,12 +57,29 @@ impl FollowRoute {
         self
     }
 
+    /// What to try when planning fails. See `FallbackChain`.
+    pub fn fallbacks(mut self, fallbacks: FallbackChain) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
     pub fn same_ball_trajectory(mut self, same_ball_trajectory: bool) -> Self {
         self.same_ball_trajectory = if same_ball_trajectory {
             Some(SameBallTrajectory::new())
         } else {
             None
         };
+        self.replan = false;
+        self
+    }
+
+    /// Like `same_ball_trajectory`, but instead of aborting when the ball
+    /// strays from its predicted path, plan the rest of the route again from
+    /// the car's current state. The segments already driven are kept, and the
+    /// behaviors that follow the route never notice.
+    pub fn replan_on_new_trajectory(mut self, replan: bool) -> Self {
+        self = self.same_ball_trajectory(replan);
+        self.replan = replan;
         self
     }
 }
@@ -59,14 +89,29 @@ impl Behavior for FollowRoute {
         name_of_type!(FollowRoute)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
-        if let Some(ref mut same_ball_trajectory) = self.same_ball_trajectory {
-            return_some!(same_ball_trajectory.execute_old(ctx));
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        if let Some(current) = &self.current {
+            visit(Child::Segment(current.runner.name()));
+        }
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        let trajectory_changed = match self.same_ball_trajectory {
+            Some(ref mut same_ball_trajectory) => same_ball_trajectory.changed(ctx),
+            None => false,
+        };
+        if trajectory_changed {
+            if !self.replan {
+                return Action::Abort;
+            }
+            if let Err(action) = self.replan(ctx) {
+                return action;
+            }
         }
 
         if self.current.is_none() {
-            let planner = &*self.planner.take().unwrap();
-            if let Err(action) = self.advance(planner, ctx) {
+            let planner = self.planner.take().unwrap();
+            if let Err(action) = self.advance_from_here(planner, ctx) {
                 return action;
             }
         }
@@ -77,7 +122,51 @@ impl Behavior for FollowRoute {
 }
 
 impl FollowRoute {
+    fn advance_from_here(
+        &mut self,
+        planner: Box<dyn RoutePlanner>,
+        ctx: &mut Context<'_>,
+    ) -> Result<(), Action> {
+        if ctx.me().OnGround {
+            return self.advance(&*planner, ctx);
+        }
+
+        // Plan from where we'll land, instead of wasting the whole time we're
+        // in the air.
+        self.advance(&AirborneStart::new(planner.clone_box()), ctx)?;
+        // Remember the bare planner, so a replan doesn't wrap it twice. If a
+        // fallback planned the route instead, keep that one.
+        let current = self.current.as_mut().unwrap();
+        if current.planner.name() == name_of_type!(AirborneStart) {
+            current.planner = planner;
+        }
+        Ok(())
+    }
+
+    /// Throw away the segment in progress and everything after it, and plan
+    /// them again from the car's current state.
+    fn replan(&mut self, ctx: &mut Context<'_>) -> Result<(), Action> {
+        let current = some_or_else!(self.current.take(), {
+            // Nothing has been planned yet, so the first plan will already see
+            // the new trajectory.
+            return Ok(());
+        });
+        ctx.eeg.log(
+            self.name(),
+            format!(
+                "ball trajectory changed; replanning {}",
+                current.planner.name()
+            ),
+        );
+        ctx.segment_accuracy.abandon();
+        self.advance_from_here(current.planner, ctx)
+    }
+
     fn draw(&mut self, ctx: &mut Context<'_>) {
+        if !ctx.eeg.is_drawing(DrawCategory::Routes) {
+            return;
+        }
+
         // This provisional expansion serves two purposes:
         // 1. Make sure each segment thinks it can complete successfully.
         // 2. Predict far enough ahead that we can draw the whole plan to the screen.
@@ -90,29 +179,61 @@ impl FollowRoute {
     }
 
     fn advance(&mut self, planner: &dyn RoutePlanner, ctx: &mut Context<'_>) -> Result<(), Action> {
+        let (planner_name, error, log) = match self.try_advance(planner, ctx) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+
+        for fallback in self.fallbacks.planners(&error) {
+            ctx.eeg.log(
+                self.name(),
+                format!("error {:?}; falling back to {}", error, fallback.name()),
+            );
+            if self.try_advance(&*fallback, ctx).is_ok() {
+                return Ok(());
+            }
+        }
+
+        Err(self.handle_error(ctx, &planner_name, error, log))
+    }
+
+    fn try_advance(
+        &mut self,
+        planner: &dyn RoutePlanner,
+        ctx: &mut Context<'_>,
+    ) -> Result<(), (String, RoutePlanError, Vec<String>)> {
         assert!(self.current.is_none());
 
         ctx.eeg
             .log(self.name(), format!("planning with {}", planner.name()));
         let (plan, log) = match PlanningContext::plan(planner, ctx) {
             Ok((plan, log)) => (plan, log),
-            Err(err) => return Err(self.handle_error(ctx, planner.name(), err.error, err.log)),
+            Err(err) => return Err((planner.name().to_string(), err.error, err.log)),
         };
         ctx.eeg.log(
             self.name(),
             format!("next segment is {}", plan.segment.name()),
         );
-        let tail = plan.provisional_expand(&ctx.scenario).map_err(|error| {
-            self.handle_error(
-                ctx,
-                error.planner_name,
+        let started = Instant::now();
+        let tail = plan.provisional_expand(
+            &ctx.scenario,
+            Some(ctx.boost),
+            ctx.planning_budget.deadline(),
+        );
+        ctx.planning_budget.record(started.elapsed());
+        let tail = tail.map_err(|error| {
+            (
+                error.planner_name.to_string(),
                 error.error,
-                log.into_iter().chain(error.log),
+                log.into_iter().chain(error.log).collect::<Vec<_>>(),
             )
         })?;
 
         let runner = plan.segment.run();
+        ctx.segment_accuracy
+            .begin(&*plan.segment, ctx.packet.GameInfo.TimeSeconds);
         self.current = Some(Current {
+            planner: planner.clone_box(),
             plan,
             runner,
             provisional_expansion_tail: tail,
@@ -137,7 +258,7 @@ impl FollowRoute {
             format!("error {:?} from planner {}", error, planner_name),
         );
 
-        match error.recover(ctx) {
+        match self.fallbacks.behavior(&error, ctx) {
             Some(b) => {
                 if self.never_recover {
                     ctx.eeg
@@ -166,6 +287,10 @@ impl FollowRoute {
             SegmentRunAction::Failure => false,
         };
 
+        let time = ctx.packet.GameInfo.TimeSeconds;
+        let me = CarState::from(ctx.me());
+        ctx.segment_accuracy.finish(time, &me, success, ctx.eeg);
+
         if !success {
             ctx.eeg.log(self.name(), "segment failure; aborting");
             return Action::Abort;
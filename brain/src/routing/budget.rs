@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+/// Caps how much wall-clock time route planning can take each tick. RL's
+/// physics runs at 120Hz, so a planner that runs long costs us whole frames.
+///
+/// Planners see the deadline as `PlanningContext::deadline`. Past it, they
+/// should settle for the cheapest plan that still works instead of searching
+/// for a better one.
+pub struct PlanningBudget {
+    per_tick: Option<Duration>,
+    deadline: Option<Instant>,
+    spent: Duration,
+}
+
+impl PlanningBudget {
+    pub fn new(per_tick: Duration) -> Self {
+        Self {
+            per_tick: Some(per_tick),
+            deadline: Some(Instant::now() + per_tick),
+            spent: Duration::from_secs(0),
+        }
+    }
+
+    /// A budget that never runs out. Whether a planner cuts corners then
+    /// doesn't depend on how fast the machine is, so replays and tests make
+    /// the same decisions every run.
+    pub fn unlimited() -> Self {
+        Self {
+            per_tick: None,
+            deadline: None,
+            spent: Duration::from_secs(0),
+        }
+    }
+
+    /// Call this at the start of each tick.
+    pub fn begin_tick(&mut self) {
+        self.deadline = self.per_tick.map(|per_tick| Instant::now() + per_tick);
+        self.spent = Duration::from_secs(0);
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Count time spent planning towards this tick's total.
+    pub fn record(&mut self, elapsed: Duration) {
+        self.spent += elapsed;
+    }
+
+    /// How long planning took so far this tick, in milliseconds.
+    pub fn spent_ms(&self) -> f32 {
+        self.spent.as_secs() as f32 * 1000.0 + self.spent.subsec_micros() as f32 / 1000.0
+    }
+}
+
+impl Default for PlanningBudget {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::budget::PlanningBudget;
+    use std::time::Duration;
+
+    #[test]
+    fn spent_resets_each_tick() {
+        let mut budget = PlanningBudget::default();
+        budget.record(Duration::from_micros(1500));
+        budget.record(Duration::from_micros(250));
+        assert_eq!(budget.spent_ms(), 1.75);
+        budget.begin_tick();
+        assert_eq!(budget.spent_ms(), 0.0);
+    }
+
+    #[test]
+    fn unlimited_has_no_deadline() {
+        let mut budget = PlanningBudget::unlimited();
+        budget.begin_tick();
+        assert!(budget.deadline().is_none());
+    }
+}
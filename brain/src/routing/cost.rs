@@ -0,0 +1,156 @@
+use crate::{
+    routing::models::SegmentPlan,
+    strategy::{Game, Goal},
+};
+use common::prelude::*;
+use nalgebra::Point2;
+
+/// How planners weigh candidate routes against each other.
+///
+/// Historically every planner minimized time. That's still the default, but
+/// when we're protecting a lead late in the game, it's worth arriving a bit
+/// later to keep our boost and stay out of our own goal mouth.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RouteCost {
+    /// Cost per second of travel.
+    pub time: f32,
+    /// Cost per unit of boost spent.
+    pub boost: f32,
+    /// Cost per second spent near our own goal, where a bad touch could end
+    /// up in the net.
+    pub own_goal_hazard: f32,
+}
+
+impl RouteCost {
+    /// Driving within this distance of our goal counts as hazard exposure.
+    const HAZARD_RADIUS: f32 = 2500.0;
+    /// Play it safe with a lead in the last this-many seconds.
+    const CLOSING_TIME: f32 = 60.0;
+
+    /// Get there as soon as possible, whatever it takes.
+    pub fn fastest() -> Self {
+        Self {
+            time: 1.0,
+            boost: 0.0,
+            own_goal_hazard: 0.0,
+        }
+    }
+
+    /// Give up a little time to save boost and avoid our own goal.
+    pub fn conservative() -> Self {
+        Self {
+            time: 1.0,
+            boost: 0.01,
+            own_goal_hazard: 0.5,
+        }
+    }
+
+    /// Choose a cost model for the state of the match.
+    pub fn for_game(game: &Game<'_>) -> Self {
+        let remaining = game.seconds_remaining();
+        let mut cost =
+            if remaining > 0.0 && remaining < Self::CLOSING_TIME && game.score_margin() > 0 {
+                Self::conservative()
+            } else {
+                Self::fastest()
+            };
+        // Boost costs nothing if it never runs out.
+        if game.mutators().unlimited_boost {
+            cost.boost = 0.0;
+        }
+        cost
+    }
+
+    pub fn evaluate(&self, segment: &dyn SegmentPlan, own_goal: &Goal) -> f32 {
+        let start = segment.start();
+        let end = segment.end();
+        let duration = segment.duration();
+        let boost_used = (start.boost - end.boost).max(0.0);
+        let exposure = duration * hazard_fraction(own_goal, start.loc.to_2d(), end.loc.to_2d());
+        self.time * duration + self.boost * boost_used + self.own_goal_hazard * exposure
+    }
+}
+
+impl Default for RouteCost {
+    fn default() -> Self {
+        Self::fastest()
+    }
+}
+
+/// Roughly what fraction of a straight path lies near our goal.
+fn hazard_fraction(goal: &Goal, start: Point2<f32>, end: Point2<f32>) -> f32 {
+    const SAMPLES: usize = 5;
+    let near = (0..SAMPLES)
+        .map(|i| start + (end - start) * (i as f32 / (SAMPLES - 1) as f32))
+        .filter(|&loc| (loc - goal.center_2d).norm() < RouteCost::HAZARD_RADIUS)
+        .count();
+    near as f32 / SAMPLES as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        routing::{
+            cost::RouteCost,
+            models::{CarState2D, SegmentPlan},
+            segments::{Straight, StraightMode},
+        },
+        strategy::{DFH_STADIUM, SOCCAR_GOAL_BLUE},
+    };
+    use nalgebra::{Point2, UnitComplex, Vector2};
+    use std::f32::consts::PI;
+
+    fn straight(from: Point2<f32>, to: Point2<f32>, allow_boost: bool) -> Straight {
+        let start = CarState2D {
+            loc: from,
+            rot: UnitComplex::new(PI / 2.0),
+            vel: Vector2::new(0.0, 1000.0),
+            boost: 100.0,
+        };
+        Straight::new(
+            &DFH_STADIUM,
+            start,
+            to,
+            0.0,
+            StraightMode::Asap,
+            allow_boost,
+        )
+    }
+
+    #[test]
+    fn fastest_is_just_time() {
+        let segment = straight(Point2::new(0.0, 0.0), Point2::new(0.0, 2000.0), true);
+        let cost = RouteCost::fastest().evaluate(&segment, &SOCCAR_GOAL_BLUE);
+        assert!((cost - segment.duration()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn conservative_penalizes_boost() {
+        let boost = straight(Point2::new(0.0, 0.0), Point2::new(0.0, 2000.0), true);
+        let coast = straight(Point2::new(0.0, 0.0), Point2::new(0.0, 2000.0), false);
+        let cost = RouteCost::conservative();
+        assert!(boost.duration() < coast.duration());
+        assert!(
+            cost.evaluate(&boost, &SOCCAR_GOAL_BLUE) - boost.duration()
+                > cost.evaluate(&coast, &SOCCAR_GOAL_BLUE) - coast.duration()
+        );
+    }
+
+    #[test]
+    fn conservative_penalizes_own_goal() {
+        let near = straight(
+            Point2::new(-1000.0, -4500.0),
+            Point2::new(-1000.0, -3500.0),
+            false,
+        );
+        let far = straight(
+            Point2::new(-1000.0, 500.0),
+            Point2::new(-1000.0, 1500.0),
+            false,
+        );
+        let cost = RouteCost::conservative();
+        assert!(
+            cost.evaluate(&near, &SOCCAR_GOAL_BLUE) > cost.evaluate(&far, &SOCCAR_GOAL_BLUE) + 0.1
+        );
+    }
+}
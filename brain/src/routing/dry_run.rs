@@ -0,0 +1,313 @@
+use crate::{
+    helpers::ball::BallTrajectory,
+    routing::{
+        cost::RouteCost,
+        models::{CarState, PlanningContext, ProvisionalPlanExpansion, RoutePlanner},
+        plan::{GroundIntercept, WallIntercept},
+    },
+    strategy::Game,
+};
+use collect::RecordingRigidBodyState;
+use common::{halfway_house, rl};
+use nalgebra::{Point3, Vector3};
+use serde_derive::{Deserialize, Serialize};
+use std::{fmt, iter};
+
+/// Run a planner without the game, and return the planned segments with their
+/// timings.
+///
+/// `car` is a single CSV row in the same layout as the `collect` crate's rigid
+/// body columns, followed by boost:
+///
+/// ```text
+/// loc_x,loc_y,loc_z,rot_x,rot_y,rot_z,rot_w,vel_x,vel_y,vel_z,ang_vel_x,ang_vel_y,ang_vel_z,boost
+/// ```
+///
+/// `ball_prediction` is one CSV row per frame:
+///
+/// ```text
+/// t,loc_x,loc_y,loc_z,vel_x,vel_y,vel_z
+/// ```
+///
+/// The planners only work with the game's own frame rate, so the rows are
+/// taken to be one physics frame apart, starting from the first row's `t`.
+/// The rest of the `t` column is ignored.
+///
+/// The result is CSV with a header row and one row per segment. Since this is
+/// deterministic and needs nothing but text, it's also suitable as a fuzz
+/// target.
+pub fn dry_run(
+    planner_name: &str,
+    car: &str,
+    ball_prediction: &str,
+) -> Result<String, DryRunError> {
+    let segments = dry_run_segments(planner_name, car, ball_prediction)?;
+    let mut result = String::from(DryRunSegment::CSV_HEADER);
+    result.push('\n');
+    for segment in &segments {
+        result.push_str(&segment.to_csv());
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Like `dry_run`, but the result is a JSON array of `DryRunSegment`s, for
+/// tools that would rather not parse CSV.
+pub fn dry_run_json(
+    planner_name: &str,
+    car: &str,
+    ball_prediction: &str,
+) -> Result<String, DryRunError> {
+    let segments = dry_run_segments(planner_name, car, ball_prediction)?;
+    Ok(serde_json::to_string(&segments).unwrap())
+}
+
+/// Like `dry_run`, but returns the segments instead of serializing them.
+pub fn dry_run_segments(
+    planner_name: &str,
+    car: &str,
+    ball_prediction: &str,
+) -> Result<Vec<DryRunSegment>, DryRunError> {
+    dry_run_segments_with_cost(planner_name, car, ball_prediction, None)
+}
+
+/// Like `dry_run_segments`, but weigh routes with `cost` instead of the cost
+/// model the game would choose.
+pub fn dry_run_segments_with_cost(
+    planner_name: &str,
+    car: &str,
+    ball_prediction: &str,
+    cost: Option<RouteCost>,
+) -> Result<Vec<DryRunSegment>, DryRunError> {
+    let planner = planner_by_name(planner_name)
+        .ok_or_else(|| DryRunError::UnknownPlanner(planner_name.to_string()))?;
+    let (state, boost) = parse_car(car)?;
+    let ball_prediction = parse_ball_prediction(ball_prediction)?;
+
+    let packet = offline_packet(&state, boost);
+    let game = Game::soccar_offline(&packet, 0);
+    let start = CarState {
+        loc: state.loc,
+        rot: state.rot,
+        vel: state.vel,
+        boost: boost as f32,
+    };
+    let ctx = PlanningContext {
+        game: &game,
+        start,
+        ball_prediction: &ball_prediction,
+        cost: cost.unwrap_or_else(|| RouteCost::for_game(&game)),
+        deadline: None,
+        boost: None,
+    };
+
+    let (plan, _log) = PlanningContext::plan_2(&*planner, &ctx).map_err(|err| {
+        DryRunError::Plan(format!("{:?} from planner {}", err.error, err.planner_name))
+    })?;
+    let tail = plan
+        .provisional_expand_2(&game, &ball_prediction, ctx.cost, None, None)
+        .map_err(|err| {
+            DryRunError::Plan(format!("{:?} from planner {}", err.error, err.planner_name))
+        })?;
+
+    let expansion = ProvisionalPlanExpansion::new(&*plan.segment, &tail);
+    let mut time = 0.0;
+    let mut segments = Vec::new();
+    for segment in expansion.iter() {
+        let end = segment.end();
+        segments.push(DryRunSegment {
+            name: segment.name().to_string(),
+            start_time: time,
+            duration: segment.duration(),
+            end_loc: end.loc,
+            end_vel: end.vel,
+            end_boost: end.boost,
+            cost: ctx.cost.evaluate(segment, game.own_goal()),
+        });
+        time += segment.duration();
+    }
+    Ok(segments)
+}
+
+/// The planners that can be run by name.
+pub fn planner_by_name(name: &str) -> Option<Box<dyn RoutePlanner>> {
+    match name {
+        "GroundIntercept" => Some(Box::new(GroundIntercept::new())),
+        "WallIntercept" => Some(Box::new(WallIntercept::new())),
+        _ => None,
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DryRunSegment {
+    pub name: String,
+    /// Seconds from the start of the plan.
+    pub start_time: f32,
+    pub duration: f32,
+    #[serde(with = "crate::utils::wire::point3")]
+    pub end_loc: Point3<f32>,
+    #[serde(with = "crate::utils::wire::vector3")]
+    pub end_vel: Vector3<f32>,
+    pub end_boost: f32,
+    /// The segment's cost according to the `RouteCost` it was planned with.
+    pub cost: f32,
+}
+
+impl DryRunSegment {
+    const CSV_HEADER: &'static str =
+        "name,start_time,duration,end_loc_x,end_loc_y,end_loc_z,end_vel_x,end_vel_y,end_vel_z,end_boost";
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            self.name,
+            self.start_time,
+            self.duration,
+            self.end_loc.x,
+            self.end_loc.y,
+            self.end_loc.z,
+            self.end_vel.x,
+            self.end_vel.y,
+            self.end_vel.z,
+            self.end_boost,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum DryRunError {
+    UnknownPlanner(String),
+    BadCar,
+    BadBallPrediction { line: usize },
+    Plan(String),
+    /// The planner panicked. Only `evaluate_batch` reports this; a plain dry
+    /// run lets the panic through.
+    Panic(String),
+}
+
+impl fmt::Display for DryRunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DryRunError::UnknownPlanner(name) => write!(f, "unknown planner {:?}", name),
+            DryRunError::BadCar => f.write_str("could not parse car state"),
+            DryRunError::BadBallPrediction { line } => {
+                write!(f, "could not parse ball prediction on line {}", line)
+            }
+            DryRunError::Plan(message) => write!(f, "planning failed: {}", message),
+            DryRunError::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+fn parse_car(car: &str) -> Result<(RecordingRigidBodyState, i32), DryRunError> {
+    let mut it = car.trim().split(',').map(str::trim);
+    let state = RecordingRigidBodyState::from_csv(&mut it).map_err(|()| DryRunError::BadCar)?;
+    let boost = it
+        .next()
+        .and_then(|s| s.parse::<f32>().ok())
+        .ok_or(DryRunError::BadCar)?;
+    if it.next().is_some() {
+        return Err(DryRunError::BadCar);
+    }
+    Ok((state, boost.round() as i32))
+}
+
+fn parse_ball_prediction(ball_prediction: &str) -> Result<BallTrajectory, DryRunError> {
+    let mut samples = Vec::new();
+    for (index, line) in ball_prediction.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let values = line
+            .split(',')
+            .map(|s| s.trim().parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| DryRunError::BadBallPrediction { line: index + 1 })?;
+        if values.len() != 7 {
+            return Err(DryRunError::BadBallPrediction { line: index + 1 });
+        }
+        let t = match samples.first() {
+            Some(&(start, _, _)) => start + samples.len() as f32 * rl::PHYSICS_DT,
+            None => values[0],
+        };
+        samples.push((
+            t,
+            Point3::new(values[1], values[2], values[3]),
+            Vector3::new(values[4], values[5], values[6]),
+        ));
+    }
+    if samples.is_empty() {
+        return Err(DryRunError::BadBallPrediction { line: 1 });
+    }
+    Ok(BallTrajectory::from_samples(samples))
+}
+
+fn offline_packet(state: &RecordingRigidBodyState, boost: i32) -> halfway_house::LiveDataPacket {
+    let player = halfway_house::PlayerInfo {
+        Physics: state.to_physics(),
+        OnGround: true,
+        Boost: boost,
+        ..Default::default()
+    };
+    halfway_house::LiveDataPacket {
+        GameInfo: halfway_house::GameInfo {
+            RoundActive: true,
+            UnlimitedTime: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+    .with_cars(iter::once(player))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::dry_run::{dry_run, DryRunError};
+
+    const CAR: &str = "0,-2000,17.01,0,0,0.7071068,0.7071068,0,0,0,0,0,0,100";
+
+    fn stationary_ball() -> String {
+        (0..840)
+            .map(|i| format!("{},0,0,92.74,0,0,0\n", i as f32 / 120.0))
+            .collect()
+    }
+
+    #[test]
+    fn ground_intercept() {
+        let result = dry_run("GroundIntercept", CAR, &stationary_ball()).unwrap();
+        let mut lines = result.lines();
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("name,start_time,duration"));
+        assert!(lines.next().is_some());
+    }
+
+    #[test]
+    fn unknown_planner() {
+        match dry_run("Teleport", CAR, &stationary_ball()) {
+            Err(DryRunError::UnknownPlanner(_)) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn odd_frame_times() {
+        let ball: String = (0..840)
+            .map(|i| format!("{},0,0,92.74,0,0,0\n", i as f32 / 7.0))
+            .collect();
+        assert!(dry_run("GroundIntercept", CAR, &ball).is_ok());
+    }
+
+    #[test]
+    fn bad_input() {
+        match dry_run("GroundIntercept", "1,2,3", &stationary_ball()) {
+            Err(DryRunError::BadCar) => {}
+            _ => panic!(),
+        }
+        match dry_run("GroundIntercept", CAR, "0,1,2\n") {
+            Err(DryRunError::BadBallPrediction { line: 1 }) => {}
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    behavior::offense::ResetBehindBall,
+    routing::{
+        models::{RoutePlanError, RoutePlanner},
+        plan::WallIntercept,
+    },
+    strategy::{Behavior, Context},
+};
+use common::prelude::*;
+
+/// What `FollowRoute` should try, in order, when its planner fails.
+///
+/// This is data rather than code so that new planners can be slotted into the
+/// decision flow in one place, instead of at every call site that happens to
+/// plan a route.
+#[derive(Clone)]
+pub struct FallbackChain {
+    steps: Vec<FallbackStep>,
+}
+
+#[derive(Clone)]
+struct FallbackStep {
+    when: fn(&RoutePlanError) -> bool,
+    then: Fallback,
+}
+
+#[derive(Clone)]
+pub enum Fallback {
+    /// Replace the failed planner with this one and keep going. The behaviors
+    /// that follow the route (e.g., a `GroundedHit`) will not know the
+    /// difference, so only use planners that end in a compatible state.
+    Planner(Box<dyn RoutePlanner>),
+    /// Abandon the route and run a behavior instead.
+    Behavior(fn(&mut Context<'_>) -> Box<dyn Behavior>),
+    /// The built-in recovery in `RoutePlanError::recover`.
+    Recover,
+}
+
+impl FallbackChain {
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Use the fallback when an error matches `when`.
+    pub fn on(mut self, when: fn(&RoutePlanError) -> bool, then: Fallback) -> Self {
+        self.steps.push(FallbackStep { when, then });
+        self
+    }
+
+    /// Use the fallback for any error.
+    pub fn always(self, then: Fallback) -> Self {
+        self.on(|_| true, then)
+    }
+
+    /// The historical behavior: just recover from the error.
+    pub fn standard() -> Self {
+        Self::new().always(Fallback::Recover)
+    }
+
+    /// For routes that go for the ball on the ground. If there's no intercept,
+    /// meet the ball on the side wall instead. Failing that, get into a
+    /// position facing the ball and wait for one to appear.
+    ///
+    /// A `GroundedHit` after the route aborts if it ends on the wall, which
+    /// leaves the strategy to pick the wall hit from there.
+    pub fn ground_intercept() -> Self {
+        fn no_intercept(error: &RoutePlanError) -> bool {
+            match error {
+                RoutePlanError::UnknownIntercept => true,
+                _ => false,
+            }
+        }
+
+        Self::new()
+            .on(
+                no_intercept,
+                Fallback::Planner(Box::new(
+                    WallIntercept::new()
+                        .must_be_wall(true)
+                        .must_be_side_wall(true),
+                )),
+            )
+            .on(no_intercept, Fallback::Behavior(face_ball))
+            .always(Fallback::Recover)
+    }
+
+    /// Planner fallbacks that apply to the given error, in order.
+    pub fn planners(&self, error: &RoutePlanError) -> Vec<Box<dyn RoutePlanner>> {
+        self.matching(error)
+            .filter_map(|fallback| match fallback {
+                Fallback::Planner(planner) => Some(planner.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Find the first non-planner fallback that produces a behavior.
+    pub fn behavior(
+        &self,
+        error: &RoutePlanError,
+        ctx: &mut Context<'_>,
+    ) -> Option<Box<dyn Behavior>> {
+        for fallback in self.matching(error) {
+            let behavior = match fallback {
+                Fallback::Planner(_) => None,
+                Fallback::Behavior(f) => Some(f(ctx)),
+                Fallback::Recover => error.recover(ctx),
+            };
+            if behavior.is_some() {
+                return behavior;
+            }
+        }
+        None
+    }
+
+    fn matching<'a>(&'a self, error: &'a RoutePlanError) -> impl Iterator<Item = &'a Fallback> {
+        self.steps
+            .iter()
+            .filter(move |step| (step.when)(error))
+            .map(|step| &step.then)
+    }
+}
+
+fn face_ball(ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+    let ball_loc = ctx.scenario.ball_prediction().at_time_or_last(2.5).loc;
+    Box::new(ResetBehindBall::behind_loc(ball_loc.to_2d(), 1600.0).never_recover(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::{fallback::FallbackChain, models::RoutePlanError};
+
+    #[test]
+    fn ground_intercept_falls_back_to_wall() {
+        let chain = FallbackChain::ground_intercept();
+        let planners: Vec<_> = chain
+            .planners(&RoutePlanError::UnknownIntercept)
+            .iter()
+            .map(|planner| planner.name())
+            .collect();
+        assert_eq!(planners, ["WallIntercept"]);
+        assert!(chain.planners(&RoutePlanError::MovingTooFast).is_empty());
+    }
+}
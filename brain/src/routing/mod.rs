@@ -1,6 +1,12 @@
 pub use self::segments::StraightMode;
 
+pub mod accuracy;
+pub mod batch;
 pub mod behavior;
+pub mod budget;
+pub mod cost;
+pub mod dry_run;
+pub mod fallback;
 pub mod models;
 pub mod plan;
 pub mod recover;
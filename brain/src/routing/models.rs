@@ -1,17 +1,29 @@
 use crate::{
     helpers::ball::BallTrajectory,
-    strategy::{Context, Context2, Game, Scenario},
+    routing::cost::RouteCost,
+    strategy::{BoostTracker, Context, Context2, Game, Scenario},
     utils::geometry::flattener::Flattener,
 };
 use common::{physics, prelude::*, rl, PrettyPrint};
 use derive_new::new;
 use nalgebra::{Point2, Point3, Unit, UnitComplex, UnitQuaternion, Vector2, Vector3};
-use std::{fmt, iter};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    fmt,
+    hash::{Hash, Hasher},
+    iter,
+    time::Instant,
+};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct CarState {
+    #[serde(with = "crate::utils::wire::point3")]
     pub loc: Point3<f32>,
+    #[serde(with = "crate::utils::wire::unit_quaternion")]
     pub rot: UnitQuaternion<f32>,
+    #[serde(with = "crate::utils::wire::vector3")]
     pub vel: Vector3<f32>,
     pub boost: f32,
 }
@@ -116,6 +128,14 @@ pub trait RoutePlanner: RoutePlannerCloneBox + Send {
         ctx: &PlanningContext<'_, '_>,
         dump: &mut PlanningDump<'_>,
     ) -> Result<RoutePlan, RoutePlanError>;
+
+    /// Describes everything about this planner's configuration that affects
+    /// its plans, so `PlanCache` can tell planners apart. Planners that return
+    /// the same key must come up with the same plan from the same start. The
+    /// default opts out of caching.
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
 }
 
 pub trait RoutePlannerCloneBox {
@@ -141,31 +161,80 @@ pub struct PlanningContext<'a: 's, 's> {
     pub game: &'s Game<'a>,
     pub start: CarState,
     pub ball_prediction: &'s BallTrajectory,
+    /// What the planners should minimize when they have a choice of routes.
+    pub cost: RouteCost,
+    /// When planning should be done by, if ever. See `PlanningBudget`.
+    pub deadline: Option<Instant>,
+    /// Which boost pads are up. Without it, every pad counts as up.
+    pub boost: Option<&'s BoostTracker>,
 }
 
 impl<'a: 's, 's> PlanningContext<'a, 's> {
-    pub fn from_context(ctx: &Context2<'a, 's>) -> PlanningContext<'a, 's> {
+    pub(crate) fn from_context(ctx: &Context2<'a, 's>) -> PlanningContext<'a, 's> {
         PlanningContext {
             game: &ctx.game,
             start: ctx.me().into(),
             ball_prediction: ctx.scenario.ball_prediction(),
+            cost: RouteCost::for_game(ctx.game),
+            deadline: None,
+            boost: Some(ctx.boost),
         }
     }
 
-    pub fn plan(
-        planner: &dyn RoutePlanner,
-        ctx: &mut Context<'_>,
-    ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
-        let (ctx, _eeg) = ctx.split();
-        Self::plan2(planner, &ctx)
+    /// Whether the pad at `pad_loc` will be up by the time we could get there
+    /// from `start`.
+    pub fn pad_up_in_time(&self, pad_loc: Point2<f32>) -> bool {
+        self.boost
+            .map(|boost| boost.pad_up_in_time(pad_loc, self.start.loc_2d()))
+            .unwrap_or(true)
     }
 
-    pub fn plan2(
+    /// Returns `true` once the deadline has passed. Planners that search
+    /// should check this and fall back to something cheap.
+    pub fn out_of_time(&self) -> bool {
+        self.deadline
+            .map(|deadline| Instant::now() >= deadline)
+            .unwrap_or(false)
+    }
+
+    /// Plan from the car's current state, reusing a plan from `ctx.plan_cache`
+    /// if an earlier tick already did the work. The time taken counts against
+    /// `ctx.planning_budget`.
+    pub(crate) fn plan(
         planner: &dyn RoutePlanner,
-        ctx: &Context2<'_, '_>,
+        ctx: &mut Context<'_>,
     ) -> Result<(RoutePlan, Vec<String>), ProvisionalExpandError<'a>> {
-        let context = PlanningContext::from_context(ctx);
-        Self::plan_2(planner, &context)
+        let context = PlanningContext {
+            game: ctx.game,
+            start: ctx.me().into(),
+            ball_prediction: ctx.scenario.ball_prediction(),
+            cost: RouteCost::for_game(ctx.game),
+            deadline: ctx.planning_budget.deadline(),
+            boost: Some(ctx.boost),
+        };
+        let key = PlanCacheKey::new(
+            planner,
+            &context.start,
+            context.ball_prediction,
+            context.cost,
+        );
+        if let Some(ref key) = key {
+            if let Some(plan) = ctx.plan_cache.get(key) {
+                let log = vec![format!("[{}] reusing cached plan", planner.name())];
+                return Ok((plan, log));
+            }
+        }
+
+        let started = Instant::now();
+        let result = Self::plan_2(planner, &context);
+        ctx.planning_budget.record(started.elapsed());
+        // A plan that ran out of time may have cut corners, so don't keep it.
+        if !context.out_of_time() {
+            if let (Some(key), Ok((plan, _))) = (key, &result) {
+                ctx.plan_cache.insert(key, plan.clone());
+            }
+        }
+        result
     }
 
     pub fn plan_2(
@@ -185,6 +254,108 @@ impl<'a: 's, 's> PlanningContext<'a, 's> {
     }
 }
 
+/// Remembers the last few plans, so that planning the same route from nearly
+/// the same state on consecutive ticks (e.g., each time a `FollowRoute`
+/// restarts) doesn't redo all the work.
+pub struct PlanCache {
+    entries: VecDeque<(PlanCacheKey, RoutePlan)>,
+}
+
+impl PlanCache {
+    /// Entries only hit while the car and ball barely move, so there's no use
+    /// keeping many.
+    const CAPACITY: usize = 8;
+
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(Self::CAPACITY),
+        }
+    }
+
+    /// Forget everything, e.g., after the game clock jumps.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn get(&self, key: &PlanCacheKey) -> Option<RoutePlan> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, plan)| plan.clone())
+    }
+
+    fn insert(&mut self, key: PlanCacheKey, plan: RoutePlan) {
+        if self.entries.len() >= Self::CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, plan));
+    }
+}
+
+/// The inputs to a plan, rounded so that states a tick apart can share a key.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PlanCacheKey {
+    planner: String,
+    car: [i32; 11],
+    /// The ball prediction is deterministic, so its start frame stands in for
+    /// the whole thing.
+    ball: u64,
+    cost: [u32; 3],
+}
+
+impl PlanCacheKey {
+    const LOC_STEP: f32 = 5.0;
+    const VEL_STEP: f32 = 10.0;
+    const ROT_STEP: f32 = 0.01;
+
+    /// Returns `None` if `planner` opts out of caching.
+    pub fn new(
+        planner: &dyn RoutePlanner,
+        start: &CarState,
+        ball_prediction: &BallTrajectory,
+        cost: RouteCost,
+    ) -> Option<Self> {
+        let planner = planner.cache_key()?;
+        let q = |x: f32, step: f32| (x / step).round() as i32;
+        let car = [
+            q(start.loc.x, Self::LOC_STEP),
+            q(start.loc.y, Self::LOC_STEP),
+            q(start.loc.z, Self::LOC_STEP),
+            q(start.vel.x, Self::VEL_STEP),
+            q(start.vel.y, Self::VEL_STEP),
+            q(start.vel.z, Self::VEL_STEP),
+            q(start.rot.coords.x, Self::ROT_STEP),
+            q(start.rot.coords.y, Self::ROT_STEP),
+            q(start.rot.coords.z, Self::ROT_STEP),
+            q(start.rot.coords.w, Self::ROT_STEP),
+            start.boost as i32,
+        ];
+
+        let ball = ball_prediction.start();
+        let mut hasher = DefaultHasher::new();
+        [
+            q(ball.loc.x, Self::LOC_STEP),
+            q(ball.loc.y, Self::LOC_STEP),
+            q(ball.loc.z, Self::LOC_STEP),
+            q(ball.vel.x, Self::VEL_STEP),
+            q(ball.vel.y, Self::VEL_STEP),
+            q(ball.vel.z, Self::VEL_STEP),
+        ]
+        .hash(&mut hasher);
+
+        Some(Self {
+            planner,
+            car,
+            ball: hasher.finish(),
+            cost: [
+                cost.time.to_bits(),
+                cost.boost.to_bits(),
+                cost.own_goal_hazard.to_bits(),
+            ],
+        })
+    }
+}
+
 pub struct PlanningDump<'a> {
     pub log: &'a mut Vec<String>,
 }
@@ -265,6 +436,7 @@ pub enum RoutePlanError {
     TurningRadiusTooTight,
     CannotOperateWall,
     NoWallIntercept,
+    InterceptHugsWall,
     TurnAngleTooLarge,
     OtherError(&'static str),
 }
@@ -280,6 +452,7 @@ impl fmt::Debug for RoutePlanError {
             RoutePlanError::TurningRadiusTooTight => f.write_str(stringify!(TurningRadiusTooTight)),
             RoutePlanError::CannotOperateWall => f.write_str(stringify!(CannotOperateWall)),
             RoutePlanError::NoWallIntercept => f.write_str(stringify!(NoWallIntercept)),
+            RoutePlanError::InterceptHugsWall => f.write_str(stringify!(InterceptHugsWall)),
             RoutePlanError::TurnAngleTooLarge => f.write_str(stringify!(TurnAngleTooLarge)),
             RoutePlanError::OtherError(msg) => write!(f, "{}({:?})", stringify!(OtherError), msg),
         }
@@ -292,18 +465,45 @@ pub struct RoutePlan {
     pub next: Option<Box<dyn RoutePlanner>>,
 }
 
+/// Segments and planners are trait objects, so only an outline of the plan is
+/// written: the segment's name, endpoints and duration, and the name of the
+/// next planner. That's enough for telemetry and fixtures, but it can't be
+/// read back into a `RoutePlan`.
+impl Serialize for RoutePlan {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("RoutePlan", 5)?;
+        s.serialize_field("segment", self.segment.name())?;
+        s.serialize_field("start", &self.segment.start())?;
+        s.serialize_field("end", &self.segment.end())?;
+        s.serialize_field("duration", &self.segment.duration())?;
+        s.serialize_field("next", &self.next.as_ref().map(|planner| planner.name()))?;
+        s.end()
+    }
+}
+
 impl RoutePlan {
-    pub fn provisional_expand(
+    pub(crate) fn provisional_expand(
         &self,
         scenario: &Scenario<'_>,
+        boost: Option<&BoostTracker>,
+        deadline: Option<Instant>,
     ) -> Result<ProvisionalPlanExpansionTail, ProvisionalExpandError<'_>> {
-        self.provisional_expand_2(scenario.game, scenario.ball_prediction())
+        self.provisional_expand_2(
+            scenario.game,
+            scenario.ball_prediction(),
+            RouteCost::for_game(scenario.game),
+            boost,
+            deadline,
+        )
     }
 
     pub fn provisional_expand_2(
         &self,
         game: &Game<'_>,
         ball_prediction: &BallTrajectory,
+        cost: RouteCost,
+        boost: Option<&BoostTracker>,
+        deadline: Option<Instant>,
     ) -> Result<ProvisionalPlanExpansionTail, ProvisionalExpandError<'_>> {
         let mut tail = Vec::new();
         if let Some(ref planner) = self.next {
@@ -311,6 +511,9 @@ impl RoutePlan {
                 game,
                 start: self.segment.end(),
                 ball_prediction,
+                cost,
+                deadline,
+                boost,
             };
             let mut log = Vec::new();
             let mut dump = PlanningDump { log: &mut log };
@@ -348,6 +551,9 @@ impl RoutePlan {
                     game: ctx.game,
                     start: state,
                     ball_prediction: &ctx.ball_prediction.hacky_expensive_slice(duration),
+                    cost: ctx.cost,
+                    deadline: ctx.deadline,
+                    boost: ctx.boost,
                 };
                 Self::expand_round(&*planner, &ctx, dump, sink)
             }
@@ -405,13 +611,42 @@ pub enum SegmentRunAction {
 #[cfg(test)]
 mod tests {
     use crate::{
-        routing::models::{CarState, PlanningContext, PlanningDump, RoutePlanner},
+        helpers::ball::BallTrajectory,
+        routing::{
+            models::{CarState, PlanCacheKey, PlanningContext, PlanningDump, RoutePlanner},
+            plan::GroundIntercept,
+        },
         strategy::Scenario,
     };
     use common::prelude::*;
     use nalgebra::{Point3, UnitComplex, Vector3};
     use std::{f32::consts::PI, mem};
 
+    #[test]
+    fn plan_cache_key() {
+        let car = |x: f32| CarState {
+            loc: Point3::new(x, -4608.0, 17.01),
+            rot: UnitComplex::new(PI / 2.0).around_z_axis(),
+            vel: Vector3::zeros(),
+            boost: 33.0,
+        };
+        let ball = BallTrajectory::from_samples(vec![(
+            0.0,
+            Point3::new(0.0, 0.0, 92.74),
+            Vector3::zeros(),
+        )]);
+        let key = |planner: &dyn RoutePlanner, x: f32| {
+            PlanCacheKey::new(planner, &car(x), &ball, Default::default())
+        };
+
+        let planner = GroundIntercept::new();
+        assert!(key(&planner, 0.0).is_some());
+        assert_eq!(key(&planner, 0.0), key(&planner, 1.0));
+        assert_ne!(key(&planner, 0.0), key(&planner, 100.0));
+        let dodging = GroundIntercept::new().allow_dodging(true);
+        assert_ne!(key(&planner, 0.0), key(&dodging, 0.0));
+    }
+
     #[test]
     #[ignore(note = "Use this as needed to debug a plan.")]
     fn debug_plan() {
@@ -429,13 +664,16 @@ mod tests {
                 boost: 33.0,
             },
             ball_prediction: &ball_prediction,
+            cost: Default::default(),
+            deadline: None,
+            boost: None,
         };
         let mut log = Vec::new();
         let mut dump = PlanningDump { log: &mut log };
         planner
             .plan(&ctx, &mut dump)
             .unwrap()
-            .provisional_expand(&scenario)
+            .provisional_expand(&scenario, None, None)
             .ok()
             .unwrap();
     }
@@ -0,0 +1,77 @@
+use crate::{
+    helpers::landing::predict_landing,
+    routing::{
+        models::{
+            PlanningContext, PlanningDump, RoutePlan, RoutePlanError, RoutePlanner, SegmentPlan,
+        },
+        segments::Fall,
+    },
+};
+use common::Time;
+use derive_new::new;
+use nameof::name_of_type;
+
+/// Start a ground route while we're still in the air. The first segment rides
+/// out the current ballistic trajectory, and `next` plans the rest from the
+/// landing spot.
+#[derive(Clone, new)]
+pub struct AirborneStart {
+    next: Box<dyn RoutePlanner>,
+}
+
+impl AirborneStart {
+    /// Don't plan around landings this far out. A lot can happen before then.
+    const MAX_FALL_TIME: f32 = 2.0;
+}
+
+impl RoutePlanner for AirborneStart {
+    fn name(&self) -> &'static str {
+        name_of_type!(AirborneStart)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+
+        let landing = predict_landing(
+            ctx.game.pitch(),
+            ctx.game.mutators().gravity,
+            ctx.start.loc,
+            ctx.start.vel,
+        )
+        .ok_or_else(|| RoutePlanError::MustBeOnFlatGround)?;
+        dump.log_pretty(self, "landing loc", landing.loc);
+        dump.log_pretty(self, "landing time", Time(landing.time));
+
+        if !landing.is_on_ground() {
+            dump.log(self, "we're going to land on a wall");
+            return Err(RoutePlanError::MustBeOnFlatGround);
+        }
+        if landing.time >= Self::MAX_FALL_TIME {
+            dump.log(self, "the landing is too far away");
+            return Err(RoutePlanError::MustBeOnFlatGround);
+        }
+
+        let fall = Fall::new(ctx.start.clone(), &landing);
+
+        // Make sure the rest of the route can be planned from where we'll land.
+        // It will be planned again for real once we're on the ground.
+        let landing_ctx = PlanningContext {
+            game: ctx.game,
+            start: fall.end(),
+            ball_prediction: &ctx.ball_prediction.hacky_expensive_slice(fall.duration()),
+            cost: ctx.cost,
+            deadline: ctx.deadline,
+            boost: ctx.boost,
+        };
+        self.next.plan(&landing_ctx, dump)?;
+
+        Ok(RoutePlan {
+            segment: Box::new(fall),
+            next: Some(self.next.clone()),
+        })
+    }
+}
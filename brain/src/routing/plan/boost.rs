@@ -82,6 +82,12 @@ impl RoutePlanner for GetDollar {
             RoutePlanError::MustBeOnFlatGround,
         );
 
+        // Pads aren't worth a detour if boost never runs out.
+        if !ctx.game.mutators().boost_pads_matter() {
+            dump.log(self, "boost pads don't matter; skipping the detour");
+            return GroundDrive::new(self.destination_hint).plan(ctx, dump);
+        }
+
         let pickup = match self.chooose_pickup(ctx, self.destination_hint) {
             Some(p) => p,
             None => return Err(RoutePlanError::OtherError("no pickup found")),
@@ -171,7 +177,7 @@ impl GetDollar {
         }
 
         Self::choose_pickup(
-            ctx.game.boost_dollars().iter(),
+            (ctx.game.boost_dollars().iter()).filter(|pickup| ctx.pad_up_in_time(pickup.loc)),
             &CarState2D {
                 loc: ctx.start.loc_2d(),
                 rot: ctx.start.rot_2d(),
@@ -0,0 +1,202 @@
+use crate::{
+    routing::{
+        models::{
+            PlanningContext, PlanningDump, ProvisionalPlanExpansion, RoutePlan, RoutePlanError,
+            RoutePlanner,
+        },
+        plan::{ground_drive::GroundDrive, higher_order::ChainedPlanner},
+    },
+    strategy::BoostPickup,
+};
+use common::{prelude::*, Time};
+use nalgebra::Point2;
+use nameof::name_of_type;
+use ordered_float::NotNan;
+
+/// Follow `inner`, but swing through a boost pad on the way if we're low on
+/// boost and the detour barely costs any time.
+#[derive(Clone)]
+pub struct BoostAwarePlanner {
+    inner: Box<dyn RoutePlanner>,
+}
+
+impl BoostAwarePlanner {
+    /// Only go out of our way for boost when we have less than this.
+    const LOW_BOOST: f32 = 50.0;
+    /// The most time a detour through a big pad may add to the route.
+    const MAX_DOLLAR_DETOUR: f32 = 0.5;
+    /// The most time a detour through a small pad may add to the route.
+    const MAX_PENNY_DETOUR: f32 = 0.15;
+    /// Only consider pads this close to the straight line to the destination.
+    const MAX_ORTHO_DIST: f32 = 750.0;
+    /// Ignore pads we're basically sitting on. If we haven't picked it up, it
+    /// isn't there.
+    const MIN_ALONG_DIST: f32 = 250.0;
+    /// Each detour is a full plan, so only try the few closest pads.
+    const MAX_CANDIDATES: usize = 3;
+
+    pub fn new(inner: impl RoutePlanner + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+        }
+    }
+}
+
+impl RoutePlanner for BoostAwarePlanner {
+    fn name(&self) -> &'static str {
+        name_of_type!(BoostAwarePlanner)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        dump.log_start(self, &ctx.start);
+
+        let direct = self.inner.plan(ctx, dump)?;
+        if ctx.start.boost >= Self::LOW_BOOST || !ctx.game.mutators().boost_pads_matter() {
+            return Ok(direct);
+        }
+
+        let (direct_duration, end_loc) = match expand(ctx, &direct) {
+            Some(x) => x,
+            None => return Ok(direct),
+        };
+
+        let dollars = ctx.game.boost_dollars().iter();
+        let pennies = ctx.game.boost_pennies().iter();
+        let pads = dollars
+            .map(|p| (p, Self::MAX_DOLLAR_DETOUR))
+            .chain(pennies.map(|p| (p, Self::MAX_PENNY_DETOUR)))
+            .filter(|(p, _)| ctx.pad_up_in_time(p.loc));
+
+        let mut best: Option<(RoutePlan, f32)> = None;
+        for (pickup, max_detour) in candidates(pads, ctx.start.loc_2d(), end_loc) {
+            let planner = ChainedPlanner::new(
+                Box::new(GroundDrive::new(pickup.loc)),
+                Some(self.inner.clone()),
+            );
+            let plan = match planner.plan(ctx, dump) {
+                Ok(plan) => plan,
+                Err(_) => continue,
+            };
+            let detour = match expand(ctx, &plan) {
+                Some((duration, _)) => duration - direct_duration,
+                None => continue,
+            };
+            dump.log_pretty(self, "pickup loc", pickup.loc);
+            dump.log_pretty(self, "detour", Time(detour));
+            if detour >= max_detour {
+                continue;
+            }
+
+            // A big pad is worth more of a detour, so compare detours relative
+            // to what each pad allows.
+            let score = detour / max_detour;
+            if best.as_ref().map(|&(_, s)| score < s).unwrap_or(true) {
+                best = Some((plan, score));
+            }
+        }
+
+        match best {
+            Some((plan, _)) => Ok(plan),
+            None => Ok(direct),
+        }
+    }
+}
+
+/// Returns the total duration of `plan` and where it ends up.
+fn expand(ctx: &PlanningContext<'_, '_>, plan: &RoutePlan) -> Option<(f32, Point2<f32>)> {
+    let tail = plan
+        .provisional_expand_2(
+            ctx.game,
+            ctx.ball_prediction,
+            ctx.cost,
+            ctx.boost,
+            ctx.deadline,
+        )
+        .ok()?;
+    let expansion = ProvisionalPlanExpansion::new(&*plan.segment, &tail);
+    let end = expansion.iter().last()?.end();
+    Some((expansion.duration(), end.loc.to_2d()))
+}
+
+/// The pads close enough to the line from `start_loc` to `end_loc` to be worth
+/// planning a detour through, closest to the line first.
+fn candidates<'a, T>(
+    pads: impl IntoIterator<Item = (&'a BoostPickup, T)>,
+    start_loc: Point2<f32>,
+    end_loc: Point2<f32>,
+) -> Vec<(&'a BoostPickup, T)> {
+    let span = end_loc - start_loc;
+    let length = span.norm();
+    if length < BoostAwarePlanner::MIN_ALONG_DIST {
+        return Vec::new();
+    }
+    let axis = span.to_axis();
+    let ortho = axis.ortho();
+
+    let mut result: Vec<_> = pads
+        .into_iter()
+        .filter(|(pickup, _)| {
+            let along_dist = (pickup.loc - start_loc).dot(&axis);
+            let ortho_dist = (pickup.loc - start_loc).dot(&ortho).abs();
+            along_dist >= BoostAwarePlanner::MIN_ALONG_DIST
+                && along_dist < length
+                && ortho_dist < BoostAwarePlanner::MAX_ORTHO_DIST
+        })
+        .collect();
+    result.sort_by_key(|(pickup, _)| {
+        NotNan::new((pickup.loc - start_loc).dot(&ortho).abs()).unwrap()
+    });
+    result.truncate(BoostAwarePlanner::MAX_CANDIDATES);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{routing::plan::boost_aware::candidates, strategy::BoostPickup};
+    use nalgebra::Point2;
+
+    fn pickup(x: f32, y: f32) -> BoostPickup {
+        BoostPickup {
+            loc: Point2::new(x, y),
+        }
+    }
+
+    #[test]
+    fn only_pads_along_the_way() {
+        let pads = [
+            pickup(100.0, 2000.0),
+            pickup(3000.0, 2000.0),
+            pickup(0.0, -1000.0),
+            pickup(0.0, 6000.0),
+            pickup(0.0, 100.0),
+        ];
+        let result = candidates(
+            pads.iter().map(|p| (p, ())),
+            Point2::new(0.0, 0.0),
+            Point2::new(0.0, 4000.0),
+        );
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0.loc, Point2::new(100.0, 2000.0));
+    }
+
+    #[test]
+    fn closest_to_the_line_first() {
+        let pads = [
+            pickup(600.0, 1000.0),
+            pickup(-200.0, 2000.0),
+            pickup(400.0, 3000.0),
+            pickup(-50.0, 3500.0),
+        ];
+        let result = candidates(
+            pads.iter().map(|p| (p, ())),
+            Point2::new(0.0, 0.0),
+            Point2::new(0.0, 4000.0),
+        );
+        let xs: Vec<_> = result.iter().map(|(p, _)| p.loc.x).collect();
+        assert_eq!(xs, vec![-50.0, -200.0, 400.0]);
+    }
+}
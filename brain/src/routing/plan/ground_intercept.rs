@@ -1,8 +1,8 @@
 use crate::{
-    behavior::strike::GroundedHit,
     helpers::{
         ball::{BallFrame, BallTrajectory},
         intercept::{naive_ground_intercept_2, naive_intercept_penalty},
+        intercept_predicate::{BelowGroundedReach, InterceptPredicate},
     },
     routing::{
         models::{
@@ -16,8 +16,9 @@ use crate::{
         segments::StraightMode,
     },
 };
-use common::{prelude::*, Time};
+use common::{prelude::*, rl, Time};
 use derive_new::new;
+use nalgebra::Point2;
 use nameof::name_of_type;
 
 #[derive(Clone, new)]
@@ -26,7 +27,7 @@ pub struct GroundIntercept {
     allow_dodging: GroundInterceptAllowDodging,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum GroundInterceptAllowDodging {
     Yes,
     No,
@@ -49,6 +50,10 @@ impl RoutePlanner for GroundIntercept {
         name_of_type!(GroundIntercept)
     }
 
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{}({:?})", self.name(), self.allow_dodging))
+    }
+
     fn plan(
         &self,
         ctx: &PlanningContext<'_, '_>,
@@ -78,13 +83,21 @@ impl RoutePlanner for GroundIntercept {
             return Err(RoutePlanError::TurningRadiusTooTight);
         }
 
+        let target_loc = match Self::wall_clearance(ctx.game.field_max_x(), guess.loc.to_2d()) {
+            Some(loc) => loc,
+            None => {
+                dump.log(self, "the ball is up against the wall");
+                return Err(RoutePlanError::InterceptHugsWall);
+            }
+        };
+
         let reverse_angle_hint =
-            if guess.loc.to_2d().y.signum() == ctx.game.own_goal().center_2d.y.signum() {
-                guess.loc.to_2d() - ctx.game.own_goal().center_2d
+            if target_loc.y.signum() == ctx.game.own_goal().center_2d.y.signum() {
+                target_loc - ctx.game.own_goal().center_2d
             } else {
-                ctx.game.enemy_goal().center_2d - guess.loc.to_2d()
+                ctx.game.enemy_goal().center_2d - target_loc
             };
-        let turn = TurnPlanner::new(guess.loc.to_2d(), None)
+        let turn = TurnPlanner::new(target_loc, None)
             .reverse_angle_hint(reverse_angle_hint.to_axis())
             .plan(ctx, dump)?;
 
@@ -107,7 +120,7 @@ impl RoutePlanner for GroundIntercept {
                     && car_speed_towards_ball * 1.1 < ctx.start.vel_2d().norm()
             }
         };
-        let straight = GroundStraightPlanner::new(guess.loc.to_2d(), StraightMode::Fake)
+        let straight = GroundStraightPlanner::new(target_loc, StraightMode::Fake)
             .target_time(straight_time)
             .end_chop(0.5)
             .allow_dodging(allow_dodging);
@@ -117,15 +130,59 @@ impl RoutePlanner for GroundIntercept {
 }
 
 impl GroundIntercept {
+    /// Keep the car's center at least this far from the side walls, so the
+    /// whole car stays on the floor.
+    const WALL_CLEARANCE: f32 = rl::OCTANE_HITBOX_WIDTH;
+    /// If the ball is further than this past the clearance line, we'd only be
+    /// able to reach it from the wall.
+    const MAX_WALL_OVERLAP: f32 = 40.0;
+
+    /// Clamp the approach so we don't plan to drive partially inside a side
+    /// wall. Returns `None` if the ball is far enough up the wall that only a
+    /// wall hit will reach it.
+    fn wall_clearance(field_max_x: f32, loc: Point2<f32>) -> Option<Point2<f32>> {
+        let max_x = field_max_x - Self::WALL_CLEARANCE;
+        let overlap = loc.x.abs() - max_x;
+        if overlap <= 0.0 {
+            Some(loc)
+        } else if overlap < Self::MAX_WALL_OVERLAP {
+            Some(Point2::new(max_x * loc.x.signum(), loc.y))
+        } else {
+            None
+        }
+    }
+
     pub fn calc_intercept<'ball>(
         start: &CarState,
         ball_prediction: &'ball BallTrajectory,
     ) -> Option<&'ball BallFrame> {
         let intercept = naive_ground_intercept_2(start, ball_prediction, |ball| {
-            ball.loc.z < GroundedHit::MAX_BALL_Z
+            BelowGroundedReach.matches(ball)
         })?;
         let intercept = ball_prediction.at_time(intercept.time).unwrap();
         let penalty = naive_intercept_penalty(start, intercept);
         Some(ball_prediction.at_time_or_last(intercept.t + penalty))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::routing::plan::GroundIntercept;
+    use common::rl;
+    use nalgebra::Point2;
+
+    #[test]
+    fn wall_clearance() {
+        let max_x = rl::FIELD_MAX_X;
+        let open = Point2::new(3000.0, 1000.0);
+        assert_eq!(GroundIntercept::wall_clearance(max_x, open), Some(open));
+
+        let close = Point2::new(-4030.0, 1000.0);
+        let clamped = GroundIntercept::wall_clearance(max_x, close).unwrap();
+        assert_eq!(clamped.x, -(max_x - rl::OCTANE_HITBOX_WIDTH));
+        assert_eq!(clamped.y, 1000.0);
+
+        let on_wall = Point2::new(4070.0, 1000.0);
+        assert_eq!(GroundIntercept::wall_clearance(max_x, on_wall), None);
+    }
+}
@@ -39,8 +39,10 @@ impl RoutePlanner for GroundJumpAndDodge {
 
         dump.log_pretty(self, "direction", direction);
 
+        let segment = JumpAndDodge::new(ctx.start.clone(), direction)
+            .hang_time_scale(ctx.game.mutators().hang_time_scale());
         Ok(RoutePlan {
-            segment: Box::new(JumpAndDodge::new(ctx.start.clone(), direction)),
+            segment: Box::new(segment),
             next: None,
         })
     }
@@ -93,16 +93,43 @@ impl RoutePlanner for GroundStraightPlanner {
             recover_target_loc: self.target_loc,
         });
 
+        // The plan accounts for flips, but once we're in the air we can't slow
+        // down, so only allow them when there's no particular time to arrive.
+        let allow_flip = self.allow_dodging && self.target_time.is_none();
+
+        // Short on time? Settle for the plain straight, the cheapest plan that
+        // still gets there, instead of searching for a better one.
+        let thorough = !ctx.out_of_time();
+        if !thorough {
+            dump.log(self, "out of time; skipping coast and dodge");
+        }
+
         let straight = StraightSimple::new(
             self.target_loc,
             self.target_time,
             self.end_chop,
             self.mode,
             self.allow_boost,
+            allow_flip,
         );
         let straight = straight.plan(ctx, dump);
 
-        let dodge = if self.allow_dodging {
+        // If boost costs something, see whether it's worth spending.
+        let coast = if thorough && self.allow_boost && ctx.cost.boost > 0.0 {
+            let planner = StraightSimple::new(
+                self.target_loc,
+                self.target_time,
+                self.end_chop,
+                self.mode,
+                false,
+                allow_flip,
+            );
+            Some(planner.plan(ctx, dump))
+        } else {
+            None
+        };
+
+        let dodge = if thorough && self.allow_dodging {
             let planner =
                 StraightWithDodge::new(self.target_loc, self.target_time, self.end_chop, self.mode);
             Some(planner.plan(ctx, dump))
@@ -110,6 +137,14 @@ impl RoutePlanner for GroundStraightPlanner {
             None
         };
 
+        // Long way to go on an empty tank? Try flipping the whole way.
+        let flips = if thorough && allow_flip && StraightWithFlips::worth_trying(self, ctx) {
+            let planner = StraightWithFlips::new(self.target_loc, self.end_chop);
+            Some(planner.plan(ctx, dump))
+        } else {
+            None
+        };
+
         // If we're prioritizing dodges and we have a dodge, return early.
         if self.always_prefer_dodge {
             if let Some(Ok(plan)) = dodge {
@@ -117,13 +152,19 @@ impl RoutePlanner for GroundStraightPlanner {
             }
         }
 
-        // Return the fastest plan.
+        // Return the cheapest plan.
         let mut plans = ArrayVec::<[_; 4]>::new();
         plans.push(straight);
+        if let Some(coast) = coast {
+            plans.push(coast);
+        }
         if let Some(dodge) = dodge {
             plans.push(dodge);
         }
-        Ok(fastest(at_least_one_ok(plans)?))
+        if let Some(flips) = flips {
+            plans.push(flips);
+        }
+        Ok(cheapest(ctx, at_least_one_ok(plans)?))
     }
 }
 
@@ -143,10 +184,14 @@ fn at_least_one_ok<T, E>(results: impl IntoIterator<Item = Result<T, E>>) -> Res
     }
 }
 
-fn fastest(steps: impl IntoIterator<Item = RoutePlan>) -> RoutePlan {
+fn cheapest(
+    ctx: &PlanningContext<'_, '_>,
+    steps: impl IntoIterator<Item = RoutePlan>,
+) -> RoutePlan {
+    let own_goal = ctx.game.own_goal();
     steps
         .into_iter()
-        .min_by_key(|s| NotNan::new(s.segment.duration()).unwrap())
+        .min_by_key(|s| NotNan::new(ctx.cost.evaluate(&*s.segment, own_goal)).unwrap())
         .unwrap()
 }
 
@@ -160,6 +205,8 @@ struct StraightSimple {
     end_chop: f32,
     mode: StraightMode,
     allow_boost: bool,
+    /// Whether the segment may front flip on a long enough straightaway.
+    allow_flip: bool,
 }
 
 impl RoutePlanner for StraightSimple {
@@ -202,6 +249,7 @@ impl RoutePlanner for StraightSimple {
         }
 
         let segment = Straight::new(
+            ctx.game.pitch(),
             CarState2D {
                 loc: ctx.start.loc.to_2d(),
                 rot: ctx.start.rot.to_2d(),
@@ -212,7 +260,8 @@ impl RoutePlanner for StraightSimple {
             self.end_chop,
             self.mode,
             self.allow_boost,
-        );
+        )
+        .allow_flip(self.allow_flip);
         Ok(RoutePlan {
             segment: Box::new(segment),
             next: None,
@@ -284,6 +333,7 @@ impl RoutePlanner for StraightWithDodge {
             .ok_or(RoutePlanError::MovingTooFast)?;
 
         let before = Straight::new(
+            ctx.game.pitch(),
             CarState2D {
                 loc: ctx.start.loc.to_2d(),
                 rot: ctx.start.rot.to_2d(),
@@ -311,6 +361,77 @@ impl RoutePlanner for StraightWithDodge {
     }
 }
 
+/// Cover a long straightaway by chaining front flips instead of boosting. The
+/// segment is a `Straight` that doesn't boost, and its runner does the
+/// flipping.
+#[derive(Clone, new)]
+pub struct StraightWithFlips {
+    target_loc: Point2<f32>,
+    /// How early to return from the SegmentRunner.
+    end_chop: f32,
+}
+
+impl StraightWithFlips {
+    /// Shorter than this, there's barely room for one flip.
+    const MIN_DIST: f32 = 3000.0;
+    /// With more boost than this, boosting is the better way to get there.
+    const MAX_BOOST: f32 = 20.0;
+
+    fn worth_trying(planner: &GroundStraightPlanner, ctx: &PlanningContext<'_, '_>) -> bool {
+        let asap = match planner.mode {
+            StraightMode::Asap => true,
+            StraightMode::Fake => false,
+        };
+        let dist = (planner.target_loc - ctx.start.loc.to_2d()).norm();
+        asap && dist >= Self::MIN_DIST && ctx.start.boost < Self::MAX_BOOST
+    }
+}
+
+impl RoutePlanner for StraightWithFlips {
+    fn name(&self) -> &'static str {
+        name_of_type!(StraightWithFlips)
+    }
+
+    fn plan(
+        &self,
+        ctx: &PlanningContext<'_, '_>,
+        _dump: &mut PlanningDump<'_>,
+    ) -> Result<RoutePlan, RoutePlanError> {
+        guard!(
+            ctx.start,
+            NotOnFlatGround,
+            RoutePlanError::MustBeOnFlatGround,
+        );
+        guard!(ctx.start, IsSkidding, RoutePlanError::MustNotBeSkidding {
+            recover_target_loc: self.target_loc,
+        });
+        guard!(
+            ctx.start,
+            NotFacingTarget2D::new(self.target_loc),
+            RoutePlanError::MustBeFacingTarget,
+        );
+
+        let segment = Straight::new(
+            ctx.game.pitch(),
+            CarState2D {
+                loc: ctx.start.loc.to_2d(),
+                rot: ctx.start.rot.to_2d(),
+                vel: ctx.start.vel.to_2d(),
+                boost: ctx.start.boost,
+            },
+            self.target_loc,
+            self.end_chop,
+            StraightMode::Asap,
+            false,
+        )
+        .allow_flip(true);
+        Ok(RoutePlan {
+            segment: Box::new(segment),
+            next: None,
+        })
+    }
+}
+
 /// Calculate motions consisting of straight, then dodge, then straight again.
 #[derive(new)]
 struct StraightDodgeCalculator {
@@ -428,6 +549,7 @@ mod integration_tests {
 
     #[test]
     fn brake_when_going_too_fast() {
+        tags!(Routing);
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(1000.0, 0.0, 0.0),
@@ -16,7 +16,7 @@ use common::{physics::CAR_LOCAL_FORWARD_AXIS_2D, prelude::*, rl};
 use derive_new::new;
 use nalgebra::{Point2, Unit, Vector2};
 use nameof::name_of_type;
-use simulate::linear_interpolate;
+use simulate::{car_turn, linear_interpolate};
 use std::f32::consts::PI;
 use vec_box::vec_box;
 
@@ -61,7 +61,16 @@ impl RoutePlanner for TurnPlanner {
             PathingUnawareTurnPlanner::new(self.target_face, self.reverse_angle_hint);
         let turn = pathing_unaware_planner.plan(ctx, dump)?;
         dump.log_plan(self, &turn);
-        let plan =
+        let goal_mouth_divert = pathing::avoid_crossing_goal_mouth(
+            ctx.game.own_goal(),
+            ctx.ball_prediction,
+            turn.segment.end().loc_2d(),
+            self.target_face,
+        );
+        let plan = if let Some(divert) = goal_mouth_divert {
+            dump.log(self, "diverting due to avoid_crossing_goal_mouth");
+            ChainedPlanner::new(divert, Some(Box::new(pathing_unaware_planner))).plan(ctx, dump)?
+        } else {
             match pathing::avoid_plowing_into_goal_wall(&turn.segment.end(), self.target_face) {
                 None => turn,
                 Some(divert) => {
@@ -69,7 +78,8 @@ impl RoutePlanner for TurnPlanner {
                     ChainedPlanner::new(divert, Some(Box::new(pathing_unaware_planner)))
                         .plan(ctx, dump)?
                 }
-            };
+            }
+        };
         Ok(ChainedPlanner::join_planner(plan, self.next.clone()))
     }
 }
@@ -179,8 +189,7 @@ impl RoutePlanner for SimpleTurnPlanner {
 
         let start = ctx.start.flatten(&Flattener::identity());
 
-        let turn_radius =
-            1.0 / chip::max_curvature(ctx.start.vel.norm().max(SLOWEST_TURNING_SPEED));
+        let turn_radius = car_turn::turn_radius(ctx.start.vel.norm().max(SLOWEST_TURNING_SPEED));
         let turn = match calculate_circle_turn(&start, turn_radius, self.target_loc)? {
             Some(x) => x,
             None => {
@@ -234,8 +243,7 @@ impl RoutePlanner for ArcTowards {
 
         let start = ctx.start.flatten(&Flattener::identity());
 
-        let turn_radius =
-            1.0 / chip::max_curvature(ctx.start.vel.norm().max(SLOWEST_TURNING_SPEED));
+        let turn_radius = car_turn::turn_radius(ctx.start.vel.norm().max(SLOWEST_TURNING_SPEED));
         let turn = match calculate_circle_turn(&start, turn_radius, self.target_loc)? {
             Some(x) => x,
             None => {
@@ -332,6 +340,7 @@ mod integration_tests {
     #[test]
     #[ignore("this is a demo, not a test")]
     fn powerslide_angle_hint_hack() {
+        tags!(Routing);
         TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3630.1199, 2566.14, 92.71),
@@ -1,7 +1,14 @@
 pub use self::{
-    boost::GetDollar, ground_drive::GroundDrive, ground_intercept::GroundIntercept,
-    ground_straight::GroundStraightPlanner, ground_turn::TurnPlanner, higher_order::ChainedPlanner,
-    pathing::avoid_goal_wall_waypoint, wall_intercept::WallIntercept,
+    airborne::AirborneStart,
+    boost::GetDollar,
+    boost_aware::BoostAwarePlanner,
+    ground_drive::GroundDrive,
+    ground_intercept::GroundIntercept,
+    ground_straight::{GroundStraightPlanner, StraightWithFlips},
+    ground_turn::TurnPlanner,
+    higher_order::ChainedPlanner,
+    pathing::{avoid_goal_wall_waypoint, drive_through_goal},
+    wall_intercept::WallIntercept,
 };
 
 macro_rules! guard {
@@ -12,7 +19,9 @@ macro_rules! guard {
     };
 }
 
+mod airborne;
 mod boost;
+mod boost_aware;
 mod ground_drive;
 mod ground_intercept;
 mod ground_jump_and_dodge;
@@ -1,13 +1,17 @@
-use crate::routing::{
-    models::{CarState, RoutePlanner},
-    plan::{
-        ground_straight::GroundStraightPlanner, ground_turn::PathingUnawareTurnPlanner,
-        higher_order::ChainedPlanner,
+use crate::{
+    helpers::ball::BallTrajectory,
+    routing::{
+        models::{CarState, RoutePlanner},
+        plan::{
+            ground_drive::GroundDrive, ground_straight::GroundStraightPlanner,
+            ground_turn::PathingUnawareTurnPlanner, higher_order::ChainedPlanner,
+        },
+        segments::StraightMode,
     },
-    segments::StraightMode,
+    strategy::{Goal, DFH_STADIUM},
 };
 use common::{physics, prelude::*, rl};
-use nalgebra::Point2;
+use nalgebra::{clamp, Point2, Vector2};
 
 /// Calculate whether driving straight to `target_loc` would intersect the goal
 /// wall. If so, return the route we should follow to get outside the goal.
@@ -63,3 +67,253 @@ pub fn avoid_goal_wall_waypoint(start: &CarState, target_loc: Point2<f32>) -> Op
         None
     }
 }
+
+/// How far in front of the goal line the goal mouth extends.
+const GOAL_MOUTH_DEPTH: f32 = 1500.0;
+/// Shots that arrive later than this are not threatening yet.
+const SHOT_HORIZON: f32 = 3.0;
+/// Paths that end this close to the shooting lane are trying to block it, not
+/// cross it.
+const SHOOTING_LANE_HALF_WIDTH: f32 = rl::BALL_RADIUS + rl::OCTANE_HITBOX_WIDTH / 2.0;
+
+/// Calculate whether driving straight from `start_loc` to `target_loc` would
+/// cut across the shooting lane in front of `goal` while a shot is on the way.
+/// If so, return the route we should follow to get around it without screening
+/// ourselves.
+pub fn avoid_crossing_goal_mouth(
+    goal: &Goal,
+    ball_prediction: &BallTrajectory,
+    start_loc: Point2<f32>,
+    target_loc: Point2<f32>,
+) -> Option<Box<dyn RoutePlanner>> {
+    let waypoint = goal_mouth_waypoint(goal, ball_prediction, start_loc, target_loc)?;
+    Some(Box::new(ChainedPlanner::chain(vec![
+        Box::new(PathingUnawareTurnPlanner::new(waypoint, None)),
+        Box::new(GroundStraightPlanner::new(waypoint, StraightMode::Asap)),
+    ])))
+}
+
+/// Calculate whether driving straight from `start_loc` to `target_loc` would
+/// cut across the shooting lane in front of `goal`. If so, return a waypoint
+/// to drive to first, either behind the goal line or upfield around the ball,
+/// whichever is shorter.
+pub fn goal_mouth_waypoint(
+    goal: &Goal,
+    ball_prediction: &BallTrajectory,
+    start_loc: Point2<f32>,
+    target_loc: Point2<f32>,
+) -> Option<Point2<f32>> {
+    let ball = ball_prediction.start();
+    let shot = ball_prediction
+        .iter()
+        .take_while(|b| b.t - ball.t < SHOT_HORIZON)
+        .find(|b| goal.ball_is_scored(b.loc))?;
+
+    let ball_loc = ball.loc.to_2d();
+    let shot_loc = shot.loc.to_2d();
+    let lane_axis = (shot_loc - ball_loc).to_axis();
+    let lane_normal = lane_axis.ortho();
+
+    // Only proceed if we're crossing from one side of the lane to the other.
+    let start_side = (start_loc - ball_loc).dot(&lane_normal);
+    let target_side = (target_loc - ball_loc).dot(&lane_normal);
+    if start_side.signum() == target_side.signum()
+        || start_side.abs() < SHOOTING_LANE_HALF_WIDTH
+        || target_side.abs() < SHOOTING_LANE_HALF_WIDTH
+    {
+        return None;
+    }
+
+    // Only proceed if the crossing is between the ball and the goal, and close
+    // enough to the goal to matter.
+    let crossing = start_loc + (target_loc - start_loc) * (start_side / (start_side - target_side));
+    let along_lane = (crossing - ball_loc).dot(&lane_axis);
+    if along_lane < 0.0 || along_lane > (shot_loc - ball_loc).norm() {
+        return None;
+    }
+    if !goal.is_y_within_range(crossing.y, ..GOAL_MOUTH_DEPTH) {
+        return None;
+    }
+
+    let margin = 250.0;
+    let behind_goal = Point2::new(
+        clamp(shot_loc.x, -goal.max_x + margin, goal.max_x - margin),
+        goal.center_2d.y - goal.normal_2d.y * margin,
+    );
+    let around_ball = ball_loc - lane_axis.into_inner() * margin * 2.0;
+    let detour_len = |p: Point2<f32>| (p - start_loc).norm() + (target_loc - p).norm();
+    if detour_len(behind_goal) <= detour_len(around_ball) {
+        Some(behind_goal)
+    } else {
+        Some(around_ball)
+    }
+}
+
+/// Only go through the goal if it's at most this much longer than driving
+/// across the front of it. We're covering the goal the whole way, so a longer
+/// route is worth it.
+const THROUGH_GOAL_SLACK: f32 = 1000.0;
+
+/// Calculate whether we should get from one side of `goal` to the other by
+/// driving through the goal itself, behind the goal line, instead of across
+/// the goal mouth. If so, return the route to the far side. The route ends at
+/// `target_loc`.
+pub fn drive_through_goal(
+    goal: &Goal,
+    start_loc: Point2<f32>,
+    target_loc: Point2<f32>,
+) -> Option<Box<dyn RoutePlanner>> {
+    let waypoints = through_goal_waypoints(goal, start_loc, target_loc)?;
+    let mut planners = Vec::<Box<dyn RoutePlanner>>::new();
+    for &waypoint in &waypoints {
+        planners.push(Box::new(PathingUnawareTurnPlanner::new(waypoint, None)));
+        // The goal is cramped, so keep the speed down.
+        planners.push(Box::new(
+            GroundStraightPlanner::new(waypoint, StraightMode::Asap).allow_boost(false),
+        ));
+    }
+    // `GroundDrive` knows how to get back out past the far post.
+    planners.push(Box::new(GroundDrive::new(target_loc)));
+    Some(Box::new(ChainedPlanner::chain(planners)))
+}
+
+/// If driving through `goal` is a reasonable way to get from `start_loc` to
+/// `target_loc`, return the waypoints to follow: a point in front of the near
+/// post, then into the goal on the near side, then across to the far side.
+///
+/// This only applies when we start deep in a corner next to the goal and need
+/// to end up on the other side of it.
+pub fn through_goal_waypoints(
+    goal: &Goal,
+    start_loc: Point2<f32>,
+    target_loc: Point2<f32>,
+) -> Option<[Point2<f32>; 3]> {
+    let margin = 150.0;
+    let near_side = (start_loc.x - goal.center_2d.x).signum();
+    let far_side = (target_loc.x - goal.center_2d.x).signum();
+
+    if near_side == far_side {
+        return None;
+    }
+    // Start in the corner, outside the near post.
+    if (start_loc.x - goal.center_2d.x).abs() < goal.max_x
+        || !goal.is_y_within_range(start_loc.y, ..1000.0)
+    {
+        return None;
+    }
+    // End somewhere near the far post.
+    if (target_loc.x - goal.center_2d.x).abs() < goal.max_x * 0.5
+        || !goal.is_y_within_range(target_loc.y, ..GOAL_MOUTH_DEPTH)
+    {
+        return None;
+    }
+
+    let normal = goal.normal_2d.into_inner();
+    let inside = -normal * DFH_STADIUM.goal_depth() * 0.3;
+    let post_x = |side: f32| Vector2::x() * (goal.max_x - margin) * side;
+    let approach = goal.center_2d + post_x(near_side) + normal * margin;
+    let entry = goal.center_2d + post_x(near_side) + inside;
+    let exit = goal.center_2d + post_x(far_side) + inside;
+
+    let through_len = (approach - start_loc).norm()
+        + (entry - approach).norm()
+        + (exit - entry).norm()
+        + (target_loc - exit).norm();
+    if through_len > (target_loc - start_loc).norm() + THROUGH_GOAL_SLACK {
+        return None;
+    }
+    Some([approach, entry, exit])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::ball::BallTrajectory,
+        routing::plan::pathing::{goal_mouth_waypoint, through_goal_waypoints},
+        strategy::SOCCAR_GOAL_BLUE,
+    };
+    use nalgebra::{Point2, Point3, Vector3};
+
+    fn shot_at_blue_goal() -> BallTrajectory {
+        BallTrajectory::from_samples((0..240).map(|i| {
+            let t = i as f32 / 120.0;
+            let vel = Vector3::new(0.0, -2000.0, 0.0);
+            (t, Point3::new(0.0, -2000.0, 93.0) + vel * t, vel)
+        }))
+    }
+
+    #[test]
+    fn detour_around_shooting_lane() {
+        let ball_prediction = shot_at_blue_goal();
+        let goal = &*SOCCAR_GOAL_BLUE;
+
+        // Cutting straight across the goal mouth goes behind the goal line.
+        let waypoint = goal_mouth_waypoint(
+            goal,
+            &ball_prediction,
+            Point2::new(-1500.0, -4500.0),
+            Point2::new(1500.0, -4500.0),
+        )
+        .unwrap();
+        assert!(waypoint.y < goal.center_2d.y);
+
+        // Driving into the lane to block the shot is fine.
+        assert_eq!(
+            goal_mouth_waypoint(
+                goal,
+                &ball_prediction,
+                Point2::new(-1500.0, -4500.0),
+                Point2::new(0.0, -4000.0),
+            ),
+            None,
+        );
+
+        // So is crossing upfield of the ball.
+        assert_eq!(
+            goal_mouth_waypoint(
+                goal,
+                &ball_prediction,
+                Point2::new(-1500.0, -1000.0),
+                Point2::new(1500.0, -1000.0),
+            ),
+            None,
+        );
+    }
+
+    #[test]
+    fn through_goal_from_corner() {
+        let goal = &*SOCCAR_GOAL_BLUE;
+
+        // From one back corner to the other, go through the goal.
+        let waypoints = through_goal_waypoints(
+            goal,
+            Point2::new(-2500.0, -4800.0),
+            Point2::new(goal.max_x, goal.center_2d.y + 100.0),
+        )
+        .unwrap();
+        let [approach, entry, exit] = waypoints;
+        assert!(approach.x < 0.0 && approach.y > goal.center_2d.y);
+        assert!(entry.x < 0.0 && entry.y < goal.center_2d.y);
+        assert!(exit.x > 0.0 && exit.y < goal.center_2d.y);
+
+        // Not when we're upfield,
+        assert_eq!(
+            through_goal_waypoints(
+                goal,
+                Point2::new(-2500.0, -2000.0),
+                Point2::new(goal.max_x, goal.center_2d.y + 100.0),
+            ),
+            None,
+        );
+
+        // or staying on the same side.
+        assert_eq!(
+            through_goal_waypoints(
+                goal,
+                Point2::new(-2500.0, -4800.0),
+                Point2::new(-goal.max_x, goal.center_2d.y + 100.0),
+            ),
+            None,
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    behavior::strike::WallHit,
-    helpers::ball::BallFrame,
+    helpers::{
+        ball::BallFrame,
+        intercept_predicate::{InterceptPredicate, OnSurface},
+    },
     routing::{
         models::{
             PlanningContext, PlanningDump, ProvisionalPlanExpansion, RoutePlan, RoutePlanError,
@@ -14,7 +16,7 @@ use crate::{
     strategy::{Context2, Game, Pitch},
     utils::geometry::{ExtendF32, Plane},
 };
-use common::{prelude::*, Time};
+use common::{prelude::*, rl, Time};
 use nalgebra::Point3;
 use nameof::name_of_type;
 use std::f32::consts::PI;
@@ -93,7 +95,7 @@ impl WallIntercept {
         let mut balls = ctx.ball_prediction.iter();
         let mut fail_reason = None;
         while let Some(ball) = balls.next() {
-            match self.eval_intercept(ctx, ball) {
+            match self.eval_intercept(ctx, ball, !ctx.out_of_time()) {
                 Ok(plan) => return Ok((ball, plan)),
                 Err((skip, reason)) => {
                     if skip == Skip::Yes {
@@ -113,12 +115,16 @@ impl WallIntercept {
         Err(fail_reason.unwrap_or("unknown intercept"))
     }
 
+    /// Plan a route to `ball`. If not `thorough`, skip expanding the rest of
+    /// the route and assume it's a straight line at the speed we come out of
+    /// the turn, which is much cheaper and usually close enough.
     fn eval_intercept(
         &self,
         ctx: &PlanningContext<'_, '_>,
         ball: &BallFrame,
+        thorough: bool,
     ) -> Result<RoutePlan, (Skip, Option<&'static str>)> {
-        if self.eligible_wall(ctx.game.pitch(), ball.loc).is_none() {
+        if self.eligible_wall(ctx.game.pitch(), ball).is_none() {
             return Err((Skip::No, None));
         }
 
@@ -135,7 +141,22 @@ impl WallIntercept {
             Ok((plan, _log)) => plan,
             Err(_) => return Err((Skip::Yes, None)),
         };
-        let tail = match plan.provisional_expand_2(ctx.game, ctx.ball_prediction) {
+        if !thorough {
+            let end = plan.segment.end();
+            let speed = end.vel.norm().max(rl::CAR_NORMAL_SPEED);
+            let straight_time = (ball.loc - end.loc).norm() / speed;
+            if plan.segment.duration() + straight_time >= ball.t {
+                return Err((Skip::No, None));
+            }
+            return Ok(plan);
+        }
+        let tail = match plan.provisional_expand_2(
+            ctx.game,
+            ctx.ball_prediction,
+            ctx.cost,
+            ctx.boost,
+            ctx.deadline,
+        ) {
             Ok(tail) => tail,
             Err(_) => return Err((Skip::Yes, None)),
         };
@@ -151,9 +172,9 @@ impl WallIntercept {
     fn eligible_wall<'pitch>(
         &self,
         pitch: &'pitch Pitch,
-        loc: Point3<f32>,
+        ball: &BallFrame,
     ) -> Option<&'pitch Plane> {
-        let plane = pitch.closest_plane(&loc);
+        let plane = pitch.closest_plane(&ball.loc);
         if self.must_be_wall && plane.normal.z != 0.0 {
             return None;
         }
@@ -163,7 +184,7 @@ impl WallIntercept {
         if self.forbid_goal_walls && plane.normal.y.abs() == 1.0 {
             return None;
         }
-        if plane.distance_to_point(&loc) >= WallHit::MAX_BALL_DISTANCE_FROM_SURFACE {
+        if !OnSurface(plane).matches(ball) {
             return None;
         }
         Some(plane)
@@ -31,11 +31,12 @@ impl RoutePlanner for WallStraightPlanner {
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
         let target_surface = which_surface(ctx.game.pitch(), &self.target_loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
-        let target_to_start = target_surface
-            .unfold(start_surface)
+        let pitch = ctx.game.pitch();
+        let target_to_start = pitch
+            .unfold(target_surface, start_surface, &self.target_loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
-        let start_to_ground = start_surface
-            .unfold(&ctx.game.pitch().ground())
+        let start_to_ground = pitch
+            .unfold(start_surface, pitch.ground(), &ctx.start.loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
         let start_to_2d = Flattener::new(start_to_ground);
         let target_to_2d = Flattener::new(start_to_ground * target_to_start);
@@ -8,6 +8,7 @@ use crate::{
 };
 use nalgebra::Point3;
 use nameof::name_of_type;
+use simulate::car_turn;
 use std::f32::consts::PI;
 
 const SLOWEST_TURNING_SPEED: f32 = 900.0;
@@ -48,11 +49,12 @@ impl RoutePlanner for WallTurnPlanner {
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
         let target_surface = which_surface(ctx.game.pitch(), &self.target_loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
-        let target_to_start = target_surface
-            .unfold(start_surface)
+        let pitch = ctx.game.pitch();
+        let target_to_start = pitch
+            .unfold(target_surface, start_surface, &self.target_loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
-        let start_to_ground = start_surface
-            .unfold(&ctx.game.pitch().ground())
+        let start_to_ground = pitch
+            .unfold(start_surface, pitch.ground(), &ctx.start.loc)
             .map_err(|_| RoutePlanError::CannotOperateWall)?;
         let start_to_2d = Flattener::new(start_to_ground);
         let target_to_2d = Flattener::new(start_to_ground * target_to_start);
@@ -67,7 +69,7 @@ impl RoutePlanner for WallTurnPlanner {
         let start = ctx.start.flatten(&start_to_2d);
         let target_loc = target_to_2d * self.target_loc;
 
-        let turn_radius = 1.0 / chip::max_curvature(start.vel.norm().max(SLOWEST_TURNING_SPEED));
+        let turn_radius = car_turn::turn_radius(start.vel.norm().max(SLOWEST_TURNING_SPEED));
         let turn = match calculate_circle_turn(&start, turn_radius, target_loc)? {
             Some(x) => x,
             None => {
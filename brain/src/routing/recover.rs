@@ -3,12 +3,13 @@ use crate::{
         higher_order::{Chain, Predicate, TimeLimit, TryChoose},
         movement::{DriveTowards, GetToFlatGround, QuickJumpAndDodge, SkidRecover, Yielder},
         offense::ResetBehindBall,
+        strike::WallHit,
     },
     helpers::ball::BallTrajectory,
     routing::{
         behavior::FollowRoute,
         models::{CarState, RoutePlanError},
-        plan::GroundDrive,
+        plan::{GroundDrive, WallIntercept},
         StraightMode,
     },
     strategy::{Behavior, Context, Priority, Scenario},
@@ -72,6 +73,20 @@ impl RoutePlanError {
                 }
                 None
             }
+            RoutePlanError::InterceptHugsWall => {
+                // There's no room to drive beside the ball, so meet it on the
+                // wall instead.
+                Some(Box::new(Chain::new(Priority::Idle, vec_box![
+                    FollowRoute::new(
+                        WallIntercept::new()
+                            .must_be_wall(true)
+                            .must_be_side_wall(true),
+                    )
+                    .same_ball_trajectory(true)
+                    .never_recover(true),
+                    WallHit::new(),
+                ])))
+            }
             RoutePlanError::MovingTooFast
             | RoutePlanError::CannotOperateWall
             | RoutePlanError::NoWallIntercept
@@ -203,6 +218,20 @@ impl Predicate for RoundIsNotActive {
     }
 }
 
+/// True after a goal until the ball is back in play. Unlike
+/// `RoundIsNotActive`, this also works when goal resets are disabled.
+pub struct GoalIsPending;
+
+impl Predicate for GoalIsPending {
+    fn name(&self) -> &str {
+        name_of_type!(GoalIsPending)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        ctx.goal_detector.pending().is_some()
+    }
+}
+
 pub struct MatchIsEnded;
 
 impl Predicate for MatchIsEnded {
@@ -0,0 +1,160 @@
+use crate::{
+    behavior::movement::Land,
+    eeg::{color, Drawable},
+    helpers::landing::Landing,
+    routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
+    strategy::{Action, Behavior, Context},
+};
+use common::{prelude::*, rl};
+use nalgebra::Vector2;
+use nameof::name_of_type;
+
+/// Ride out our current ballistic trajectory until we land on the floor.
+#[derive(Clone)]
+pub struct Fall {
+    start: CarState,
+    end: CarState,
+    duration: f32,
+}
+
+impl Fall {
+    /// Below this horizontal speed, assume we land facing wherever we're facing
+    /// now, instead of along our velocity.
+    const MIN_HEADING_SPEED: f32 = 500.0;
+    /// If landing takes this much longer than predicted, something went wrong.
+    const LATE_TOLERANCE: f32 = 0.5;
+
+    pub fn new(start: CarState, landing: &Landing) -> Self {
+        // Assume `Land` will get our wheels down and the nose pointed along our
+        // velocity. The vertical velocity is absorbed by the suspension.
+        let vel = landing.surface_vel().to_2d();
+        let rot = if vel.norm() >= Self::MIN_HEADING_SPEED {
+            Vector2::x().rotation_to(&vel)
+        } else {
+            start.rot_2d()
+        };
+        let end = CarState {
+            loc: landing.loc.to_2d().to_3d(rl::OCTANE_NEUTRAL_Z),
+            rot: rot.around_z_axis(),
+            vel: vel.to_3d(0.0),
+            boost: start.boost,
+        };
+
+        Self {
+            start,
+            end,
+            duration: landing.time,
+        }
+    }
+}
+
+impl SegmentPlan for Fall {
+    fn name(&self) -> &str {
+        name_of_type!(Fall)
+    }
+
+    fn start(&self) -> CarState {
+        self.start.clone()
+    }
+
+    fn end(&self) -> CarState {
+        self.end.clone()
+    }
+
+    fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    fn run(&self) -> Box<dyn SegmentRunner> {
+        Box::new(Faller::new(self.clone()))
+    }
+
+    fn draw(&self, ctx: &mut Context<'_>) {
+        ctx.eeg.draw(Drawable::Line(
+            self.start.loc_2d(),
+            self.end.loc_2d(),
+            color::PITCH,
+        ));
+        ctx.eeg.draw(Drawable::ghost_car_ground(
+            self.end.loc_2d(),
+            self.end.rot.to_rotation_matrix(),
+        ));
+    }
+}
+
+struct Faller {
+    plan: Fall,
+    /// `Land`, or whatever it handed off to (e.g. a wavedash).
+    behavior: Box<dyn Behavior>,
+    start_time: Option<f32>,
+}
+
+impl Faller {
+    fn new(plan: Fall) -> Self {
+        Self {
+            plan,
+            behavior: Box::new(Land::new()),
+            start_time: None,
+        }
+    }
+}
+
+impl SegmentRunner for Faller {
+    fn name(&self) -> &str {
+        name_of_type!(Faller)
+    }
+
+    fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let start_time = *self.start_time.get_or_insert(now);
+        if now - start_time >= self.plan.duration + Fall::LATE_TOLERANCE {
+            ctx.eeg.log(self.name(), "we should have landed by now");
+            return SegmentRunAction::Failure;
+        }
+
+        // Follow tail calls the way the `Runner` would, but only a few, in case
+        // something keeps handing off without ever doing anything.
+        for _ in 0..5 {
+            match self.behavior.execute(ctx) {
+                Action::Yield(input) => return SegmentRunAction::Yield(input),
+                Action::TailCall(behavior) => {
+                    ctx.eeg.log(self.name(), format!("> {}", behavior.name()));
+                    self.behavior = behavior;
+                }
+                Action::Return => return SegmentRunAction::Success,
+                Action::RootCall(_) | Action::Abort => return SegmentRunAction::Failure,
+            }
+        }
+        ctx.eeg.log(self.name(), "infinite loop?");
+        SegmentRunAction::Failure
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geometry::Plane;
+    use nalgebra::{Point3, UnitQuaternion, Vector3};
+
+    #[test]
+    fn lands_along_velocity() {
+        let start = CarState {
+            loc: Point3::new(0.0, 0.0, 300.0),
+            rot: UnitQuaternion::identity(),
+            vel: Vector3::new(0.0, 1000.0, 200.0),
+            boost: 50.0,
+        };
+        let landing = Landing {
+            time: 1.2,
+            loc: Point3::new(0.0, 1200.0, 17.0),
+            vel: Vector3::new(0.0, 1000.0, -580.0),
+            plane: Plane::point_normal(Point3::origin(), Vector3::z_axis()),
+        };
+        let segment = Fall::new(start, &landing);
+        let end = segment.end();
+        assert_eq!(segment.duration(), 1.2);
+        assert_eq!(end.loc.z, rl::OCTANE_NEUTRAL_Z);
+        assert_eq!(end.vel, Vector3::new(0.0, 1000.0, 0.0));
+        assert!(end.forward_axis_2d().angle_to(&Vector2::y_axis()).abs() < 0.01);
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
 };
 use common::prelude::*;
 use derive_new::new;
+use nalgebra::Point2;
 use nameof::name_of_type;
 use simulate::CarForwardDodge1D;
 
@@ -65,43 +66,48 @@ struct ForwardDodgeRunner {
 
 impl ForwardDodgeRunner {
     pub fn new(plan: ForwardDodge) -> Self {
-        let behavior = Box::new(Chain::new(Priority::Idle, vec![
-            Box::new(Yielder::new(
-                plan.dodge.jump_duration,
-                common::halfway_house::PlayerInput {
-                    Jump: true,
-                    ..Default::default()
-                },
-            )),
-            Box::new(Yielder::new(
-                plan.dodge.wait_duration,
-                common::halfway_house::PlayerInput {
-                    ..Default::default()
-                },
-            )),
-            Box::new(
-                Dodge::new()
-                    .towards(plan.end().loc.to_2d())
-                    .follow_through_time(0.0),
-            ),
-            Box::new(Yielder::new(
-                plan.dodge.dodge_duration - 6.0 / 120.0,
-                common::halfway_house::PlayerInput {
-                    ..Default::default()
-                },
-            )),
-        ]));
+        let behavior = forward_dodge(&plan.dodge, plan.end().loc.to_2d());
         Self { behavior }
     }
 }
 
+/// The inputs for a forward dodge towards `target_loc`, timed to match the
+/// simulation in `dodge`.
+pub(super) fn forward_dodge(
+    dodge: &CarForwardDodge1D,
+    target_loc: Point2<f32>,
+) -> Box<dyn Behavior> {
+    Box::new(Chain::new(Priority::Idle, vec![
+        Box::new(Yielder::new(
+            dodge.jump_duration,
+            common::halfway_house::PlayerInput {
+                Jump: true,
+                ..Default::default()
+            },
+        )),
+        Box::new(Yielder::new(
+            dodge.wait_duration,
+            common::halfway_house::PlayerInput {
+                ..Default::default()
+            },
+        )),
+        Box::new(Dodge::new().towards(target_loc).follow_through_time(0.0)),
+        Box::new(Yielder::new(
+            dodge.dodge_duration - 6.0 / 120.0,
+            common::halfway_house::PlayerInput {
+                ..Default::default()
+            },
+        )),
+    ]))
+}
+
 impl SegmentRunner for ForwardDodgeRunner {
     fn name(&self) -> &str {
         name_of_type!(ForwardDodgeRunner)
     }
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
-        match self.behavior.execute_old(ctx) {
+        match self.behavior.execute(ctx) {
             Action::Yield(i) => SegmentRunAction::Yield(i),
             Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
             Action::RootCall(_) => SegmentRunAction::Failure,
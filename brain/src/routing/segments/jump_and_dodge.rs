@@ -18,6 +18,17 @@ const DODGE_IMPULSE: f32 = 500.0; // This is inaccurate (but it's an exact minim
 pub struct JumpAndDodge {
     start: CarState,
     direction: UnitComplex<f32>,
+    #[new(value = "FLOAT_TIME")]
+    float_time: f32,
+}
+
+impl JumpAndDodge {
+    /// Stretch the time spent floating after the dodge, e.g., under low
+    /// gravity.
+    pub fn hang_time_scale(mut self, scale: f32) -> Self {
+        self.float_time = FLOAT_TIME * scale;
+        self
+    }
 }
 
 impl SegmentPlan for JumpAndDodge {
@@ -38,7 +49,7 @@ impl SegmentPlan for JumpAndDodge {
         let dodge_vel = self.start.vel.to_2d() + impulse;
         let loc = self.start.loc.to_2d()
             + (JUMP_TIME + WAIT_TIME) * self.start.vel.to_2d()
-            + FLOAT_TIME * dodge_vel;
+            + self.float_time * dodge_vel;
         CarState2D {
             loc,
             rot: self.start.rot.to_2d(),
@@ -49,7 +60,7 @@ impl SegmentPlan for JumpAndDodge {
     }
 
     fn duration(&self) -> f32 {
-        JUMP_TIME + WAIT_TIME + FLOAT_TIME
+        JUMP_TIME + WAIT_TIME + self.float_time
     }
 
     fn run(&self) -> Box<dyn SegmentRunner> {
@@ -95,7 +106,7 @@ impl JumpAndDodgeRunner {
                 },
             )),
             Box::new(Yielder::new(
-                FLOAT_TIME - 6.0 / 120.0,
+                plan.float_time - 6.0 / 120.0,
                 common::halfway_house::PlayerInput {
                     ..Default::default()
                 },
@@ -111,7 +122,7 @@ impl SegmentRunner for JumpAndDodgeRunner {
     }
 
     fn execute_old(&mut self, ctx: &mut Context<'_>) -> SegmentRunAction {
-        match self.behavior.execute_old(ctx) {
+        match self.behavior.execute(ctx) {
             Action::Yield(i) => SegmentRunAction::Yield(i),
             Action::TailCall(_) => panic!("TailCall not yet supported in SegmentRunner"),
             Action::RootCall(_) => SegmentRunAction::Failure,
@@ -1,6 +1,7 @@
 pub use crate::routing::segments::{
     brake::Brake,
     chain::Chain,
+    fall::Fall,
     forward_dodge::ForwardDodge,
     jump_and_dodge::JumpAndDodge,
     null::NullSegment,
@@ -14,6 +15,7 @@ pub use crate::routing::segments::{
 
 mod brake;
 mod chain;
+mod fall;
 mod forward_dodge;
 mod jump_and_dodge;
 mod null;
@@ -228,6 +228,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "This is a demo, not a test")]
     fn simple_arc_demo() {
+        tags!(Routing);
         TestRunner::new()
             .scenario(TestScenario {
                 car_loc: Point3::new(1000.0, 0.0, 17.01),
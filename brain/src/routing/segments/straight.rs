@@ -1,13 +1,17 @@
 use crate::{
     behavior::movement::{simple_steer_towards, GetToFlatGround},
     eeg::{color, Drawable},
-    routing::models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
-    strategy::Context,
+    helpers::drive::{supersonic_control, SUPERSONIC_HOLD_SPEED},
+    routing::{
+        models::{CarState, CarState2D, SegmentPlan, SegmentRunAction, SegmentRunner},
+        segments::forward_dodge::forward_dodge,
+    },
+    strategy::{Action, Behavior, Context, Pitch},
 };
-use common::{prelude::*, rl};
+use common::prelude::*;
 use nalgebra::{Point2, Vector2};
 use nameof::name_of_type;
-use simulate::Car1D;
+use simulate::{Car1DWithDodge, CarForwardDodge};
 
 #[derive(Clone)]
 pub struct Straight {
@@ -18,6 +22,17 @@ pub struct Straight {
     duration: f32,
     mode: StraightMode,
     allow_boost: bool,
+    allow_flip: bool,
+    /// What the plan was simulated from, so it can be simulated again if the
+    /// options change. `None` for a zero-length segment.
+    course: Option<Course>,
+}
+
+#[derive(Copy, Clone)]
+struct Course {
+    target_loc: Point2<f32>,
+    surface_dist: f32,
+    end_chop: f32,
 }
 
 /// This is a workaround for the lack of "arrive-at-time" behavior.
@@ -34,7 +49,11 @@ pub enum StraightMode {
 }
 
 impl Straight {
+    /// The simulation runs over the distance along the pitch's surface, so
+    /// paths that climb a wall base or the ramp into a goal take a bit longer
+    /// than their 2D length suggests.
     pub fn new(
+        pitch: &Pitch,
         start: CarState2D,
         end_loc: Point2<f32>,
         end_chop: f32,
@@ -45,27 +64,51 @@ impl Straight {
         if start_to_end_dist < 0.1 {
             return Self::zero(start);
         }
+        let course = Course {
+            target_loc: end_loc,
+            surface_dist: pitch.surface_distance(start.loc, end_loc),
+            end_chop,
+        };
+        Self::simulate(start, course, mode, allow_boost, false)
+    }
+
+    fn simulate(
+        start: CarState2D,
+        course: Course,
+        mode: StraightMode,
+        allow_boost: bool,
+        allow_flip: bool,
+    ) -> Self {
+        let start_to_end_dist = (course.target_loc - start.loc).norm();
 
-        let mut sim = Car1D::new()
-            .with_speed(start.vel.norm())
-            .with_boost(start.boost);
-        sim.advance_by_distance(start_to_end_dist, 1.0, allow_boost);
+        // Flips only happen when we're not boosting, so without flips this is
+        // the same as `Car1D`. Boost is feathered the same way the runner does
+        // it.
+        let runway = if allow_flip { course.surface_dist } else { 0.0 };
+        let new_sim = || {
+            Car1DWithDodge::new()
+                .with_speed(start.vel.norm())
+                .with_boost(start.boost)
+                .with_boost_cap(SUPERSONIC_HOLD_SPEED)
+                .with_runway(runway)
+        };
+        let mut sim = new_sim();
+        sim.advance_by_distance(course.surface_dist, allow_boost);
 
         // end_chop is the caller requesting we end the segment before reaching the
         // target.
-        if end_chop != 0.0 {
-            let duration = (sim.time() - end_chop).max(0.0);
-            sim = Car1D::new()
-                .with_speed(start.vel.norm())
-                .with_boost(start.boost);
-            sim.advance(duration, 1.0, allow_boost);
+        if course.end_chop != 0.0 {
+            let duration = (sim.time() - course.end_chop).max(0.0);
+            sim = new_sim();
+            sim.advance(duration, allow_boost);
         }
 
-        let sim_end_loc = sim.distance();
+        // Convert back from surface distance to 2D distance.
+        let sim_end_loc = sim.distance() * start_to_end_dist / course.surface_dist;
         let sim_end_speed = sim.speed();
         let sim_end_boost = sim.boost();
 
-        let end_loc = start.loc + (end_loc - start.loc).normalize() * sim_end_loc;
+        let end_loc = start.loc + (course.target_loc - start.loc).normalize() * sim_end_loc;
         if (end_loc - start.loc).norm() < 1.0 {
             return Self::zero(start);
         }
@@ -79,6 +122,24 @@ impl Straight {
             duration: sim.time(),
             mode,
             allow_boost,
+            allow_flip,
+            course: Some(course),
+        }
+    }
+
+    /// Front flip along the way when there's enough runway and no boost to
+    /// spend, to pick up speed. The plan accounts for the flips, so the
+    /// duration and end speed come out right for a car driving on an empty
+    /// tank.
+    pub fn allow_flip(self, allow_flip: bool) -> Self {
+        if allow_flip == self.allow_flip {
+            return self;
+        }
+        match self.course {
+            Some(course) => {
+                Self::simulate(self.start, course, self.mode, self.allow_boost, allow_flip)
+            }
+            None => self,
         }
     }
 
@@ -91,6 +152,8 @@ impl Straight {
             duration: 0.0,
             mode: StraightMode::Fake,
             allow_boost: true,
+            allow_flip: false,
+            course: None,
         }
     }
 }
@@ -132,14 +195,64 @@ impl SegmentPlan for Straight {
 
 struct StraightRunner {
     plan: Straight,
+    /// A front flip in progress, if we started one.
+    flip: Option<Box<dyn Behavior>>,
 }
 
 impl StraightRunner {
+    /// These match the simulation, so we flip where the plan expects us to.
+    const FLIP_MIN_SPEED: f32 = Car1DWithDodge::MIN_DODGE_SPEED;
+    const FLIP_MAX_SPEED: f32 = Car1DWithDodge::MAX_DODGE_SPEED;
+    /// Only flip if we're pointed down the line at least this precisely,
+    const FLIP_MAX_ANGLE: f32 = 0.05;
+    /// and this close to it, since we can't steer once we're off the ground.
+    const FLIP_MAX_OFFSET: f32 = 50.0;
+    const FLIP_RUNWAY_MARGIN: f32 = Car1DWithDodge::RUNWAY_MARGIN;
+
     pub fn new(plan: Straight) -> Self {
-        StraightRunner { plan }
+        StraightRunner { plan, flip: None }
+    }
+
+    /// Start a front flip if it's safe and worth it.
+    fn start_flip(&self, ctx: &mut Context<'_>, cur_dist: f32) -> Option<Box<dyn Behavior>> {
+        if !self.plan.allow_flip || !ctx.jump.jump_available() {
+            return None;
+        }
+
+        let me = ctx.me();
+        // Like the plan, boost first and flip once the tank is dry.
+        if self.plan.allow_boost && me.Boost > 0 {
+            return None;
+        }
+
+        let start_to_end = self.plan.end_loc - self.plan.start.loc;
+        let dir = start_to_end.to_axis();
+        let speed = me.Physics.vel_2d().norm();
+        let angle = me.Physics.forward_axis_2d().rotation_to(&dir).angle().abs();
+        let offset = (me.Physics.loc_2d() - self.plan.start.loc)
+            .dot(&dir.ortho())
+            .abs();
+        let runway = start_to_end.norm() - cur_dist;
+        let dodge = CarForwardDodge::calc_1d(speed);
+        if !flip_fits(speed, angle, offset, runway, dodge.end_dist) {
+            return None;
+        }
+
+        ctx.eeg.log(self.name(), "front flipping to save boost");
+        Some(forward_dodge(&dodge, self.plan.end_loc))
     }
 }
 
+/// Returns `true` if a front flip from this state lands on the straightaway
+/// with room to spare.
+fn flip_fits(speed: f32, angle: f32, offset: f32, runway: f32, flip_dist: f32) -> bool {
+    speed >= StraightRunner::FLIP_MIN_SPEED
+        && speed <= StraightRunner::FLIP_MAX_SPEED
+        && angle < StraightRunner::FLIP_MAX_ANGLE
+        && offset < StraightRunner::FLIP_MAX_OFFSET
+        && runway >= flip_dist + StraightRunner::FLIP_RUNWAY_MARGIN
+}
+
 impl SegmentRunner for StraightRunner {
     fn name(&self) -> &str {
         name_of_type!(StraightRunner)
@@ -154,6 +267,19 @@ impl SegmentRunner for StraightRunner {
             StraightMode::Asap => {} // continued below :)
         }
 
+        // Finish any flip we started before doing anything else, since we're
+        // not on the ground during it.
+        if let Some(flip) = &mut self.flip {
+            match flip.execute(ctx) {
+                Action::Yield(i) => return SegmentRunAction::Yield(i),
+                Action::Return => self.flip = None,
+                Action::TailCall(_) | Action::RootCall(_) | Action::Abort => {
+                    ctx.eeg.log(self.name(), "flip failed");
+                    return SegmentRunAction::Failure;
+                }
+            }
+        }
+
         let me = ctx.me();
         let me_loc = me.Physics.loc_2d();
         let start_to_end = self.plan.end_loc - self.plan.start.loc;
@@ -168,6 +294,11 @@ impl SegmentRunner for StraightRunner {
             return SegmentRunAction::Failure;
         }
 
+        if let Some(flip) = self.start_flip(ctx, cur_dist) {
+            self.flip = Some(flip);
+            return self.execute_old(ctx);
+        }
+
         // Drive to a point slightly in front of us, so we "hug the line" and get back
         // on course quicker in case of any inaccuracies.
         let target_loc = self.plan.start.loc + start_to_end.normalize() * (cur_dist + 500.0);
@@ -175,13 +306,84 @@ impl SegmentRunner for StraightRunner {
         ctx.eeg
             .draw(Drawable::ghost_car_ground(target_loc, me.Physics.rot()));
 
+        // The plan feathers boost the same way.
+        let (throttle, boost) = supersonic_control(me.Physics.vel().norm(), me.Boost);
         SegmentRunAction::Yield(common::halfway_house::PlayerInput {
-            Throttle: 1.0,
+            Throttle: throttle,
             Steer: simple_steer_towards(&me.Physics, target_loc),
-            Boost: self.plan.allow_boost
-                && me.Physics.vel().norm() < rl::CAR_ALMOST_MAX_SPEED
-                && me.Boost > 0,
+            Boost: self.plan.allow_boost && boost,
             ..Default::default()
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::drive::SUPERSONIC_HOLD_SPEED,
+        routing::{
+            models::{CarState2D, SegmentPlan},
+            segments::{straight::flip_fits, Straight, StraightMode},
+        },
+        strategy::DFH_STADIUM,
+    };
+    use nalgebra::{Point2, UnitComplex, Vector2};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn flip_needs_runway() {
+        assert!(flip_fits(1400.0, 0.0, 0.0, 2000.0, 1000.0));
+        assert!(!flip_fits(1400.0, 0.0, 0.0, 1200.0, 1000.0));
+    }
+
+    #[test]
+    fn flip_needs_to_be_lined_up() {
+        assert!(!flip_fits(1400.0, 0.2, 0.0, 2000.0, 1000.0));
+        assert!(!flip_fits(1400.0, 0.0, 200.0, 2000.0, 1000.0));
+    }
+
+    #[test]
+    fn flip_needs_moderate_speed() {
+        assert!(!flip_fits(500.0, 0.0, 0.0, 2000.0, 1000.0));
+        assert!(!flip_fits(2200.0, 0.0, 0.0, 2000.0, 1000.0));
+    }
+
+    #[test]
+    fn flipping_beats_driving() {
+        let start = CarState2D {
+            loc: Point2::new(0.0, -4000.0),
+            rot: UnitComplex::new(PI / 2.0),
+            vel: Vector2::new(0.0, 1200.0),
+            boost: 10.0,
+        };
+        let end_loc = Point2::new(0.0, 3000.0);
+        let drive = Straight::new(
+            &DFH_STADIUM,
+            start.clone(),
+            end_loc,
+            0.0,
+            StraightMode::Asap,
+            false,
+        );
+        let flip = drive.clone().allow_flip(true);
+        assert!(flip.duration() < drive.duration());
+        // It never boosts.
+        assert_eq!(flip.end().boost, 10.0);
+    }
+
+    #[test]
+    fn plan_feathers_boost_like_the_runner() {
+        let start = CarState2D {
+            loc: Point2::new(0.0, -4000.0),
+            rot: UnitComplex::new(PI / 2.0),
+            vel: Vector2::new(0.0, 1200.0),
+            boost: 100.0,
+        };
+        let end_loc = Point2::new(0.0, 4000.0);
+        let plan = Straight::new(&DFH_STADIUM, start, end_loc, 0.0, StraightMode::Asap, true);
+        let end = plan.end();
+        assert!((end.vel.norm() - SUPERSONIC_HOLD_SPEED).abs() < 1.0);
+        // Boosting to max speed would burn most of the tank.
+        assert!(end.boost > 50.0);
+    }
+}
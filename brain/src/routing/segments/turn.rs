@@ -7,6 +7,7 @@ use crate::{
 use common::prelude::*;
 use nalgebra::{Point2, UnitComplex, Vector2};
 use nameof::name_of_type;
+use simulate::car_turn;
 use std::f32::consts::PI;
 
 #[derive(Clone)]
@@ -79,8 +80,9 @@ impl SegmentPlan for Turn {
     }
 
     fn duration(&self) -> f32 {
-        let assume_speed = f32::max(self.start.vel.norm(), 800.0) * 2.0;
-        self.radius * self.sweep.abs() / assume_speed
+        // The planner picks the tightest radius for the speed it expects, so
+        // the car holds whatever speed the radius allows.
+        self.sweep.abs() / car_turn::angular_velocity(car_turn::max_speed_for_radius(self.radius))
     }
 
     fn run(&self) -> Box<dyn SegmentRunner> {
@@ -1,4 +1,5 @@
 use crate::{
+    eeg::{color, Drawable},
     routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
     strategy::Context,
     utils::geometry::flattener::Flattener,
@@ -80,7 +81,14 @@ impl SegmentPlan for WallStraight {
         Box::new(WallStraightRunner)
     }
 
-    fn draw(&self, _ctx: &mut Context<'_>) {}
+    fn draw(&self, ctx: &mut Context<'_>) {
+        let flat_start_loc = self.start_to_flat * self.start.loc;
+        ctx.eeg.draw(Drawable::unfolded(Drawable::Line(
+            flat_start_loc,
+            self.flat_end_loc,
+            color::YELLOW,
+        )));
+    }
 }
 
 struct WallStraightRunner;
@@ -1,10 +1,11 @@
 use crate::{
+    eeg::{color, Drawable},
     routing::models::{CarState, SegmentPlan, SegmentRunAction, SegmentRunner},
     strategy::Context,
     utils::geometry::{flattener::Flattener, Plane},
 };
 use common::{prelude::*, rl};
-use nalgebra::{Point2, UnitComplex};
+use nalgebra::{Point2, UnitComplex, Vector2};
 use nameof::name_of_type;
 use std::f32::consts::PI;
 
@@ -89,7 +90,18 @@ impl SegmentPlan for WallTurn {
         Box::new(WallTurnRunner::new(self.clone()))
     }
 
-    fn draw(&self, _ctx: &mut Context<'_>) {}
+    fn draw(&self, ctx: &mut Context<'_>) {
+        let flat_start_loc = self.flattener * self.start.loc;
+        let theta1 = Vector2::x().angle_to(&(flat_start_loc - self.flat_center));
+        let theta2 = theta1 + self.sweep;
+        ctx.eeg.draw(Drawable::unfolded(Drawable::Arc(
+            self.flat_center,
+            self.radius,
+            theta1.min(theta2),
+            theta1.max(theta2),
+            color::YELLOW,
+        )));
+    }
 }
 
 struct WallTurnRunner {
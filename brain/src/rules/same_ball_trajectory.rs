@@ -21,19 +21,26 @@ impl SameBallTrajectory {
         SameBallTrajectory { prediction: None }
     }
 
-    pub fn execute_old(&mut self, ctx: &mut Context<'_>) -> Option<Action> {
-        if self.eval_vel_changed(ctx) {
+    pub fn execute(&mut self, ctx: &mut Context<'_>) -> Option<Action> {
+        if self.changed(ctx) {
             Some(Action::Abort)
         } else {
-            self.update_snapshot(ctx);
             None
         }
     }
 
+    /// Returns `true` if the ball strayed from the trajectory it was on last
+    /// time. Either way, start tracking the current trajectory.
+    pub fn changed(&mut self, ctx: &mut Context<'_>) -> bool {
+        let changed = self.eval_vel_changed(ctx);
+        self.update_snapshot(ctx);
+        changed
+    }
+
     fn update_snapshot(&mut self, ctx: &mut Context<'_>) {
         let frame = ctx.scenario.ball_prediction().at_time_or_last(0.1);
         self.prediction = Some(Prediction {
-            t: ctx.packet.GameInfo.TimeSeconds + frame.t,
+            t: ctx.clock.now() + frame.t,
             loc: frame.loc,
         });
     }
@@ -42,7 +49,7 @@ impl SameBallTrajectory {
         let prediction = some_or_else!(self.prediction.as_ref(), {
             return false;
         });
-        let rel_time = prediction.t - ctx.packet.GameInfo.TimeSeconds;
+        let rel_time = prediction.t - ctx.clock.now();
         let frame = match ctx.scenario.ball_prediction().at_time(rel_time) {
             Some(f) => f,
             None => {
@@ -0,0 +1,189 @@
+use crate::{behavior::movement::simple_yaw_diff, strategy::Game};
+use common::prelude::*;
+use nalgebra::Point2;
+use ordered_float::NotNan;
+use std::f32::consts::PI;
+
+/// A scripted opponent, so defensive behaviors can be tried against an enemy
+/// that reacts to them instead of a fixed recording. `TestRunner` puts it in
+/// the enemy's car with `enemy_attacker`.
+///
+/// It plays like a simple attacker would: line up behind the ball, drive
+/// through it at our net with a flip when it's lined up, and go pick up boost
+/// when it runs low. It's stateless except for the flip, so it can take over
+/// any car in any packet.
+pub struct MirrorAttacker {
+    player_index: usize,
+    /// When we jumped for a shot, if we're in the middle of one.
+    jumped_at: Option<f32>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AttackerMode {
+    /// Get behind the ball, facing our net.
+    Chase,
+    /// Lined up, so hit it as hard as possible.
+    Shoot,
+    /// Out of boost, so go get some.
+    CollectBoost,
+}
+
+impl MirrorAttacker {
+    /// Below this, go get boost instead of chasing.
+    const LOW_BOOST: i32 = 20;
+    /// Shoot if the car, the ball and the net line up within this angle.
+    const SHOT_ANGLE: f32 = PI / 8.0;
+    /// Don't bother shooting from further than this.
+    const SHOT_RANGE: f32 = 3000.0;
+    /// Line up this far behind the ball.
+    const APPROACH_DIST: f32 = 500.0;
+    /// Flip into the ball from this close.
+    const FLIP_DIST: f32 = 300.0;
+    /// How long to hold the jump before the flip.
+    const JUMP_TIME: f32 = 0.1;
+
+    pub fn new(player_index: usize) -> Self {
+        Self {
+            player_index,
+            jumped_at: None,
+        }
+    }
+
+    pub fn mode(&self, packet: &common::halfway_house::LiveDataPacket) -> AttackerMode {
+        let game = Game::soccar_offline(packet, self.player_index);
+        let car = game.me();
+        choose_mode(
+            car.Physics.loc_2d(),
+            car.Boost,
+            packet.GameBall.Physics.loc_2d(),
+            game.enemy_goal().center_2d,
+        )
+    }
+
+    /// Returns the controls for the attacker this frame.
+    pub fn input(
+        &mut self,
+        packet: &common::halfway_house::LiveDataPacket,
+    ) -> common::halfway_house::PlayerInput {
+        let now = packet.GameInfo.TimeSeconds;
+        let game = Game::soccar_offline(packet, self.player_index);
+        let car = game.me();
+
+        if let Some(jumped_at) = self.jumped_at {
+            return self.flip(car, now - jumped_at);
+        }
+
+        let ball_loc = packet.GameBall.Physics.loc_2d();
+        let net = game.enemy_goal().center_2d;
+        let mode = self.mode(packet);
+        let target_loc = match mode {
+            AttackerMode::Chase => ball_loc + (ball_loc - net).normalize() * Self::APPROACH_DIST,
+            AttackerMode::Shoot => ball_loc,
+            AttackerMode::CollectBoost => game
+                .boost_dollars()
+                .iter()
+                .map(|pickup| pickup.loc)
+                .min_by_key(|&loc| NotNan::new((loc - car.Physics.loc_2d()).norm()).unwrap())
+                .unwrap_or(ball_loc),
+        };
+
+        if mode == AttackerMode::Shoot
+            && car.OnGround
+            && (ball_loc - car.Physics.loc_2d()).norm() < Self::FLIP_DIST
+        {
+            self.jumped_at = Some(now);
+            return self.flip(car, 0.0);
+        }
+
+        let yaw_diff = simple_yaw_diff(&car.Physics, target_loc);
+        common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Steer: (yaw_diff * 2.0).max(-1.0).min(1.0),
+            Handbrake: yaw_diff.abs() >= PI / 2.0,
+            Boost: mode != AttackerMode::CollectBoost && yaw_diff.abs() < PI / 6.0,
+            ..Default::default()
+        }
+    }
+
+    /// Jump, release, then flip forwards into the ball.
+    fn flip(
+        &mut self,
+        car: &common::halfway_house::PlayerInfo,
+        elapsed: f32,
+    ) -> common::halfway_house::PlayerInput {
+        let jump = if elapsed < Self::JUMP_TIME {
+            true
+        } else if elapsed < Self::JUMP_TIME + 0.05 {
+            false
+        } else {
+            if car.OnGround || car.DoubleJumped {
+                self.jumped_at = None;
+            }
+            !car.DoubleJumped
+        };
+        common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            Pitch: if elapsed < Self::JUMP_TIME { 0.0 } else { -1.0 },
+            Jump: jump,
+            Boost: true,
+            ..Default::default()
+        }
+    }
+}
+
+fn choose_mode(
+    car_loc: Point2<f32>,
+    boost: i32,
+    ball_loc: Point2<f32>,
+    net: Point2<f32>,
+) -> AttackerMode {
+    let car_to_ball = ball_loc - car_loc;
+    let ball_to_net = net - ball_loc;
+    let lined_up = car_to_ball.norm() < MirrorAttacker::SHOT_RANGE
+        && car_to_ball.angle(&ball_to_net) < MirrorAttacker::SHOT_ANGLE;
+    if lined_up {
+        AttackerMode::Shoot
+    } else if boost < MirrorAttacker::LOW_BOOST {
+        AttackerMode::CollectBoost
+    } else {
+        AttackerMode::Chase
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sim::mirror_attacker::{choose_mode, AttackerMode};
+    use nalgebra::Point2;
+
+    fn net() -> Point2<f32> {
+        Point2::new(0.0, -5120.0)
+    }
+
+    #[test]
+    fn shoot_when_lined_up() {
+        let mode = choose_mode(Point2::new(0.0, 0.0), 0, Point2::new(0.0, -1000.0), net());
+        assert_eq!(mode, AttackerMode::Shoot);
+    }
+
+    #[test]
+    fn chase_from_the_wrong_side() {
+        let mode = choose_mode(
+            Point2::new(0.0, -3000.0),
+            100,
+            Point2::new(0.0, -1000.0),
+            net(),
+        );
+        assert_eq!(mode, AttackerMode::Chase);
+    }
+
+    #[test]
+    fn collect_boost_when_low() {
+        let mode = choose_mode(
+            Point2::new(0.0, -3000.0),
+            0,
+            Point2::new(0.0, -1000.0),
+            net(),
+        );
+        assert_eq!(mode, AttackerMode::CollectBoost);
+    }
+}
@@ -1,6 +1,11 @@
 // TODO: move this, and its dependencies, to the `simulate` crate.
 
-pub use self::{sim_ground_drive::SimGroundDrive, sim_jump::SimJump};
+pub use self::{
+    mirror_attacker::{AttackerMode, MirrorAttacker},
+    sim_ground_drive::SimGroundDrive,
+    sim_jump::SimJump,
+};
 
+mod mirror_attacker;
 mod sim_ground_drive;
 mod sim_jump;
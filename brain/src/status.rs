@@ -0,0 +1,219 @@
+use crate::telemetry::{TelemetryBody, TelemetryCar};
+use serde_derive::Serialize;
+use std::collections::VecDeque;
+#[cfg(feature = "status-server")]
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// A summary of what the bot is up to, kept up to date every frame for
+/// dashboards and tournament overlays. See `EEG::publish_status`.
+///
+/// The JSON field names are a public interface. Add to them, but don't rename
+/// or remove any.
+///
+/// The ball and our car are the same snapshots the telemetry log records.
+#[derive(Clone, Debug)]
+pub struct BotStatus {
+    /// Game time as of the last frame.
+    pub time: f32,
+    pub ball: TelemetryBody,
+    pub car: TelemetryCar,
+    /// The behavior being run, with any children it's running.
+    pub behavior: String,
+    /// Seconds until we reach the ball, minus seconds until the enemy does.
+    pub possession: f32,
+    /// The most recent behavior changes, oldest first.
+    decisions: VecDeque<String>,
+    pub frame_timing: FrameTiming,
+}
+
+impl Default for BotStatus {
+    fn default() -> Self {
+        let body = TelemetryBody::from_physics(&Default::default());
+        Self {
+            time: 0.0,
+            ball: body.clone(),
+            car: TelemetryCar { body, boost: 0 },
+            behavior: String::new(),
+            possession: 0.0,
+            decisions: VecDeque::new(),
+            frame_timing: FrameTiming::default(),
+        }
+    }
+}
+
+impl BotStatus {
+    /// How many decisions to remember.
+    pub const MAX_DECISIONS: usize = 20;
+
+    pub fn decisions(&self) -> impl Iterator<Item = &str> {
+        self.decisions.iter().map(|d| d.as_str())
+    }
+
+    pub fn push_decision(&mut self, decision: String) {
+        if self.decisions.len() >= Self::MAX_DECISIONS {
+            self.decisions.pop_front();
+        }
+        self.decisions.push_back(decision);
+    }
+
+    pub fn to_json(&self) -> String {
+        let json = StatusJson {
+            time: self.time,
+            behavior: &self.behavior,
+            possession: self.possession,
+            boost: self.car.boost,
+            decisions: &self.decisions,
+            frame_timing: &self.frame_timing,
+            ball: &self.ball,
+            car: &self.car.body,
+        };
+        serde_json::to_string(&json).unwrap()
+    }
+}
+
+/// The layout of `BotStatus::to_json`. Non-finite numbers come out as `null`.
+#[derive(Serialize)]
+struct StatusJson<'a> {
+    time: f32,
+    behavior: &'a str,
+    possession: f32,
+    boost: u8,
+    decisions: &'a VecDeque<String>,
+    frame_timing: &'a FrameTiming,
+    ball: &'a TelemetryBody,
+    car: &'a TelemetryBody,
+}
+
+/// How long the bot takes to decide what to do each frame.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct FrameTiming {
+    pub frames: u32,
+    pub last_ms: f32,
+    pub mean_ms: f32,
+    pub max_ms: f32,
+    /// Frames that took longer than the game's physics tick.
+    pub slow_frames: u32,
+}
+
+impl FrameTiming {
+    /// RL's physics runs at 120Hz, which leaves us ~8ms to make a decision.
+    pub const SLOW_FRAME_MS: f32 = 8.0;
+
+    pub fn record(&mut self, ms: f32) {
+        self.frames += 1;
+        self.last_ms = ms;
+        self.mean_ms += (ms - self.mean_ms) / self.frames as f32;
+        self.max_ms = self.max_ms.max(ms);
+        if ms >= Self::SLOW_FRAME_MS {
+            self.slow_frames += 1;
+        }
+    }
+}
+
+/// How long to wait for a client to send its request.
+#[cfg(feature = "status-server")]
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Serve `status` as JSON at `GET /status` on `addr`, e.g., `127.0.0.1:8321`,
+/// from a background thread.
+///
+/// This is meant for a dashboard on the same machine polling a few times a
+/// second, so it handles one request at a time and nothing fancier.
+#[cfg(feature = "status-server")]
+pub fn serve_status(addr: &str, status: Arc<Mutex<BotStatus>>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            // A misbehaving client shouldn't take the endpoint down.
+            if let Ok(stream) = stream {
+                if let Err(e) = respond(stream, &status) {
+                    log::warn!("status request failed: {}", e);
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(feature = "status-server")]
+fn respond(mut stream: TcpStream, status: &Mutex<BotStatus>) -> io::Result<()> {
+    // Requests are handled one at a time, so don't let a client that connects
+    // and says nothing hold up everyone else.
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+
+    let mut words = request_line.split_whitespace();
+    let (code, body) = match (words.next(), words.next()) {
+        (Some("GET"), Some("/status")) => ("200 OK", status.lock().unwrap().to_json()),
+        _ => ("404 Not Found", r#"{"error":"not found"}"#.to_string()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n{}",
+        code,
+        body.len(),
+        body,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::status::{BotStatus, FrameTiming};
+
+    #[test]
+    fn keeps_recent_decisions() {
+        let mut status = BotStatus::default();
+        for i in 0..30 {
+            status.push_decision(format!("> Behavior{}", i));
+        }
+        let decisions: Vec<_> = status.decisions().collect();
+        assert_eq!(decisions.len(), BotStatus::MAX_DECISIONS);
+        assert_eq!(decisions[0], "> Behavior10");
+        assert_eq!(decisions[19], "> Behavior29");
+    }
+
+    #[test]
+    fn frame_timing() {
+        let mut timing = FrameTiming::default();
+        for &ms in &[2.0, 4.0, 12.0] {
+            timing.record(ms);
+        }
+        assert_eq!(timing.frames, 3);
+        assert_eq!(timing.last_ms, 12.0);
+        assert!((timing.mean_ms - 6.0).abs() < 1e-5);
+        assert_eq!(timing.max_ms, 12.0);
+        assert_eq!(timing.slow_frames, 1);
+    }
+
+    #[test]
+    fn json() {
+        let mut status = BotStatus {
+            time: 12.5,
+            behavior: "Chain(\"Dodge\")".to_string(),
+            possession: std::f32::INFINITY,
+            ..Default::default()
+        };
+        status.car.boost = 33;
+        status.car.body.loc.x = 1000.0;
+        status.push_decision("> Defense".to_string());
+        status.frame_timing.record(3.0);
+        assert_eq!(
+            status.to_json(),
+            concat!(
+                r#"{"time":12.5,"behavior":"Chain(\"Dodge\")","possession":null,"boost":33,"#,
+                r#""decisions":["> Defense"],"frame_timing":{"frames":1,"last_ms":3.0,"#,
+                r#""mean_ms":3.0,"max_ms":3.0,"slow_frames":0},"#,
+                r#""ball":{"loc":[0.0,0.0,0.0],"rot":[0.0,0.0,0.0],"vel":[0.0,0.0,0.0]},"#,
+                r#""car":{"loc":[1000.0,0.0,0.0],"rot":[0.0,0.0,0.0],"vel":[0.0,0.0,0.0]}}"#,
+            ),
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::behavior::PreKickoff;
+use common::halfway_house::TileState;
+
+/// How much of the floor the Dropshot ball breaks when it lands.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum BallCharge {
+    Normal,
+    Charged,
+    SuperCharged,
+}
+
+impl BallCharge {
+    /// The game charges the ball by how hard it's hit, which the packet
+    /// doesn't tell us. Counting touches is close enough to know when to worry.
+    const CHARGED_TOUCHES: u32 = 3;
+    const SUPER_CHARGED_TOUCHES: u32 = 6;
+
+    pub fn from_touches(touches: u32) -> Self {
+        if touches >= Self::SUPER_CHARGED_TOUCHES {
+            BallCharge::SuperCharged
+        } else if touches >= Self::CHARGED_TOUCHES {
+            BallCharge::Charged
+        } else {
+            BallCharge::Normal
+        }
+    }
+
+    /// How far from where the ball lands tiles get damaged. A plain ball only
+    /// damages the tile it lands on. A charged ball takes out the ring of tiles
+    /// around it too, and a super-charged ball two rings.
+    pub fn blast_radius(self) -> f32 {
+        match self {
+            BallCharge::Normal => 0.0,
+            BallCharge::Charged => 800.0,
+            BallCharge::SuperCharged => 1600.0,
+        }
+    }
+}
+
+/// Keeps track of the Dropshot ball's charge by counting touches since it
+/// last broke the floor. The touches come from `MatchMemory`.
+pub struct BallChargeTracker {
+    touches: u32,
+    /// The last touch that was counted.
+    counted_touch: Option<f32>,
+    last_tiles: Vec<TileState>,
+}
+
+impl BallChargeTracker {
+    pub fn new() -> Self {
+        Self {
+            touches: 0,
+            counted_touch: None,
+            last_tiles: Vec::new(),
+        }
+    }
+
+    pub fn charge(&self) -> BallCharge {
+        BallCharge::from_touches(self.touches)
+    }
+
+    /// Update from the latest packet and `MatchMemory::last_touch`. Call this
+    /// before any behavior runs.
+    pub fn update(
+        &mut self,
+        packet: &common::halfway_house::LiveDataPacket,
+        last_touch: Option<f32>,
+    ) {
+        // Breaking tiles spends the charge, and each kickoff starts fresh.
+        let tiles_broke = (self.last_tiles.iter())
+            .zip(&packet.DropshotTiles)
+            .any(|(&before, &after)| damage(after) > damage(before));
+        self.last_tiles = packet.DropshotTiles.clone();
+        if tiles_broke || PreKickoff::is_kickoff(&packet.GameBall) {
+            self.touches = 0;
+        }
+
+        if last_touch.is_some() && last_touch != self.counted_touch {
+            self.counted_touch = last_touch;
+            self.touches += 1;
+        }
+    }
+}
+
+fn damage(state: TileState) -> u8 {
+    match state {
+        TileState::Unknown | TileState::Filled => 0,
+        TileState::Damaged => 1,
+        TileState::Open => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{BallCharge, BallChargeTracker};
+    use common::halfway_house::{LiveDataPacket, TileState};
+
+    fn packet(tiles: Vec<TileState>) -> LiveDataPacket {
+        let mut packet = LiveDataPacket {
+            DropshotTiles: tiles,
+            ..Default::default()
+        };
+        // Away from the kickoff spot.
+        packet.GameBall.Physics.Location.X = 150.0;
+        packet
+    }
+
+    #[test]
+    fn charge_levels() {
+        assert_eq!(BallCharge::from_touches(0), BallCharge::Normal);
+        assert_eq!(BallCharge::from_touches(3), BallCharge::Charged);
+        assert_eq!(BallCharge::from_touches(10), BallCharge::SuperCharged);
+        assert!(BallCharge::Charged.blast_radius() > BallCharge::Normal.blast_radius());
+    }
+
+    #[test]
+    fn touches_charge_until_tiles_break() {
+        let filled = vec![TileState::Filled; 2];
+        let mut tracker = BallChargeTracker::new();
+        tracker.update(&packet(filled.clone()), None);
+        for i in 0..3 {
+            tracker.update(&packet(filled.clone()), Some(i as f32));
+        }
+        assert_eq!(tracker.charge(), BallCharge::Charged);
+
+        let broken = vec![TileState::Damaged, TileState::Filled];
+        tracker.update(&packet(broken), Some(2.0));
+        assert_eq!(tracker.charge(), BallCharge::Normal);
+    }
+
+    #[test]
+    fn each_touch_counts_once() {
+        let mut tracker = BallChargeTracker::new();
+        tracker.update(&packet(Vec::new()), Some(1.0));
+        tracker.update(&packet(Vec::new()), Some(1.0));
+        assert_eq!(tracker.touches, 1);
+    }
+}
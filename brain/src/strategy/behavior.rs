@@ -14,7 +14,12 @@ pub trait Behavior: Send {
         Priority::Idle
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action;
+    /// Call `visit` with everything this behavior is running underneath it,
+    /// the active one first. This only feeds the EEG's behavior panel.
+    fn visit_children(&self, _visit: &mut dyn FnMut(Child<'_>)) {}
+
+    /// Run the behavior for one frame.
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action;
 }
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -27,6 +32,12 @@ pub enum Priority {
     Force,
 }
 
+pub enum Child<'a> {
+    Behavior(&'a dyn Behavior),
+    /// Something that isn't a behavior, e.g., a route segment.
+    Segment(&'a str),
+}
+
 pub enum Action {
     Yield(common::halfway_house::PlayerInput),
     TailCall(Box<dyn Behavior>),
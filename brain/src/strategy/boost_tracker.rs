@@ -0,0 +1,201 @@
+use crate::strategy::{game::Game, BoostPad};
+use common::{prelude::*, rl};
+use nalgebra::Point2;
+
+/// Keeps track of which boost pads are up, and when the rest will respawn.
+///
+/// The packet reports each pad's state directly. Packets built offline don't,
+/// so then we watch for cars driving over pads instead.
+pub struct BoostTracker {
+    pads: Vec<TrackedPad>,
+    /// The game time of the latest update.
+    now: f32,
+}
+
+struct TrackedPad {
+    pad: BoostPad,
+    /// The game time the pad comes back, or `None` if it's up now.
+    respawn_time: Option<f32>,
+}
+
+impl BoostTracker {
+    const BIG_RESPAWN_TIME: f32 = 10.0;
+    const SMALL_RESPAWN_TIME: f32 = 4.0;
+    /// Cars this close to a pad pick it up.
+    const BIG_PICKUP_RADIUS: f32 = 208.0;
+    const SMALL_PICKUP_RADIUS: f32 = 144.0;
+    /// A full car drives straight over a pad without taking it.
+    const MAX_BOOST: i32 = 100;
+
+    pub fn new() -> Self {
+        Self {
+            pads: Vec::new(),
+            now: 0.0,
+        }
+    }
+
+    /// Update from the latest packet. Call this before any behavior runs.
+    pub fn update(&mut self, game: &Game<'_>, packet: &common::halfway_house::LiveDataPacket) {
+        if self.pads.len() != game.boost_pads().len() {
+            self.pads = (game.boost_pads().iter())
+                .map(|pad| TrackedPad {
+                    pad: pad.clone(),
+                    respawn_time: None,
+                })
+                .collect();
+        }
+
+        let now = packet.GameInfo.TimeSeconds;
+        self.now = now;
+        for (index, tracked) in self.pads.iter_mut().enumerate() {
+            let respawn = Self::respawn_time(&tracked.pad);
+            if let Some(state) = packet.BoostPads.get(index) {
+                tracked.respawn_time = if state.IsActive {
+                    None
+                } else {
+                    Some(now + (respawn - state.Timer).max(0.0))
+                };
+                continue;
+            }
+
+            if tracked.respawn_time.map(|t| t <= now).unwrap_or(false) {
+                tracked.respawn_time = None;
+            }
+            let radius = Self::pickup_radius(&tracked.pad);
+            let picked_up = packet.cars().any(|car| {
+                !car.Demolished
+                    && car.Boost < Self::MAX_BOOST
+                    && (car.Physics.loc_2d() - tracked.pad.loc).norm() < radius
+            });
+            if tracked.respawn_time.is_none() && picked_up {
+                tracked.respawn_time = Some(now + respawn);
+            }
+        }
+    }
+
+    /// How long until the pad at `index` (in `Game::boost_pads` order) is up.
+    /// Zero if it's up now.
+    pub fn time_until_up(&self, index: usize, now: f32) -> f32 {
+        self.pads[index]
+            .respawn_time
+            .map(|t| (t - now).max(0.0))
+            .unwrap_or(0.0)
+    }
+
+    /// The closest big pad to `loc` that will be up by the time we could
+    /// possibly get there, assuming top speed the whole way. Returns its index
+    /// and the pad.
+    pub fn nearest_dollar(&self, loc: Point2<f32>, now: f32) -> Option<(usize, &BoostPad)> {
+        self.pads
+            .iter()
+            .enumerate()
+            .filter(|(_, tracked)| tracked.pad.big)
+            .filter(|&(index, _)| self.up_in_time(index, loc, now))
+            .min_by(|(_, a), (_, b)| {
+                let a = (a.pad.loc - loc).norm();
+                let b = (b.pad.loc - loc).norm();
+                a.partial_cmp(&b).unwrap()
+            })
+            .map(|(index, tracked)| (index, &tracked.pad))
+    }
+
+    /// Whether the pad at `pad_loc` will be up by the time a car at `loc`
+    /// could possibly get there, as of the latest update. Pads we don't know
+    /// about count as up.
+    pub fn pad_up_in_time(&self, pad_loc: Point2<f32>, loc: Point2<f32>) -> bool {
+        let index = self.pads.iter().position(|t| t.pad.loc == pad_loc);
+        index.map_or(true, |index| self.up_in_time(index, loc, self.now))
+    }
+
+    fn up_in_time(&self, index: usize, loc: Point2<f32>, now: f32) -> bool {
+        let fastest = (self.pads[index].pad.loc - loc).norm() / rl::CAR_MAX_SPEED;
+        self.time_until_up(index, now) <= fastest
+    }
+
+    fn respawn_time(pad: &BoostPad) -> f32 {
+        if pad.big {
+            Self::BIG_RESPAWN_TIME
+        } else {
+            Self::SMALL_RESPAWN_TIME
+        }
+    }
+
+    fn pickup_radius(pad: &BoostPad) -> f32 {
+        if pad.big {
+            Self::BIG_PICKUP_RADIUS
+        } else {
+            Self::SMALL_PICKUP_RADIUS
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{BoostTracker, Game};
+    use common::halfway_house::{BoostPadState, LiveDataPacket, PlayerInfo};
+    use nalgebra::Point2;
+    use std::iter;
+
+    fn packet(time: f32, car_loc: Point2<f32>, boost_pads: Vec<BoostPadState>) -> LiveDataPacket {
+        let mut car = PlayerInfo {
+            Boost: 30,
+            ..Default::default()
+        };
+        car.Physics.Location.X = car_loc.x;
+        car.Physics.Location.Y = car_loc.y;
+        LiveDataPacket {
+            BoostPads: boost_pads,
+            ..Default::default()
+        }
+        .with_time(time)
+        .with_cars(iter::once(car))
+    }
+
+    fn corner_index(game: &Game<'_>) -> usize {
+        game.boost_pads()
+            .iter()
+            .position(|p| p.loc == Point2::new(-3072.0, -4096.0))
+            .unwrap()
+    }
+
+    #[test]
+    fn watch_pickups_offline() {
+        let mut tracker = BoostTracker::new();
+        let corner = Point2::new(-3072.0, -4096.0);
+
+        let first = packet(10.0, corner, Vec::new());
+        let game = Game::soccar_offline(&first, 0);
+        let index = corner_index(&game);
+        tracker.update(&game, &first);
+        assert_eq!(tracker.time_until_up(index, 10.0), 10.0);
+
+        // The nearest big pad is taken, so go for the next one.
+        let (_, pad) = tracker.nearest_dollar(corner, 10.0).unwrap();
+        assert!(pad.loc != corner);
+        assert!(!tracker.pad_up_in_time(corner, corner));
+        assert!(tracker.pad_up_in_time(pad.loc, corner));
+
+        let later = packet(21.0, Point2::origin(), Vec::new());
+        tracker.update(&Game::soccar_offline(&later, 0), &later);
+        assert_eq!(tracker.time_until_up(index, 21.0), 0.0);
+    }
+
+    #[test]
+    fn trust_the_packet() {
+        let mut tracker = BoostTracker::new();
+        let mut states = vec![BoostPadState::default(); 34];
+        for state in &mut states {
+            state.IsActive = true;
+        }
+        let game_packet = packet(0.0, Point2::origin(), Vec::new());
+        let index = corner_index(&Game::soccar_offline(&game_packet, 0));
+        states[index] = BoostPadState {
+            IsActive: false,
+            Timer: 4.0,
+        };
+
+        let packet = packet(50.0, Point2::origin(), states);
+        tracker.update(&Game::soccar_offline(&packet, 0), &packet);
+        assert_eq!(tracker.time_until_up(index, 50.0), 6.0);
+    }
+}
@@ -1,15 +1,38 @@
 use crate::{
     eeg::EEG,
-    strategy::{game::Game, scenario::Scenario, Team},
+    routing::{accuracy::SegmentAccuracy, budget::PlanningBudget, models::PlanCache},
+    strategy::{
+        ball_charge::BallChargeTracker, boost_tracker::BoostTracker, difficulty::Difficulty,
+        game::Game, match_memory::MatchMemory, personality::Personality, risk_profile::RiskProfile,
+        scenario::Scenario, Team,
+    },
+    utils::{GameClock, JumpTracker},
 };
-use common::prelude::*;
+use common::{prelude::*, GoalDetector};
 
 pub struct Context<'a> {
     pub packet: &'a common::halfway_house::LiveDataPacket,
     pub game: &'a Game<'a>,
     pub scenario: &'a Scenario<'a>,
+    pub clock: &'a GameClock,
+    /// Our car's jump state, e.g., whether a flip is still available.
+    pub jump: &'a JumpTracker,
+    /// Which boost pads are up, and when the rest come back.
+    pub boost: &'a BoostTracker,
+    /// How much of the floor the Dropshot ball will break. Only meaningful in
+    /// Dropshot.
+    pub ball_charge: &'a BallChargeTracker,
     pub eeg: &'a mut EEG,
     pub last_quick_chat: &'a mut f32,
+    pub segment_accuracy: &'a mut SegmentAccuracy,
+    pub plan_cache: &'a mut PlanCache,
+    pub planning_budget: &'a mut PlanningBudget,
+    pub goal_detector: &'a GoalDetector,
+    pub personality: &'a Personality,
+    pub difficulty: &'a Difficulty,
+    pub match_memory: &'a mut MatchMemory,
+    /// How the score and the clock shift the personality's risk thresholds.
+    pub risk: RiskProfile,
 }
 
 impl<'a> Context<'a> {
@@ -17,15 +40,39 @@ impl<'a> Context<'a> {
         game: &'a Game<'_>,
         packet: &'a common::halfway_house::LiveDataPacket,
         scenario: &'a Scenario<'a>,
+        clock: &'a GameClock,
+        jump: &'a JumpTracker,
+        boost: &'a BoostTracker,
+        ball_charge: &'a BallChargeTracker,
         eeg: &'a mut EEG,
         last_quick_chat: &'a mut f32,
+        segment_accuracy: &'a mut SegmentAccuracy,
+        plan_cache: &'a mut PlanCache,
+        planning_budget: &'a mut PlanningBudget,
+        goal_detector: &'a GoalDetector,
+        personality: &'a Personality,
+        difficulty: &'a Difficulty,
+        match_memory: &'a mut MatchMemory,
+        risk: RiskProfile,
     ) -> Self {
         Self {
             packet,
             game,
             scenario,
+            clock,
+            jump,
+            boost,
+            ball_charge,
             eeg,
             last_quick_chat,
+            segment_accuracy,
+            plan_cache,
+            planning_budget,
+            goal_detector,
+            personality,
+            difficulty,
+            match_memory,
+            risk,
         }
     }
 
@@ -49,6 +96,7 @@ impl<'a> Context<'a> {
             packet: self.packet,
             game: self.game,
             scenario: &self.scenario,
+            boost: self.boost,
         };
         (ctx, self.eeg)
     }
@@ -85,6 +133,7 @@ pub struct Context2<'c, 's> {
     pub packet: &'c common::halfway_house::LiveDataPacket,
     pub game: &'c Game<'c>,
     pub scenario: &'s Scenario<'c>,
+    pub boost: &'c BoostTracker,
 }
 
 impl<'c, 's> Context2<'c, 's> {
@@ -0,0 +1,82 @@
+use common::rl;
+use std::str::FromStr;
+
+/// How good the bot is allowed to be. Lower levels make for gentler practice
+/// opponents without needing a separate build.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Difficulty {
+    /// How long the strategy takes to notice that something changed, i.e., the
+    /// minimum time between looking for a better behavior.
+    pub reaction_time: f32,
+    /// Don't drive faster than this.
+    pub max_speed: f32,
+    /// Whether to jump for balls that are out of reach from the ground.
+    pub aerials: bool,
+    /// Whether to speedflip on kickoffs.
+    pub speedflips: bool,
+    /// Whether to catch the ball and carry it on the roof.
+    pub dribbles: bool,
+}
+
+impl Difficulty {
+    pub fn rookie() -> Self {
+        Self {
+            reaction_time: 0.4,
+            max_speed: 1400.0,
+            aerials: false,
+            speedflips: false,
+            dribbles: false,
+        }
+    }
+
+    pub fn pro() -> Self {
+        Self {
+            reaction_time: 0.2,
+            max_speed: 1900.0,
+            aerials: true,
+            speedflips: false,
+            dribbles: true,
+        }
+    }
+
+    /// The historical behavior.
+    pub fn all_star() -> Self {
+        Self {
+            reaction_time: 0.0,
+            max_speed: rl::CAR_MAX_SPEED,
+            aerials: true,
+            speedflips: true,
+            dribbles: true,
+        }
+    }
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::all_star()
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rookie" => Ok(Self::rookie()),
+            "pro" => Ok(Self::pro()),
+            "allstar" => Ok(Self::all_star()),
+            _ => Err(format!("unknown difficulty {:?}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::Difficulty;
+
+    #[test]
+    fn parse() {
+        assert_eq!("rookie".parse(), Ok(Difficulty::rookie()));
+        assert!("legend".parse::<Difficulty>().is_err());
+    }
+}
@@ -1,26 +1,256 @@
 use crate::{
-    behavior::{movement::GetToFlatGround, offense::TepidHit},
-    strategy::{strategy::Strategy, Behavior, Context},
+    behavior::{
+        higher_order::Chain,
+        movement::{BlitzToLocation, GetToFlatGround},
+        strike::{GroundedHit, GroundedHitAimContext, GroundedHitTarget, GroundedHitTargetAdjust},
+    },
+    eeg::{color, Drawable},
+    routing::{behavior::FollowRoute, plan::GroundIntercept},
+    strategy::{
+        strategy::Strategy, Action, BallCharge, Behavior, Context, DropshotTile, Game, Priority,
+        Scenario, Team,
+    },
 };
+use common::{halfway_house::TileState, prelude::*, rl};
 use derive_new::new;
+use nalgebra::Point2;
+use nameof::name_of_type;
+use ordered_float::NotNan;
+use vec_box::vec_box;
 
+/// Break the enemy's floor, and keep ours from breaking.
+///
+/// Every hit is aimed at the weakest enemy tile. Damaged tiles open up when
+/// the ball lands on them again, and an open tile is a goal, so those are
+/// worth more than any number of filled ones. On defense, if the enemy is
+/// going to get to the ball first and it's coming down on one of our damaged
+/// tiles, we go stand on the tile.
+///
+/// A charged ball breaks a whole patch of floor, so once it's charged, we aim
+/// at the weakest patch of their tiles instead of a single one, and we get in
+/// the way of it landing anywhere on our side, not just on damaged tiles.
 #[derive(new)]
 pub struct Dropshot;
 
+impl Dropshot {
+    /// The ball lands "on" a tile if it comes down within this distance of the
+    /// tile's center. Tiles are hexagons about 770 uu across.
+    const TILE_RADIUS: f32 = 400.0;
+    /// The ball is coming down if it's this low.
+    const LANDING_Z: f32 = rl::BALL_RADIUS + 20.0;
+    /// Once we're this close to a threatened tile, go for the ball instead.
+    const COVER_DIST: f32 = 500.0;
+}
+
 impl Strategy for Dropshot {
     fn baseline(&mut self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+        ctx.eeg
+            .print_value("ball charge", format!("{:?}", ctx.ball_charge.charge()));
+
         if !GetToFlatGround::on_flat_ground(ctx.me()) {
             return Box::new(GetToFlatGround::new());
         }
 
-        Box::new(TepidHit::new())
+        if let Some(cover) = cover_tile(ctx) {
+            return cover;
+        }
+
+        Box::new(DropshotHit::new())
     }
 
     fn interrupt(
         &mut self,
-        _ctx: &mut Context<'_>,
-        _current: &dyn Behavior,
+        ctx: &mut Context<'_>,
+        current: &dyn Behavior,
     ) -> Option<Box<dyn Behavior>> {
+        if current.priority() < Priority::Defense {
+            return cover_tile(ctx);
+        }
         None
     }
 }
+
+/// If the enemy is about to break one of our tiles, returns a behavior that
+/// goes to stand in the way.
+fn cover_tile(ctx: &mut Context<'_>) -> Option<Box<dyn Behavior>> {
+    if ctx.scenario.possession() >= -Scenario::POSSESSION_CONTESTABLE {
+        return None;
+    }
+    let tile = threatened_tile(ctx.game, ctx.scenario, ctx.ball_charge.charge())?;
+    if (tile - ctx.me().Physics.loc_2d()).norm() < Dropshot::COVER_DIST {
+        return None;
+    }
+
+    ctx.eeg
+        .log(name_of_type!(Dropshot), "covering a damaged tile");
+    ctx.eeg
+        .draw(Drawable::ghost_ball(tile.to_3d(rl::BALL_RADIUS)));
+    Some(Box::new(Chain::new(Priority::Defense, vec_box![
+        BlitzToLocation::new(tile),
+    ])))
+}
+
+/// Returns the location of the tile on our side that the ball will land on
+/// next, if that would break anything. An uncharged ball only breaks tiles
+/// that are already weakened.
+fn threatened_tile(
+    game: &Game<'_>,
+    scenario: &Scenario<'_>,
+    charge: BallCharge,
+) -> Option<Point2<f32>> {
+    let landing = scenario
+        .ball_prediction()
+        .iter()
+        .find(|ball| ball.loc.z < Dropshot::LANDING_Z && ball.vel.z <= 0.0)?
+        .loc
+        .to_2d();
+
+    let reach = Dropshot::TILE_RADIUS + charge.blast_radius();
+    game.dropshot_tiles()
+        .filter(|&(tile, state)| {
+            tile.team == game.team
+                && (charge > BallCharge::Normal
+                    || state == TileState::Damaged
+                    || state == TileState::Open)
+                && (tile.loc - landing).norm() < reach
+        })
+        .map(|(tile, _)| tile.loc)
+        .min_by_key(|&loc| NotNan::new((loc - landing).norm()).unwrap())
+}
+
+/// How much we want the ball to land on a tile in this state.
+fn tile_value(state: TileState) -> f32 {
+    match state {
+        TileState::Open => 3.0,
+        TileState::Damaged => 2.0,
+        TileState::Filled | TileState::Unknown => 1.0,
+    }
+}
+
+/// Choose the best of `team`'s tiles to aim at from `ball_loc`. Weaker tiles
+/// win, and among equally weak ones, the closest. With a charge, a tile is
+/// worth everything the blast around it would break.
+fn weakest_tile<'a>(
+    tiles: impl Iterator<Item = (&'a DropshotTile, TileState)>,
+    team: Team,
+    ball_loc: Point2<f32>,
+    charge: BallCharge,
+) -> Option<Point2<f32>> {
+    let tiles: Vec<_> = tiles.filter(|&(tile, _)| tile.team == team).collect();
+    let blast_value = |center: &DropshotTile| -> f32 {
+        (tiles.iter())
+            .filter(|(tile, _)| (tile.loc - center.loc).norm() <= charge.blast_radius())
+            .map(|&(_, state)| tile_value(state))
+            .sum()
+    };
+    tiles
+        .iter()
+        .max_by_key(|&&(tile, _)| {
+            let dist = (tile.loc - ball_loc).norm();
+            NotNan::new(blast_value(tile) * 1000.0 - dist).unwrap()
+        })
+        .map(|(tile, _)| tile.loc)
+}
+
+/// Intercept the ball and hit it towards the weakest enemy tile.
+#[derive(new)]
+struct DropshotHit;
+
+impl Behavior for DropshotHit {
+    fn name(&self) -> &str {
+        name_of_type!(DropshotHit)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        let charge = ctx.ball_charge.charge();
+        Action::tail_call(Chain::new(Priority::Strike, vec_box![
+            FollowRoute::new(GroundIntercept::new()),
+            GroundedHit::hit_towards(move |ctx| Self::aim(ctx, charge)),
+        ]))
+    }
+}
+
+impl DropshotHit {
+    fn aim(
+        ctx: &mut GroundedHitAimContext<'_, '_>,
+        charge: BallCharge,
+    ) -> Result<GroundedHitTarget, ()> {
+        let ball_loc = ctx.intercept_ball_loc.to_2d();
+        let tiles = ctx.game.dropshot_tiles();
+        let target = weakest_tile(tiles, ctx.game.enemy_team, ball_loc, charge)
+            .unwrap_or_else(|| ctx.game.enemy_back_wall_center());
+        ctx.eeg
+            .draw(Drawable::ghost_ball(target.to_3d(rl::BALL_RADIUS)));
+        ctx.eeg
+            .draw(Drawable::print("aiming at a tile", color::GREEN));
+        Ok(GroundedHitTarget::new(
+            ctx.intercept_time,
+            GroundedHitTargetAdjust::RoughAim,
+            target,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{dropshot::weakest_tile, BallCharge, DropshotTile, Team};
+    use common::halfway_house::TileState;
+    use nalgebra::Point2;
+
+    fn tile(x: f32, y: f32, team: Team) -> DropshotTile {
+        DropshotTile {
+            loc: Point2::new(x, y),
+            team,
+        }
+    }
+
+    #[test]
+    fn prefers_weak_tiles() {
+        let all = vec![
+            (tile(0.0, 1000.0, Team::Orange), TileState::Filled),
+            (tile(0.0, 3000.0, Team::Orange), TileState::Damaged),
+            (tile(0.0, -1000.0, Team::Blue), TileState::Open),
+        ];
+        let tiles = || all.iter().map(|(t, s)| (t, *s));
+        let ball = Point2::new(0.0, 0.0);
+
+        // The damaged tile is worth the extra distance.
+        let target = weakest_tile(tiles(), Team::Orange, ball, BallCharge::Normal).unwrap();
+        assert!((target.y - 3000.0).abs() < 1e-3);
+
+        // Only the given team's tiles count.
+        let target = weakest_tile(tiles(), Team::Blue, ball, BallCharge::Normal).unwrap();
+        assert!((target.y + 1000.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn prefers_close_tiles() {
+        let tiles = vec![
+            (tile(0.0, 3000.0, Team::Orange), TileState::Filled),
+            (tile(0.0, 1000.0, Team::Orange), TileState::Filled),
+        ];
+        let tiles = tiles.iter().map(|(t, s)| (t, *s));
+        let target = weakest_tile(tiles, Team::Orange, Point2::origin(), BallCharge::Normal);
+        assert!((target.unwrap().y - 1000.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn charged_ball_prefers_weak_patches() {
+        let all = vec![
+            (tile(0.0, 1000.0, Team::Orange), TileState::Damaged),
+            (tile(-2000.0, 3000.0, Team::Orange), TileState::Filled),
+            (tile(-2770.0, 3000.0, Team::Orange), TileState::Damaged),
+            (tile(-1230.0, 3000.0, Team::Orange), TileState::Damaged),
+        ];
+        let tiles = || all.iter().map(|(t, s)| (t, *s));
+        let ball = Point2::new(0.0, 0.0);
+
+        // On its own, the nearby damaged tile is the best target.
+        let target = weakest_tile(tiles(), Team::Orange, ball, BallCharge::Normal).unwrap();
+        assert!((target.y - 1000.0).abs() < 1e-3);
+
+        // A charged ball breaks the filled tile and both its damaged neighbors.
+        let target = weakest_tile(tiles(), Team::Orange, ball, BallCharge::Charged).unwrap();
+        assert!((target.x + 2000.0).abs() < 1e-3);
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{
-    strategy::pitch::{Pitch, DFH_STADIUM},
+    behavior::PreKickoff,
+    strategy::{
+        mutators::Mutators,
+        pitch::{Pitch, DFH_STADIUM},
+    },
     utils::geometry::Line2,
 };
-use common::{prelude::*, rl, vector_iter};
+use common::{halfway_house::TileState, prelude::*, rl, vector_iter};
 use lazy_static::lazy_static;
 use nalgebra::{Point2, Point3, Unit, Vector2, Vector3};
-use std::ops::RangeTo;
+use std::{iter, ops::RangeTo};
 
 pub struct Game<'a> {
     packet: &'a common::halfway_house::LiveDataPacket,
@@ -14,24 +18,61 @@ pub struct Game<'a> {
     player_index: usize,
     pub team: Team,
     pub enemy_team: Team,
+    own_goal: Goal,
+    enemy_goal: Goal,
     boost_dollars: Box<[BoostPickup]>,
+    boost_pennies: Box<[BoostPickup]>,
+    boost_pads: Box<[BoostPad]>,
+    dropshot_tiles: Box<[DropshotTile]>,
     me_vehicle: &'a Vehicle,
+    mutators: Mutators,
 }
 
 impl<'a> Game<'a> {
+    /// `mode` is decided once per match, by `infer_game_mode`.
     pub fn new(
         field_info: rlbot::flat::FieldInfo<'_>,
+        mode: rlbot::GameMode,
         packet: &'a common::halfway_house::LiveDataPacket,
         player_index: usize,
     ) -> Self {
         let team = Team::from_ffi(packet.GameCars[player_index].Team);
+        let dropshot_tiles = match mode {
+            // In Dropshot, the "goals" are the floor tiles.
+            rlbot::GameMode::Dropshot => vector_iter(field_info.goals().unwrap())
+                .map(|info| DropshotTile {
+                    loc: point3(info.location().unwrap()).to_2d(),
+                    team: Team::from_ffi(info.teamNum() as u8),
+                })
+                .collect(),
+            _ => Box::new([]) as Box<[_]>,
+        };
+        let goal = |team: Team| match mode {
+            // Read the goals from the field, so a map with goals somewhere
+            // else doesn't quietly throw off all the goal math.
+            rlbot::GameMode::Soccer | rlbot::GameMode::Hockey => {
+                vector_iter(field_info.goals().unwrap())
+                    .filter(|info| Team::from_ffi(info.teamNum() as u8) == team)
+                    .filter_map(|info| {
+                        Goal::from_field_info(
+                            point3(info.location().unwrap()),
+                            point3(info.direction().unwrap()).coords,
+                        )
+                    })
+                    .next()
+                    .unwrap_or_else(|| Goal::soccar(team).clone())
+            }
+            _ => Goal::for_mode(mode, team).clone(),
+        };
         Self {
             packet,
-            mode: infer_game_mode(field_info),
+            mode,
             pitch: &*DFH_STADIUM,
             player_index,
             team,
             enemy_team: team.opposing(),
+            own_goal: goal(team),
+            enemy_goal: goal(team.opposing()),
             boost_dollars: vector_iter(field_info.boostPads().unwrap())
                 .filter(|info| info.isFullBoost())
                 .map(|info| BoostPickup {
@@ -39,17 +80,65 @@ impl<'a> Game<'a> {
                 })
                 .collect::<Vec<_>>()
                 .into_boxed_slice(),
+            boost_pennies: vector_iter(field_info.boostPads().unwrap())
+                .filter(|info| !info.isFullBoost())
+                .map(|info| BoostPickup {
+                    loc: point3(info.location().unwrap()).to_2d(),
+                })
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+            boost_pads: vector_iter(field_info.boostPads().unwrap())
+                .map(|info| BoostPad {
+                    loc: point3(info.location().unwrap()).to_2d(),
+                    big: info.isFullBoost(),
+                })
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+            dropshot_tiles,
             me_vehicle: &OCTANE,
+            mutators: Mutators::from_game_info(&packet.GameInfo),
         }
     }
 
+    /// Create a soccar `Game` on the standard stadium without the framework's
+    /// `FieldInfo`, e.g., for planning offline.
+    pub fn soccar_offline(
+        packet: &'a common::halfway_house::LiveDataPacket,
+        player_index: usize,
+    ) -> Self {
+        let team = Team::from_ffi(packet.GameCars[player_index].Team);
+        Self {
+            packet,
+            mode: rlbot::GameMode::Soccer,
+            pitch: &*DFH_STADIUM,
+            player_index,
+            team,
+            enemy_team: team.opposing(),
+            own_goal: Goal::soccar(team).clone(),
+            enemy_goal: Goal::soccar(team.opposing()).clone(),
+            boost_dollars: SOCCAR_BOOST_DOLLARS.clone().into_boxed_slice(),
+            boost_pennies: SOCCAR_BOOST_PENNIES.clone().into_boxed_slice(),
+            boost_pads: SOCCAR_BOOST_PADS.clone().into_boxed_slice(),
+            dropshot_tiles: Box::new([]),
+            me_vehicle: &OCTANE,
+            mutators: Mutators::from_game_info(&packet.GameInfo),
+        }
+    }
+
+    /// Replace what the packet says about mutators with a fuller picture,
+    /// e.g., from `MutatorTracker`.
+    pub fn with_mutators(mut self, mutators: Mutators) -> Self {
+        self.mutators = mutators;
+        self
+    }
+
     pub fn pitch(&self) -> &Pitch {
         self.pitch
     }
 
     pub fn field_max_x(&self) -> f32 {
         match self.mode {
-            rlbot::GameMode::Soccer => rl::FIELD_MAX_X,
+            rlbot::GameMode::Soccer | rlbot::GameMode::Hockey => rl::FIELD_MAX_X,
             rlbot::GameMode::Dropshot => 5026.0,
             rlbot::GameMode::Hoops => 2966.67,
             mode => panic!("unexpected game mode {:?}", mode),
@@ -58,7 +147,7 @@ impl<'a> Game<'a> {
 
     pub fn field_max_y(&self) -> f32 {
         match self.mode {
-            rlbot::GameMode::Soccer => rl::FIELD_MAX_Y,
+            rlbot::GameMode::Soccer | rlbot::GameMode::Hockey => rl::FIELD_MAX_Y,
             rlbot::GameMode::Dropshot => 4555.0,
             rlbot::GameMode::Hoops => 3586.0,
             mode => panic!("unexpected game mode {:?}", mode),
@@ -77,26 +166,37 @@ impl<'a> Game<'a> {
         &self.me_vehicle
     }
 
+    pub fn mutators(&self) -> &Mutators {
+        &self.mutators
+    }
+
     pub fn cars(&self, team: Team) -> impl Iterator<Item = &common::halfway_house::PlayerInfo> {
         self.packet
             .cars()
             .filter(move |p| Team::from_ffi(p.Team) == team)
     }
 
+    /// Our score minus the enemy's score.
+    pub fn score_margin(&self) -> i32 {
+        let score = |team: Team| {
+            self.packet
+                .Teams
+                .get(team.to_ffi() as usize)
+                .map_or(0, |info| info.Score)
+        };
+        score(self.team) - score(self.enemy_team)
+    }
+
+    pub fn seconds_remaining(&self) -> f32 {
+        self.packet.GameInfo.GameTimeRemaining
+    }
+
     pub fn own_goal(&self) -> &Goal {
-        match self.mode {
-            rlbot::GameMode::Soccer => Goal::soccar(self.team),
-            rlbot::GameMode::Hoops => Goal::hoops(self.team),
-            _ => panic!("unexpected game mode"),
-        }
+        &self.own_goal
     }
 
     pub fn enemy_goal(&self) -> &Goal {
-        match self.mode {
-            rlbot::GameMode::Soccer => Goal::soccar(self.enemy_team),
-            rlbot::GameMode::Hoops => Goal::hoops(self.enemy_team),
-            _ => panic!("unexpected game mode"),
-        }
+        &self.enemy_goal
     }
 
     pub fn own_back_wall_center(&self) -> Point2<f32> {
@@ -115,20 +215,67 @@ impl<'a> Game<'a> {
         &*self.boost_dollars
     }
 
+    /// The small pads, which only give a little boost each.
+    pub fn boost_pennies(&self) -> &[BoostPickup] {
+        &*self.boost_pennies
+    }
+
+    /// Every pad, big and small, in the same order as the packet's
+    /// `BoostPads`.
+    pub fn boost_pads(&self) -> &[BoostPad] {
+        &*self.boost_pads
+    }
+
+    /// The Dropshot floor tiles, with their current states. Empty in other
+    /// game modes.
+    pub fn dropshot_tiles(&self) -> impl Iterator<Item = (&DropshotTile, TileState)> {
+        let states = self.packet.DropshotTiles.iter().cloned();
+        self.dropshot_tiles
+            .iter()
+            .zip(states.chain(iter::repeat(TileState::Unknown)))
+    }
+
+    /// How far the ball reaches out from its center. For the puck, that's
+    /// sideways; it's much thinner top to bottom.
     pub fn ball_radius(&self) -> f32 {
-        rl::BALL_RADIUS
+        match self.mode {
+            rlbot::GameMode::Hockey => rl::PUCK_HALF_WIDTH,
+            _ => rl::BALL_RADIUS,
+        }
     }
 }
 
-pub fn infer_game_mode(field_info: rlbot::flat::FieldInfo<'_>) -> rlbot::GameMode {
+/// Snow Day plays on the soccar field, so it's told apart by the puck, which
+/// rests much lower than a ball. That only works while it's resting on the
+/// kickoff spot, so this returns `None` until the packet shows it there.
+pub fn infer_game_mode(
+    field_info: rlbot::flat::FieldInfo<'_>,
+    ball: &common::halfway_house::BallInfo,
+) -> Option<rlbot::GameMode> {
     match field_info.boostPads().unwrap().len() {
-        0 => rlbot::GameMode::Dropshot,
-        20 => rlbot::GameMode::Hoops,
-        34 => rlbot::GameMode::Soccer,
+        0 => Some(rlbot::GameMode::Dropshot),
+        20 => Some(rlbot::GameMode::Hoops),
+        34 => ball_or_puck(ball),
         _ => panic!("unknown game mode"),
     }
 }
 
+fn ball_or_puck(ball: &common::halfway_house::BallInfo) -> Option<rlbot::GameMode> {
+    // A resting ball's center is a whole radius up, and a puck's is half its
+    // height up. Anything lower is a packet that hasn't been filled in yet.
+    const PUCK_MAX_REST_Z: f32 = rl::BALL_RADIUS / 2.0;
+    const MIN_REST_Z: f32 = rl::PUCK_HALF_HEIGHT / 2.0;
+
+    let z = ball.Physics.loc().z;
+    if !PreKickoff::is_kickoff(ball) || z < MIN_REST_Z {
+        None
+    } else if z < PUCK_MAX_REST_Z {
+        Some(rlbot::GameMode::Hockey)
+    } else {
+        Some(rlbot::GameMode::Soccer)
+    }
+}
+
 fn point3(v: &rlbot::flat::Vector3) -> Point3<f32> {
     Point3::new(v.x(), v.y(), v.z())
 }
@@ -163,6 +310,7 @@ impl Team {
     }
 }
 
+#[derive(Clone)]
 pub struct Goal {
     pub center_2d: Point2<f32>,
     pub normal_2d: Unit<Vector2<f32>>,
@@ -170,6 +318,27 @@ pub struct Goal {
 }
 
 impl Goal {
+    /// The goals we model ourselves, for when the framework doesn't tell us.
+    fn for_mode(mode: rlbot::GameMode, team: Team) -> &'static Self {
+        match mode {
+            rlbot::GameMode::Soccer | rlbot::GameMode::Hockey => Goal::soccar(team),
+            rlbot::GameMode::Hoops => Goal::hoops(team),
+            rlbot::GameMode::Dropshot => Goal::dropshot(team),
+            _ => panic!("unexpected game mode"),
+        }
+    }
+
+    /// A goal from the framework's `GoalInfo`: the center of its mouth on the
+    /// ground, and the direction it faces, into the field. The framework
+    /// doesn't say how wide it is, so this assumes the standard posts.
+    fn from_field_info(loc: Point3<f32>, direction: Vector3<f32>) -> Option<Self> {
+        Some(Self {
+            center_2d: loc.to_2d(),
+            normal_2d: Unit::try_new(direction.to_2d(), 1e-3)?,
+            max_x: rl::GOALPOST_X,
+        })
+    }
+
     fn soccar(team: Team) -> &'static Self {
         match team {
             Team::Blue => &SOCCAR_GOAL_BLUE,
@@ -187,6 +356,15 @@ impl Goal {
         }
     }
 
+    /// Dropshot has no goals at all. Treat each team's back wall as its goal,
+    /// so the code that wants to know which way is "defense" keeps working.
+    fn dropshot(team: Team) -> &'static Self {
+        match team {
+            Team::Blue => &DROPSHOT_GOAL_BLUE,
+            Team::Orange => &DROPSHOT_GOAL_ORANGE,
+        }
+    }
+
     pub fn goalline(&self) -> Line2 {
         Line2::from_origin_dir(self.center_2d, self.normal_2d.ortho().to_axis())
     }
@@ -229,6 +407,31 @@ impl Goal {
         let goal_to_ball_axis = (ball_loc - self.center_2d).to_axis();
         goal_to_ball_axis.angle_to(&self.normal_2d).abs()
     }
+
+    /// Returns the post on the same side of the goal as the ball. Shots that
+    /// beat a keeper usually go in here.
+    pub fn near_post(&self, ball_loc: Point2<f32>) -> Point2<f32> {
+        let side = (ball_loc.x - self.center_2d.x).signum();
+        Point2::new(self.center_2d.x + side * self.max_x, self.center_2d.y)
+    }
+
+    /// Returns the post on the opposite side of the goal from the ball. This is
+    /// the "back post" that defenders should rotate to.
+    pub fn far_post(&self, ball_loc: Point2<f32>) -> Point2<f32> {
+        let side = -(ball_loc.x - self.center_2d.x).signum();
+        Point2::new(self.center_2d.x + side * self.max_x, self.center_2d.y)
+    }
+
+    /// Returns a point upfield of and outside the far post. Driving through
+    /// this point on the way back lets us enter the goal from behind the back
+    /// post, instead of cutting across the goal mouth.
+    pub fn back_post_approach(&self, ball_loc: Point2<f32>, upfield: f32) -> Point2<f32> {
+        let far_post = self.far_post(ball_loc);
+        let outward = (far_post.x - self.center_2d.x).signum();
+        far_post
+            + Vector2::x() * outward * self.max_x * 0.75
+            + self.normal_2d.into_inner() * upfield
+    }
 }
 
 #[derive(Clone)]
@@ -236,6 +439,24 @@ pub struct BoostPickup {
     pub loc: Point2<f32>,
 }
 
+#[derive(Clone)]
+pub struct BoostPad {
+    pub loc: Point2<f32>,
+    /// A big pad fills the meter. A small one only gives a little.
+    pub big: bool,
+}
+
+impl BoostPad {
+    pub fn pickup(&self) -> BoostPickup {
+        BoostPickup { loc: self.loc }
+    }
+}
+
+pub struct DropshotTile {
+    pub loc: Point2<f32>,
+    pub team: Team,
+}
+
 pub struct Vehicle {
     half_size: Vector3<f32>,
     pivot_offset: Vector3<f32>,
@@ -269,6 +490,75 @@ lazy_static! {
         normal_2d: -Vector2::y_axis(),
         max_x: rl::GOALPOST_X,
     };
+    static ref DROPSHOT_GOAL_BLUE: Goal = Goal {
+        center_2d: Point2::new(0.0, -4555.0),
+        normal_2d: Vector2::y_axis(),
+        max_x: rl::GOALPOST_X,
+    };
+    static ref DROPSHOT_GOAL_ORANGE: Goal = Goal {
+        center_2d: Point2::new(0.0, 4555.0),
+        normal_2d: -Vector2::y_axis(),
+        max_x: rl::GOALPOST_X,
+    };
+    static ref SOCCAR_BOOST_DOLLARS: Vec<BoostPickup> = [
+        (-3584.0, 0.0),
+        (3584.0, 0.0),
+        (-3072.0, -4096.0),
+        (3072.0, -4096.0),
+        (-3072.0, 4096.0),
+        (3072.0, 4096.0),
+    ]
+    .iter()
+    .map(|&(x, y)| BoostPickup {
+        loc: Point2::new(x, y),
+    })
+    .collect();
+    static ref SOCCAR_BOOST_PENNIES: Vec<BoostPickup> = [
+        (0.0, -4240.0),
+        (-1792.0, -4184.0),
+        (1792.0, -4184.0),
+        (-940.0, -3308.0),
+        (940.0, -3308.0),
+        (0.0, -2816.0),
+        (-3584.0, -2484.0),
+        (3584.0, -2484.0),
+        (-1788.0, -2300.0),
+        (1788.0, -2300.0),
+        (-2048.0, -1036.0),
+        (0.0, -1024.0),
+        (2048.0, -1036.0),
+        (-1024.0, 0.0),
+        (1024.0, 0.0),
+        (-2048.0, 1036.0),
+        (0.0, 1024.0),
+        (2048.0, 1036.0),
+        (-1788.0, 2300.0),
+        (1788.0, 2300.0),
+        (-3584.0, 2484.0),
+        (3584.0, 2484.0),
+        (0.0, 2816.0),
+        (-940.0, 3310.0),
+        (940.0, 3308.0),
+        (-1792.0, 4184.0),
+        (1792.0, 4184.0),
+        (0.0, 4240.0),
+    ]
+    .iter()
+    .map(|&(x, y)| BoostPickup {
+        loc: Point2::new(x, y),
+    })
+    .collect();
+    /// The framework lists the pads sorted by y, then x.
+    static ref SOCCAR_BOOST_PADS: Vec<BoostPad> = {
+        let dollars = SOCCAR_BOOST_DOLLARS.iter().map(|p| (p, true));
+        let pennies = SOCCAR_BOOST_PENNIES.iter().map(|p| (p, false));
+        let mut pads: Vec<_> = dollars
+            .chain(pennies)
+            .map(|(p, big)| BoostPad { loc: p.loc, big })
+            .collect();
+        pads.sort_by(|a, b| (a.loc.y, a.loc.x).partial_cmp(&(b.loc.y, b.loc.x)).unwrap());
+        pads
+    };
     static ref OCTANE: Vehicle = Vehicle {
         // Source:
         // https://www.youtube.com/watch?v=4OBMq9faWzg
@@ -277,3 +567,62 @@ lazy_static! {
         pivot_offset: Vector3::new(13.87566, 0.0, 20.75499),
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::game::{ball_or_puck, Goal, SOCCAR_GOAL_BLUE, SOCCAR_GOAL_ORANGE};
+    use common::{halfway_house::BallInfo, rl};
+    use nalgebra::{Point3, Vector3};
+
+    fn ball_at_z(z: f32) -> BallInfo {
+        let mut ball = BallInfo::default();
+        ball.Physics.Location.Z = z;
+        ball
+    }
+
+    #[test]
+    fn ball_or_puck_on_the_kickoff_spot() {
+        match ball_or_puck(&ball_at_z(92.75)) {
+            Some(rlbot::GameMode::Soccer) => {}
+            mode => panic!("{:?}", mode),
+        }
+        match ball_or_puck(&ball_at_z(30.0)) {
+            Some(rlbot::GameMode::Hockey) => {}
+            mode => panic!("{:?}", mode),
+        }
+    }
+
+    #[test]
+    fn ball_or_puck_waits_for_real_data() {
+        // A packet that hasn't been filled in yet.
+        assert!(ball_or_puck(&ball_at_z(0.0)).is_none());
+        // The ball is in play.
+        let mut ball = ball_at_z(30.0);
+        ball.Physics.Velocity.X = 500.0;
+        assert!(ball_or_puck(&ball).is_none());
+    }
+
+    #[test]
+    fn standard_goals_from_field_info() {
+        let blue = Goal::from_field_info(
+            Point3::new(0.0, -rl::FIELD_MAX_Y, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(blue.center_2d, SOCCAR_GOAL_BLUE.center_2d);
+        assert_eq!(blue.normal_2d, SOCCAR_GOAL_BLUE.normal_2d);
+
+        let orange = Goal::from_field_info(
+            Point3::new(0.0, rl::FIELD_MAX_Y, 0.0),
+            Vector3::new(0.0, -1.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(orange.center_2d, SOCCAR_GOAL_ORANGE.center_2d);
+        assert_eq!(orange.normal_2d, SOCCAR_GOAL_ORANGE.normal_2d);
+    }
+
+    #[test]
+    fn goal_without_direction() {
+        assert!(Goal::from_field_info(Point3::origin(), Vector3::zeros()).is_none());
+    }
+}
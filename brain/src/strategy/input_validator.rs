@@ -0,0 +1,144 @@
+use crate::utils::JumpTracker;
+use common::halfway_house::{PlayerInfo, PlayerInput};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Violation {
+    SteerWhileDodging,
+    JumpUnavailable,
+}
+
+impl Violation {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Violation::SteerWhileDodging => "steer while dodging",
+            Violation::JumpUnavailable => "jump with no jump available",
+        }
+    }
+}
+
+/// Enforces the physical interlocks on the controller input that leaves the
+/// `Runner`. Corrects `input` in place, and returns the interlocks it
+/// violated.
+///
+/// Behaviors sometimes ask for things the car can't do: a jump with no jump
+/// left, or steering halfway through a flip. The game
+/// quietly ignores most of those, or worse, does something else with them, and
+/// then the car does something nobody can explain. This strips the offending
+/// inputs and reports each one, so the culprit shows up in the log.
+pub fn validate_input(
+    jump: &JumpTracker,
+    car: &PlayerInfo,
+    input: &mut PlayerInput,
+) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    // Handbrake in the air is fine. Holding it through a landing keeps the car
+    // sliding instead of bouncing, which `Land` relies on.
+
+    // Steer does nothing in the air. A behavior that means to turn mirrors it
+    // onto yaw, like a controller's stick would; anything else is probably
+    // ground logic that didn't notice we're flipping.
+    let air_steer = (input.Steer - input.Yaw).abs() < 1e-3;
+    if !car.OnGround && jump.is_dodging() && input.Steer != 0.0 && !air_steer {
+        input.Steer = 0.0;
+        violations.push(Violation::SteerWhileDodging);
+    }
+
+    // Only a new press of the button does anything, so holding it is always
+    // fine.
+    if input.Jump && !jump.holding_jump() && !jump.jump_available() {
+        input.Jump = false;
+        violations.push(Violation::JumpUnavailable);
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        strategy::input_validator::{validate_input, Violation},
+        utils::JumpTracker,
+    };
+    use common::halfway_house::{PlayerInfo, PlayerInput};
+
+    fn car(on_ground: bool) -> PlayerInfo {
+        PlayerInfo {
+            OnGround: on_ground,
+            ..Default::default()
+        }
+    }
+
+    fn jump(pitch: f32) -> PlayerInput {
+        PlayerInput {
+            Jump: true,
+            Pitch: pitch,
+            ..Default::default()
+        }
+    }
+
+    /// Run one frame the way the brain does, and return the violations.
+    fn step(
+        tracker: &mut JumpTracker,
+        now: f32,
+        car: &PlayerInfo,
+        input: &mut PlayerInput,
+    ) -> Vec<Violation> {
+        tracker.update(now, car);
+        let violations = validate_input(tracker, car, input);
+        tracker.record_input(input);
+        violations
+    }
+
+    #[test]
+    fn airborne_handbrake() {
+        let mut tracker = JumpTracker::new();
+        let mut input = PlayerInput {
+            Handbrake: true,
+            ..Default::default()
+        };
+        assert!(step(&mut tracker, 0.0, &car(true), &mut input).is_empty());
+        assert!(step(&mut tracker, 0.1, &car(false), &mut input).is_empty());
+        assert!(input.Handbrake);
+    }
+
+    #[test]
+    fn one_flip_per_jump() {
+        let mut tracker = JumpTracker::new();
+        let none = || PlayerInput::default();
+        assert!(step(&mut tracker, 0.0, &car(true), &mut jump(0.0)).is_empty());
+        assert!(step(&mut tracker, 0.1, &car(false), &mut none()).is_empty());
+        assert!(step(&mut tracker, 0.2, &car(false), &mut jump(-1.0)).is_empty());
+        assert!(step(&mut tracker, 0.3, &car(false), &mut none()).is_empty());
+
+        let mut input = jump(-1.0);
+        let violations = step(&mut tracker, 0.4, &car(false), &mut input);
+        assert_eq!(violations, vec![Violation::JumpUnavailable]);
+        assert!(!input.Jump);
+    }
+
+    #[test]
+    fn steer_while_dodging() {
+        let mut tracker = JumpTracker::new();
+        let none = || PlayerInput::default();
+        assert!(step(&mut tracker, 0.0, &car(true), &mut jump(0.0)).is_empty());
+        assert!(step(&mut tracker, 0.1, &car(false), &mut none()).is_empty());
+        assert!(step(&mut tracker, 0.2, &car(false), &mut jump(-1.0)).is_empty());
+
+        let mut input = PlayerInput {
+            Steer: 1.0,
+            ..Default::default()
+        };
+        let violations = step(&mut tracker, 0.3, &car(false), &mut input);
+        assert_eq!(violations, vec![Violation::SteerWhileDodging]);
+        assert!(input.Steer.abs() < 1e-5);
+
+        // Steering on purpose is fine.
+        let mut input = PlayerInput {
+            Steer: 1.0,
+            Yaw: 1.0,
+            ..Default::default()
+        };
+        assert!(step(&mut tracker, 0.4, &car(false), &mut input).is_empty());
+    }
+}
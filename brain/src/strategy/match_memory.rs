@@ -0,0 +1,698 @@
+use crate::{
+    behavior::PreKickoff,
+    eeg::EEG,
+    strategy::{game::Game, opponent_model::Tendencies, scenario::Scenario},
+};
+use common::prelude::*;
+use nalgebra::Vector3;
+use nameof::name_of_type;
+use std::{
+    collections::VecDeque,
+    fmt, fs, io, mem,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Things we've learned about the opponent over the course of a match.
+pub struct MatchMemory {
+    kickoffs: Vec<KickoffRecord>,
+    pending_kickoff: Option<PendingKickoff>,
+    /// Just this match's kickoffs, unlike `kickoffs`, which may include ones
+    /// from a `ScoutingReport`.
+    kickoff_stats: KickoffStats,
+    /// Times of recent enemy touches, oldest first.
+    enemy_touches: VecDeque<f32>,
+    /// Whether we were the last to touch the ball, if anyone has.
+    last_touch_ours: Option<bool>,
+    /// The time of our most recent touch, if any.
+    last_own_touch: Option<f32>,
+    /// The time of the most recent touch by anyone, even if we couldn't tell
+    /// whose it was.
+    last_touch: Option<f32>,
+    last_ball_vel: Option<Vector3<f32>>,
+}
+
+/// How we time the commit at the end of a kickoff approach.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KickoffVariant {
+    Early,
+    Standard,
+    Late,
+}
+
+impl KickoffVariant {
+    pub const ALL: [KickoffVariant; 3] = [
+        KickoffVariant::Early,
+        KickoffVariant::Standard,
+        KickoffVariant::Late,
+    ];
+
+    /// How much closer to the ball than usual (in uu) to wait before
+    /// committing.
+    pub fn commit_offset(self) -> f32 {
+        match self {
+            KickoffVariant::Early => -25.0,
+            KickoffVariant::Standard => 0.0,
+            KickoffVariant::Late => 25.0,
+        }
+    }
+}
+
+/// What happened in the first few seconds after a kickoff.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KickoffOutcome {
+    /// Whether we touched the ball first.
+    pub first_touch: bool,
+    /// Whether the ball was on the enemy's half once things settled down.
+    pub ball_on_enemy_half: bool,
+    pub first_shot: Option<FirstShot>,
+}
+
+/// The first shot on goal after a kickoff, and how many seconds after the
+/// first touch it happened.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FirstShot {
+    Ours(f32),
+    Theirs(f32),
+}
+
+impl KickoffOutcome {
+    /// Returns `true` if the kickoff went the enemy's way.
+    fn enemy_won(&self) -> bool {
+        self.score() <= 0.0
+    }
+
+    /// Higher is better for us.
+    fn score(&self) -> f32 {
+        let mut score = 0.0;
+        if self.first_touch {
+            score += 1.0;
+        }
+        if self.ball_on_enemy_half {
+            score += 1.0;
+        }
+        // A quick shot means the kickoff itself created the chance.
+        let shot_value = |time: f32| 2.0 - time / MatchMemory::OUTCOME_WINDOW;
+        match self.first_shot {
+            Some(FirstShot::Ours(time)) => score += shot_value(time),
+            Some(FirstShot::Theirs(time)) => score -= shot_value(time),
+            None => {}
+        }
+        score
+    }
+}
+
+/// A running tally of the kickoffs we went for this match.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct KickoffStats {
+    pub contested: u32,
+    /// How many of those went the enemy's way.
+    pub lost: u32,
+}
+
+impl KickoffStats {
+    /// Don't read anything into fewer kickoffs than this.
+    const MIN_KICKOFFS: u32 = 3;
+    /// Losing at least this share of kickoffs counts as consistently.
+    const LOSING_RATE: f32 = 0.6;
+    /// Faking every time would mean never finding out if the enemy's kickoff
+    /// got worse, so go for the ball now and then anyway.
+    const RETRY_CHANCE: f32 = 0.25;
+
+    fn record(&mut self, outcome: &KickoffOutcome) {
+        self.contested += 1;
+        if outcome.enemy_won() {
+            self.lost += 1;
+        }
+    }
+
+    pub fn enemy_wins_consistently(&self) -> bool {
+        self.contested >= Self::MIN_KICKOFFS
+            && self.lost as f32 / self.contested as f32 >= Self::LOSING_RATE
+    }
+
+    /// Whether to fake the next kickoff. `random` should be uniform in
+    /// `[0, 1)`.
+    pub fn should_fake(&self, random: f32) -> bool {
+        self.enemy_wins_consistently() && random >= Self::RETRY_CHANCE
+    }
+}
+
+#[derive(Copy, Clone)]
+struct KickoffRecord {
+    variant: KickoffVariant,
+    outcome: KickoffOutcome,
+}
+
+struct PendingKickoff {
+    variant: KickoffVariant,
+    /// The time of the first touch, and whether it was ours.
+    first_touch: Option<(f32, bool)>,
+    first_shot: Option<FirstShot>,
+}
+
+impl MatchMemory {
+    /// How long after the first touch a kickoff is judged.
+    const OUTCOME_WINDOW: f32 = 5.0;
+    /// Pick variants at random until we've seen this many kickoffs.
+    const MIN_KICKOFFS: usize = 3;
+    /// Even once we know what works, try something else now and then in case
+    /// the opponent adapts.
+    const EXPLORE_CHANCE: f32 = 0.2;
+    /// A change in ball velocity at least this big (in uu/s, over one frame)
+    /// counts as a touch.
+    const TOUCH_MIN_DELTA_V: f32 = 100.0;
+    /// A car this close to the ball (center to center) might have touched it.
+    const TOUCH_MAX_DIST: f32 = 250.0;
+    /// One touch can span a couple frames.
+    const TOUCH_MIN_GAP: f32 = 0.1;
+    /// Forget touches older than this.
+    const TOUCH_MEMORY: f32 = 10.0;
+
+    pub fn new() -> Self {
+        Self {
+            kickoffs: Vec::new(),
+            pending_kickoff: None,
+            kickoff_stats: KickoffStats::default(),
+            enemy_touches: VecDeque::new(),
+            last_touch_ours: None,
+            last_own_touch: None,
+            last_touch: None,
+            last_ball_vel: None,
+        }
+    }
+
+    /// The time of the enemy's most recent touch, if any.
+    pub fn last_enemy_touch(&self) -> Option<f32> {
+        self.enemy_touches.back().cloned()
+    }
+
+    /// Returns `true` if we were the last to touch the ball.
+    pub fn last_touch_ours(&self) -> bool {
+        self.last_touch_ours == Some(true)
+    }
+
+    /// The time of our most recent touch, if any.
+    pub fn last_own_touch(&self) -> Option<f32> {
+        self.last_own_touch
+    }
+
+    /// The time of the most recent touch by anyone, if any. A touch that spans
+    /// a few frames is stamped with the first. Unlike the other touch queries,
+    /// this includes touches we couldn't pin on either team.
+    pub fn last_touch(&self) -> Option<f32> {
+        self.last_touch
+    }
+
+    /// How many times the enemy has touched the ball since `time`.
+    pub fn enemy_touches_since(&self, time: f32) -> usize {
+        self.enemy_touches.iter().filter(|&&t| t >= time).count()
+    }
+
+    pub fn kickoff_stats(&self) -> KickoffStats {
+        self.kickoff_stats
+    }
+
+    /// Choose a kickoff variant. `random` should be uniform in `[0, 1)`.
+    pub fn choose_kickoff_variant(&self, random: f32) -> KickoffVariant {
+        if self.kickoffs.len() < Self::MIN_KICKOFFS {
+            return pick_variant(random);
+        }
+        if random < Self::EXPLORE_CHANCE {
+            return pick_variant(random / Self::EXPLORE_CHANCE);
+        }
+        *KickoffVariant::ALL
+            .iter()
+            .max_by(|&&a, &&b| self.mean_score(a).partial_cmp(&self.mean_score(b)).unwrap())
+            .unwrap()
+    }
+
+    /// Untried variants score as infinitely good, so that each one gets a
+    /// chance.
+    fn mean_score(&self, variant: KickoffVariant) -> f32 {
+        let scores: Vec<_> = self
+            .kickoffs
+            .iter()
+            .filter(|r| r.variant == variant)
+            .map(|r| r.outcome.score())
+            .collect();
+        if scores.is_empty() {
+            return std::f32::INFINITY;
+        }
+        scores.iter().sum::<f32>() / scores.len() as f32
+    }
+
+    /// Start from what we learned in earlier matches against this opponent.
+    pub fn learn_from(&mut self, report: ScoutingReport) {
+        let current = mem::replace(&mut self.kickoffs, report.kickoffs);
+        self.kickoffs.extend(current);
+    }
+
+    /// What's worth remembering about this opponent for next time: our
+    /// kickoffs, plus the `tendencies` the `OpponentModel` picked up.
+    pub fn scouting_report(&self, tendencies: Tendencies) -> ScoutingReport {
+        let skip = self
+            .kickoffs
+            .len()
+            .saturating_sub(ScoutingReport::MAX_KICKOFFS);
+        ScoutingReport {
+            kickoffs: self.kickoffs[skip..].to_vec(),
+            tendencies,
+        }
+    }
+
+    /// Start watching for the outcome of a kickoff that is about to happen.
+    pub fn kickoff_started(&mut self, variant: KickoffVariant) {
+        self.pending_kickoff = Some(PendingKickoff {
+            variant,
+            first_touch: None,
+            first_shot: None,
+        });
+    }
+
+    /// Call this once per frame.
+    pub fn update(
+        &mut self,
+        game: &Game<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        scenario: &Scenario<'_>,
+        eeg: &mut EEG,
+    ) {
+        self.track_touches(game, packet);
+        self.follow_up_kickoff(game, packet, scenario, eeg);
+    }
+
+    /// The packet doesn't say who touched the ball, so infer it from sudden
+    /// changes in the ball's velocity while only one team is close enough to
+    /// have caused them.
+    fn track_touches(&mut self, game: &Game<'_>, packet: &common::halfway_house::LiveDataPacket) {
+        let now = packet.GameInfo.TimeSeconds;
+        let ball = &packet.GameBall.Physics;
+        let last_ball_vel = mem::replace(&mut self.last_ball_vel, Some(ball.vel()));
+        let last_ball_vel = some_or_else!(last_ball_vel, {
+            return;
+        });
+        if (ball.vel() - last_ball_vel).norm() < Self::TOUCH_MIN_DELTA_V {
+            return;
+        }
+
+        let near_ball = |car: &common::halfway_house::PlayerInfo| {
+            (car.Physics.loc() - ball.loc()).norm() < Self::TOUCH_MAX_DIST
+        };
+        let ours = game.cars(game.team).any(near_ball);
+        let theirs = game.cars(game.enemy_team).any(near_ball);
+        if !ours && !theirs {
+            // It bounced off the floor or a wall.
+            return;
+        }
+        if self
+            .last_touch
+            .map_or(true, |t| now - t >= Self::TOUCH_MIN_GAP)
+        {
+            self.last_touch = Some(now);
+        }
+        if ours == theirs {
+            // Both teams were close enough, so we can't tell whose it was.
+            return;
+        }
+        self.last_touch_ours = Some(ours);
+        if ours {
+            self.last_own_touch = Some(now);
+            return;
+        }
+        if self
+            .last_enemy_touch()
+            .map_or(false, |t| now - t < Self::TOUCH_MIN_GAP)
+        {
+            return;
+        }
+
+        self.enemy_touches.push_back(now);
+        let forget_before = now - Self::TOUCH_MEMORY;
+        self.enemy_touches.retain(|&t| t >= forget_before);
+    }
+
+    /// Follow up on the latest kickoff, and record its outcome once it's clear.
+    fn follow_up_kickoff(
+        &mut self,
+        game: &Game<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        scenario: &Scenario<'_>,
+        eeg: &mut EEG,
+    ) {
+        let pending = some_or_else!(self.pending_kickoff.as_mut(), {
+            return;
+        });
+        let now = packet.GameInfo.TimeSeconds;
+        let is_kickoff = PreKickoff::is_kickoff(&packet.GameBall);
+
+        let (touch_time, _) = match pending.first_touch {
+            Some(touch) => touch,
+            None if is_kickoff => return,
+            None => {
+                // The ball just left the center spot. There's no touch info in
+                // the packet, so assume whoever is closest hit it.
+                let touch = (now, we_are_closest(game, packet));
+                pending.first_touch = Some(touch);
+                touch
+            }
+        };
+        let elapsed = now - touch_time;
+
+        if pending.first_shot.is_none() {
+            pending.first_shot = detect_shot(game, scenario, elapsed);
+        }
+
+        // A goal brings the ball back to center, which also ends the window.
+        if elapsed >= Self::OUTCOME_WINDOW || is_kickoff {
+            let ball_y = packet.GameBall.Physics.loc().y;
+            let record = KickoffRecord {
+                variant: pending.variant,
+                outcome: KickoffOutcome {
+                    first_touch: pending.first_touch.map(|(_, ours)| ours).unwrap(),
+                    ball_on_enemy_half: ball_y.signum() == game.enemy_goal().center_2d.y.signum(),
+                    first_shot: pending.first_shot,
+                },
+            };
+            eeg.log(
+                name_of_type!(MatchMemory),
+                format!("kickoff {:?}: {:?}", record.variant, record.outcome),
+            );
+            self.kickoff_stats.record(&record.outcome);
+            self.kickoffs.push(record);
+            self.pending_kickoff = None;
+        }
+    }
+}
+
+/// Everything worth keeping between matches against the same opponent, e.g.,
+/// across a tournament series: how our kickoffs went, from `MatchMemory`, and
+/// the `OpponentModel`'s `Tendencies`. Saved as one file per opponent.
+#[derive(Default)]
+pub struct ScoutingReport {
+    kickoffs: Vec<KickoffRecord>,
+    tendencies: Tendencies,
+}
+
+impl ScoutingReport {
+    /// Only remember this many of the most recent kickoffs, so old habits can
+    /// fade if the opponent gets updated.
+    const MAX_KICKOFFS: usize = 100;
+
+    fn path(dir: &Path, opponent: &str) -> PathBuf {
+        let name: String = opponent
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        dir.join(format!("{}.tsv", name))
+    }
+
+    pub fn tendencies(&self) -> Tendencies {
+        self.tendencies
+    }
+
+    /// Load what we know about `opponent`. If we've never met, that's an empty
+    /// report.
+    pub fn load(dir: impl AsRef<Path>, opponent: &str) -> io::Result<Self> {
+        let path = Self::path(dir.as_ref(), opponent);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, dir: impl AsRef<Path>, opponent: &str) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(Self::path(dir, opponent), self.to_string())
+    }
+}
+
+/// One line per kickoff, then one for the tendencies, tab-separated, in the
+/// same format `FromStr` reads.
+impl fmt::Display for ScoutingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for record in &self.kickoffs {
+            let outcome = &record.outcome;
+            let first_shot = match outcome.first_shot {
+                Some(FirstShot::Ours(time)) => format!("ours:{}", time),
+                Some(FirstShot::Theirs(time)) => format!("theirs:{}", time),
+                None => "none".to_string(),
+            };
+            writeln!(
+                f,
+                "kickoff\t{:?}\t{}\t{}\t{}",
+                record.variant,
+                outcome.first_touch as u8,
+                outcome.ball_on_enemy_half as u8,
+                first_shot,
+            )?;
+        }
+        let t = &self.tendencies;
+        writeln!(
+            f,
+            "tendencies\t{}\t{}\t{}\t{}",
+            t.challenges, t.challenge_delay_total, t.aerials, t.play_time,
+        )
+    }
+}
+
+impl FromStr for ScoutingReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut result = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let bad_line = || format!("bad scouting line {}: {:?}", index + 1, line);
+            let fields: Vec<_> = line.split('\t').collect();
+            match &fields[..] {
+                ["kickoff", variant, first_touch, ball_on_enemy_half, first_shot] => {
+                    let variant = KickoffVariant::ALL
+                        .iter()
+                        .cloned()
+                        .find(|v| format!("{:?}", v) == *variant)
+                        .ok_or_else(bad_line)?;
+                    let flag = |s: &str| match s {
+                        "0" => Ok(false),
+                        "1" => Ok(true),
+                        _ => Err(bad_line()),
+                    };
+                    let first_shot = match first_shot.find(':') {
+                        None if *first_shot == "none" => None,
+                        Some(i) => {
+                            let time = first_shot[i + 1..].parse().map_err(|_| bad_line())?;
+                            match &first_shot[..i] {
+                                "ours" => Some(FirstShot::Ours(time)),
+                                "theirs" => Some(FirstShot::Theirs(time)),
+                                _ => return Err(bad_line()),
+                            }
+                        }
+                        None => return Err(bad_line()),
+                    };
+                    result.kickoffs.push(KickoffRecord {
+                        variant,
+                        outcome: KickoffOutcome {
+                            first_touch: flag(first_touch)?,
+                            ball_on_enemy_half: flag(ball_on_enemy_half)?,
+                            first_shot,
+                        },
+                    });
+                }
+                ["tendencies", challenges, challenge_delay_total, aerials, play_time] => {
+                    result.tendencies = Tendencies {
+                        challenges: challenges.parse().map_err(|_| bad_line())?,
+                        challenge_delay_total: challenge_delay_total
+                            .parse()
+                            .map_err(|_| bad_line())?,
+                        aerials: aerials.parse().map_err(|_| bad_line())?,
+                        play_time: play_time.parse().map_err(|_| bad_line())?,
+                    };
+                }
+                _ if line.trim().is_empty() => {}
+                _ => return Err(bad_line()),
+            }
+        }
+        Ok(result)
+    }
+}
+
+fn pick_variant(random: f32) -> KickoffVariant {
+    let count = KickoffVariant::ALL.len();
+    KickoffVariant::ALL[((random * count as f32) as usize).min(count - 1)]
+}
+
+fn we_are_closest(game: &Game<'_>, packet: &common::halfway_house::LiveDataPacket) -> bool {
+    let ball_loc = packet.GameBall.Physics.loc();
+    let closest = |team| {
+        game.cars(team)
+            .map(|car| (car.Physics.loc() - ball_loc).norm())
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap_or(std::f32::INFINITY)
+    };
+    closest(game.team) <= closest(game.enemy_team)
+}
+
+fn detect_shot(game: &Game<'_>, scenario: &Scenario<'_>, elapsed: f32) -> Option<FirstShot> {
+    let prediction = scenario.ball_prediction();
+    if prediction
+        .iter()
+        .any(|ball| game.enemy_goal().ball_is_scored(ball.loc))
+    {
+        Some(FirstShot::Ours(elapsed))
+    } else if prediction
+        .iter()
+        .any(|ball| game.own_goal().ball_is_scored(ball.loc))
+    {
+        Some(FirstShot::Theirs(elapsed))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{
+        match_memory::{
+            FirstShot, KickoffOutcome, KickoffRecord, KickoffStats, KickoffVariant, MatchMemory,
+            ScoutingReport,
+        },
+        opponent_model::Tendencies,
+        Game,
+    };
+    use common::halfway_house::{LiveDataPacket, PlayerInfo};
+    use std::iter;
+
+    fn record(
+        variant: KickoffVariant,
+        first_touch: bool,
+        first_shot: Option<FirstShot>,
+    ) -> KickoffRecord {
+        KickoffRecord {
+            variant,
+            outcome: KickoffOutcome {
+                first_touch,
+                ball_on_enemy_half: first_touch,
+                first_shot,
+            },
+        }
+    }
+
+    #[test]
+    fn outcome_score() {
+        let won = record(KickoffVariant::Early, true, Some(FirstShot::Ours(1.0)));
+        let lost = record(KickoffVariant::Early, false, Some(FirstShot::Theirs(1.0)));
+        let slow = record(KickoffVariant::Early, true, Some(FirstShot::Ours(4.0)));
+        assert!(won.outcome.score() > slow.outcome.score());
+        assert!(slow.outcome.score() > lost.outcome.score());
+    }
+
+    #[test]
+    fn kickoff_stats() {
+        let mut stats = KickoffStats::default();
+        let lost = record(KickoffVariant::Standard, false, None);
+        let won = record(KickoffVariant::Standard, true, None);
+        stats.record(&lost.outcome);
+        stats.record(&lost.outcome);
+        assert!(!stats.enemy_wins_consistently());
+        stats.record(&won.outcome);
+        assert!(stats.enemy_wins_consistently());
+        assert!(stats.should_fake(0.9));
+        assert!(!stats.should_fake(0.1));
+        stats.record(&won.outcome);
+        assert!(!stats.enemy_wins_consistently());
+    }
+
+    #[test]
+    fn random_until_enough_kickoffs() {
+        let memory = MatchMemory::new();
+        assert_eq!(memory.choose_kickoff_variant(0.0), KickoffVariant::Early);
+        assert_eq!(memory.choose_kickoff_variant(0.5), KickoffVariant::Standard);
+        assert_eq!(memory.choose_kickoff_variant(0.99), KickoffVariant::Late);
+    }
+
+    #[test]
+    fn prefer_best_outcome() {
+        let mut memory = MatchMemory::new();
+        memory
+            .kickoffs
+            .push(record(KickoffVariant::Early, false, None));
+        memory.kickoffs.push(record(
+            KickoffVariant::Standard,
+            false,
+            Some(FirstShot::Theirs(2.0)),
+        ));
+        memory
+            .kickoffs
+            .push(record(KickoffVariant::Late, true, None));
+        memory
+            .kickoffs
+            .push(record(KickoffVariant::Early, true, None));
+
+        for &random in &[0.3, 0.5, 0.99] {
+            assert_eq!(memory.choose_kickoff_variant(random), KickoffVariant::Late);
+        }
+        // Still explore now and then.
+        assert_eq!(memory.choose_kickoff_variant(0.0), KickoffVariant::Early);
+    }
+
+    #[test]
+    fn scouting_report_round_trip() {
+        let mut memory = MatchMemory::new();
+        memory.kickoffs = vec![
+            record(KickoffVariant::Late, true, Some(FirstShot::Ours(1.5))),
+            record(KickoffVariant::Early, false, None),
+        ];
+        let tendencies = Tendencies {
+            challenges: 4,
+            challenge_delay_total: 1.5,
+            aerials: 3,
+            play_time: 95.5,
+        };
+        let text = memory.scouting_report(tendencies).to_string();
+        let report: ScoutingReport = text.parse().unwrap();
+        assert_eq!(report.to_string(), text);
+        assert_eq!(report.tendencies(), tendencies);
+
+        // A fresh match starts out knowing what the last one learned.
+        let mut next_match = MatchMemory::new();
+        next_match.learn_from(report);
+        assert_eq!(next_match.kickoffs.len(), 2);
+        assert_eq!(
+            next_match.kickoffs[0].outcome.first_shot,
+            Some(FirstShot::Ours(1.5)),
+        );
+    }
+
+    #[test]
+    fn scouting_report_bad_line() {
+        assert!("kickoff\tSideways\t1\t1\tnone"
+            .parse::<ScoutingReport>()
+            .is_err());
+        assert!("kickoff\tEarly\t1\t1\tours:soon"
+            .parse::<ScoutingReport>()
+            .is_err());
+    }
+
+    fn touch_packet(time: f32, ball_vel_x: f32) -> LiveDataPacket {
+        let mut packet = LiveDataPacket::default()
+            .with_time(time)
+            .with_cars(iter::once(PlayerInfo::default()));
+        // Right next to the car.
+        packet.GameBall.Physics.Location.X = 150.0;
+        packet.GameBall.Physics.Velocity.X = ball_vel_x;
+        packet
+    }
+
+    #[test]
+    fn one_touch_spans_frames() {
+        let mut memory = MatchMemory::new();
+        for &(time, ball_vel_x) in &[(0.0, 0.0), (0.01, 1000.0), (0.02, 2000.0)] {
+            let packet = touch_packet(time, ball_vel_x);
+            memory.track_touches(&Game::soccar_offline(&packet, 0), &packet);
+        }
+        assert_eq!(memory.last_touch(), Some(0.01));
+        assert_eq!(memory.last_own_touch(), Some(0.02));
+    }
+}
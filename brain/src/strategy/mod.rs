@@ -1,26 +1,49 @@
 pub use crate::strategy::{
-    behavior::{Action, Behavior, Priority},
+    ball_charge::{BallCharge, BallChargeTracker},
+    behavior::{Action, Behavior, Child, Priority},
+    boost_tracker::BoostTracker,
     context::{Context, Context2},
+    difficulty::Difficulty,
     dropshot::Dropshot,
     game::{
-        infer_game_mode, BoostPickup, Game, Goal, Team, Vehicle, SOCCAR_GOAL_BLUE,
-        SOCCAR_GOAL_ORANGE,
+        infer_game_mode, BoostPad, BoostPickup, DropshotTile, Game, Goal, Team, Vehicle,
+        SOCCAR_GOAL_BLUE, SOCCAR_GOAL_ORANGE,
     },
-    pitch::Pitch,
+    match_memory::{KickoffStats, MatchMemory, ScoutingReport},
+    mutators::Mutators,
+    opponent_model::{OpponentModel, Tendencies},
+    personality::Personality,
+    pitch::{Pitch, DFH_STADIUM},
+    risk_profile::{RiskMode, RiskProfile},
+    rumble::Rumble,
     runner::Runner,
+    safety_filter::{SafetyFilter, Veto},
     scenario::Scenario,
     soccar::Soccar,
+    team_coordination::{Role, TeamCoordination},
 };
 
+mod ball_charge;
 mod behavior;
+mod boost_tracker;
 mod context;
+mod difficulty;
 mod dropshot;
 mod game;
+mod input_validator;
+mod match_memory;
+mod mutators;
 #[cfg(test)]
 pub mod null;
+mod opponent_model;
+mod personality;
 mod pitch;
+mod risk_profile;
+mod rumble;
 mod runner;
+mod safety_filter;
 mod scenario;
 mod soccar;
 #[allow(clippy::module_inception)]
 mod strategy;
+mod team_coordination;
@@ -0,0 +1,165 @@
+use common::{kinematics::kinematic_time, rl};
+use simulate::{
+    car_double_jump,
+    car_single_jump::{self, JUMP_MAX_Z},
+};
+
+/// The physics settings that mutators change, as far as we can tell.
+///
+/// Gravity comes straight from the packet. The boost settings aren't reported
+/// anywhere, so `MutatorTracker` infers them from watching our boost meter.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mutators {
+    /// Vertical acceleration due to gravity. Negative is down.
+    pub gravity: f32,
+    /// Boosting never drains the meter.
+    pub unlimited_boost: bool,
+    /// The meter refills on its own over time.
+    pub boost_recharge: bool,
+}
+
+impl Mutators {
+    /// Gravity this close to the default counts as the default, so our tables
+    /// still apply.
+    const GRAVITY_TOLERANCE: f32 = 1.0;
+
+    pub fn standard() -> Self {
+        Self {
+            gravity: rl::GRAVITY,
+            unlimited_boost: false,
+            boost_recharge: false,
+        }
+    }
+
+    /// Read what the packet tells us. Boost settings are left at the defaults.
+    pub fn from_game_info(info: &common::halfway_house::GameInfo) -> Self {
+        // Packets built offline leave this at zero.
+        let gravity = if info.WorldGravityZ < 0.0 {
+            info.WorldGravityZ
+        } else {
+            rl::GRAVITY
+        };
+        Self {
+            gravity,
+            ..Self::standard()
+        }
+    }
+
+    pub fn standard_gravity(&self) -> bool {
+        (self.gravity - rl::GRAVITY).abs() < Self::GRAVITY_TOLERANCE
+    }
+
+    /// How much longer things stay in the air than with normal gravity.
+    pub fn hang_time_scale(&self) -> f32 {
+        rl::GRAVITY / self.gravity
+    }
+
+    /// Whether it's worth going out of our way for boost pads.
+    pub fn boost_pads_matter(&self) -> bool {
+        !self.unlimited_boost
+    }
+
+    /// The highest a single jump can reach.
+    pub fn jump_max_z(&self) -> f32 {
+        if self.standard_gravity() {
+            return JUMP_MAX_Z;
+        }
+        let (z_0, v_1) = self.jump_force_phase();
+        rl::OCTANE_NEUTRAL_Z + z_0 + v_1 * v_1 / (2.0 * -self.gravity)
+    }
+
+    /// How long a single jump takes to reach `z`, or `None` if it can't.
+    pub fn jump_time_to_z(&self, z: f32) -> Option<f32> {
+        if self.standard_gravity() {
+            return car_single_jump::time_to_z(z);
+        }
+        if z > self.jump_max_z() {
+            return None;
+        }
+        let dz = z - rl::OCTANE_NEUTRAL_Z;
+        if dz <= 0.0 {
+            return Some(0.0);
+        }
+        // Holding the button pushes us up for a while, then we coast.
+        let (z_0, v_1) = self.jump_force_phase();
+        if dz <= z_0 {
+            let a = rl::CAR_JUMP_ACCEL + self.gravity;
+            return kinematic_time(dz, rl::CAR_JUMP_IMPULSE_SPEED, a);
+        }
+        kinematic_time(dz - z_0, v_1, self.gravity).map(|t| rl::CAR_JUMP_FORCE_TIME + t)
+    }
+
+    /// The highest a fast double jump can reach.
+    pub fn double_jump_max_z(&self) -> f32 {
+        car_double_jump::max_z(self.gravity)
+    }
+
+    /// How long a fast double jump takes to reach `z`, or `None` if it can't.
+    pub fn double_jump_time_to_z(&self, z: f32) -> Option<f32> {
+        car_double_jump::time_to_z(z, self.gravity)
+    }
+
+    /// The height gained and the vertical speed at the moment we have to let
+    /// go of jump.
+    fn jump_force_phase(&self) -> (f32, f32) {
+        let t = rl::CAR_JUMP_FORCE_TIME;
+        let v_0 = rl::CAR_JUMP_IMPULSE_SPEED;
+        let a = rl::CAR_JUMP_ACCEL + self.gravity;
+        (v_0 * t + 0.5 * a * t * t, v_0 + a * t)
+    }
+}
+
+impl Default for Mutators {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::Mutators;
+    use common::{halfway_house::GameInfo, rl};
+    use simulate::car_single_jump::JUMP_MAX_Z;
+
+    fn low_gravity() -> Mutators {
+        Mutators {
+            gravity: rl::GRAVITY / 2.0,
+            ..Mutators::standard()
+        }
+    }
+
+    #[test]
+    fn offline_packets_get_standard_gravity() {
+        let mutators = Mutators::from_game_info(&GameInfo::default());
+        assert_eq!(mutators, Mutators::standard());
+    }
+
+    #[test]
+    fn low_gravity_jumps_higher_and_slower() {
+        let low = low_gravity();
+        assert!(!low.standard_gravity());
+        assert!(low.jump_max_z() > JUMP_MAX_Z);
+        assert!((low.hang_time_scale() - 2.0).abs() < 1e-3);
+
+        let standard = Mutators::standard();
+        let z = 200.0;
+        assert!(low.jump_time_to_z(z).unwrap() < standard.jump_time_to_z(z).unwrap());
+        assert!(low.jump_time_to_z(JUMP_MAX_Z + 50.0).is_some());
+        assert!(standard.jump_time_to_z(JUMP_MAX_Z + 50.0).is_none());
+    }
+
+    #[test]
+    fn model_roughly_matches_table() {
+        // Nudge gravity just past the tolerance so the model is used.
+        let model = Mutators {
+            gravity: rl::GRAVITY - 1.5,
+            ..Mutators::standard()
+        };
+        assert!((model.jump_max_z() - JUMP_MAX_Z).abs() < 20.0);
+        let (table, model) = (
+            Mutators::standard().jump_time_to_z(150.0).unwrap(),
+            model.jump_time_to_z(150.0).unwrap(),
+        );
+        assert!((table - model).abs() < 0.05);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::strategy::game::Game;
+use common::prelude::*;
+
+/// How the enemy actually plays, as opposed to how an optimal opponent would.
+///
+/// `Scenario` races us against the enemy as if they drove for the ball the
+/// instant it became worth going for. Most opponents are slower than that on
+/// the uptake, so this watches how long they really take to answer our touches
+/// (and how often they fly) over the match.
+pub struct OpponentModel {
+    challenge_delay: RunningMean,
+    /// The time of our last touch that the enemy hasn't answered yet.
+    unanswered_touch: Option<f32>,
+    last_own_touch: Option<f32>,
+    /// Whether each car in the packet was in an aerial last frame, so it lines
+    /// up with `GameCars`.
+    in_aerial: Vec<bool>,
+    /// Seconds of live play watched.
+    play_time: f32,
+    last_time: Option<f32>,
+    aerials: u32,
+}
+
+/// The part of an `OpponentModel` worth keeping between matches against the
+/// same opponent. See `ScoutingReport`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Tendencies {
+    /// How many of our touches the enemy answered,
+    pub challenges: u32,
+    /// and the total seconds they took to do it.
+    pub challenge_delay_total: f32,
+    pub aerials: u32,
+    /// Seconds of live play those were seen over.
+    pub play_time: f32,
+}
+
+impl OpponentModel {
+    /// Don't read anything into fewer challenges than this.
+    const MIN_CHALLENGES: u32 = 3;
+    /// If the enemy still hasn't gone for the ball this long after our touch,
+    /// they weren't going to. Don't count it.
+    const MAX_CHALLENGE_DELAY: f32 = 1.5;
+    /// An enemy closing on the ball at least this fast is going for it. (Same
+    /// as `Scenario`'s idea of committed.)
+    const COMMITTED_SPEED: f32 = 500.0;
+    /// Answering our touches quicker than this on average counts as
+    /// challenging early.
+    const EARLY_CHALLENGE_DELAY: f32 = 0.4;
+    /// Off the ground and at least this high counts as an aerial.
+    const AERIAL_MIN_Z: f32 = 300.0;
+    /// Only carry this many challenges' worth of history into the next match,
+    /// so old habits can fade if the opponent gets updated.
+    const MAX_REMEMBERED_CHALLENGES: u32 = 100;
+
+    pub fn new() -> Self {
+        Self {
+            challenge_delay: RunningMean::default(),
+            unanswered_touch: None,
+            last_own_touch: None,
+            in_aerial: Vec::new(),
+            play_time: 0.0,
+            last_time: None,
+            aerials: 0,
+        }
+    }
+
+    /// Start from what we learned in earlier matches against this opponent.
+    pub fn learn_from(&mut self, tendencies: Tendencies) {
+        self.challenge_delay.total += tendencies.challenge_delay_total;
+        self.challenge_delay.count += tendencies.challenges;
+        self.aerials += tendencies.aerials;
+        self.play_time += tendencies.play_time;
+    }
+
+    /// What's worth remembering about this opponent for next time.
+    pub fn tendencies(&self) -> Tendencies {
+        let max = Self::MAX_REMEMBERED_CHALLENGES;
+        let mut delay = self.challenge_delay;
+        if delay.count > max {
+            delay.total *= max as f32 / delay.count as f32;
+            delay.count = max;
+        }
+        Tendencies {
+            challenges: delay.count,
+            challenge_delay_total: delay.total,
+            aerials: self.aerials,
+            play_time: self.play_time,
+        }
+    }
+
+    /// Update from the latest packet. `last_own_touch` comes from
+    /// `MatchMemory`.
+    pub fn update(
+        &mut self,
+        game: &Game<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        last_own_touch: Option<f32>,
+    ) {
+        let now = packet.GameInfo.TimeSeconds;
+        let last_time = self.last_time.replace(now);
+        if !packet.GameInfo.RoundActive {
+            self.unanswered_touch = None;
+            return;
+        }
+        if let Some(last_time) = last_time {
+            self.play_time += (now - last_time).max(0.0);
+        }
+
+        self.observe_challenges(game, packet, last_own_touch);
+        self.observe_aerials(game, packet);
+    }
+
+    fn observe_challenges(
+        &mut self,
+        game: &Game<'_>,
+        packet: &common::halfway_house::LiveDataPacket,
+        last_own_touch: Option<f32>,
+    ) {
+        let now = packet.GameInfo.TimeSeconds;
+        if last_own_touch.is_some() && last_own_touch != self.last_own_touch {
+            self.unanswered_touch = last_own_touch;
+        }
+        self.last_own_touch = last_own_touch;
+
+        let touch = some_or_else!(self.unanswered_touch, {
+            return;
+        });
+        let delay = now - touch;
+        if delay >= Self::MAX_CHALLENGE_DELAY {
+            self.unanswered_touch = None;
+            return;
+        }
+
+        let ball_loc = packet.GameBall.Physics.loc_2d();
+        let committed = game.cars(game.enemy_team).any(|enemy| {
+            let to_ball = (ball_loc - enemy.Physics.loc_2d()).normalize();
+            enemy.Physics.vel_2d().dot(&to_ball) >= Self::COMMITTED_SPEED
+        });
+        if committed {
+            self.challenge_delay.record(delay);
+            self.unanswered_touch = None;
+        }
+    }
+
+    fn observe_aerials(&mut self, game: &Game<'_>, packet: &common::halfway_house::LiveDataPacket) {
+        self.in_aerial.resize(packet.GameCars.len(), false);
+        let enemy_team = game.enemy_team.to_ffi();
+        for (car, was_in_aerial) in packet.GameCars.iter().zip(&mut self.in_aerial) {
+            if car.Team != enemy_team || car.Demolished {
+                continue;
+            }
+
+            let in_aerial = !car.OnGround && car.Physics.loc().z >= Self::AERIAL_MIN_Z;
+            if in_aerial && !*was_in_aerial {
+                self.aerials += 1;
+            }
+            *was_in_aerial = in_aerial;
+        }
+    }
+
+    /// How long the enemy usually takes to go for the ball after we touch it.
+    /// Zero until we've seen enough to say.
+    pub fn challenge_delay(&self) -> f32 {
+        if self.challenge_delay.count < Self::MIN_CHALLENGES {
+            return 0.0;
+        }
+        self.challenge_delay.mean().unwrap_or(0.0)
+    }
+
+    /// Returns `true` if the enemy makes a habit of going for the ball right
+    /// after we touch it, e.g., a keeper who charges out to meet every shot.
+    pub fn early_challenger(&self) -> bool {
+        self.challenge_delay.count >= Self::MIN_CHALLENGES
+            && self.challenge_delay() < Self::EARLY_CHALLENGE_DELAY
+    }
+
+    /// How much longer the enemy will likely wait before going for the ball.
+    /// This is zero unless we touched the ball recently and they haven't
+    /// answered yet; otherwise they're as quick as anyone.
+    pub fn pending_challenge_delay(&self, now: f32) -> f32 {
+        let touch = some_or_else!(self.unanswered_touch, {
+            return 0.0;
+        });
+        (self.challenge_delay() - (now - touch)).max(0.0)
+    }
+
+    /// How many aerials the enemy team goes for per minute of play.
+    pub fn aerials_per_minute(&self) -> f32 {
+        if self.play_time <= 0.0 {
+            return 0.0;
+        }
+        self.aerials as f32 / (self.play_time / 60.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct RunningMean {
+    total: f32,
+    count: u32,
+}
+
+impl RunningMean {
+    fn record(&mut self, x: f32) {
+        self.total += x;
+        self.count += 1;
+    }
+
+    fn mean(&self) -> Option<f32> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.total / self.count as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::opponent_model::{OpponentModel, RunningMean};
+
+    #[test]
+    fn running_mean() {
+        let mut mean = RunningMean::default();
+        assert_eq!(mean.mean(), None);
+        mean.record(1.0);
+        mean.record(2.0);
+        assert_eq!(mean.mean(), Some(1.5));
+    }
+
+    #[test]
+    fn challenge_delay_needs_a_few_samples() {
+        let mut model = OpponentModel::new();
+        model.challenge_delay.record(0.6);
+        model.challenge_delay.record(0.4);
+        assert_eq!(model.challenge_delay(), 0.0);
+        model.challenge_delay.record(0.5);
+        assert!((model.challenge_delay() - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn early_challenger() {
+        let mut model = OpponentModel::new();
+        model.challenge_delay.record(0.1);
+        model.challenge_delay.record(0.2);
+        assert!(!model.early_challenger());
+        model.challenge_delay.record(0.3);
+        assert!(model.early_challenger());
+        for _ in 0..3 {
+            model.challenge_delay.record(1.0);
+        }
+        assert!(!model.early_challenger());
+    }
+
+    #[test]
+    fn delay_only_after_our_touch() {
+        let mut model = OpponentModel::new();
+        for _ in 0..3 {
+            model.challenge_delay.record(0.5);
+        }
+        assert_eq!(model.pending_challenge_delay(10.0), 0.0);
+        model.unanswered_touch = Some(10.0);
+        assert!((model.pending_challenge_delay(10.2) - 0.3).abs() < 1e-5);
+        assert_eq!(model.pending_challenge_delay(11.0), 0.0);
+    }
+
+    #[test]
+    fn tendencies_carry_over() {
+        let mut model = OpponentModel::new();
+        for _ in 0..150 {
+            model.challenge_delay.record(0.4);
+        }
+        model.aerials = 6;
+        model.play_time = 120.0;
+        let tendencies = model.tendencies();
+        assert_eq!(
+            tendencies.challenges,
+            OpponentModel::MAX_REMEMBERED_CHALLENGES,
+        );
+
+        let mut next_match = OpponentModel::new();
+        next_match.learn_from(tendencies);
+        assert!((next_match.challenge_delay() - 0.4).abs() < 1e-5);
+        assert!((next_match.aerials_per_minute() - 3.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+/// How much risk the bot is willing to take. The same code can field very
+/// different opponents by turning these knobs.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Personality {
+    /// When things look dicey and our possession is below this many seconds,
+    /// fall back to defense.
+    pub retreat_possession: f32,
+    /// Keep challenging for the ball until the enemy is this many seconds
+    /// ahead of us in the race to it.
+    pub challenge_deficit: f32,
+    /// Go out of our way to collect boost when we have less than this.
+    pub boost_reserve: i32,
+}
+
+impl Personality {
+    pub fn aggressive() -> Self {
+        Self {
+            retreat_possession: 0.0,
+            challenge_deficit: 3.0,
+            boost_reserve: 30,
+        }
+    }
+
+    /// The historical behavior.
+    pub fn balanced() -> Self {
+        Self {
+            retreat_possession: 0.5,
+            challenge_deficit: 2.0,
+            boost_reserve: 50,
+        }
+    }
+
+    pub fn passive() -> Self {
+        Self {
+            retreat_possession: 1.0,
+            challenge_deficit: 1.0,
+            boost_reserve: 70,
+        }
+    }
+}
+
+impl Default for Personality {
+    fn default() -> Self {
+        Self::balanced()
+    }
+}
+
+impl FromStr for Personality {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "aggressive" => Ok(Self::aggressive()),
+            "balanced" => Ok(Self::balanced()),
+            "passive" => Ok(Self::passive()),
+            _ => Err(format!("unknown personality {:?}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::Personality;
+
+    #[test]
+    fn parse() {
+        assert_eq!("passive".parse(), Ok(Personality::passive()));
+        assert!("reckless".parse::<Personality>().is_err());
+    }
+}
@@ -1,33 +1,173 @@
-use crate::utils::geometry::Plane;
-use common::rl;
+use crate::utils::geometry::{flattener::Flattener, Plane};
+use common::{prelude::*, rl};
 use lazy_static::lazy_static;
-use nalgebra::{Point3, Unit, Vector3};
+use nalgebra::{clamp, Isometry3, Point2, Point3, Unit, Vector3};
 use ordered_float::NotNan;
 
 pub struct Pitch {
     planes: Vec<Plane>,
+    /// The floor, walls and ceiling inside each goal. These take over from
+    /// `planes` once you're past the goal line.
+    goal_planes: Vec<Plane>,
+    /// The radius of the curve where the floor meets each wall.
+    wall_base_radius: f32,
+    /// Half the width of the opening in each back wall.
+    goal_mouth_max_x: f32,
+    /// How far each goal extends behind the goal line.
+    goal_depth: f32,
 }
 
 impl Pitch {
+    /// The surface nearest `point`. Near the top of each wall, where it curves
+    /// into the ceiling, this is a flat stand-in for the curve.
     pub fn closest_plane(&self, point: &Point3<f32>) -> &Plane {
-        self.planes
+        self.planes_at(point.to_2d())
             .iter()
             .min_by_key(|plane| NotNan::new(plane.distance_to_point(point)).unwrap())
             .unwrap()
     }
 
+    /// The surfaces that enclose `loc`: the goal's inside the goal, and the
+    /// field's everywhere else.
+    pub fn planes_at(&self, loc: Point2<f32>) -> &[Plane] {
+        if self.is_in_goal(loc) {
+            &self.goal_planes
+        } else {
+            &self.planes
+        }
+    }
+
     pub fn ground(&self) -> &Plane {
         &self.planes[0]
     }
+
+    /// The transform that folds the surface nearest `loc` flat onto the floor,
+    /// hinged at the seam between the two. This is the same unfolding wall
+    /// routes are planned in.
+    pub fn unfold_to_ground(&self, loc: &Point3<f32>) -> Flattener {
+        // The floor inside the goals never meets the floor, so there's nowhere
+        // to fold it. Just look straight down at it instead.
+        let transform = self
+            .unfold(self.closest_plane(loc), self.ground(), loc)
+            .unwrap_or_else(|()| Isometry3::identity());
+        Flattener::new(transform)
+    }
+
+    /// The transform that folds `surface` flat onto `target`, where `loc` is a
+    /// point on `surface`.
+    ///
+    /// Neighboring surfaces fold along their seam. Surfaces that face each
+    /// other, like the ceiling and the floor, fold over whichever wall is
+    /// nearest `loc`, the way a ball rolls from one to the other.
+    ///
+    /// Returns `Err(())` if the surfaces are parallel and face the same way.
+    pub fn unfold(
+        &self,
+        surface: &Plane,
+        target: &Plane,
+        loc: &Point3<f32>,
+    ) -> Result<Isometry3<f32>, ()> {
+        if surface.normal.dot(&target.normal) > -FACING_DOT {
+            return surface.unfold(target);
+        }
+
+        let hinge = self
+            .planes_at(loc.to_2d())
+            .iter()
+            .filter(|p| {
+                let to_surface = p.normal.dot(&surface.normal);
+                let to_target = p.normal.dot(&target.normal);
+                to_surface > -FACING_DOT
+                    && to_surface < 1.0 - FACING_DOT
+                    && to_target > -FACING_DOT
+                    && to_target < 1.0 - FACING_DOT
+            })
+            .min_by_key(|p| NotNan::new(p.distance_to_point(loc).abs()).unwrap())
+            .ok_or(())?;
+        Ok(hinge.unfold(target)? * surface.unfold(hinge)?)
+    }
+
+    /// Returns true if `loc` is behind a goal line, inside the goal.
+    pub fn is_in_goal(&self, loc: Point2<f32>) -> bool {
+        loc.x.abs() < self.goal_mouth_max_x
+            && loc.y.abs() > rl::FIELD_MAX_Y
+            && loc.y.abs() < rl::FIELD_MAX_Y + self.goal_depth
+    }
+
+    /// How far each goal extends behind the goal line.
+    pub fn goal_depth(&self) -> f32 {
+        self.goal_depth
+    }
+
+    /// The height of the drivable surface at a point on the floor, taking into
+    /// account the curved bases of the walls and the ramp into each goal.
+    pub fn floor_elevation(&self, loc: Point2<f32>) -> f32 {
+        let in_goal_mouth = loc.x.abs() < self.goal_mouth_max_x;
+        let mut result: f32 = 0.0;
+
+        if in_goal_mouth {
+            let into_goal = loc.y.abs() - (rl::FIELD_MAX_Y - GOAL_RAMP_LENGTH);
+            result = clamp(into_goal / GOAL_RAMP_LENGTH, 0.0, 1.0) * GOAL_RAMP_HEIGHT;
+        }
+
+        let r = self.wall_base_radius;
+        let loc_3d = loc.to_3d(0.0);
+        let walls = if self.is_in_goal(loc) {
+            &self.goal_planes
+        } else {
+            &self.planes
+        };
+        for wall in walls.iter().filter(|p| p.normal.z.abs() < 0.1) {
+            // There's no wall (and no curve) across the goal mouth.
+            if in_goal_mouth && !self.is_in_goal(loc) && wall.normal.y.abs() > 0.99 {
+                continue;
+            }
+            let dist = wall.distance_to_point(&loc_3d).max(0.0);
+            if dist < r {
+                let x = r - dist;
+                result = result.max(r - (r * r - x * x).sqrt());
+            }
+        }
+        result
+    }
+
+    /// The distance travelled along the surface when driving straight from
+    /// `start` to `end`. This is longer than the 2D distance wherever the path
+    /// climbs onto a wall base or into a goal.
+    pub fn surface_distance(&self, start: Point2<f32>, end: Point2<f32>) -> f32 {
+        const STEP: f32 = 50.0;
+
+        let flat_dist = (end - start).norm();
+        let steps = (flat_dist / STEP).ceil().max(1.0) as usize;
+        let step = flat_dist / steps as f32;
+        let mut result = 0.0;
+        let mut prev_z = self.floor_elevation(start);
+        for i in 1..=steps {
+            let loc = start + (end - start) * (i as f32 / steps as f32);
+            let z = self.floor_elevation(loc);
+            result += (step * step + (z - prev_z) * (z - prev_z)).sqrt();
+            prev_z = z;
+        }
+        result
+    }
 }
 
 const CORNER_WALL_X: f32 = 3518.0;
 const CORNER_WALL_Y: f32 = 4546.0;
+const WALL_BASE_RADIUS: f32 = 256.0;
+const WALL_TOP_RADIUS: f32 = 256.0;
+/// Surfaces whose normals point this much against each other face each other.
+const FACING_DOT: f32 = 0.1;
+/// The floor ramps up slightly over this distance in front of each goal line.
+const GOAL_RAMP_LENGTH: f32 = 100.0;
+const GOAL_RAMP_HEIGHT: f32 = 15.0;
+const GOAL_DEPTH: f32 = 880.0;
+const GOAL_HEIGHT: f32 = 642.775;
 
 lazy_static! {
     /// I believe all soccar maps are the same as DFH Stadium.
     pub static ref DFH_STADIUM: Pitch = Pitch {
-        planes: vec![
+        planes: with_rounded_top_edges(vec![
             // Floor and ceiling
             Plane::point_normal(Point3::origin(), Vector3::z_axis()),
             Plane::point_normal(Point3::new(0.0, 0.0, rl::FIELD_MAX_Z), -Vector3::z_axis()),
@@ -55,6 +195,137 @@ lazy_static! {
                 Point3::new(CORNER_WALL_X, CORNER_WALL_Y, 0.0),
                 Unit::new_normalize(Vector3::new(-1.0, -1.0, 0.0)),
             ),
+        ]),
+        goal_planes: vec![
+            // Floor and ceiling. The floor inside the goal is at the top of the
+            // ramp.
+            Plane::point_normal(Point3::new(0.0, 0.0, GOAL_RAMP_HEIGHT), Vector3::z_axis()),
+            Plane::point_normal(Point3::new(0.0, 0.0, GOAL_HEIGHT), -Vector3::z_axis()),
+
+            // Side walls, shared by both goals
+            Plane::point_normal(Point3::new(-rl::GOALPOST_X, 0.0, 0.0), Vector3::x_axis()),
+            Plane::point_normal(Point3::new(rl::GOALPOST_X, 0.0, 0.0), -Vector3::x_axis()),
+
+            // Back walls
+            Plane::point_normal(
+                Point3::new(0.0, -rl::FIELD_MAX_Y - GOAL_DEPTH, 0.0),
+                Vector3::y_axis(),
+            ),
+            Plane::point_normal(
+                Point3::new(0.0, rl::FIELD_MAX_Y + GOAL_DEPTH, 0.0),
+                -Vector3::y_axis(),
+            ),
         ],
+        wall_base_radius: WALL_BASE_RADIUS,
+        goal_mouth_max_x: rl::GOALPOST_X,
+        goal_depth: GOAL_DEPTH,
     };
 }
+
+/// Add a flat stand-in for the curve where each wall meets the ceiling
+/// (`planes[1]`), so a ball rolling over it onto the ceiling has a surface to
+/// be on.
+fn with_rounded_top_edges(mut planes: Vec<Plane>) -> Vec<Plane> {
+    let ceiling = planes[1];
+    let edges: Vec<_> = (planes.iter())
+        .filter(|p| p.normal.z == 0.0)
+        .map(|wall| rounded_edge(wall, &ceiling, WALL_TOP_RADIUS))
+        .collect();
+    planes.extend(edges);
+    planes
+}
+
+/// The plane tangent to the middle of the curve joining two perpendicular
+/// surfaces.
+fn rounded_edge(a: &Plane, b: &Plane, radius: f32) -> Plane {
+    let seam = a.intersect(b).unwrap();
+    let center = seam.origin + (a.normal.into_inner() + b.normal.into_inner()) * radius;
+    let normal = Unit::new_normalize(a.normal.into_inner() + b.normal.into_inner());
+    Plane::point_normal(center - normal.into_inner() * radius, normal)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::DFH_STADIUM;
+    use common::{prelude::*, rl};
+    use nalgebra::{Point2, Point3};
+
+    #[test]
+    fn floor_elevation() {
+        let pitch = &*DFH_STADIUM;
+        assert!(pitch.floor_elevation(Point2::origin()).abs() < 0.001);
+        assert!(pitch.floor_elevation(Point2::new(rl::FIELD_MAX_X - 50.0, 0.0)) > 50.0);
+        assert!(pitch.floor_elevation(Point2::new(0.0, rl::FIELD_MAX_Y)) > 0.0);
+        assert!(pitch.floor_elevation(Point2::new(0.0, rl::FIELD_MAX_Y)) < 50.0);
+    }
+
+    #[test]
+    fn surface_distance() {
+        let pitch = &*DFH_STADIUM;
+        let midfield = pitch.surface_distance(Point2::new(0.0, 0.0), Point2::new(0.0, 1000.0));
+        assert!((midfield - 1000.0).abs() < 0.1);
+
+        let wall_base = pitch.surface_distance(
+            Point2::new(rl::FIELD_MAX_X - 300.0, 0.0),
+            Point2::new(rl::FIELD_MAX_X - 20.0, 0.0),
+        );
+        assert!(wall_base > 300.0);
+    }
+
+    #[test]
+    fn goal_interior() {
+        let pitch = &*DFH_STADIUM;
+        let inside = Point2::new(0.0, -rl::FIELD_MAX_Y - 400.0);
+        assert!(pitch.is_in_goal(inside));
+        assert!(!pitch.is_in_goal(Point2::new(0.0, -rl::FIELD_MAX_Y + 100.0)));
+        assert!(!pitch.is_in_goal(Point2::new(2000.0, -rl::FIELD_MAX_Y - 400.0)));
+
+        // Inside the goal, the floor is drivable and the nearest wall is the
+        // goal's own back wall, not the back wall of the field.
+        let floor = pitch.closest_plane(&inside.to_3d(17.0));
+        assert!(floor.normal.z > 0.99);
+        let back = pitch.closest_plane(&Point3::new(0.0, -rl::FIELD_MAX_Y - 850.0, 300.0));
+        assert!(back.normal.y > 0.99);
+        assert!(pitch.floor_elevation(inside) < 50.0);
+    }
+
+    #[test]
+    fn unfold_to_ground() {
+        let pitch = &*DFH_STADIUM;
+        let unfold = |loc: Point3<f32>| pitch.unfold_to_ground(&loc) * loc;
+
+        let floor = unfold(Point3::new(1000.0, 2000.0, 17.0));
+        assert!((floor - Point2::new(1000.0, 2000.0)).norm() < 1.0);
+
+        // Walls fold outward, so height up the wall becomes distance past it.
+        let side = unfold(Point3::new(rl::FIELD_MAX_X, 1000.0, 500.0));
+        assert!((side - Point2::new(rl::FIELD_MAX_X + 500.0, 1000.0)).norm() < 1.0);
+        let back = unfold(Point3::new(-1000.0, -rl::FIELD_MAX_Y, 300.0));
+        assert!((back - Point2::new(-1000.0, -rl::FIELD_MAX_Y - 300.0)).norm() < 1.0);
+
+        // The ceiling folds out past the nearest wall.
+        let ceiling = unfold(Point3::new(3000.0, 0.0, rl::FIELD_MAX_Z));
+        let expected = rl::FIELD_MAX_X + rl::FIELD_MAX_Z + (rl::FIELD_MAX_X - 3000.0);
+        assert!((ceiling - Point2::new(expected, 0.0)).norm() < 1.0);
+    }
+
+    #[test]
+    fn wall_top_curve() {
+        let pitch = &*DFH_STADIUM;
+        // A ball rolling through the curve between the side wall and ceiling.
+        let ball = Point3::new(rl::FIELD_MAX_X - 140.0, 0.0, rl::FIELD_MAX_Z - 140.0);
+        let curve = pitch.closest_plane(&ball);
+        assert!(curve.normal.x < -0.5 && curve.normal.z < -0.5);
+
+        // It's on a surface we can route over, from the floor or the wall.
+        let floor = pitch.ground();
+        let wall = pitch.closest_plane(&Point3::new(rl::FIELD_MAX_X, 0.0, 500.0));
+        let flat = pitch.unfold(curve, floor, &ball).unwrap() * ball;
+        assert!(flat.x > rl::FIELD_MAX_X + rl::FIELD_MAX_Z - 500.0);
+        assert!(pitch.unfold(curve, wall, &ball).is_ok());
+
+        // Lower on the wall, it's still just the wall.
+        let lower = pitch.closest_plane(&Point3::new(rl::FIELD_MAX_X - 92.0, 0.0, 1500.0));
+        assert_eq!(lower.normal.x, -1.0);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{helpers::contest::Contest, strategy::Personality};
+use std::f32;
+
+/// The scoreboard and the clock. `Personality` sets how much risk we take in
+/// general; this shifts it for the situation. Behind late, a goal against costs
+/// nothing that isn't already lost, so throw everything forward. Ahead late,
+/// the opposite.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RiskProfile {
+    /// Our goals minus theirs.
+    pub score_margin: i32,
+    /// Zero in overtime, and in matches without a time limit.
+    pub seconds_remaining: f32,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RiskMode {
+    Normal,
+    /// Ahead with little time left. Don't give them chances.
+    ProtectLead,
+    /// Behind with little time left. Forget about defense.
+    AllIn,
+}
+
+impl RiskProfile {
+    /// Go all in when behind with less than this many seconds left for each
+    /// goal we need.
+    const ALL_IN_SECONDS_PER_GOAL: f32 = 45.0;
+    /// Protect a lead with less than this many seconds left.
+    const PROTECT_SECONDS: f32 = 90.0;
+    /// While protecting a lead, fall back to defense with this much more
+    /// possession than usual,
+    const PROTECT_RETREAT_SHIFT: f32 = 0.5;
+    /// and give up the race to the ball this much sooner.
+    const PROTECT_CHALLENGE_SHIFT: f32 = 1.0;
+    /// While all in, keep racing for the ball this much longer.
+    const ALL_IN_CHALLENGE_SHIFT: f32 = 1.0;
+
+    pub fn new(score_margin: i32, seconds_remaining: f32) -> Self {
+        Self {
+            score_margin,
+            seconds_remaining,
+        }
+    }
+
+    pub fn mode(&self) -> RiskMode {
+        if self.seconds_remaining <= 0.0 {
+            return RiskMode::Normal;
+        }
+        let deficit = -self.score_margin as f32;
+        if deficit > 0.0 && self.seconds_remaining < Self::ALL_IN_SECONDS_PER_GOAL * deficit {
+            RiskMode::AllIn
+        } else if deficit < 0.0 && self.seconds_remaining < Self::PROTECT_SECONDS {
+            RiskMode::ProtectLead
+        } else {
+            RiskMode::Normal
+        }
+    }
+
+    /// `Personality::retreat_possession`, adjusted for the situation.
+    pub fn retreat_possession(&self, personality: &Personality) -> f32 {
+        match self.mode() {
+            RiskMode::Normal => personality.retreat_possession,
+            RiskMode::ProtectLead => personality.retreat_possession + Self::PROTECT_RETREAT_SHIFT,
+            RiskMode::AllIn => f32::NEG_INFINITY,
+        }
+    }
+
+    /// `Personality::challenge_deficit`, adjusted for the situation.
+    pub fn challenge_deficit(&self, personality: &Personality) -> f32 {
+        match self.mode() {
+            RiskMode::Normal => personality.challenge_deficit,
+            RiskMode::ProtectLead => {
+                (personality.challenge_deficit - Self::PROTECT_CHALLENGE_SHIFT).max(0.0)
+            }
+            RiskMode::AllIn => personality.challenge_deficit + Self::ALL_IN_CHALLENGE_SHIFT,
+        }
+    }
+
+    /// Returns `true` if we should skip defensive positioning (retreating,
+    /// shadowing) and go for the ball instead.
+    pub fn all_out_offense(&self) -> bool {
+        self.mode() == RiskMode::AllIn
+    }
+
+    /// Whether to take a 50/50. Protecting a lead, only take the ones we win.
+    pub fn should_challenge(&self, contest: &Contest) -> bool {
+        match self.mode() {
+            RiskMode::Normal => contest.should_challenge(),
+            RiskMode::ProtectLead => contest.score >= 0.0,
+            RiskMode::AllIn => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::{
+        risk_profile::{RiskMode, RiskProfile},
+        Personality,
+    };
+
+    #[test]
+    fn mode() {
+        let mode = |margin, seconds| RiskProfile::new(margin, seconds).mode();
+        assert_eq!(mode(-1, 30.0), RiskMode::AllIn);
+        assert_eq!(mode(-1, 120.0), RiskMode::Normal);
+        // Two goals take longer to get.
+        assert_eq!(mode(-2, 60.0), RiskMode::AllIn);
+        assert_eq!(mode(1, 60.0), RiskMode::ProtectLead);
+        assert_eq!(mode(1, 200.0), RiskMode::Normal);
+        assert_eq!(mode(0, 10.0), RiskMode::Normal);
+        // Overtime, or no time limit.
+        assert_eq!(mode(-1, 0.0), RiskMode::Normal);
+    }
+
+    #[test]
+    fn thresholds() {
+        let personality = Personality::balanced();
+        let normal = RiskProfile::new(0, 200.0);
+        let protect = RiskProfile::new(1, 30.0);
+        let all_in = RiskProfile::new(-1, 30.0);
+
+        assert!(protect.retreat_possession(&personality) > normal.retreat_possession(&personality));
+        assert!(all_in.retreat_possession(&personality) < normal.retreat_possession(&personality));
+        assert!(protect.challenge_deficit(&personality) < normal.challenge_deficit(&personality));
+        assert!(all_in.challenge_deficit(&personality) > normal.challenge_deficit(&personality));
+        assert!(all_in.all_out_offense());
+        assert!(!protect.all_out_offense());
+    }
+}
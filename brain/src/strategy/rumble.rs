@@ -0,0 +1,196 @@
+use crate::{
+    behavior::defense::Defense,
+    strategy::{strategy::Strategy, Action, Behavior, Context, Priority, Scenario, Soccar},
+};
+use common::{halfway_house::RumbleItem, prelude::*};
+use derive_new::new;
+use nameof::name_of_type;
+use std::sync::{Arc, Mutex};
+
+/// Soccar, plus power-ups.
+///
+/// Most items are worth more spent than held, since holding one means we can't
+/// pick up the next. The ones that can break up a shot are saved until the
+/// enemy lines one up.
+pub struct Rumble {
+    soccar: Soccar,
+    /// When we last pressed the button. The item lingers in the packet for a
+    /// few frames after it's used, so don't press it again right away.
+    ///
+    /// `UseItem` sets this when it presses, since it can be interrupted before
+    /// it gets the chance.
+    last_use: Arc<Mutex<f32>>,
+}
+
+impl Rumble {
+    /// How long to wait before pressing the button again.
+    const USE_COOLDOWN: f32 = 0.5;
+    /// Live packets don't say what we're holding, or if we're holding
+    /// anything. Items are handed out about this often, so when we can't tell,
+    /// press the button this often, in case there's something to spend.
+    ///
+    /// Pressing blind could waste a Boot or a Plunger, so only do it when the
+    /// enemy isn't threatening, and never in the middle of a save.
+    const BLIND_USE_INTERVAL: f32 = 10.0;
+
+    pub fn new() -> Self {
+        Self {
+            soccar: Soccar::new(),
+            last_use: Arc::new(Mutex::new(f32::NEG_INFINITY)),
+        }
+    }
+}
+
+impl Strategy for Rumble {
+    fn baseline(&mut self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+        self.soccar.baseline(ctx)
+    }
+
+    fn interrupt(
+        &mut self,
+        ctx: &mut Context<'_>,
+        current: &dyn Behavior,
+    ) -> Option<Box<dyn Behavior>> {
+        let since_use = ctx.packet.GameInfo.TimeSeconds - *self.last_use.lock().unwrap();
+        let use_item = match ctx.me().Item {
+            Some(item) => {
+                since_use >= Self::USE_COOLDOWN
+                    && current.priority() < Priority::Strike
+                    && should_use(item, threat(ctx).as_ref())
+            }
+            None => {
+                since_use >= Self::BLIND_USE_INTERVAL
+                    && current.priority() < Priority::Defense
+                    && ctx.scenario.possession() >= -Scenario::POSSESSION_CONTESTABLE
+            }
+        };
+        if use_item {
+            ctx.eeg
+                .log(name_of_type!(Rumble), format!("using {:?}", ctx.me().Item));
+            return Some(Box::new(UseItem::new(self.last_use.clone())));
+        }
+
+        self.soccar.interrupt(ctx, current)
+    }
+
+    fn reflex(
+        &mut self,
+        ctx: &mut Context<'_>,
+        current: &dyn Behavior,
+    ) -> Option<Box<dyn Behavior>> {
+        self.soccar.reflex(ctx, current)
+    }
+}
+
+/// The enemy is about to take a shot on our goal.
+struct Threat {
+    /// From us to the shooter.
+    shooter_dist: f32,
+    /// From us to the ball.
+    ball_dist: f32,
+    /// From the shooter to the ball.
+    shooter_ball_dist: f32,
+}
+
+fn threat(ctx: &mut Context<'_>) -> Option<Threat> {
+    if ctx.scenario.possession() >= -Scenario::POSSESSION_CONTESTABLE {
+        return None;
+    }
+    if !Defense::enemy_can_shoot(ctx) {
+        return None;
+    }
+    let me_loc = ctx.me().Physics.loc();
+    let ball_loc = ctx.packet.GameBall.Physics.loc();
+    let shooter = ctx.scenario.primary_enemy()?;
+    Some(Threat {
+        shooter_dist: (shooter.Physics.loc() - me_loc).norm(),
+        ball_dist: (ball_loc - me_loc).norm(),
+        shooter_ball_dist: (ball_loc - shooter.Physics.loc()).norm(),
+    })
+}
+
+/// Roughly how far each targeted item reaches.
+const BOOT_RANGE: f32 = 2000.0;
+const PLUNGER_RANGE: f32 = 2000.0;
+const SWAPPER_RANGE: f32 = 3000.0;
+
+fn should_use(item: RumbleItem, threat: Option<&Threat>) -> bool {
+    match item {
+        // Punt the shooter out of the play.
+        RumbleItem::Boot => threat.map_or(false, |t| t.shooter_dist < BOOT_RANGE),
+        // Yank the ball away before they get to it.
+        RumbleItem::Plunger => threat.map_or(false, |t| t.ball_dist < PLUNGER_RANGE),
+        // Trade places with the shooter, if their place is the better one.
+        RumbleItem::Swapper => threat.map_or(false, |t| {
+            t.shooter_ball_dist < t.ball_dist && t.shooter_dist < SWAPPER_RANGE
+        }),
+        _ => true,
+    }
+}
+
+/// Press the item button for a frame.
+#[derive(new)]
+struct UseItem {
+    last_use: Arc<Mutex<f32>>,
+    #[new(value = "false")]
+    pressed: bool,
+}
+
+impl Behavior for UseItem {
+    fn name(&self) -> &str {
+        name_of_type!(UseItem)
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
+        if self.pressed {
+            return Action::Return;
+        }
+        self.pressed = true;
+        *self.last_use.lock().unwrap() = ctx.packet.GameInfo.TimeSeconds;
+        Action::Yield(common::halfway_house::PlayerInput {
+            Throttle: 1.0,
+            UseItem: true,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::rumble::{should_use, Threat};
+    use common::halfway_house::RumbleItem;
+
+    fn threat(shooter_dist: f32, ball_dist: f32, shooter_ball_dist: f32) -> Threat {
+        Threat {
+            shooter_dist,
+            ball_dist,
+            shooter_ball_dist,
+        }
+    }
+
+    #[test]
+    fn hold_targeted_items_for_a_shot() {
+        for &item in &[RumbleItem::Boot, RumbleItem::Plunger, RumbleItem::Swapper] {
+            assert!(!should_use(item, None), "{:?}", item);
+        }
+        assert!(should_use(RumbleItem::Spikes, None));
+        assert!(should_use(RumbleItem::PowerHitter, None));
+    }
+
+    #[test]
+    fn spend_targeted_items_in_range() {
+        let close = threat(1000.0, 1500.0, 300.0);
+        assert!(should_use(RumbleItem::Boot, Some(&close)));
+        assert!(should_use(RumbleItem::Plunger, Some(&close)));
+        assert!(should_use(RumbleItem::Swapper, Some(&close)));
+
+        let far = threat(5000.0, 5000.0, 300.0);
+        assert!(!should_use(RumbleItem::Boot, Some(&far)));
+        assert!(!should_use(RumbleItem::Plunger, Some(&far)));
+        assert!(!should_use(RumbleItem::Swapper, Some(&far)));
+
+        // Swapping only helps if they're closer to the ball than we are.
+        let behind = threat(1000.0, 300.0, 1200.0);
+        assert!(!should_use(RumbleItem::Swapper, Some(&behind)));
+    }
+}
@@ -1,12 +1,20 @@
 use crate::{
-    eeg::{color, Drawable},
-    strategy::{strategy::Strategy, Action, Behavior, Context},
+    eeg::{color, Drawable, NodeStatus},
+    strategy::{
+        input_validator::validate_input, safety_filter::SafetyFilter, strategy::Strategy, Action,
+        Behavior, Child, Context, Dropshot,
+    },
 };
+use common::prelude::*;
 use nameof::name_of_type;
 
 pub struct Runner {
     strategy: Box<dyn Strategy>,
     current: Option<Box<dyn Behavior>>,
+    /// When the strategy last got a chance to interrupt. Slower difficulty
+    /// levels wait longer between chances, and only get reflexes in between.
+    last_interrupt_check: f32,
+    safety: SafetyFilter,
 }
 
 impl Runner {
@@ -14,19 +22,66 @@ impl Runner {
         Self {
             strategy: Box::new(strategy),
             current: None,
+            last_interrupt_check: f32::NEG_INFINITY,
+            safety: SafetyFilter::new(),
         }
     }
 
+    /// A runner that plays Dropshot.
+    pub fn dropshot() -> Self {
+        Self::new(Dropshot::new())
+    }
+
     #[cfg(test)]
     pub fn with_current(current: impl Behavior + 'static) -> Self {
         Self {
             strategy: Box::new(crate::strategy::null::NullStrategy::new()),
             current: Some(Box::new(current)),
+            last_interrupt_check: f32::NEG_INFINITY,
+            safety: SafetyFilter::new(),
         }
     }
 
-    pub fn execute_old(&mut self, ctx: &mut Context<'_>) -> common::halfway_house::PlayerInput {
-        self.exec(0, ctx)
+    /// Replace the current behavior. The strategy takes over again once it
+    /// returns.
+    pub fn set_current(&mut self, current: Box<dyn Behavior>) {
+        self.current = Some(current);
+    }
+
+    /// Throw away the current behavior, e.g., because the game clock jumped and
+    /// its plans no longer make sense. The strategy will choose a fresh one.
+    pub fn invalidate(&mut self) {
+        self.current = None;
+        self.last_interrupt_check = f32::NEG_INFINITY;
+    }
+
+    /// What the current behavior calls itself, including any children it's
+    /// running.
+    pub fn current_blurb(&self) -> Option<&str> {
+        self.current.as_ref().map(|b| b.blurb())
+    }
+
+    /// Run one frame and return the controller input, with anything the car
+    /// can't physically do stripped out, and held to the difficulty's top speed.
+    pub fn next_input(&mut self, ctx: &mut Context<'_>) -> common::halfway_house::PlayerInput {
+        let mut input = self.exec(0, ctx);
+        limit_speed(ctx.me(), ctx.difficulty.max_speed, &mut input);
+        self.safety.filter(ctx, &mut input);
+        for violation in validate_input(ctx.jump, ctx.me(), &mut input) {
+            let culprit = self
+                .current
+                .as_ref()
+                .map(|b| leaf_blurb(&**b))
+                .unwrap_or_else(|| "none".to_string());
+            ctx.eeg.log(
+                self.name(),
+                format!("invalid input from {}: {}", culprit, violation.describe()),
+            );
+        }
+        if let Some(current) = &self.current {
+            ctx.eeg.draw_behavior_tree(&**current);
+        }
+        input
     }
 }
 
@@ -35,7 +90,13 @@ impl Behavior for Runner {
         name_of_type!(Runner)
     }
 
-    fn execute_old(&mut self, ctx: &mut Context<'_>) -> Action {
+    fn visit_children(&self, visit: &mut dyn FnMut(Child<'_>)) {
+        if let Some(current) = &self.current {
+            visit(Child::Behavior(&**current));
+        }
+    }
+
+    fn execute(&mut self, ctx: &mut Context<'_>) -> Action {
         Action::Yield(self.exec(0, ctx))
     }
 }
@@ -44,6 +105,7 @@ impl Runner {
     fn exec(&mut self, depth: u32, ctx: &mut Context<'_>) -> common::halfway_house::PlayerInput {
         if depth > 5 {
             ctx.eeg.log(self.name(), "infinite loop?");
+            ctx.eeg.clip("watchdog");
             return Default::default();
         }
 
@@ -51,44 +113,64 @@ impl Runner {
         ctx.eeg
             .draw(Drawable::print(behavior.blurb(), color::YELLOW));
 
-        match behavior.execute_old(ctx) {
+        match behavior.execute(ctx) {
             Action::Yield(i) => i,
             Action::TailCall(b) => {
-                ctx.eeg.log(self.name(), format!("> {}", b.name()));
+                ctx.eeg.log_decision(self.name(), format!("> {}", b.name()));
+                ctx.eeg
+                    .track_activation(self.current.as_ref().map(|c| c.name()), b.name());
                 self.current = Some(b);
                 self.exec(depth + 1, ctx)
             }
             Action::RootCall(b) => {
-                ctx.eeg.log(self.name(), format!("! {}", b.name()));
+                ctx.eeg.log_decision(self.name(), format!("! {}", b.name()));
+                ctx.eeg
+                    .track_activation(self.current.as_ref().map(|c| c.name()), b.name());
                 self.current = Some(b);
                 self.exec(depth + 1, ctx)
             }
-            Action::Return | Action::Abort => {
-                ctx.eeg.log(
-                    self.name(),
-                    format!("< {}", self.current.as_ref().unwrap().name()),
-                );
-                self.current = None;
-                self.exec(depth + 1, ctx)
-            }
+            Action::Return => self.finish(depth, ctx, NodeStatus::Succeeded),
+            Action::Abort => self.finish(depth, ctx, NodeStatus::Aborted),
         }
     }
 
+    fn finish(
+        &mut self,
+        depth: u32,
+        ctx: &mut Context<'_>,
+        status: NodeStatus,
+    ) -> common::halfway_house::PlayerInput {
+        let name = self.current.as_ref().unwrap().name();
+        ctx.eeg.log_decision(self.name(), format!("< {}", name));
+        ctx.eeg.track_outcome(name, status);
+        self.current = None;
+        self.exec(depth + 1, ctx)
+    }
+
     fn choose_behavior(&mut self, ctx: &mut Context<'_>) -> &mut dyn Behavior {
         if self.current.is_none() {
             self.current = Some(self.strategy.baseline(ctx));
-            ctx.eeg.log(
-                self.name(),
-                format!("baseline: {}", self.current.as_ref().unwrap().name()),
-            );
+            let name = self.current.as_ref().unwrap().name();
+            ctx.eeg
+                .log_decision(self.name(), format!("baseline: {}", name));
+            ctx.eeg.track_activation(None, name);
         }
 
-        if let Some(b) = self
-            .strategy
-            .interrupt(ctx, &**self.current.as_ref().unwrap())
-        {
+        let now = ctx.packet.GameInfo.TimeSeconds;
+        let current = &**self.current.as_ref().unwrap();
+        let interrupt = if now - self.last_interrupt_check >= ctx.difficulty.reaction_time {
+            self.last_interrupt_check = now;
+            self.strategy.interrupt(ctx, current)
+        } else {
+            // Kickoffs and the like can't wait for a slow reaction.
+            self.strategy.reflex(ctx, current)
+        };
+
+        if let Some(b) = interrupt {
+            ctx.eeg
+                .track_activation(self.current.as_ref().map(|c| c.name()), b.name());
             self.current = Some(b);
-            ctx.eeg.log(
+            ctx.eeg.log_decision(
                 self.name(),
                 format!("override: {}", self.current.as_ref().unwrap().name()),
             );
@@ -97,3 +179,28 @@ impl Runner {
         &mut **self.current.as_mut().unwrap()
     }
 }
+
+/// Coast instead of accelerating past `max_speed`.
+fn limit_speed(
+    me: &common::halfway_house::PlayerInfo,
+    max_speed: f32,
+    input: &mut common::halfway_house::PlayerInput,
+) {
+    if me.Physics.vel().norm() < max_speed {
+        return;
+    }
+    input.Boost = false;
+    input.Throttle = input.Throttle.min(0.0);
+}
+
+/// The innermost running behavior under `behavior`. That's the one that chose
+/// the input, so that's who to blame for it.
+fn leaf_blurb(behavior: &dyn Behavior) -> String {
+    let mut leaf = None;
+    behavior.visit_children(&mut |child| {
+        if let Child::Behavior(child) = child {
+            leaf = Some(leaf_blurb(child));
+        }
+    });
+    leaf.unwrap_or_else(|| behavior.blurb().to_string())
+}
@@ -0,0 +1,145 @@
+use crate::{
+    eeg::Event,
+    helpers::self_contact::{predict_own_goal_contact, predict_own_goal_contact_at},
+    strategy::Context,
+    utils::JumpTracker,
+};
+use common::{
+    halfway_house::{PlayerInfo, PlayerInput},
+    prelude::*,
+    rl,
+};
+use nalgebra::Vector3;
+use nameof::name_of_type;
+
+/// The last line of defense against own goals. It runs on the input after
+/// the behaviors are done with it, so it covers all of them, including the ones
+/// that never think about our own goal.
+///
+/// If the car is about to touch the ball, predict the touch with
+/// `predict_own_goal_contact`. If the ball would end up in our net, take back
+/// the dodge if that's what does it, and otherwise brake and steer away.
+pub struct SafetyFilter {
+    /// Whether we vetoed anything last frame, to log once per veto instead of
+    /// every frame.
+    vetoing: bool,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Veto {
+    /// The dodge would knock the ball in.
+    Dodge,
+    /// Driving into the ball would knock it in, so brake and steer away
+    /// instead.
+    Hit,
+}
+
+impl SafetyFilter {
+    pub fn new() -> Self {
+        Self { vetoing: false }
+    }
+
+    /// Correct `input` in place, and return what was vetoed, if anything.
+    pub fn filter(&mut self, ctx: &mut Context<'_>, input: &mut PlayerInput) -> Option<Veto> {
+        let veto = check(ctx, input);
+        if let Some(veto) = veto {
+            if !self.vetoing {
+                ctx.eeg.log(
+                    name_of_type!(SafetyFilter),
+                    format!("vetoed {:?}: it would be an own goal", veto),
+                );
+                ctx.eeg.track(Event::SafetyFilterVeto);
+            }
+        }
+        self.vetoing = veto.is_some();
+        veto
+    }
+}
+
+fn check(ctx: &mut Context<'_>, input: &mut PlayerInput) -> Option<Veto> {
+    // If the ball is going in anyway, touching it can only help.
+    if ctx.scenario.impending_concede().is_some() {
+        return None;
+    }
+
+    let car = ctx.me();
+    let prediction = ctx.scenario.ball_prediction();
+
+    if starts_dodge(ctx.jump, car, input) {
+        let dodge_vel = car.Physics.vel() + dodge_direction(car, input) * rl::DODGE_FORWARD_IMPULSE;
+        if predict_own_goal_contact_at(ctx.game, car, dodge_vel, prediction).is_some() {
+            input.Jump = false;
+            return Some(Veto::Dodge);
+        }
+    }
+
+    let contact = predict_own_goal_contact(ctx.game, car, prediction)?;
+
+    // Do what `AvoidOwnGoal` does: brake, and steer away from whichever side
+    // the ball is on, so that if we can't miss it, at least the touch is soft.
+    let car_loc = car.Physics.loc_2d() + car.Physics.vel_2d() * contact.time;
+    let offset = (contact.ball_loc.to_2d() - car_loc).dot(&car.Physics.right_axis().to_2d());
+    let forward_speed = car.Physics.vel().dot(&car.Physics.forward_axis());
+    input.Throttle = if forward_speed >= 0.0 { -1.0 } else { 1.0 };
+    input.Boost = false;
+    input.Steer = -offset.signum();
+
+    if predict_own_goal_contact_at(ctx.game, car, braking_vel(car), prediction).is_some() {
+        ctx.eeg.log(
+            name_of_type!(SafetyFilter),
+            "can't brake in time; the touch will still go in",
+        );
+    }
+    Some(Veto::Hit)
+}
+
+/// Roughly the car's average velocity over the next half second if it brakes
+/// the whole time.
+fn braking_vel(car: &PlayerInfo) -> Vector3<f32> {
+    const BRAKE_DECEL: f32 = 3500.0;
+    const BRAKE_TIME: f32 = 0.5;
+
+    let vel = car.Physics.vel();
+    let speed = vel.norm();
+    if speed < 1.0 {
+        return vel;
+    }
+    vel * ((speed - BRAKE_DECEL * BRAKE_TIME / 2.0).max(0.0) / speed)
+}
+
+/// Returns `true` if `input` would start a dodge this frame.
+fn starts_dodge(jump: &JumpTracker, car: &PlayerInfo, input: &PlayerInput) -> bool {
+    input.Jump
+        && !jump.holding_jump()
+        && !car.OnGround
+        && jump.has_flip()
+        && (input.Pitch != 0.0 || input.Yaw != 0.0)
+}
+
+/// Pitching forward dodges forward, and yaw dodges to the side.
+fn dodge_direction(car: &PlayerInfo, input: &PlayerInput) -> Vector3<f32> {
+    let forward = car.Physics.forward_axis_2d().into_inner();
+    let right = car.Physics.right_axis().to_2d();
+    (forward * -input.Pitch + right * input.Yaw)
+        .normalize()
+        .to_3d(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::safety_filter::braking_vel;
+    use common::halfway_house::PlayerInfo;
+
+    #[test]
+    fn braking_slows_the_car() {
+        let mut car = PlayerInfo::default();
+        car.Physics.Velocity.Y = -2000.0;
+        let vel = braking_vel(&car);
+        assert!(vel.y < 0.0);
+        assert!(vel.y > -2000.0);
+
+        // Braking can stop the car, but not send it backwards.
+        car.Physics.Velocity.Y = -500.0;
+        assert_eq!(braking_vel(&car).y, 0.0);
+    }
+}
@@ -1,14 +1,20 @@
 use crate::{
     helpers::{
         ball::{BallFrame, BallPredictor, BallTrajectory},
-        intercept::{naive_intercept_penalty, NaiveIntercept},
+        contest::{evaluate_contest, Contest},
+        hit_outcome::predict_touch_towards,
+        intercept::{heading_aware_ground_intercept, turn_time, NaiveIntercept},
+        landing::{predict_landing, Landing},
+    },
+    strategy::{
+        game::Game, opponent_model::OpponentModel, team_coordination::TeamCoordination, Goal,
     },
-    strategy::{game::Game, Goal},
     utils::{Wall, WallRayCalculator},
 };
-use common::prelude::*;
+use common::{prelude::*, rl};
+use itertools::Itertools;
 use lazycell::LazyCell;
-use nalgebra::Vector2;
+use nalgebra::{Point2, Vector2};
 use ordered_float::NotNan;
 use simulate::{linear_interpolate, Car1D};
 use std::f32::{self, consts::PI};
@@ -17,41 +23,67 @@ pub struct Scenario<'a> {
     packet: &'a common::halfway_house::LiveDataPacket,
     pub game: &'a Game<'a>,
     ball_predictor: &'a dyn BallPredictor,
+    opponent: &'a OpponentModel,
+    first_man: Option<usize>,
     ball_prediction: LazyCell<BallTrajectory>,
     me_intercept: LazyCell<Option<NaiveIntercept>>,
     enemy_intercept: LazyCell<Option<(&'a common::halfway_house::PlayerInfo, NaiveIntercept)>>,
     possession: LazyCell<f32>,
+    contest: LazyCell<Option<Contest>>,
+    time_to_pressure: LazyCell<f32>,
+    me_landing: LazyCell<Option<Landing>>,
     push_wall: LazyCell<Wall>,
     impending_score_conservative: LazyCell<Option<BallFrame>>,
     impending_concede: LazyCell<Option<BallFrame>>,
+    enemy_touch_prediction: LazyCell<Option<BallTrajectory>>,
+    impending_concede_if_touched: LazyCell<Option<BallFrame>>,
     enemy_shoot_score_seconds: LazyCell<f32>,
+    enemy_touch_time: LazyCell<f32>,
     slightly_panicky_retreat: LazyCell<bool>,
     very_panicky_retreat: LazyCell<bool>,
+    team: LazyCell<TeamCoordination>,
 }
 
 impl<'a> Scenario<'a> {
     pub const POSSESSION_CONTESTABLE: f32 = 0.5;
     pub const POSSESSION_SATURATED: f32 = 5.0;
+    /// How long the enemy takes to notice what we're doing and start driving
+    /// towards it.
+    pub const ENEMY_REACTION_TIME: f32 = 0.25;
+    /// An enemy closing on the ball at least this fast is already going for
+    /// it.
+    const ENEMY_COMMITTED_SPEED: f32 = 500.0;
 
     pub fn new(
         game: &'a Game<'_>,
         ball_predictor: &'a dyn BallPredictor,
+        opponent: &'a OpponentModel,
+        first_man: Option<usize>,
         packet: &'a common::halfway_house::LiveDataPacket,
     ) -> Scenario<'a> {
         Scenario {
             packet,
             game,
             ball_predictor,
+            opponent,
+            first_man,
             ball_prediction: LazyCell::new(),
             me_intercept: LazyCell::new(),
             enemy_intercept: LazyCell::new(),
             possession: LazyCell::new(),
+            contest: LazyCell::new(),
+            time_to_pressure: LazyCell::new(),
+            me_landing: LazyCell::new(),
             push_wall: LazyCell::new(),
             impending_concede: LazyCell::new(),
             impending_score_conservative: LazyCell::new(),
+            enemy_touch_prediction: LazyCell::new(),
+            impending_concede_if_touched: LazyCell::new(),
             enemy_shoot_score_seconds: LazyCell::new(),
+            enemy_touch_time: LazyCell::new(),
             slightly_panicky_retreat: LazyCell::new(),
             very_panicky_retreat: LazyCell::new(),
+            team: LazyCell::new(),
         }
     }
 
@@ -67,6 +99,8 @@ impl<'a> Scenario<'a> {
         self.me_intercept.borrow().unwrap().as_ref()
     }
 
+    /// The enemy's earliest intercept. Right after we touch the ball, they
+    /// start late, per the `OpponentModel`.
     pub fn enemy_intercept(
         &self,
     ) -> Option<&(&'a common::halfway_house::PlayerInfo, NaiveIntercept)> {
@@ -76,11 +110,25 @@ impl<'a> Scenario<'a> {
         self.enemy_intercept.borrow().unwrap().as_ref()
     }
 
+    /// How the enemy has been playing this match.
+    pub fn opponent(&self) -> &OpponentModel {
+        self.opponent
+    }
+
+    /// Which role we play on our team.
+    pub fn team(&self) -> &TeamCoordination {
+        self.team.borrow_with(|| {
+            TeamCoordination::new(self.game, self.ball_prediction(), self.first_man)
+        })
+    }
+
     pub fn primary_enemy(&self) -> Option<&'a common::halfway_house::PlayerInfo> {
         self.enemy_intercept().map(|&(enemy, ref _intercept)| enemy)
     }
 
-    /// Number of seconds I can reach the ball before the opponent
+    /// Number of seconds I can reach the ball before the opponent. Right after
+    /// we touch the ball, this allows for how slow the opponent has been to
+    /// answer our touches so far, per the `OpponentModel`.
     pub fn possession(&self) -> f32 {
         if !self.me_intercept.filled() {
             self.race();
@@ -88,13 +136,49 @@ impl<'a> Scenario<'a> {
         *self.possession.borrow().unwrap()
     }
 
+    /// If we and the primary enemy both went for the ball, how would the 50/50
+    /// play out?
+    pub fn contest(&self) -> Option<&Contest> {
+        self.contest
+            .borrow_with(|| {
+                let me_intercept = self.me_intercept()?;
+                let (enemy, enemy_intercept) = self.enemy_intercept()?;
+                let contact_dist =
+                    self.game.ball_radius() + self.game.me_vehicle().pivot_to_front_dist();
+                Some(evaluate_contest(
+                    self.ball_prediction(),
+                    contact_dist,
+                    self.game.me(),
+                    me_intercept,
+                    enemy,
+                    enemy_intercept,
+                    self.game.enemy_goal().center_2d,
+                ))
+            })
+            .as_ref()
+    }
+
     fn race(&self) {
         let blitz_me = simulate_ball_blitz(self.ball_prediction(), self.game.me());
+
+        // Right after we touch the ball, the enemy may take a moment to answer.
+        let delay = self.enemy_delay();
+        let enemy_prediction;
+        let enemy_prediction = if delay > 0.0 {
+            enemy_prediction = self.ball_prediction().hacky_expensive_slice(delay);
+            &enemy_prediction
+        } else {
+            self.ball_prediction()
+        };
         let blitz_enemy = self
             .game
             .cars(self.game.enemy_team)
-            .map(|enemy| (enemy, simulate_ball_blitz(self.ball_prediction(), enemy)))
+            .map(|enemy| (enemy, simulate_ball_blitz(enemy_prediction, enemy)))
             .filter_map(|(enemy, intercept)| intercept.map(|i| (enemy, i)))
+            .map(|(enemy, intercept)| {
+                let time = intercept.time + delay;
+                (enemy, NaiveIntercept { time, ..intercept })
+            })
             .min_by_key(|(_enemy, intercept)| NotNan::new(intercept.time).unwrap());
 
         let possession = match (&blitz_me, &blitz_enemy) {
@@ -111,6 +195,52 @@ impl<'a> Scenario<'a> {
         self.possession.fill(possession).ok().unwrap();
     }
 
+    /// How long the enemy will likely wait before going for the ball, per the
+    /// `OpponentModel`.
+    fn enemy_delay(&self) -> f32 {
+        self.opponent
+            .pending_challenge_delay(self.packet.GameInfo.TimeSeconds)
+    }
+
+    /// Number of seconds until an enemy could be pressuring us at the spot
+    /// where we'll likely touch the ball.
+    ///
+    /// Unlike `possession`, this races the enemy to our contact point rather
+    /// than to their own first intercept. Subtract `me_intercept().time` to
+    /// find how long we'll have the ball to ourselves, e.g., to decide whether
+    /// there's time to set up a slow shot.
+    pub fn time_to_pressure(&self) -> f32 {
+        *self.time_to_pressure.borrow_with(|| {
+            let reaction_time = Self::ENEMY_REACTION_TIME.max(self.enemy_delay());
+            let contact_loc = match self.me_intercept() {
+                Some(intercept) => intercept.ball_loc.to_2d(),
+                None => self.ball_prediction().last().loc.to_2d(),
+            };
+            self.game
+                .cars(self.game.enemy_team)
+                .map(|enemy| time_to_reach(enemy, contact_loc) + reaction_time)
+                .fold(f32::INFINITY, f32::min)
+        })
+    }
+
+    /// If we're in the air, where and when will we land?
+    pub fn me_landing(&self) -> Option<&Landing> {
+        self.me_landing
+            .borrow_with(|| {
+                let me = self.game.me();
+                if me.OnGround {
+                    return None;
+                }
+                predict_landing(
+                    self.game.pitch(),
+                    self.game.mutators().gravity,
+                    me.Physics.loc(),
+                    me.Physics.vel(),
+                )
+            })
+            .as_ref()
+    }
+
     /// If I blitz to the ball and hit it straight-on, where will it go?
     pub fn push_wall(&self) -> Wall {
         *self.push_wall.borrow_with(|| {
@@ -131,18 +261,40 @@ impl<'a> Scenario<'a> {
             .as_ref()
     }
 
-    /// If nobody touches the ball, will it end up in our goal?
+    /// If nobody touches the ball, will it end up in our goal? If so, returns
+    /// the ball at the moment its center crosses the goal line.
     pub fn impending_concede(&self) -> Option<&BallFrame> {
         self.impending_concede
-            .borrow_with(|| self.calc_impending_ball_in_goal(self.game.own_goal()))
+            .borrow_with(|| ball_in_goal(self.ball_prediction(), self.game.own_goal()))
             .as_ref()
     }
 
-    /// If nobody touches the ball, will it end up in the given goal?
-    fn calc_impending_ball_in_goal(&self, goal: &Goal) -> Option<BallFrame> {
-        self.ball_prediction()
-            .iter_step_by(0.5)
-            .find(|ball| goal.ball_is_scored(ball.loc))
+    /// The worst case for defense: the ball's trajectory if the enemy gets to
+    /// it at their earliest intercept and hits it straight at our goal.
+    pub fn enemy_touch_prediction(&self) -> Option<&BallTrajectory> {
+        self.enemy_touch_prediction
+            .borrow_with(|| {
+                let (enemy, intercept) = self.enemy_intercept()?;
+                let dist = (intercept.ball_loc - enemy.Physics.loc()).to_2d().norm();
+                let arrival_speed = (dist / intercept.time)
+                    .max(enemy.Physics.vel().norm())
+                    .min(rl::CAR_MAX_SPEED);
+                Some(predict_touch_towards(
+                    self.ball_prediction(),
+                    intercept.time,
+                    arrival_speed,
+                    self.game.own_goal().center_2d,
+                ))
+            })
+            .as_ref()
+    }
+
+    /// Like `impending_concede`, but for the trajectory from
+    /// `enemy_touch_prediction`.
+    pub fn impending_concede_if_touched(&self) -> Option<&BallFrame> {
+        self.impending_concede_if_touched
+            .borrow_with(|| ball_in_goal(self.enemy_touch_prediction()?, self.game.own_goal()))
+            .as_ref()
     }
 
     /// If nobody touches the ball, will it end up in the given goal? (Use this
@@ -182,6 +334,26 @@ impl<'a> Scenario<'a> {
         })
     }
 
+    /// Number of seconds until the primary enemy touches the ball.
+    ///
+    /// `enemy_intercept` assumes they floor it the instant the race starts.
+    /// Unless they're already driving at the ball, they have to notice it's on
+    /// first, so this adds their reaction time.
+    pub fn enemy_touch_time(&self) -> f32 {
+        *self.enemy_touch_time.borrow_with(|| {
+            let (enemy, intercept) = some_or_else!(self.enemy_intercept(), {
+                return f32::INFINITY;
+            });
+            let to_ball = (intercept.ball_loc.to_2d() - enemy.Physics.loc_2d()).to_axis();
+            let closing_speed = enemy.Physics.vel_2d().dot(&to_ball);
+            if closing_speed >= Self::ENEMY_COMMITTED_SPEED {
+                intercept.time
+            } else {
+                intercept.time + Self::ENEMY_REACTION_TIME
+            }
+        })
+    }
+
     /// Is the ball and everyone around it moving towards our goal?
     pub fn slightly_panicky_retreat(&self) -> bool {
         *self.slightly_panicky_retreat.borrow_with(|| {
@@ -243,45 +415,79 @@ impl<'a> Scenario<'a> {
     }
 }
 
-fn blitz_start(car: &common::halfway_house::PlayerInfo, ball_prediction: &BallTrajectory) -> Car1D {
-    let ball_loc = ball_prediction.start().loc.to_2d();
-    let ball_vel = ball_prediction.start().vel.to_2d();
-    let car_vel = car.Physics.vel_2d();
-    let car_to_ball = ball_loc - car.Physics.loc_2d();
-    let speed_towards_ball = car_vel.dot(&car_to_ball.normalize());
-    let speed_with_ball = car_vel.dot(&ball_vel.normalize());
-    Car1D::new()
-        .with_speed(speed_towards_ball.max(speed_with_ball).max(0.0))
-        .with_boost(car.Boost as f32)
+/// Guesstimate how long it would take `car` to drive to `target_loc`, turning
+/// included.
+fn time_to_reach(car: &common::halfway_house::PlayerInfo, target_loc: Point2<f32>) -> f32 {
+    let car_to_target = target_loc - car.Physics.loc_2d();
+    let speed_towards_target = car.Physics.vel_2d().dot(&car_to_target.normalize());
+    let mut sim = Car1D::new()
+        .with_speed(speed_towards_target.max(0.0))
+        .with_boost(car.Boost as f32);
+    sim.advance_by_distance(car_to_target.norm(), 1.0, true);
+
+    sim.time() + turn_time(&car.into(), target_loc)
 }
 
-// Basically simulate a "race to the ball" (poorly) and guesstimate where our
-// first possible intercept might be.
-fn simulate_ball_blitz(
+// Simulate a "race to the ball" and guesstimate where our first possible
+// intercept might be.
+pub(super) fn simulate_ball_blitz(
     ball_prediction: &BallTrajectory,
     car: &common::halfway_house::PlayerInfo,
 ) -> Option<NaiveIntercept> {
-    let mut sim = blitz_start(car, ball_prediction);
-    let mut naive_result = None;
-
-    for ball in ball_prediction.iter_step_by(0.125) {
-        let dist_to_ball = (car.Physics.loc() - ball.loc).to_2d().norm();
-        if sim.distance() >= dist_to_ball {
-            naive_result = Some(ball);
-            break;
-        }
-        sim.advance(ball.dt(), 1.0, true);
+    heading_aware_ground_intercept(&car.into(), ball_prediction.iter_step_by(0.125), |_| true)
+}
+
+/// If `prediction` ends up in `goal`, returns the ball at the moment its center
+/// crosses the goal line.
+fn ball_in_goal(prediction: &BallTrajectory, goal: &Goal) -> Option<BallFrame> {
+    if goal.ball_is_scored(prediction.start().loc) {
+        return Some(prediction.start().clone());
     }
+    prediction
+        .iter()
+        .tuple_windows()
+        .find(|(_, next)| goal.ball_is_scored(next.loc))
+        .map(|(prev, next)| goal_line_crossing(goal, prev, next))
+}
 
-    let naive_result = naive_result?;
-    let penalty = naive_intercept_penalty(&car.into(), &naive_result);
-    let ball = ball_prediction.at_time_or_last(naive_result.t + penalty);
-    Some(NaiveIntercept {
-        time: ball.t - ball_prediction.start().t,
-        ball_loc: ball.loc,
-        ball_vel: ball.vel,
-        car_loc: ball.loc,
-        car_speed: ball.vel.norm(),
-        data: (),
-    })
+/// Find the moment the ball's center crosses the goal line between two
+/// consecutive frames. Going by the frames alone can be off by a whole frame,
+/// which is enough to turn a save into a goal.
+fn goal_line_crossing(goal: &Goal, prev: &BallFrame, next: &BallFrame) -> BallFrame {
+    let dt = next.t - prev.t;
+    let tau = if prev.vel.y.abs() >= 1e-3 {
+        ((goal.center_2d.y - prev.loc.y) / prev.vel.y)
+            .max(0.0)
+            .min(dt)
+    } else {
+        dt
+    };
+    prev.extrapolate(tau)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::ball::BallTrajectory,
+        strategy::{scenario::goal_line_crossing, SOCCAR_GOAL_BLUE},
+    };
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    #[test]
+    fn crossing_between_frames() {
+        let dt = rl::PHYSICS_DT;
+        let vel = Vector3::new(0.0, -1200.0, 0.0);
+        // The goal line is a quarter of the way between these frames.
+        let prev = Point3::new(100.0, -rl::FIELD_MAX_Y + 2.5, 300.0);
+        let next = prev + vel * dt;
+        let prediction =
+            BallTrajectory::from_samples(vec![(1.0, prev, vel), (1.0 + dt, next, vel)]);
+        let frames: Vec<_> = prediction.iter().collect();
+
+        let crossing = goal_line_crossing(&SOCCAR_GOAL_BLUE, frames[0], frames[1]);
+        assert!((crossing.loc.y + rl::FIELD_MAX_Y).abs() < 0.01);
+        assert!((crossing.t - (1.0 + dt / 4.0)).abs() < 1e-4);
+        assert!(crossing.loc.z < prev.z);
+    }
 }
@@ -1,9 +1,9 @@
 use crate::{
     behavior::{
-        defense::Defense,
+        defense::{AvoidOwnGoal, Defense},
         higher_order::{Chain, Predicate, TryChoose, While},
-        movement::{GetToFlatGround, Land, Yielder},
-        offense::Offense,
+        movement::{CeilingDrop, GetToFlatGround, Land, Yielder},
+        offense::{FollowShot, KeepBallAirborne, Offense},
         strike::{FiftyFifty, WallHit},
         taunt::{PodiumBlastoff, PodiumSpew, PodiumStare, SaltWhileDemolished, TurtleSpin},
         PreKickoff,
@@ -11,7 +11,7 @@ use crate::{
     routing::{
         behavior::FollowRoute,
         plan::WallIntercept,
-        recover::{IsSkidding, MatchIsEnded, RoundIsNotActive},
+        recover::{GoalIsPending, IsSkidding, MatchIsEnded, RoundIsNotActive},
     },
     strategy::{scenario::Scenario, strategy::Strategy, Behavior, Context, Priority},
     utils::Wall,
@@ -26,6 +26,11 @@ pub struct Soccar;
 
 impl Strategy for Soccar {
     fn baseline(&mut self, ctx: &mut Context<'_>) -> Box<dyn Behavior> {
+        // None of the planners know what to do upside down.
+        if CeilingDrop::on_ceiling(ctx.me()) {
+            return Box::new(CeilingDrop::new());
+        }
+
         // If we have no wheel contact, i.e. we're floating in the air
         if !ctx.me().OnGround {
             return Box::new(Land::new());
@@ -54,19 +59,34 @@ impl Strategy for Soccar {
             _ => {}
         }
 
+        // Right after our shot, wait for the rebound instead of retreating.
+        if FollowShot::applicable(ctx) {
+            ctx.eeg
+                .log(name_of_type!(Soccar), "we just shot; following up");
+            return Box::new(FollowShot::new());
+        }
+
         if ctx.scenario.slightly_panicky_retreat()
-            && ctx.scenario.possession() < Scenario::POSSESSION_CONTESTABLE
+            && ctx.scenario.possession() < ctx.risk.retreat_possession(ctx.personality)
         {
             ctx.eeg
                 .log(name_of_type!(Soccar), "slightly_panicky_retreat");
             return Box::new(Defense::new());
         }
 
-        if Defense::enemy_can_attack(ctx) {
+        if !ctx.risk.all_out_offense() && Defense::enemy_can_attack(ctx) {
             ctx.eeg.log(name_of_type!(Soccar), "enemy_can_attack");
             return Box::new(Defense::new());
         }
 
+        if !ctx.scenario.team().may_commit() {
+            ctx.eeg.log(
+                name_of_type!(Soccar),
+                format!("{:?}; leaving the ball", ctx.scenario.team().my_role()),
+            );
+            return Box::new(Defense::new());
+        }
+
         Box::new(Offense::new())
     }
 
@@ -90,14 +110,27 @@ impl Strategy for Soccar {
             return None;
         }
 
-        // Force kickoff behavior. We can't rely on the normal routing, because it
-        // doesn't account for boost pads that you pick up on the way, so it dodges and
-        // goes too slow.
-        if current.priority() < Priority::Force && PreKickoff::is_kickoff(&ctx.packet.GameBall) {
-            ctx.eeg.log(name_of_type!(Soccar), "forcing kickoff");
-            return Some(Box::new(Chain::new(Priority::Force, vec![Box::new(
-                PreKickoff::new(),
-            )])));
+        return_some!(self.reflex(ctx, current));
+
+        match endgame(ctx) {
+            Endgame::ZeroSeconds => {
+                if current.priority() < Priority::Strike && ZeroSecondChance.evaluate(ctx) {
+                    ctx.eeg.log(
+                        name_of_type!(Soccar),
+                        "0:00 and we need a goal; keep the ball up",
+                    );
+                    return Some(Box::new(Chain::new(
+                        Priority::Strike,
+                        vec![Box::new(While::new(
+                            ZeroSecondChance,
+                            KeepBallAirborne::new(),
+                        ))],
+                    )));
+                }
+            }
+            // Next goal wins, so the clock no longer matters, and neither does
+            // the risk profile. Play it straight.
+            Endgame::Overtime | Endgame::Regulation => {}
         }
 
         if current.priority() < Priority::Strike
@@ -106,16 +139,30 @@ impl Strategy for Soccar {
             && !IsSkidding.evaluate(&ctx.me().into())
             && ctx.scenario.possession().abs() < Scenario::POSSESSION_CONTESTABLE
         {
-            ctx.eeg.log(
-                name_of_type!(Soccar),
-                format!(
-                    "enemy can shoot, possession = {:.2}, going for 50/50",
-                    ctx.scenario.possession(),
-                ),
-            );
-            return Some(Box::new(Chain::new(Priority::Strike, vec![Box::new(
-                FiftyFifty::new(),
-            )])));
+            // Being there in time isn't enough. If their touch would beat ours,
+            // stay home instead of feeding them the ball.
+            let challenge = ctx
+                .scenario
+                .contest()
+                .map_or(true, |contest| ctx.risk.should_challenge(contest));
+            if challenge {
+                ctx.eeg.log(
+                    name_of_type!(Soccar),
+                    format!(
+                        "enemy can shoot, possession = {:.2}, going for 50/50",
+                        ctx.scenario.possession(),
+                    ),
+                );
+                return Some(Box::new(Chain::new(Priority::Strike, vec![Box::new(
+                    FiftyFifty::new(),
+                )])));
+            } else if current.priority() < Priority::Defense {
+                ctx.eeg
+                    .log(name_of_type!(Soccar), "we'd lose the 50/50, going to defense");
+                return Some(Box::new(Chain::new(Priority::Defense, vec![Box::new(
+                    Defense::new(),
+                )])));
+            }
         }
 
         if current.priority() < Priority::Save
@@ -135,6 +182,7 @@ impl Strategy for Soccar {
         }
 
         if current.priority() < Priority::Defense
+            && !ctx.risk.all_out_offense()
             && Defense::enemy_can_shoot(ctx)
             && GetToFlatGround::on_flat_ground(ctx.me())
             && !IsSkidding.evaluate(&ctx.me().into())
@@ -152,7 +200,10 @@ impl Strategy for Soccar {
             )])));
         }
 
-        if current.priority() < Priority::Defense && ctx.scenario.very_panicky_retreat() {
+        if current.priority() < Priority::Defense
+            && !ctx.risk.all_out_offense()
+            && ctx.scenario.very_panicky_retreat()
+        {
             ctx.eeg.log(name_of_type!(Soccar), "very_panicky_retreat");
             return Some(Box::new(Chain::new(Priority::Defense, vec![Box::new(
                 Defense::new(),
@@ -174,6 +225,22 @@ impl Strategy for Soccar {
             let spin = TurtleSpin::new();
             return Some(Box::new(While::new(ScoringVerySoon, spin)));
         }
+        if current.priority() < Priority::Taunt {
+            if let Some(goal) = ctx.goal_detector.pending() {
+                // Whatever we were doing no longer makes sense. Wait until the ball is
+                // back in play, then start fresh. Only show off if the goal sealed it.
+                let ours = goal.scoring_team == ctx.game.team.to_ffi();
+                let behavior: Box<dyn Behavior> = if ours && commanding_lead(ctx) {
+                    Box::new(While::new(GoalIsPending, TurtleSpin::new()))
+                } else {
+                    Box::new(While::new(
+                        GoalIsPending,
+                        Yielder::new(9999.0, Default::default()).priority(Priority::Taunt),
+                    ))
+                };
+                return Some(behavior);
+            }
+        }
         if current.priority() < Priority::Taunt && !ctx.packet.GameInfo.RoundActive {
             let behavior = if commanding_lead(ctx) && ball_in_enemy_half(ctx) {
                 While::new(RoundIsNotActive, TurtleSpin::new())
@@ -188,6 +255,37 @@ impl Strategy for Soccar {
 
         None
     }
+
+    fn reflex(
+        &mut self,
+        ctx: &mut Context<'_>,
+        current: &dyn Behavior,
+    ) -> Option<Box<dyn Behavior>> {
+        if ctx.packet.GameInfo.MatchEnded {
+            return None;
+        }
+
+        // Don't score on ourselves. This is a reflex, so it skips all the usual
+        // deliberation.
+        if current.priority() < Priority::Force && AvoidOwnGoal::triggered(ctx) {
+            ctx.eeg
+                .log(name_of_type!(Soccar), "about to score an own goal");
+            return Some(Box::new(AvoidOwnGoal::new()));
+        }
+
+        // Force kickoff behavior. We can't rely on the normal routing, because it
+        // doesn't account for boost pads that you pick up on the way, so it dodges and
+        // goes too slow.
+        if current.priority() < Priority::Force && PreKickoff::is_kickoff(&ctx.packet.GameBall) {
+            ctx.eeg.log(name_of_type!(Soccar), "forcing kickoff");
+            return Some(Box::new(Chain::new(
+                Priority::Force,
+                vec![Box::new(PreKickoff::new())],
+            )));
+        }
+
+        None
+    }
 }
 
 fn ball_in_enemy_half(ctx: &mut Context<'_>) -> bool {
@@ -231,11 +329,57 @@ impl Predicate for ScoringVerySoon {
     }
 }
 
+/// Where the match stands against the clock.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Endgame {
+    /// The clock is running, or there isn't one.
+    Regulation,
+    /// The clock has run out, but play goes on until the ball touches the
+    /// ground.
+    ZeroSeconds,
+    /// Next goal wins.
+    Overtime,
+}
+
+fn endgame(ctx: &Context<'_>) -> Endgame {
+    let info = &ctx.packet.GameInfo;
+    if info.Overtime {
+        Endgame::Overtime
+    } else if !info.UnlimitedTime && info.RoundActive && info.GameTimeRemaining <= 0.0 {
+        Endgame::ZeroSeconds
+    } else {
+        Endgame::Regulation
+    }
+}
+
+/// The clock is out but the ball is still up on the enemy's side, and a goal
+/// would still win it or tie it.
+struct ZeroSecondChance;
+
+impl Predicate for ZeroSecondChance {
+    fn name(&self) -> &str {
+        name_of_type!(ZeroSecondChance)
+    }
+
+    fn evaluate(&mut self, ctx: &mut Context<'_>) -> bool {
+        let margin = ctx.game.score_margin();
+        let airborne = KeepBallAirborne::landing_time(ctx.scenario.ball_prediction())
+            .map_or(true, |t| t > 0.0);
+        endgame(ctx) == Endgame::ZeroSeconds
+            && (margin == 0 || margin == -1)
+            && airborne
+            && ball_in_enemy_half(ctx)
+    }
+}
+
 fn commanding_lead(ctx: &mut Context<'_>) -> bool {
-    let minutes_remaining = (ctx.packet.GameInfo.GameTimeRemaining / 60.0) as i32;
-    let us = ctx.packet.Teams[ctx.game.team.to_ffi() as usize].Score;
-    let them = ctx.packet.Teams[ctx.game.enemy_team.to_ffi() as usize].Score;
-    us - them >= minutes_remaining
+    // Past the end of regulation, the only lead that counts is one that's
+    // already won.
+    if endgame(ctx) != Endgame::Regulation {
+        return ctx.game.score_margin() > 0;
+    }
+    let minutes_remaining = (ctx.game.seconds_remaining() / 60.0) as i32;
+    ctx.game.score_margin() >= minutes_remaining
 }
 
 #[cfg(test)]
@@ -247,9 +391,11 @@ mod integration_tests {
     use brain_test_data::recordings;
     use common::prelude::*;
     use nalgebra::{Point3, Rotation3, Vector3};
+    use std::f32::consts::PI;
 
     #[test]
     fn dont_panic_when_no_intercept() {
+        tags!();
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-1185.1904, 1242.3097, 133.98555),
@@ -270,6 +416,7 @@ mod integration_tests {
 
     #[test]
     fn get_boost_on_defense_if_we_have_time() {
+        tags!();
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-3112.23, -2548.45, 93.72),
@@ -298,6 +445,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn get_boost_on_offense_if_we_have_time() {
+        tags!();
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(810.51, 373.18, 227.86),
@@ -319,6 +467,7 @@ mod integration_tests {
 
     #[test]
     fn clear_stationary_ball() {
+        tags!();
         let test = TestRunner::new()
             .scenario(TestScenario {
                 ball_loc: Point3::new(-500.0, -5000.0, 0.0),
@@ -335,6 +484,7 @@ mod integration_tests {
 
     #[test]
     fn clear_defensive_ball() {
+        tags!();
         let test = TestRunner::new()
             .one_v_one(&*recordings::CLEAR_DEFENSIVE_BALL, 53.0)
             .soccar()
@@ -347,6 +497,7 @@ mod integration_tests {
     #[test]
     #[ignore(note = "TODO")]
     fn dont_allow_long_shot() {
+        tags!();
         let test = TestRunner::new()
             .one_v_one(&*recordings::DONT_ALLOW_LONG_SHOT, 282.5)
             .starting_boost(0.0)
@@ -356,8 +507,27 @@ mod integration_tests {
         assert!(!test.enemy_has_scored());
     }
 
+    #[test]
+    fn hold_off_mirror_attacker() {
+        tags!(Defense);
+        let test = TestRunner::new()
+            .scenario(TestScenario {
+                ball_loc: Point3::new(0.0, -1000.0, 92.74),
+                car_loc: Point3::new(0.0, -4000.0, 17.01),
+                enemy_loc: Point3::new(0.0, 1000.0, 17.01),
+                enemy_rot: Rotation3::from_unreal_angles(0.0, -PI / 2.0, 0.0),
+                ..Default::default()
+            })
+            .enemy_attacker()
+            .soccar()
+            .run_for_millis(7000);
+
+        assert!(!test.enemy_has_scored());
+    }
+
     #[test]
     fn transition_from_defense_to_save() {
+        tags!();
         let test = TestRunner::new()
             .one_v_one(&*recordings::TRANSITION_FROM_DEFENSE_TO_SAVE, 109.0)
             .starting_boost(30.0)
@@ -7,4 +7,15 @@ pub trait Strategy: Send {
         ctx: &mut Context<'_>,
         current: &dyn Behavior,
     ) -> Option<Box<dyn Behavior>>;
+
+    /// The interrupts that can't wait out the reaction time, e.g., kickoffs.
+    /// This is asked instead of `interrupt` while the bot is still reacting, so
+    /// `interrupt` should check these too.
+    fn reflex(
+        &mut self,
+        _ctx: &mut Context<'_>,
+        _current: &dyn Behavior,
+    ) -> Option<Box<dyn Behavior>> {
+        None
+    }
 }
@@ -0,0 +1,194 @@
+use crate::{
+    helpers::ball::BallTrajectory,
+    strategy::{scenario::simulate_ball_blitz, Game},
+};
+use common::prelude::*;
+use ordered_float::NotNan;
+use std::ptr;
+
+/// Who on our team does what, so that several copies of the bot don't all
+/// chase the ball at once.
+///
+/// There's no communication between teammates. Every copy computes the same
+/// assignment from the same packets, so they agree without having to talk.
+pub struct TeamCoordination {
+    me: Role,
+    first_man: Option<usize>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Goes for the ball.
+    FirstMan,
+    /// Supports the first man, ready to follow up.
+    SecondMan,
+    /// Stays home.
+    BackPost,
+}
+
+impl TeamCoordination {
+    /// A car on the wrong side of the ball has to rotate back before it can
+    /// make a useful touch. Charge it this many seconds for that.
+    const OUT_OF_POSITION_PENALTY: f32 = 1.0;
+    /// To take over as first man, a teammate has to beat the current one to
+    /// the ball by this many seconds. Otherwise two cars about as close would
+    /// trade the role back and forth every frame.
+    const HANDOFF_MARGIN: f32 = 0.3;
+
+    /// `first_man` is who had the role last frame, as an index into our
+    /// team's cars.
+    pub fn new(
+        game: &Game<'_>,
+        ball_prediction: &BallTrajectory,
+        first_man: Option<usize>,
+    ) -> Self {
+        let own_goal = game.own_goal().center_2d;
+        let ball_loc = ball_prediction.start().loc.to_2d();
+        let ball_goal_dist = (ball_loc - own_goal).norm();
+
+        let mut me_index = 0;
+        let candidates: Vec<_> = game
+            .cars(game.team)
+            .enumerate()
+            .map(|(i, car)| {
+                if ptr::eq(car, game.me()) {
+                    me_index = i;
+                }
+                let goal_dist = (car.Physics.loc_2d() - own_goal).norm();
+                let time_to_ball = if car.Demolished {
+                    None
+                } else {
+                    simulate_ball_blitz(ball_prediction, car).map(|i| {
+                        let penalty = if goal_dist > ball_goal_dist {
+                            Self::OUT_OF_POSITION_PENALTY
+                        } else {
+                            0.0
+                        };
+                        i.time + penalty
+                    })
+                };
+                Candidate {
+                    time_to_ball,
+                    goal_dist,
+                }
+            })
+            .collect();
+
+        let roles = assign_roles(&candidates, first_man);
+        Self {
+            me: roles[me_index],
+            first_man: roles.iter().position(|&r| r == Role::FirstMan),
+        }
+    }
+
+    pub fn my_role(&self) -> Role {
+        self.me
+    }
+
+    /// Which of our team's cars is going for the ball, to carry over to the
+    /// next frame.
+    pub fn first_man(&self) -> Option<usize> {
+        self.first_man
+    }
+
+    /// Returns `true` if we're the one who should be going for the ball.
+    pub fn may_commit(&self) -> bool {
+        self.me == Role::FirstMan
+    }
+}
+
+struct Candidate {
+    /// Seconds until the car could touch the ball, including the cost of
+    /// rotating back if needed. `None` if it can't.
+    time_to_ball: Option<f32>,
+    goal_dist: f32,
+}
+
+/// The fastest car to the ball is the first man, though whoever already has
+/// the role keeps it unless someone is faster by `HANDOFF_MARGIN`. Of the
+/// rest, the one closest to our goal is the back post, and anyone else is a
+/// second man. With only two of us, the other car is the second man. Ties go to
+/// the lower index, so every copy of the bot breaks them the same way.
+fn assign_roles(candidates: &[Candidate], first_man: Option<usize>) -> Vec<Role> {
+    let mut roles = vec![Role::SecondMan; candidates.len()];
+    let first = candidates
+        .iter()
+        .enumerate()
+        .min_by_key(|&(i, c)| {
+            let mut time = c.time_to_ball.unwrap_or(f32::INFINITY);
+            if Some(i) == first_man {
+                time -= TeamCoordination::HANDOFF_MARGIN;
+            }
+            (NotNan::new(time).unwrap(), i)
+        })
+        .map(|(i, _)| i);
+    let first = match first {
+        Some(first) => first,
+        None => return roles,
+    };
+    roles[first] = Role::FirstMan;
+
+    if candidates.len() >= 3 {
+        let back = candidates
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| i != first)
+            .min_by_key(|&(i, c)| (NotNan::new(c.goal_dist).unwrap(), i))
+            .map(|(i, _)| i)
+            .unwrap();
+        roles[back] = Role::BackPost;
+    }
+    roles
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::strategy::team_coordination::{assign_roles, Candidate, Role};
+
+    fn candidate(time_to_ball: Option<f32>, goal_dist: f32) -> Candidate {
+        Candidate {
+            time_to_ball,
+            goal_dist,
+        }
+    }
+
+    #[test]
+    fn solo() {
+        let roles = assign_roles(&[candidate(None, 1000.0)], None);
+        assert_eq!(roles, vec![Role::FirstMan]);
+    }
+
+    #[test]
+    fn three_v_three() {
+        let roles = assign_roles(
+            &[
+                candidate(Some(2.0), 1000.0),
+                candidate(Some(1.0), 4000.0),
+                candidate(None, 3000.0),
+            ],
+            None,
+        );
+        assert_eq!(roles, vec![Role::BackPost, Role::FirstMan, Role::SecondMan]);
+    }
+
+    #[test]
+    fn ties_break_the_same_way() {
+        let roles = assign_roles(
+            &[candidate(Some(1.0), 2000.0), candidate(Some(1.0), 2000.0)],
+            None,
+        );
+        assert_eq!(roles, vec![Role::FirstMan, Role::SecondMan]);
+    }
+
+    #[test]
+    fn first_man_keeps_the_role() {
+        let candidates = [candidate(Some(1.1), 2000.0), candidate(Some(1.0), 2000.0)];
+        let roles = assign_roles(&candidates, Some(0));
+        assert_eq!(roles, vec![Role::FirstMan, Role::SecondMan]);
+
+        // Until someone is clearly faster.
+        let candidates = [candidate(Some(1.5), 2000.0), candidate(Some(1.0), 2000.0)];
+        let roles = assign_roles(&candidates, Some(0));
+        assert_eq!(roles, vec![Role::SecondMan, Role::FirstMan]);
+    }
+}
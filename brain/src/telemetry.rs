@@ -0,0 +1,353 @@
+use crate::eeg::Event;
+use common::halfway_house;
+use nalgebra::{Point3, Vector3};
+use serde_derive::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    iter::{self, once},
+    path::Path,
+};
+
+/// Every telemetry log starts with this, so we don't go reading something
+/// else by mistake.
+const MAGIC: &[u8; 4] = b"SDCT";
+const VERSION: u8 = 2;
+
+const BODY_FIELDS: &[&str] = &[
+    "loc_x", "loc_y", "loc_z", "pitch", "yaw", "roll", "vel_x", "vel_y", "vel_z",
+];
+const FRAME_FIELDS: &[&str] = &[
+    "behavior",
+    "throttle",
+    "steer",
+    "pitch",
+    "yaw",
+    "roll",
+    "jump",
+    "boost",
+    "handbrake",
+    "decisions",
+    "events",
+];
+
+/// Records every frame of a match: what the bot saw, what it was running, what
+/// it pressed, and what it noticed along the way. Unlike a clip, it keeps the
+/// whole match, so post-match analysis can work out why something happened
+/// without rerunning anything.
+///
+/// The log is the header, then each frame written with `bincode`. Use
+/// `export_telemetry_csv` to read it.
+pub struct TelemetryRecorder {
+    w: BufWriter<File>,
+    frame: Option<TelemetryFrame>,
+}
+
+impl TelemetryRecorder {
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut w = BufWriter::new(File::create(path)?);
+        write_header(&mut w)?;
+        Ok(Self { w, frame: None })
+    }
+
+    /// Call this at the start of each frame.
+    pub fn begin(&mut self, packet: &halfway_house::LiveDataPacket) {
+        self.frame = Some(TelemetryFrame::new(packet));
+    }
+
+    pub fn event(&mut self, event: &Event) {
+        if let Some(frame) = &mut self.frame {
+            frame.events.push(format!("{:?}", event));
+        }
+    }
+
+    pub fn decision(&mut self, line: &str) {
+        if let Some(frame) = &mut self.frame {
+            frame.decisions.push(line.to_string());
+        }
+    }
+
+    pub fn input(&mut self, behavior: &str, input: &halfway_house::PlayerInput) {
+        if let Some(frame) = &mut self.frame {
+            frame.behavior = behavior.to_string();
+            frame.input = *input;
+        }
+    }
+
+    /// Call this at the end of each frame.
+    pub fn finish(&mut self) -> io::Result<()> {
+        let frame = some_or_else!(self.frame.take(), {
+            return Ok(());
+        });
+        bincode::serialize_into(&mut self.w, &frame).map_err(bincode_error)?;
+        // If the bot crashes, the log should still have everything up to the
+        // crash.
+        self.w.flush()
+    }
+}
+
+/// One frame of a telemetry log.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TelemetryFrame {
+    pub time: f32,
+    pub ball: TelemetryBody,
+    pub cars: Vec<TelemetryCar>,
+    /// The blurb of the behavior the `Runner` ended the frame with.
+    pub behavior: String,
+    /// What we pressed.
+    #[serde(with = "crate::utils::wire::player_input")]
+    pub input: halfway_house::PlayerInput,
+    /// Every behavior switch the `Runner` made, in order.
+    pub decisions: Vec<String>,
+    /// Every `Event` tracked, in order.
+    pub events: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryBody {
+    #[serde(with = "crate::utils::wire::point3")]
+    pub loc: Point3<f32>,
+    /// Pitch, yaw and roll, as they appear in the packet.
+    #[serde(with = "crate::utils::wire::vector3")]
+    pub rot: Vector3<f32>,
+    #[serde(with = "crate::utils::wire::vector3")]
+    pub vel: Vector3<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryCar {
+    pub body: TelemetryBody,
+    pub boost: u8,
+}
+
+impl TelemetryFrame {
+    fn new(packet: &halfway_house::LiveDataPacket) -> Self {
+        Self {
+            time: packet.GameInfo.TimeSeconds,
+            ball: TelemetryBody::from_physics(&packet.GameBall.Physics),
+            cars: packet.cars().map(TelemetryCar::from_player).collect(),
+            behavior: String::new(),
+            input: Default::default(),
+            decisions: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+impl TelemetryCar {
+    pub(crate) fn from_player(car: &halfway_house::PlayerInfo) -> Self {
+        Self {
+            body: TelemetryBody::from_physics(&car.Physics),
+            boost: car.Boost.max(0).min(255) as u8,
+        }
+    }
+}
+
+impl TelemetryBody {
+    pub(crate) fn from_physics(physics: &halfway_house::Physics) -> Self {
+        let rot = &physics.Rotation;
+        let vel = &physics.Velocity;
+        Self {
+            loc: Point3::new(physics.Location.X, physics.Location.Y, physics.Location.Z),
+            rot: Vector3::new(rot.Pitch, rot.Yaw, rot.Roll),
+            vel: Vector3::new(vel.X, vel.Y, vel.Z),
+        }
+    }
+}
+
+/// Read every frame of a telemetry log. If the bot died partway through a
+/// frame, the frames before it are still returned.
+pub fn read_telemetry(mut r: impl Read) -> io::Result<Vec<TelemetryFrame>> {
+    let mut magic = [0; 4];
+    r.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("not a telemetry log"));
+    }
+    let mut version = [0];
+    r.read_exact(&mut version)?;
+    let version = version[0];
+    if version != VERSION {
+        return Err(invalid_data(format!(
+            "unsupported telemetry version {}",
+            version,
+        )));
+    }
+
+    let mut frames = Vec::new();
+    loop {
+        match bincode::deserialize_from(&mut r).map_err(bincode_error) {
+            Ok(frame) => frames.push(frame),
+            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(frames),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Convert a telemetry log to CSV, one row per frame. Decisions and events
+/// that happened in the same frame share a cell.
+///
+/// There are columns for as many cars as were ever on the field at once. Cars
+/// that weren't there for a frame get blank cells.
+pub fn export_telemetry_csv(r: impl Read, w: impl Write) -> io::Result<()> {
+    let frames = read_telemetry(r)?;
+    let num_cars = frames.iter().map(|f| f.cars.len()).max().unwrap_or(0);
+
+    let mut w = csv::Writer::from_writer(w);
+    let header = once("time".to_string())
+        .chain(body_header("ball".to_string()))
+        .chain((0..num_cars).flat_map(|i| {
+            let prefix = format!("player{}", i);
+            let boost = format!("{}_boost", prefix);
+            body_header(prefix).chain(once(boost))
+        }))
+        .chain(FRAME_FIELDS.iter().map(|s| s.to_string()));
+    w.write_record(header).map_err(csv_error)?;
+
+    for frame in &frames {
+        let mut record = vec![frame.time.to_string()];
+        record.extend(frame.ball.to_csv());
+        for car in &frame.cars {
+            record.extend(car.body.to_csv());
+            record.push(car.boost.to_string());
+        }
+        let missing = num_cars - frame.cars.len();
+        record.extend(iter::repeat(String::new()).take(missing * (BODY_FIELDS.len() + 1)));
+        let input = &frame.input;
+        record.push(frame.behavior.clone());
+        record.push(input.Throttle.to_string());
+        record.push(input.Steer.to_string());
+        record.push(input.Pitch.to_string());
+        record.push(input.Yaw.to_string());
+        record.push(input.Roll.to_string());
+        record.push((input.Jump as i32).to_string());
+        record.push((input.Boost as i32).to_string());
+        record.push((input.Handbrake as i32).to_string());
+        record.push(frame.decisions.join("; "));
+        record.push(frame.events.join(" "));
+        w.write_record(&record).map_err(csv_error)?;
+    }
+    w.flush()
+}
+
+impl TelemetryBody {
+    fn to_csv(&self) -> impl Iterator<Item = String> + '_ {
+        (self.loc.iter())
+            .chain(self.rot.iter())
+            .chain(self.vel.iter())
+            .map(f32::to_string)
+    }
+}
+
+fn body_header(prefix: String) -> impl Iterator<Item = String> {
+    BODY_FIELDS
+        .iter()
+        .map(move |field| format!("{}_{}", prefix, field))
+}
+
+fn write_header(w: &mut impl Write) -> io::Result<()> {
+    w.write_all(MAGIC)?;
+    w.write_all(&[VERSION])
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn bincode_error(error: bincode::Error) -> io::Error {
+    match *error {
+        bincode::ErrorKind::Io(error) => error,
+        error => invalid_data(error),
+    }
+}
+
+fn csv_error(error: csv::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        eeg::Event,
+        telemetry::{export_telemetry_csv, read_telemetry, write_header, TelemetryFrame},
+    };
+    use common::halfway_house::{LiveDataPacket, PlayerInfo, PlayerInput};
+    use std::iter;
+
+    fn frame(time: f32) -> TelemetryFrame {
+        let mut car = PlayerInfo {
+            Boost: 42,
+            ..Default::default()
+        };
+        car.Physics.Location.X = 1000.0;
+        let packet = LiveDataPacket::default().with_cars(iter::once(car));
+        let mut frame = TelemetryFrame::new(&packet);
+        frame.time = time;
+        frame.behavior = "Chain (FollowRoute, GroundedHit)".to_string();
+        frame.input = PlayerInput {
+            Throttle: 1.0,
+            Boost: true,
+            ..Default::default()
+        };
+        frame.decisions.push("1.000 [Runner] > Shoot".to_string());
+        frame.events.push(format!("{:?}", Event::Offense));
+        frame
+    }
+
+    fn log(frames: &[TelemetryFrame]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_header(&mut buf).unwrap();
+        for frame in frames {
+            bincode::serialize_into(&mut buf, frame).unwrap();
+        }
+        buf
+    }
+
+    #[test]
+    fn round_trip() {
+        let frames = read_telemetry(&log(&[frame(1.0), frame(2.0)])[..]).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[1].time, 2.0);
+        assert_eq!(frames[0].cars[0].boost, 42);
+        assert_eq!(frames[0].cars[0].body.loc.x, 1000.0);
+        assert_eq!(frames[0].behavior, "Chain (FollowRoute, GroundedHit)");
+        assert!(frames[0].input.Boost && !frames[0].input.Jump);
+        assert_eq!(frames[0].events, vec!["Offense".to_string()]);
+    }
+
+    #[test]
+    fn truncated_frame() {
+        let mut buf = log(&[frame(1.0), frame(2.0)]);
+        let len = buf.len();
+        buf.truncate(len - 10);
+        assert_eq!(read_telemetry(&buf[..]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn csv() {
+        let mut out = Vec::new();
+        export_telemetry_csv(&log(&[frame(1.0)])[..], &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let mut lines = out.lines();
+        assert!(lines.next().unwrap().contains("player0_boost"));
+        let row = lines.next().unwrap();
+        assert!(
+            row.contains("\"Chain (FollowRoute, GroundedHit)\""),
+            "{}",
+            row
+        );
+        assert!(row.contains("[Runner] > Shoot"), "{}", row);
+    }
+
+    #[test]
+    fn csv_with_cars_coming_and_going() {
+        let mut two_cars = frame(2.0);
+        two_cars.cars.push(two_cars.cars[0].clone());
+        let mut out = Vec::new();
+        export_telemetry_csv(&log(&[frame(1.0), two_cars])[..], &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let mut lines = out.lines();
+        assert!(lines.next().unwrap().contains("player1_boost"));
+        assert!(lines.next().unwrap().contains(",,,"));
+        assert!(lines.next().unwrap().contains("42,1000"));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{
+    eeg::EEG,
+    helpers::ball::{BallFrame, BallTrajectory},
+};
+use common::Distance;
+use nalgebra::{Point3, Vector3};
+use nameof::name_of_type;
+use std::collections::VecDeque;
+
+/// Keeps a rolling record of old ball predictions and scores them against
+/// where the ball actually ended up. This lets us tell whether a whiff was the
+/// fault of the prediction or the fault of whatever was following it.
+pub struct BallPredictionAccuracy {
+    pending: VecDeque<Snapshot>,
+    last_snapshot_time: Option<f32>,
+    last_time: Option<f32>,
+    stats: [[ErrorStats; BOUNCE_BUCKETS]; NUM_HORIZONS],
+}
+
+/// The horizons (in seconds) at which predictions are sampled.
+pub const HORIZONS: [f32; NUM_HORIZONS] = [0.25, 0.5, 1.0, 2.0, 3.0];
+const NUM_HORIZONS: usize = 5;
+
+/// Predictions are grouped by the number of bounces they went through. Anything
+/// past the last bucket is lumped into it.
+pub const BOUNCE_BUCKETS: usize = 3;
+
+struct Snapshot {
+    time: f32,
+    samples: Vec<Sample>,
+}
+
+struct Sample {
+    horizon_index: usize,
+    loc: Point3<f32>,
+    bounces: usize,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct ErrorStats {
+    pub count: usize,
+    pub total: f32,
+    pub max: f32,
+}
+
+impl ErrorStats {
+    fn add(&mut self, error: f32) {
+        self.count += 1;
+        self.total += error;
+        self.max = self.max.max(error);
+    }
+
+    pub fn mean(&self) -> Option<f32> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.total / self.count as f32)
+        }
+    }
+}
+
+impl BallPredictionAccuracy {
+    /// Don't bother taking a snapshot every frame; it's a lot of memory for very
+    /// little extra information.
+    const SNAPSHOT_INTERVAL: f32 = 0.1;
+    /// A velocity change this far beyond what gravity explains counts as a
+    /// bounce.
+    const BOUNCE_DV: f32 = 100.0;
+
+    pub fn new() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            last_snapshot_time: None,
+            last_time: None,
+            stats: Default::default(),
+        }
+    }
+
+    /// Call this once per frame with the current prediction, the observed ball
+    /// location, and the gravity the prediction was made under.
+    pub fn update(
+        &mut self,
+        time: f32,
+        prediction: &BallTrajectory,
+        ball_loc: Point3<f32>,
+        gravity: f32,
+        eeg: &mut EEG,
+    ) {
+        if self.last_time.map(|t| time < t).unwrap_or(false) {
+            eeg.log(
+                name_of_type!(BallPredictionAccuracy),
+                "time went backwards; dropping pending predictions",
+            );
+            self.pending.clear();
+            self.last_snapshot_time = None;
+        }
+        self.last_time = Some(time);
+
+        self.score(time, ball_loc);
+
+        let due = self
+            .last_snapshot_time
+            .map(|t| time - t >= Self::SNAPSHOT_INTERVAL)
+            .unwrap_or(true);
+        if due {
+            self.pending
+                .push_back(Self::snapshot(time, prediction, gravity));
+            self.last_snapshot_time = Some(time);
+        }
+
+        self.report(eeg);
+    }
+
+    fn snapshot(time: f32, prediction: &BallTrajectory, gravity: f32) -> Snapshot {
+        let mut samples = Vec::with_capacity(HORIZONS.len());
+        let mut bounces = 0;
+        let mut prev: Option<&BallFrame> = None;
+        let mut horizons = HORIZONS.iter().enumerate().peekable();
+
+        for frame in prediction.iter() {
+            if let Some(prev) = prev {
+                if is_bounce(prev.vel, frame.vel, frame.t - prev.t, gravity) {
+                    bounces += 1;
+                }
+            }
+            while let Some(&(horizon_index, &horizon)) = horizons.peek() {
+                if frame.t < horizon {
+                    break;
+                }
+                samples.push(Sample {
+                    horizon_index,
+                    loc: frame.loc,
+                    bounces,
+                });
+                horizons.next();
+            }
+            prev = Some(frame);
+        }
+
+        Snapshot { time, samples }
+    }
+
+    fn score(&mut self, time: f32, ball_loc: Point3<f32>) {
+        for snapshot in self.pending.iter_mut() {
+            let stats = &mut self.stats;
+            let snapshot_time = snapshot.time;
+            snapshot.samples.retain(|sample| {
+                if time - snapshot_time < HORIZONS[sample.horizon_index] {
+                    return true;
+                }
+                let bucket = sample.bounces.min(BOUNCE_BUCKETS - 1);
+                let error = (sample.loc - ball_loc).norm();
+                stats[sample.horizon_index][bucket].add(error);
+                false
+            });
+        }
+        while self
+            .pending
+            .front()
+            .map(|s| s.samples.is_empty())
+            .unwrap_or(false)
+        {
+            self.pending.pop_front();
+        }
+    }
+
+    fn report(&self, eeg: &mut EEG) {
+        for (horizon_index, horizon) in HORIZONS.iter().enumerate() {
+            let mean = some_or_else!(self.horizon_mean(horizon_index), {
+                continue;
+            });
+            eeg.print_value(&format!("pred err {:.2}s", horizon), Distance(mean));
+        }
+    }
+
+    /// Error statistics for the given horizon index and bounce bucket.
+    pub fn stats(&self, horizon_index: usize, bounce_bucket: usize) -> &ErrorStats {
+        &self.stats[horizon_index][bounce_bucket]
+    }
+
+    /// Mean error across all bounce buckets at the given horizon.
+    pub fn horizon_mean(&self, horizon_index: usize) -> Option<f32> {
+        let (count, total) = self.stats[horizon_index]
+            .iter()
+            .fold((0, 0.0), |(c, t), s| (c + s.count, t + s.total));
+        if count == 0 {
+            None
+        } else {
+            Some(total / count as f32)
+        }
+    }
+}
+
+fn is_bounce(prev_vel: Vector3<f32>, vel: Vector3<f32>, dt: f32, gravity: f32) -> bool {
+    let expected = prev_vel + Vector3::z() * gravity * dt;
+    (vel - expected).norm() >= BallPredictionAccuracy::BOUNCE_DV
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::utils::ball_prediction_accuracy::{
+        BallPredictionAccuracy, Sample, Snapshot, HORIZONS,
+    };
+    use nalgebra::Point3;
+
+    #[test]
+    fn scores_by_horizon_and_bounce() {
+        let mut tracker = BallPredictionAccuracy::new();
+        tracker.pending.push_back(Snapshot {
+            time: 10.0,
+            samples: vec![
+                Sample {
+                    horizon_index: 0,
+                    loc: Point3::new(0.0, 0.0, 100.0),
+                    bounces: 0,
+                },
+                Sample {
+                    horizon_index: 2,
+                    loc: Point3::new(0.0, 0.0, 100.0),
+                    bounces: 5,
+                },
+            ],
+        });
+
+        tracker.score(10.0 + HORIZONS[0], Point3::new(30.0, 40.0, 100.0));
+        assert_eq!(tracker.stats(0, 0).count, 1);
+        assert_eq!(tracker.stats(0, 0).mean(), Some(50.0));
+        assert_eq!(tracker.stats(2, 2).count, 0);
+        assert_eq!(tracker.pending.len(), 1);
+
+        tracker.score(10.0 + HORIZONS[2], Point3::new(0.0, 0.0, 110.0));
+        assert_eq!(tracker.stats(2, 2).max, 10.0);
+        assert!(tracker.pending.is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+/// A monotonic view of the game's clock.
+///
+/// `TimeSeconds` in the packet is usually well-behaved, but it stands still
+/// while the game is paused, and it jumps when a match restarts or the test
+/// harness resets things between scenarios. Anything keyed to an absolute
+/// game time goes haywire when that happens. This keeps a clock that only
+/// moves forward in reasonable steps, and flags the frames where the real one
+/// jumped.
+#[derive(Default)]
+pub struct GameClock {
+    last_time: Option<f32>,
+    now: f32,
+    discontinuity: bool,
+}
+
+impl GameClock {
+    /// A gap longer than this between two packets is a jump, not a slow frame.
+    const MAX_STEP: f32 = 0.5;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance the clock to the packet's `TimeSeconds`. Returns `true` if the
+    /// game time jumped since the last call.
+    pub fn update(&mut self, time: f32) -> bool {
+        let step = self.last_time.map(|last| time - last).unwrap_or(0.0);
+        self.last_time = Some(time);
+        self.discontinuity = step < 0.0 || step > Self::MAX_STEP;
+        if !self.discontinuity {
+            self.now += step;
+        }
+        self.discontinuity
+    }
+
+    /// Seconds of game time since the first update, not counting any jumps.
+    pub fn now(&self) -> f32 {
+        self.now
+    }
+
+    /// Returns `true` if the game time jumped on the most recent update.
+    pub fn discontinuity(&self) -> bool {
+        self.discontinuity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::GameClock;
+
+    #[test]
+    fn steady() {
+        let mut clock = GameClock::new();
+        for i in 0..120 {
+            assert!(!clock.update(100.0 + i as f32 / 120.0));
+        }
+        assert!((clock.now() - 119.0 / 120.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn paused() {
+        let mut clock = GameClock::new();
+        clock.update(100.0);
+        assert!(!clock.update(100.0));
+        assert!(!clock.update(100.0));
+        assert!(clock.now().abs() < 1e-5);
+    }
+
+    #[test]
+    fn jumps() {
+        let mut clock = GameClock::new();
+        clock.update(100.0);
+        clock.update(100.0 + 1.0 / 120.0);
+        let before = clock.now();
+
+        // Time set back, e.g., a restarted match.
+        assert!(clock.update(5.0));
+        assert!(clock.discontinuity());
+        assert!((clock.now() - before).abs() < 1e-5);
+
+        assert!(!clock.update(5.0 + 1.0 / 120.0));
+        assert!(!clock.discontinuity());
+
+        // Time skipped ahead.
+        assert!(clock.update(60.0));
+        assert!(clock.now() < before + 0.1);
+    }
+}
@@ -0,0 +1,168 @@
+use common::halfway_house::{PlayerInfo, PlayerInput};
+
+/// Our car's jump state machine, reconstructed from the packets and the
+/// inputs we sent.
+///
+/// The packet only says whether the wheels are touching something and whether
+/// the double jump was spent. Whether a flip is still available depends on
+/// when we left the ground, which only we know, so it's tracked here.
+#[derive(Default)]
+pub struct JumpTracker {
+    now: f32,
+    on_ground: bool,
+    double_jumped: bool,
+    holding_jump: bool,
+    /// When we last jumped off the ground.
+    jumped_at: Option<f32>,
+    /// Whether the second jump (a double jump or a dodge) has been used.
+    second_jump_used: bool,
+    /// A dodge is in progress until this time.
+    dodging_until: Option<f32>,
+}
+
+impl JumpTracker {
+    /// After the first jump, the second jump stays available for 1.25 seconds
+    /// after the jump button is released, and the first jump can be held for
+    /// up to 0.2 seconds.
+    pub const FLIP_WINDOW: f32 = 1.25 + 0.2;
+    /// A dodge happens instead of a double jump if the stick is past this.
+    const DODGE_DEADZONE: f32 = 0.5;
+    /// How long a dodge's rotation lasts.
+    const DODGE_DURATION: f32 = 0.65;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update from the latest packet. Call this before any behavior runs.
+    pub fn update(&mut self, now: f32, car: &PlayerInfo) {
+        self.now = now;
+        self.on_ground = car.OnGround;
+        self.double_jumped = car.DoubleJumped;
+        // The wheels still touch the ground for a frame or two after we press
+        // jump, so only land once the button is released.
+        if car.OnGround && !self.holding_jump {
+            self.jumped_at = None;
+            self.second_jump_used = false;
+            self.dodging_until = None;
+        }
+    }
+
+    /// Record the input that was actually sent this frame.
+    pub fn record_input(&mut self, input: &PlayerInput) {
+        if input.Jump && !self.holding_jump {
+            if self.on_ground {
+                self.jumped_at = Some(self.now);
+            } else if self.has_flip() {
+                self.second_jump_used = true;
+                let stick = input.Pitch.abs().max(input.Yaw.abs()).max(input.Roll.abs());
+                if stick >= Self::DODGE_DEADZONE {
+                    self.dodging_until = Some(self.now + Self::DODGE_DURATION);
+                }
+            }
+        }
+        self.holding_jump = input.Jump;
+    }
+
+    /// Returns `true` if the jump button was held last frame. Holding it again
+    /// won't start a new jump.
+    pub fn holding_jump(&self) -> bool {
+        self.holding_jump
+    }
+
+    /// Returns `true` if pressing jump now would do something.
+    pub fn jump_available(&self) -> bool {
+        self.on_ground || self.has_flip()
+    }
+
+    /// Returns `true` if we're airborne and can still double jump or dodge.
+    pub fn has_flip(&self) -> bool {
+        if self.on_ground || self.double_jumped || self.second_jump_used {
+            return false;
+        }
+        // If we left the ground without jumping, e.g., by driving off a ramp
+        // or getting bumped, give the benefit of the doubt.
+        self.flip_window_remaining()
+            .map(|t| t > 0.0)
+            .unwrap_or(true)
+    }
+
+    /// Seconds until the flip from our last jump expires, if we jumped.
+    pub fn flip_window_remaining(&self) -> Option<f32> {
+        self.jumped_at
+            .map(|jumped_at| jumped_at + Self::FLIP_WINDOW - self.now)
+    }
+
+    /// Returns `true` while a dodge's rotation is still playing out.
+    pub fn is_dodging(&self) -> bool {
+        self.dodging_until.map(|t| self.now < t).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::JumpTracker;
+    use common::halfway_house::{PlayerInfo, PlayerInput};
+
+    fn car(on_ground: bool) -> PlayerInfo {
+        PlayerInfo {
+            OnGround: on_ground,
+            ..Default::default()
+        }
+    }
+
+    fn step(tracker: &mut JumpTracker, now: f32, on_ground: bool, jump: bool, pitch: f32) {
+        tracker.update(now, &car(on_ground));
+        tracker.record_input(&PlayerInput {
+            Jump: jump,
+            Pitch: pitch,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn jump_then_dodge() {
+        let mut tracker = JumpTracker::new();
+        step(&mut tracker, 0.0, true, true, 0.0);
+        // Still touching the ground while the jump starts.
+        step(&mut tracker, 0.01, true, true, 0.0);
+        step(&mut tracker, 0.1, false, false, 0.0);
+        assert!(tracker.has_flip());
+        assert!((tracker.flip_window_remaining().unwrap() - 1.35).abs() < 1e-3);
+
+        step(&mut tracker, 0.2, false, true, -1.0);
+        tracker.update(0.3, &car(false));
+        assert!(!tracker.has_flip());
+        assert!(tracker.is_dodging());
+        assert!(!tracker.jump_available());
+    }
+
+    #[test]
+    fn flip_window_expires() {
+        let mut tracker = JumpTracker::new();
+        step(&mut tracker, 0.0, true, true, 0.0);
+        step(&mut tracker, 0.1, false, false, 0.0);
+        tracker.update(2.0, &car(false));
+        assert!(!tracker.has_flip());
+    }
+
+    #[test]
+    fn landing_restores_jump() {
+        let mut tracker = JumpTracker::new();
+        step(&mut tracker, 0.0, true, true, 0.0);
+        step(&mut tracker, 0.1, false, false, 0.0);
+        step(&mut tracker, 0.2, false, true, -1.0);
+        step(&mut tracker, 1.0, true, false, 0.0);
+        assert!(tracker.jump_available());
+        assert!(!tracker.is_dodging());
+        assert!(tracker.flip_window_remaining().is_none());
+    }
+
+    #[test]
+    fn drove_off_a_ledge() {
+        let mut tracker = JumpTracker::new();
+        step(&mut tracker, 0.0, true, false, 0.0);
+        tracker.update(0.1, &car(false));
+        assert!(tracker.has_flip());
+    }
+}
@@ -1,11 +1,20 @@
 pub use crate::utils::{
+    ball_prediction_accuracy::BallPredictionAccuracy,
     fps_counter::FPSCounter,
+    game_clock::GameClock,
+    jump_tracker::JumpTracker,
+    mutator_tracker::MutatorTracker,
     stopwatch::Stopwatch,
     wall_ray_calculator::{Wall, WallRayCalculator},
 };
 
+mod ball_prediction_accuracy;
 mod fps_counter;
+mod game_clock;
 pub mod geometry;
 pub mod intercept_memory;
+mod jump_tracker;
+mod mutator_tracker;
 mod stopwatch;
 mod wall_ray_calculator;
+pub mod wire;
@@ -0,0 +1,130 @@
+use crate::strategy::Mutators;
+use common::halfway_house::{GameInfo, PlayerInfo, PlayerInput};
+
+/// Works out the boost mutators by watching our own boost meter, since the
+/// packet doesn't say.
+///
+/// Once a mutator is detected, it stays detected for the rest of the match.
+#[derive(Default)]
+pub struct MutatorTracker {
+    prev_boost: Option<i32>,
+    boosting: bool,
+    /// Consecutive frames of boosting that didn't drain the meter.
+    undrained_frames: u32,
+    /// Frames where the meter crept up without a pickup.
+    recharge_frames: u32,
+    unlimited_boost: bool,
+    boost_recharge: bool,
+}
+
+impl MutatorTracker {
+    /// A normal meter drops at least once in this many frames of boosting.
+    const UNLIMITED_FRAMES: u32 = 30;
+    /// See this many small increases before believing in recharge.
+    const RECHARGE_FRAMES: u32 = 10;
+    /// Pickups add at least this much at once, so anything smaller is a
+    /// recharge.
+    const MIN_PICKUP: i32 = 12;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update from the latest packet. Call this before any behavior runs.
+    pub fn update(&mut self, car: &PlayerInfo) {
+        if let Some(prev) = self.prev_boost {
+            let gained = car.Boost - prev;
+            if car.Demolished {
+                self.undrained_frames = 0;
+            } else if self.boosting && car.Boost > 0 {
+                if gained >= 0 {
+                    self.undrained_frames += 1;
+                } else {
+                    self.undrained_frames = 0;
+                }
+            } else if !self.boosting && gained > 0 && gained < Self::MIN_PICKUP {
+                self.recharge_frames += 1;
+            }
+        }
+        self.prev_boost = Some(car.Boost);
+
+        if self.undrained_frames >= Self::UNLIMITED_FRAMES {
+            self.unlimited_boost = true;
+        }
+        if self.recharge_frames >= Self::RECHARGE_FRAMES {
+            self.boost_recharge = true;
+        }
+    }
+
+    /// Record the input that was actually sent this frame.
+    pub fn record_input(&mut self, input: &PlayerInput) {
+        self.boosting = input.Boost;
+    }
+
+    pub fn mutators(&self, info: &GameInfo) -> Mutators {
+        Mutators {
+            unlimited_boost: self.unlimited_boost,
+            boost_recharge: self.boost_recharge,
+            ..Mutators::from_game_info(info)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::MutatorTracker;
+    use common::halfway_house::{GameInfo, PlayerInfo, PlayerInput};
+
+    fn step(tracker: &mut MutatorTracker, boost: i32, boosting: bool) {
+        tracker.update(&PlayerInfo {
+            Boost: boost,
+            ..Default::default()
+        });
+        tracker.record_input(&PlayerInput {
+            Boost: boosting,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn normal_boost_drains() {
+        let mut tracker = MutatorTracker::new();
+        for i in 0..120 {
+            // About a third of a unit per frame.
+            step(&mut tracker, 100 - i / 3, true);
+        }
+        let mutators = tracker.mutators(&GameInfo::default());
+        assert!(!mutators.unlimited_boost);
+        assert!(!mutators.boost_recharge);
+    }
+
+    #[test]
+    fn unlimited_boost_never_drains() {
+        let mut tracker = MutatorTracker::new();
+        for _ in 0..60 {
+            step(&mut tracker, 100, true);
+        }
+        assert!(tracker.mutators(&GameInfo::default()).unlimited_boost);
+    }
+
+    #[test]
+    fn recharge_creeps_up() {
+        let mut tracker = MutatorTracker::new();
+        for i in 0..120 {
+            // Ten units per second.
+            step(&mut tracker, i / 12, false);
+        }
+        let mutators = tracker.mutators(&GameInfo::default());
+        assert!(mutators.boost_recharge);
+        assert!(!mutators.unlimited_boost);
+    }
+
+    #[test]
+    fn pickups_are_not_recharge() {
+        let mut tracker = MutatorTracker::new();
+        for i in 0..20 {
+            step(&mut tracker, i * 12, false);
+        }
+        assert!(!tracker.mutators(&GameInfo::default()).boost_recharge);
+    }
+}
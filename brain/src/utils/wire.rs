@@ -0,0 +1,143 @@
+//! `serde` adapters for the nalgebra types in our models. They're written as
+//! plain arrays so the wire format doesn't depend on nalgebra's internals.
+//!
+//! Use with `#[serde(with = "crate::utils::wire::point3")]`.
+
+pub mod point3 {
+    use nalgebra::Point3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(p: &Point3<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+        [p.x, p.y, p.z].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Point3<f32>, D::Error> {
+        let [x, y, z] = <[f32; 3]>::deserialize(deserializer)?;
+        Ok(Point3::new(x, y, z))
+    }
+}
+
+pub mod vector3 {
+    use nalgebra::Vector3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &Vector3<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+        [v.x, v.y, v.z].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vector3<f32>, D::Error> {
+        let [x, y, z] = <[f32; 3]>::deserialize(deserializer)?;
+        Ok(Vector3::new(x, y, z))
+    }
+}
+
+/// Written as `[x, y, z, w]`, the same order as the `collect` recordings.
+pub mod unit_quaternion {
+    use nalgebra::{Quaternion, UnitQuaternion};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        q: &UnitQuaternion<f32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let c = q.into_inner().coords;
+        [c.x, c.y, c.z, c.w].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<UnitQuaternion<f32>, D::Error> {
+        let [x, y, z, w] = <[f32; 4]>::deserialize(deserializer)?;
+        Ok(UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z)))
+    }
+}
+
+/// Written as `[pitch, yaw, roll]`, like the packet.
+pub mod rotation3 {
+    use common::prelude::*;
+    use nalgebra::Rotation3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(r: &Rotation3<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+        let (pitch, yaw, roll) = r.to_unreal_angles();
+        [pitch, yaw, roll].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Rotation3<f32>, D::Error> {
+        let [pitch, yaw, roll] = <[f32; 3]>::deserialize(deserializer)?;
+        Ok(Rotation3::from_unreal_angles(pitch, yaw, roll))
+    }
+}
+
+pub mod point2 {
+    use nalgebra::Point2;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(p: &Point2<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+        [p.x, p.y].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Point2<f32>, D::Error> {
+        let [x, y] = <[f32; 2]>::deserialize(deserializer)?;
+        Ok(Point2::new(x, y))
+    }
+}
+
+pub mod player_input {
+    use common::halfway_house::PlayerInput;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Input {
+        throttle: f32,
+        steer: f32,
+        pitch: f32,
+        yaw: f32,
+        roll: f32,
+        jump: bool,
+        boost: bool,
+        handbrake: bool,
+        use_item: bool,
+    }
+
+    pub fn serialize<S: Serializer>(i: &PlayerInput, serializer: S) -> Result<S::Ok, S::Error> {
+        Input {
+            throttle: i.Throttle,
+            steer: i.Steer,
+            pitch: i.Pitch,
+            yaw: i.Yaw,
+            roll: i.Roll,
+            jump: i.Jump,
+            boost: i.Boost,
+            handbrake: i.Handbrake,
+            use_item: i.UseItem,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<PlayerInput, D::Error> {
+        let i = Input::deserialize(deserializer)?;
+        Ok(PlayerInput {
+            Throttle: i.throttle,
+            Steer: i.steer,
+            Pitch: i.pitch,
+            Yaw: i.yaw,
+            Roll: i.roll,
+            Jump: i.jump,
+            Boost: i.boost,
+            Handbrake: i.handbrake,
+            UseItem: i.use_item,
+        })
+    }
+}
@@ -53,7 +53,7 @@ fn flat_vector_iter<'a>(
     (0..xs.len()).map(move |i| xs.get(i))
 }
 
-fn rigid_body_header(prefix: impl AsRef<str>) -> impl Iterator<Item = String> {
+pub(crate) fn rigid_body_header(prefix: impl AsRef<str>) -> impl Iterator<Item = String> {
     [
         "_loc_x", "_loc_y", "_loc_z", "_rot_x", "_rot_y", "_rot_z", "_rot_w", "_vel_x", "_vel_y",
         "_vel_z", "_ang_x", "_ang_y", "_ang_z",
@@ -81,7 +81,7 @@ fn rigid_body(state: rlbot::flat::RigidBodyState<'_>) -> impl Iterator<Item = St
     .into_iter()
 }
 
-fn controller_header(prefix: impl AsRef<str>) -> impl Iterator<Item = String> {
+pub(crate) fn controller_header(prefix: impl AsRef<str>) -> impl Iterator<Item = String> {
     [
         "_throttle",
         "_steer",
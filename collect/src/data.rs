@@ -1,6 +1,12 @@
+use crate::collector::{controller_header, rigid_body_header};
+use common::{halfway_house, prelude::*};
 use nalgebra::{Point3, Quaternion, UnitQuaternion, Vector3};
-use std::io::Read;
+use std::{
+    io::{Read, Write},
+    iter::once,
+};
 
+#[derive(Clone)]
 pub struct RecordingTick {
     pub time: f32,
     pub ball: RecordingRigidBodyState,
@@ -53,7 +59,68 @@ impl RecordingTick {
     }
 }
 
+impl RecordingTick {
+    /// Write ticks in the same format `parse` reads. All ticks must have the
+    /// same number of players.
+    pub fn write(ticks: &[RecordingTick], w: impl Write) -> csv::Result<()> {
+        let mut w = csv::Writer::from_writer(w);
+        let num_players = ticks.first().map(|t| t.players.len()).unwrap_or(0);
+        w.write_record(
+            once(String::from("time"))
+                .chain(rigid_body_header("ball"))
+                .chain(
+                    (0..num_players)
+                        .map(|i| format!("player{}", i))
+                        .flat_map(|s| controller_header(s.clone()).chain(rigid_body_header(s))),
+                ),
+        )?;
+        for tick in ticks {
+            let mut record = vec![tick.time.to_string()];
+            record.extend(tick.ball.to_csv());
+            for player in &tick.players {
+                record.extend(input_to_csv(&player.input));
+                record.extend(player.state.to_csv());
+            }
+            w.write_record(record)?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+}
+
 impl RecordingRigidBodyState {
+    /// Convert from the layout used in game packets.
+    pub fn from_physics(physics: &halfway_house::Physics) -> Self {
+        Self {
+            loc: physics.loc(),
+            rot: physics.quat(),
+            vel: physics.vel(),
+            ang_vel: physics.ang_vel(),
+        }
+    }
+
+    fn to_csv(&self) -> Vec<String> {
+        let rot = self.rot.coords;
+        vec![
+            self.loc.x,
+            self.loc.y,
+            self.loc.z,
+            rot.x,
+            rot.y,
+            rot.z,
+            rot.w,
+            self.vel.x,
+            self.vel.y,
+            self.vel.z,
+            self.ang_vel.x,
+            self.ang_vel.y,
+            self.ang_vel.z,
+        ]
+        .into_iter()
+        .map(|x| x.to_string())
+        .collect()
+    }
+
     pub fn from_csv<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<Self, ()> {
         Ok(Self {
             loc: Point3::from(csv_vector3(it)?),
@@ -62,6 +129,42 @@ impl RecordingRigidBodyState {
             ang_vel: csv_vector3(it)?,
         })
     }
+
+    /// Convert to the layout used in game packets.
+    pub fn to_physics(&self) -> halfway_house::Physics {
+        let (pitch, yaw, roll) = self.rot.to_rotation_matrix().to_unreal_angles();
+        halfway_house::Physics {
+            Location: vector3(self.loc.coords),
+            Rotation: halfway_house::Rotator {
+                Pitch: pitch,
+                Yaw: yaw,
+                Roll: roll,
+            },
+            Velocity: vector3(self.vel),
+            AngularVelocity: vector3(self.ang_vel),
+        }
+    }
+}
+
+fn vector3(v: Vector3<f32>) -> halfway_house::Vector3 {
+    halfway_house::Vector3 {
+        X: v.x,
+        Y: v.y,
+        Z: v.z,
+    }
+}
+
+fn input_to_csv(input: &RecordingPlayerInput) -> Vec<String> {
+    vec![
+        input.Throttle.to_string(),
+        input.Steer.to_string(),
+        input.Pitch.to_string(),
+        input.Yaw.to_string(),
+        input.Roll.to_string(),
+        input.Jump.to_string(),
+        input.Boost.to_string(),
+        input.Handbrake.to_string(),
+    ]
 }
 
 fn csv_input<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<RecordingPlayerInput, ()> {
@@ -74,6 +177,7 @@ fn csv_input<'a>(it: &mut impl Iterator<Item = &'a str>) -> Result<RecordingPlay
         Jump: csv_bool(it)?,
         Boost: csv_bool(it)?,
         Handbrake: csv_bool(it)?,
+        UseItem: false,
     })
 }
 
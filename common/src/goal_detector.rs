@@ -0,0 +1,205 @@
+//! Detecting goals from the packet stream.
+
+use crate::{halfway_house::LiveDataPacket, prelude::*, rl};
+use nalgebra::Point3;
+
+/// Watches packets and reports each goal exactly once.
+///
+/// The score in the packet is not enough on its own. It never changes when
+/// goal resets are disabled (as in the integration tests), and when it does
+/// change, it lags the ball crossing the line by a variable number of frames.
+/// So instead, watch for the ball entering the goal volume, and require it to
+/// stay there for a few frames to filter out glitches (e.g., state setting
+/// teleporting the ball). A score change or the start of the goal replay
+/// confirms a goal immediately.
+pub struct GoalDetector {
+    /// Consecutive frames the ball has been inside each goal, indexed by the
+    /// team that owns the goal.
+    frames_inside: [u32; 2],
+    last_scores: Option<[i32; 2]>,
+    last_round_active: bool,
+    /// After a goal is reported, ignore everything until the ball is back in
+    /// play.
+    pending: Option<DetectedGoal>,
+    goals: Vec<DetectedGoal>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DetectedGoal {
+    /// The team that scored, in the framework's numbering (0 is blue).
+    pub scoring_team: u8,
+    /// The game time when the goal was detected.
+    pub time: f32,
+}
+
+impl GoalDetector {
+    /// How many consecutive frames the ball must be in the goal before we
+    /// believe it.
+    const DEBOUNCE_FRAMES: u32 = 3;
+
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            frames_inside: [0; 2],
+            last_scores: None,
+            last_round_active: false,
+            pending: None,
+            goals: Vec::new(),
+        }
+    }
+
+    /// Call this once per frame. Returns the goal if one was scored this frame.
+    pub fn update(&mut self, packet: &LiveDataPacket) -> Option<DetectedGoal> {
+        let ball_loc = packet.GameBall.Physics.loc();
+        let inside = goal_containing(ball_loc);
+        for owner in 0..2 {
+            if inside == Some(owner) {
+                self.frames_inside[owner as usize] += 1;
+            } else {
+                self.frames_inside[owner as usize] = 0;
+            }
+        }
+
+        let scores = [score(packet, 0), score(packet, 1)];
+        let score_changed = self
+            .last_scores
+            .and_then(|last| (0..2).find(|&team| scores[team as usize] > last[team as usize]));
+        self.last_scores = Some(scores);
+
+        let replay_started = self.last_round_active && !packet.GameInfo.RoundActive;
+        self.last_round_active = packet.GameInfo.RoundActive;
+
+        if self.pending.is_some() {
+            if inside.is_none() && ball_loc.y.abs() < rl::FIELD_MAX_Y - 500.0 {
+                self.pending = None;
+            }
+            return None;
+        }
+
+        let scoring_team = match (score_changed, inside) {
+            (Some(team), _) => team,
+            (None, Some(owner))
+                if self.frames_inside[owner as usize] >= Self::DEBOUNCE_FRAMES
+                    || replay_started =>
+            {
+                1 - owner
+            }
+            _ => return None,
+        };
+
+        let goal = DetectedGoal {
+            scoring_team,
+            time: packet.GameInfo.TimeSeconds,
+        };
+        self.pending = Some(goal);
+        self.goals.push(goal);
+        Some(goal)
+    }
+
+    /// Returns the most recent goal if the ball has not yet come back into
+    /// play since.
+    pub fn pending(&self) -> Option<&DetectedGoal> {
+        self.pending.as_ref()
+    }
+
+    /// Every goal detected so far, in order.
+    pub fn goals(&self) -> &[DetectedGoal] {
+        &self.goals
+    }
+
+    /// Returns `true` if the given team has scored at least once.
+    pub fn has_scored(&self, team: u8) -> bool {
+        self.goals.iter().any(|g| g.scoring_team == team)
+    }
+}
+
+/// If the ball is entirely over the goal line, returns the team that owns that
+/// goal.
+fn goal_containing(ball_loc: Point3<f32>) -> Option<u8> {
+    if ball_loc.x.abs() >= rl::GOALPOST_X
+        || ball_loc.z >= rl::CROSSBAR_Z
+        || ball_loc.y.abs() < rl::FIELD_MAX_Y + rl::BALL_RADIUS
+    {
+        return None;
+    }
+    if ball_loc.y < 0.0 {
+        Some(0)
+    } else {
+        Some(1)
+    }
+}
+
+fn score(packet: &LiveDataPacket, team: u8) -> i32 {
+    packet
+        .Teams
+        .iter()
+        .find(|t| t.TeamIndex == i32::from(team))
+        .map(|t| t.Score)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        goal_detector::{DetectedGoal, GoalDetector},
+        halfway_house::LiveDataPacket,
+    };
+
+    fn packet(time: f32, ball_y: f32, round_active: bool) -> LiveDataPacket {
+        let mut packet = LiveDataPacket::default().with_time(time).with_scores(0, 0);
+        packet.GameInfo.RoundActive = round_active;
+        packet.GameBall.Physics.Location.Y = ball_y;
+        packet.GameBall.Physics.Location.Z = 92.74;
+        packet
+    }
+
+    #[test]
+    fn debounce() {
+        let mut detector = GoalDetector::new();
+        assert_eq!(detector.update(&packet(0.0, -4000.0, true)), None);
+        // A single glitchy frame inside the goal should not count.
+        assert_eq!(detector.update(&packet(0.1, -5300.0, true)), None);
+        assert_eq!(detector.update(&packet(0.2, -4000.0, true)), None);
+        assert!(detector.goals().is_empty());
+
+        assert_eq!(detector.update(&packet(1.0, -5300.0, true)), None);
+        assert_eq!(detector.update(&packet(1.1, -5310.0, true)), None);
+        let goal = detector.update(&packet(1.2, -5320.0, true));
+        assert_eq!(
+            goal,
+            Some(DetectedGoal {
+                scoring_team: 1,
+                time: 1.2,
+            }),
+        );
+
+        // Only report it once.
+        assert_eq!(detector.update(&packet(1.3, -5330.0, true)), None);
+        assert_eq!(detector.goals().len(), 1);
+        assert!(detector.pending().is_some());
+
+        // Back in play.
+        assert_eq!(detector.update(&packet(5.0, 0.0, true)), None);
+        assert!(detector.pending().is_none());
+    }
+
+    #[test]
+    fn replay_confirms_immediately() {
+        let mut detector = GoalDetector::new();
+        detector.update(&packet(0.0, 5000.0, true));
+        let goal = detector.update(&packet(0.1, 5300.0, false));
+        assert_eq!(goal.map(|g| g.scoring_team), Some(0));
+        assert!(detector.has_scored(0));
+        assert!(!detector.has_scored(1));
+    }
+
+    #[test]
+    fn score_change() {
+        let mut detector = GoalDetector::new();
+        detector.update(&packet(0.0, 0.0, true));
+        let mut scored = packet(0.1, 0.0, true);
+        scored.Teams[0].Score = 1;
+        let goal = detector.update(&scored);
+        assert_eq!(goal.map(|g| g.scoring_team), Some(0));
+    }
+}
@@ -33,6 +33,26 @@ pub struct PlayerInfo {
     pub DoubleJumped: bool,
     pub Team: ::std::os::raw::c_uchar,
     pub Boost: ::std::os::raw::c_int,
+    /// The Rumble power-up we're holding, if we know. The framework's packet
+    /// doesn't carry items, so this is `None` in live packets, whether or not
+    /// we're holding one; only packets built offline fill it in.
+    pub Item: Option<RumbleItem>,
+}
+
+/// A Rumble power-up.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RumbleItem {
+    BallFreeze,
+    Boot,
+    Disruptor,
+    GrapplingHook,
+    Haymaker,
+    MagnetBall,
+    Plunger,
+    PowerHitter,
+    Spikes,
+    Swapper,
+    Tornado,
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -52,9 +72,38 @@ pub struct GameInfo {
     pub GameTimeRemaining: f32,
     pub RoundActive: bool,
     pub MatchEnded: bool,
+    /// Next goal wins.
+    pub Overtime: bool,
+    /// The clock doesn't count down. Packets built offline have no clock, so
+    /// they should set this.
+    pub UnlimitedTime: bool,
+    /// Zero if unknown, e.g., in packets built offline.
+    pub WorldGravityZ: f32,
+}
+
+/// The state of one Dropshot floor tile.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TileState {
+    Unknown,
+    Filled,
+    Damaged,
+    Open,
+}
+
+impl Default for TileState {
+    fn default() -> Self {
+        TileState::Unknown
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct BoostPadState {
+    pub IsActive: bool,
+    /// Seconds since the pad was picked up. Zero while it's active.
+    pub Timer: f32,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct LiveDataPacket {
     pub GameCars: SmallVec<[PlayerInfo; 4]>,
     pub NumCars: ::std::os::raw::c_int,
@@ -62,6 +111,12 @@ pub struct LiveDataPacket {
     pub GameInfo: GameInfo,
     pub Teams: SmallVec<[TeamInfo; 2usize]>,
     pub NumTeams: ::std::os::raw::c_int,
+    /// Dropshot tile states, in the same order as the tiles in `FieldInfo`.
+    /// Empty in other game modes.
+    pub DropshotTiles: Vec<TileState>,
+    /// Boost pad states, in the same order as the pads in `FieldInfo`. Empty
+    /// for packets built offline.
+    pub BoostPads: Vec<BoostPadState>,
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -74,12 +129,42 @@ pub struct PlayerInput {
     pub Jump: bool,
     pub Boost: bool,
     pub Handbrake: bool,
+    /// Use the Rumble item we're holding.
+    pub UseItem: bool,
 }
 
 impl LiveDataPacket {
     pub fn cars(&self) -> impl Iterator<Item = &PlayerInfo> {
         self.GameCars.iter().take(self.NumCars as usize)
     }
+
+    // The rest are for building packets offline, starting from `default()`.
+
+    /// Replace the cars, keeping `NumCars` in step.
+    pub fn with_cars(mut self, cars: impl IntoIterator<Item = PlayerInfo>) -> Self {
+        self.GameCars = cars.into_iter().collect();
+        self.NumCars = self.GameCars.len() as i32;
+        self
+    }
+
+    /// Add both teams with the given scores, keeping `NumTeams` in step.
+    pub fn with_scores(mut self, blue: i32, orange: i32) -> Self {
+        self.Teams = [blue, orange]
+            .iter()
+            .enumerate()
+            .map(|(index, &score)| TeamInfo {
+                TeamIndex: index as i32,
+                Score: score,
+            })
+            .collect();
+        self.NumTeams = self.Teams.len() as i32;
+        self
+    }
+
+    pub fn with_time(mut self, time: f32) -> Self {
+        self.GameInfo.TimeSeconds = time;
+        self
+    }
 }
 
 pub fn deserialize_game_tick_packet(packet: rlbot::flat::GameTickPacket<'_>) -> LiveDataPacket {
@@ -99,6 +184,18 @@ pub fn deserialize_game_tick_packet(packet: rlbot::flat::GameTickPacket<'_>) ->
             .map(|ts| vector_iter(ts).map(deserialize_team_info).collect())
             .unwrap_or_default(),
         NumTeams: packet.teams().map(|ts| ts.len() as i32).unwrap_or(0),
+        DropshotTiles: packet
+            .tileInformation()
+            .map(|ts| {
+                vector_iter(ts)
+                    .map(|t| deserialize_tile_state(t.tileState()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        BoostPads: packet
+            .boostPadStates()
+            .map(|ps| vector_iter(ps).map(deserialize_boost_pad_state).collect())
+            .unwrap_or_default(),
     }
 }
 
@@ -110,6 +207,7 @@ fn deserialize_player_info(info: rlbot::flat::PlayerInfo<'_>) -> PlayerInfo {
         DoubleJumped: info.doubleJumped(),
         Team: info.team() as u8,
         Boost: info.boost(),
+        Item: None,
     }
 }
 
@@ -125,6 +223,9 @@ fn deserialize_game_info(info: rlbot::flat::GameInfo<'_>) -> GameInfo {
         GameTimeRemaining: info.gameTimeRemaining(),
         RoundActive: info.isRoundActive(),
         MatchEnded: info.isMatchEnded(),
+        Overtime: info.isOvertime(),
+        UnlimitedTime: info.isUnlimitedTime(),
+        WorldGravityZ: info.worldGravityZ(),
     }
 }
 
@@ -135,6 +236,22 @@ fn deserialize_team_info(info: rlbot::flat::TeamInfo<'_>) -> TeamInfo {
     }
 }
 
+fn deserialize_boost_pad_state(state: rlbot::flat::BoostPadState<'_>) -> BoostPadState {
+    BoostPadState {
+        IsActive: state.isActive(),
+        Timer: state.timer(),
+    }
+}
+
+fn deserialize_tile_state(state: rlbot::flat::TileState) -> TileState {
+    match state {
+        rlbot::flat::TileState::Filled => TileState::Filled,
+        rlbot::flat::TileState::Damaged => TileState::Damaged,
+        rlbot::flat::TileState::Open => TileState::Open,
+        _ => TileState::Unknown,
+    }
+}
+
 fn deserialize_physics(physics: rlbot::flat::Physics<'_>) -> Physics {
     Physics {
         Location: physics
@@ -182,5 +299,51 @@ pub fn translate_player_input(input: &PlayerInput) -> rlbot::ControllerState {
         jump: input.Jump,
         boost: input.Boost,
         handbrake: input.Handbrake,
+        use_item: input.UseItem,
+    }
+}
+
+/// The inverse of `translate_player_input`.
+pub fn deserialize_controller_state(state: &rlbot::ControllerState) -> PlayerInput {
+    PlayerInput {
+        Throttle: state.throttle,
+        Steer: state.steer,
+        Pitch: state.pitch,
+        Yaw: state.yaw,
+        Roll: state.roll,
+        Jump: state.jump,
+        Boost: state.boost,
+        Handbrake: state.handbrake,
+        UseItem: state.use_item,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::halfway_house::{deserialize_controller_state, translate_player_input, PlayerInput};
+
+    #[test]
+    fn input_round_trip() {
+        let input = PlayerInput {
+            Throttle: 1.0,
+            Steer: -0.5,
+            Pitch: 0.25,
+            Yaw: -0.25,
+            Roll: 0.75,
+            Jump: true,
+            Boost: false,
+            Handbrake: true,
+            UseItem: true,
+        };
+        let output = deserialize_controller_state(&translate_player_input(&input));
+        assert_eq!(output.Throttle, input.Throttle);
+        assert_eq!(output.Steer, input.Steer);
+        assert_eq!(output.Pitch, input.Pitch);
+        assert_eq!(output.Yaw, input.Yaw);
+        assert_eq!(output.Roll, input.Roll);
+        assert_eq!(output.Jump, input.Jump);
+        assert_eq!(output.Boost, input.Boost);
+        assert_eq!(output.Handbrake, input.Handbrake);
+        assert!(output.UseItem);
     }
 }
@@ -5,6 +5,7 @@
 pub use crate::{
     ext::ExtendRLBot,
     flatbuffers::vector_iter,
+    goal_detector::{DetectedGoal, GoalDetector},
     polyfill::ExtendDuration,
     pretty::{
         Angle, AngularVelocity, ControllerInput, Coordinate, Distance, PrettyPrint, Speed, Time,
@@ -13,6 +14,7 @@ pub use crate::{
 
 pub mod ext;
 mod flatbuffers;
+pub mod goal_detector;
 pub mod halfway_house;
 pub mod kinematics;
 pub mod math;
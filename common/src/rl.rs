@@ -33,6 +33,16 @@ pub const GRAVITY: f32 = -650.0;
 /// This value was observed in data from `collect`.
 pub const BALL_RADIUS: f32 = 91.24;
 
+/// Half the width of the Snow Day puck. Its hitbox is a flat box.
+///
+/// Source: observed in-game
+pub const PUCK_HALF_WIDTH: f32 = 114.25;
+
+/// Half the height of the Snow Day puck.
+///
+/// Source: observed in-game
+pub const PUCK_HALF_HEIGHT: f32 = 31.3;
+
 /// Source: https://discordapp.com/channels/348658686962696195/348659150793736193/525795583307415552
 pub const BALL_MASS: f32 = 30.0;
 
@@ -44,6 +54,21 @@ pub const CAR_MASS: f32 = 180.0;
 /// This value was observed in data from `collect`.
 pub const OCTANE_NEUTRAL_Z: f32 = 17.01;
 
+/// The width of the Octane's hitbox.
+///
+/// Source: https://github.com/RLBot/RLBot/wiki/Useful-Game-Values
+pub const OCTANE_HITBOX_WIDTH: f32 = 84.2;
+
+/// The length of the Octane's hitbox.
+///
+/// Source: https://github.com/RLBot/RLBot/wiki/Useful-Game-Values
+pub const OCTANE_HITBOX_LENGTH: f32 = 118.01;
+
+/// The height of the Octane's hitbox.
+///
+/// Source: https://github.com/RLBot/RLBot/wiki/Useful-Game-Values
+pub const OCTANE_HITBOX_HEIGHT: f32 = 36.16;
+
 /// The constant frequency of RL's physics engine.
 pub const PHYSICS_TICK_FREQ: f32 = 120.0;
 
@@ -60,6 +85,10 @@ pub const CAR_NORMAL_SPEED: f32 = 1410.001;
 /// This value was observed in data from `collect`.
 pub const CAR_MAX_SPEED: f32 = 2299.981;
 
+/// The speed at which a car goes supersonic, and demolishes other cars on
+/// contact.
+pub const CAR_SUPERSONIC_SPEED: f32 = 2200.0;
+
 /// The maximum norm of the car's angular velocity vector, in rad/s.
 ///
 /// Source: A Rocket Science video
@@ -3,11 +3,23 @@
 #![warn(clippy::all)]
 
 use crate::{banner::Banner, hacketeer::Hacketeer};
-use brain::{Brain, EEG};
+use brain::{
+    diff_decisions, export_telemetry_csv, replay_decisions, Brain, Coverage, Difficulty,
+    DrawCategory, Personality, ScoutingReport, View, EEG,
+};
 use chrono::Local;
 use collect::Collector;
 use common::{ext::ExtendRLBot, halfway_house::translate_player_input};
-use std::{error::Error, fs, panic, path::PathBuf, thread::sleep, time::Duration};
+use std::{
+    env,
+    error::Error,
+    fs::{self, File},
+    io, panic,
+    path::PathBuf,
+    process,
+    thread::sleep,
+    time::Duration,
+};
 
 mod banner;
 mod built;
@@ -24,14 +36,30 @@ fn main() {
         .format(logging::format)
         .init();
 
+    if let Some(recording) = arg_value("--replay=") {
+        replay(
+            &recording,
+            arg_value("--baseline=").as_ref().map(String::as_str),
+        );
+        return;
+    }
+    if let Some(telemetry) = arg_value("--telemetry-csv=") {
+        telemetry_csv(&telemetry);
+        return;
+    }
+    if let Some(coverage) = arg_value("--coverage-report=") {
+        coverage_report(
+            &coverage,
+            arg_value("--baseline=").as_ref().map(String::as_str),
+        );
+        return;
+    }
+
     let StartArgs {
         init_options,
         should_start_match,
         should_recover_from_panics,
-        log_game_data,
-        log_to_stdout,
-        show_window,
-        player_index,
+        bot,
     } = parse_args().expect("Error parsing command-line arguments");
 
     let rlbot = rlbot::init_with_options(init_options).expect("Could not initialize RLBot");
@@ -41,15 +69,7 @@ fn main() {
         start_match(&rlbot).expect("Error starting match");
     }
 
-    let run_the_bot = || {
-        run_bot(
-            rlbot,
-            player_index,
-            log_game_data,
-            log_to_stdout,
-            show_window,
-        );
-    };
+    let run_the_bot = || run_bot(rlbot, &bot);
 
     if !should_recover_from_panics {
         // In dev mode, halt on panics so they can't be ignored.
@@ -61,38 +81,195 @@ fn main() {
 }
 
 fn parse_args() -> Result<StartArgs, ()> {
+    // Unlike the rest, this works in both modes, so different copies of the bot
+    // can be fielded with different personalities.
+    let personality = match arg_value("--personality=") {
+        Some(name) => name.parse().map_err(|e| eprintln!("{}", e))?,
+        None => Personality::default(),
+    };
+    // And for difficulty, so a gentler copy can be fielded for practice.
+    let difficulty = match arg_value("--difficulty=") {
+        Some(name) => name.parse().map_err(|e| eprintln!("{}", e))?,
+        None => Difficulty::default(),
+    };
+    // Same goes for scouting, which matters most in tournaments.
+    let scouting_dir = arg_value("--scouting=").map(PathBuf::from);
+    // And for the status endpoint, which feeds tournament overlays.
+    let status_addr = arg_value("--status-addr=");
+    // And for telemetry, which is most useful after a tournament match.
+    let telemetry_path = arg_value("--telemetry=").map(PathBuf::from);
+    // And for the EEG stream, since the framework runs us without a window.
+    let eeg_stream = arg_value("--eeg-stream=");
+    // And for drawing in the game, which is the only place to see anything then.
+    let draw_in_game = env::args().any(|a| a == "--draw-in-game");
+    // And for Rumble, which the field alone can't tell us about.
+    let rumble = env::args().any(|a| a == "--rumble");
+
     match rlbot::parse_framework_args()? {
         // If we're running in the framework:
         Some(args) => Ok(StartArgs {
-            player_index: args.player_index,
+            bot: BotArgs {
+                player_index: args.player_index,
+                log_game_data: false,
+                log_to_stdout: false,
+                show_window: false,
+                view: View::TopDown,
+                hidden_draw_categories: Vec::new(),
+                coverage_path: None,
+                clips_dir: None,
+                scouting_dir,
+                status_addr,
+                telemetry_path,
+                eeg_stream,
+                draw_in_game,
+                personality,
+                difficulty,
+                rumble,
+                calibrate: false,
+                behavior: None,
+            },
             init_options: args.into(),
             should_start_match: false,
             should_recover_from_panics: true,
-            log_game_data: false,
-            log_to_stdout: false,
-            show_window: false,
         }),
         // If we're running standalone:
         None => Ok(StartArgs {
             init_options: rlbot::InitOptions::new(),
             should_start_match: true,
             should_recover_from_panics: false,
-            log_game_data: true,
-            log_to_stdout: true,
-            show_window: true,
-            player_index: 0,
+            bot: BotArgs {
+                player_index: 0,
+                log_game_data: true,
+                log_to_stdout: true,
+                show_window: !env::args().any(|a| a == "--headless"),
+                view: match arg_value("--view=") {
+                    Some(name) => name.parse().map_err(|e| eprintln!("{}", e))?,
+                    None => View::TopDown,
+                },
+                hidden_draw_categories: parse_hidden_draw_categories()?,
+                coverage_path: arg_value("--coverage=").map(PathBuf::from),
+                clips_dir: arg_value("--clips=").map(PathBuf::from),
+                scouting_dir,
+                status_addr,
+                telemetry_path,
+                eeg_stream,
+                draw_in_game,
+                personality,
+                difficulty,
+                rumble,
+                calibrate: env::args().any(|a| a == "--calibrate"),
+                behavior: arg_value("--behavior="),
+            },
         }),
     }
 }
 
+/// Parse `--hide-draw=routes,text`.
+fn parse_hidden_draw_categories() -> Result<Vec<DrawCategory>, ()> {
+    const PREFIX: &str = "--hide-draw=";
+    let mut result = Vec::new();
+    for arg in env::args().filter(|a| a.starts_with(PREFIX)) {
+        for name in arg[PREFIX.len()..].split(',') {
+            result.push(name.parse().map_err(|e| eprintln!("{}", e))?);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(feature = "status-server")]
+fn serve_status(addr: &str, eeg: &mut EEG) {
+    brain::serve_status(addr, eeg.publish_status()).expect("Error starting status server");
+    println!("Serving status at http://{}/status", addr);
+}
+
+#[cfg(not(feature = "status-server"))]
+fn serve_status(_addr: &str, _eeg: &mut EEG) {
+    eprintln!("Ignoring --status-addr; build with --features status-server to use it.");
+}
+
+/// Return the value of an argument like `--name=value`.
+fn arg_value(prefix: &str) -> Option<String> {
+    env::args()
+        .find(|a| a.starts_with(prefix))
+        .map(|a| a[prefix.len()..].to_string())
+}
+
+/// Replay a recorded match through the strategy layer without a game. With a
+/// baseline, exit with an error if the decisions changed. Otherwise, print the
+/// decisions so they can be saved as the new baseline.
+fn replay(recording: &str, baseline: Option<&str>) {
+    let file = fs::File::open(recording).expect("Error opening recording");
+    let decisions = replay_decisions(file, 0);
+
+    let baseline = match baseline {
+        Some(path) => fs::read_to_string(path).expect("Error reading baseline"),
+        None => {
+            print!("{}", decisions);
+            return;
+        }
+    };
+    match diff_decisions(&baseline, &decisions) {
+        Some(diff) => {
+            println!("{}", diff);
+            process::exit(1);
+        }
+        None => println!("Decisions match the baseline."),
+    }
+}
+
+/// Print what never happened in the coverage saved at `path`. With a
+/// baseline, also check which behaviors and transitions went missing.
+fn coverage_report(path: &str, baseline: Option<&str>) {
+    let read = |path| -> Coverage {
+        fs::read_to_string(path)
+            .expect("Error reading coverage")
+            .parse()
+            .expect("Error parsing coverage")
+    };
+    let coverage = read(path);
+    let baseline = baseline.map(read);
+    print!("{}", coverage.report(baseline.as_ref()));
+}
+
+fn telemetry_csv(path: &str) {
+    let file = File::open(path).expect("Error opening telemetry");
+    let stdout = io::stdout();
+    export_telemetry_csv(file, stdout.lock()).expect("Error exporting telemetry");
+}
+
 struct StartArgs {
     init_options: rlbot::InitOptions,
     should_start_match: bool,
     should_recover_from_panics: bool,
+    bot: BotArgs,
+}
+
+struct BotArgs {
+    player_index: i32,
     log_game_data: bool,
     log_to_stdout: bool,
     show_window: bool,
-    player_index: i32,
+    /// `--view=unfolded` folds the walls out flat, to check wall routes.
+    view: View,
+    hidden_draw_categories: Vec<DrawCategory>,
+    coverage_path: Option<PathBuf>,
+    clips_dir: Option<PathBuf>,
+    /// Where to keep what we learn about each opponent between matches.
+    scouting_dir: Option<PathBuf>,
+    /// Where to serve `BotStatus`, e.g., `127.0.0.1:8321`.
+    status_addr: Option<String>,
+    /// Where to record a telemetry log of the whole match.
+    telemetry_path: Option<PathBuf>,
+    /// Where to stream the EEG to, e.g., `0.0.0.0:8322`.
+    eeg_stream: Option<String>,
+    /// `--draw-in-game` renders the EEG through RLBot, into the game window.
+    draw_in_game: bool,
+    personality: Personality,
+    difficulty: Difficulty,
+    /// `--rumble` plays with power-ups.
+    rumble: bool,
+    calibrate: bool,
+    behavior: Option<String>,
 }
 
 fn start_match(rlbot: &rlbot::RLBot) -> Result<(), Box<dyn Error>> {
@@ -119,36 +296,84 @@ fn deny_climate_change<R>(f: impl Fn() -> R) -> R {
     }
 }
 
-fn run_bot(
-    rlbot: &'static rlbot::RLBot,
-    player_index: i32,
-    log_game_data: bool,
-    log_to_stdout: bool,
-    show_window: bool,
-) {
+fn run_bot(rlbot: &'static rlbot::RLBot, args: &BotArgs) {
     let field_info = wait_for_field_info(rlbot);
-    let brain = match Brain::infer_game_mode(field_info) {
+    let mut brain = match wait_for_game_mode(rlbot, field_info) {
+        rlbot::GameMode::Soccer if args.rumble => Brain::rumble(),
         rlbot::GameMode::Soccer => Brain::soccar(),
         rlbot::GameMode::Dropshot => Brain::dropshot(rlbot),
         rlbot::GameMode::Hoops => Brain::hoops(rlbot),
+        rlbot::GameMode::Hockey => Brain::snow_day(),
         mode => panic!("unexpected game mode {:?}", mode),
     };
+    brain.set_personality(args.personality);
+    brain.set_difficulty(args.difficulty);
+    let scouting = args
+        .scouting_dir
+        .clone()
+        .and_then(|dir| Some((dir, opponent_name(rlbot, args.player_index)?)));
+    if let Some((ref dir, ref opponent)) = scouting {
+        match ScoutingReport::load(dir, opponent) {
+            Ok(report) => brain.learn_from(report),
+            Err(err) => log::warn!("could not load scouting report for {}: {}", opponent, err),
+        }
+    }
+    if args.calibrate {
+        brain.calibrate();
+    }
+    if let Some(ref name) = args.behavior {
+        brain
+            .start_behavior(name)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
 
-    let collector = if log_game_data {
+    let collector = if args.log_game_data {
         Some(create_collector())
     } else {
         None
     };
     let mut eeg = EEG::new();
-    if log_to_stdout {
+    if args.log_to_stdout {
         eeg.log_to_stdout();
     }
-    if show_window {
+    if args.show_window {
         eeg.show_window();
     }
-    let mut bot = FormulaNone::new(rlbot, field_info, collector, eeg, brain);
-    bot.set_player_index(player_index);
-    bot_loop(&rlbot, player_index, &mut bot);
+    if args.draw_in_game {
+        eeg.render_in_game(rlbot);
+    }
+    eeg.set_view(args.view);
+    for &category in &args.hidden_draw_categories {
+        eeg.hide(category);
+    }
+    if args.coverage_path.is_some() {
+        eeg.track_coverage();
+    }
+    if let Some(ref dir) = args.clips_dir {
+        eeg.save_clips_to(dir.clone());
+    }
+    if let Some(ref path) = args.telemetry_path {
+        eeg.record_telemetry_to(path)
+            .expect("Error creating telemetry log");
+    }
+    if let Some(ref addr) = args.eeg_stream {
+        eeg.stream_to(addr).expect("Error starting EEG stream");
+        println!("Streaming the EEG on {}", addr);
+    }
+    if let Some(ref addr) = args.status_addr {
+        serve_status(addr, &mut eeg);
+    }
+    let mut bot = FormulaNone::new(
+        rlbot,
+        field_info,
+        collector,
+        args.coverage_path.clone(),
+        eeg,
+        brain,
+        scouting,
+    );
+    bot.set_player_index(args.player_index);
+    bot_loop(&rlbot, args.player_index, &mut bot);
 }
 
 fn wait_for_field_info(rlbot: &rlbot::RLBot) -> rlbot::flat::FieldInfo<'_> {
@@ -163,6 +388,34 @@ fn wait_for_field_info(rlbot: &rlbot::RLBot) -> rlbot::flat::FieldInfo<'_> {
     }
 }
 
+/// The packets before the first kickoff can have a zeroed ball, or one that's
+/// still settling, so wait until it's clear what we're playing.
+fn wait_for_game_mode(
+    rlbot: &rlbot::RLBot,
+    field_info: rlbot::flat::FieldInfo<'_>,
+) -> rlbot::GameMode {
+    let mut hacketeer = Hacketeer::new(rlbot);
+    loop {
+        let (packet, _) = hacketeer.next().unwrap();
+        if let Some(mode) = Brain::infer_game_mode(field_info, &packet.GameBall) {
+            break mode;
+        }
+    }
+}
+
+/// The name of the first car on the other team, e.g., for keying scouting
+/// reports.
+fn opponent_name(rlbot: &rlbot::RLBot, player_index: i32) -> Option<String> {
+    let packet = rlbot.packeteer().next_flatbuffer().ok()?;
+    let players = packet.players()?;
+    let team = players.get(player_index as usize).team();
+    (0..players.len())
+        .map(|i| players.get(i))
+        .find(|p| p.team() != team)
+        .and_then(|p| p.name())
+        .map(str::to_string)
+}
+
 fn bot_loop(rlbot: &rlbot::RLBot, player_index: i32, bot: &mut FormulaNone<'_>) {
     let mut packeteer = Hacketeer::new(rlbot);
     loop {
@@ -200,9 +453,14 @@ struct FormulaNone<'a> {
     rlbot: &'static rlbot::RLBot,
     field_info: rlbot::flat::FieldInfo<'a>,
     collector: Option<collect::Collector>,
+    coverage_path: Option<PathBuf>,
+    last_coverage_save: f32,
     eeg: EEG,
     brain: Brain,
     banner: Banner,
+    /// Where to save the scouting report, and the opponent it's about.
+    scouting: Option<(PathBuf, String)>,
+    scouting_saved: bool,
 }
 
 impl<'a> FormulaNone<'a> {
@@ -210,16 +468,22 @@ impl<'a> FormulaNone<'a> {
         rlbot: &'static rlbot::RLBot,
         field_info: rlbot::flat::FieldInfo<'a>,
         collector: Option<collect::Collector>,
+        coverage_path: Option<PathBuf>,
         eeg: brain::EEG,
         brain: brain::Brain,
+        scouting: Option<(PathBuf, String)>,
     ) -> Self {
         Self {
             rlbot,
             field_info,
             collector,
+            coverage_path,
+            last_coverage_save: 0.0,
             eeg,
             brain,
             banner: Banner::new(),
+            scouting,
+            scouting_saved: false,
         }
     }
 
@@ -247,7 +511,43 @@ impl<'a> FormulaNone<'a> {
             }
         }
         self.eeg.show(&packet);
+        self.save_coverage(packet.GameInfo.TimeSeconds);
+        self.save_scouting_report(packet.GameInfo.MatchEnded);
 
         (input, self.eeg.quick_chat)
     }
+
+    /// Once the match is over, save what we learned about the opponent.
+    fn save_scouting_report(&mut self, match_ended: bool) {
+        if !match_ended || self.scouting_saved {
+            return;
+        }
+        self.scouting_saved = true;
+        let (dir, opponent) = match &self.scouting {
+            Some(x) => x,
+            None => return,
+        };
+        if let Err(err) = self.brain.scouting_report().save(dir, opponent) {
+            log::warn!("could not save scouting report for {}: {}", opponent, err);
+        }
+    }
+
+    /// Every so often, add the coverage collected so far to the file, and start
+    /// counting from zero again.
+    fn save_coverage(&mut self, now: f32) {
+        const INTERVAL: f32 = 10.0;
+
+        let path = match &self.coverage_path {
+            Some(path) => path,
+            None => return,
+        };
+        if now - self.last_coverage_save < INTERVAL {
+            return;
+        }
+        self.last_coverage_save = now;
+        let coverage = self.eeg.coverage.replace(Coverage::new()).unwrap();
+        if let Err(err) = coverage.merge_into_file(path) {
+            log::warn!("could not save coverage: {}", err);
+        }
+    }
 }
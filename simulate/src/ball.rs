@@ -0,0 +1,114 @@
+use common::rl;
+use nalgebra::{Point3, Unit, Vector3};
+
+/// The ball as a spinning sphere, for working out how contacts trade its speed
+/// for spin and back again.
+///
+/// This follows RLUtilities. A contact pushes the ball back out along the
+/// contact normal, and friction drags the ball's surface towards the speed of
+/// whatever it's touching. So a ball with backspin checks up when it lands, a
+/// ball that's rolling keeps rolling, and a ball that's skidding picks up spin
+/// and slows down.
+///
+/// `step` only knows about the floor. Walls and the rest of the arena are
+/// chip's business; this is for the contacts themselves.
+#[derive(Clone)]
+pub struct Ball {
+    pub loc: Point3<f32>,
+    pub vel: Vector3<f32>,
+    pub omega: Vector3<f32>,
+}
+
+impl Ball {
+    /// How much of the speed into a surface comes back out of a bounce.
+    const RESTITUTION: f32 = 0.6;
+    const FRICTION: f32 = 2.0;
+    /// Friction can't take more than this many times the impulse along the
+    /// normal. A ball grazing a surface barely grips it.
+    const GRIP: f32 = 2.0;
+    /// Per second, as a fraction of the ball's velocity.
+    const DRAG: f32 = -0.0305;
+    /// Source: https://github.com/RLBot/RLBot/wiki/Useful-Game-Values
+    const MAX_OMEGA: f32 = 6.0;
+
+    pub fn new(loc: Point3<f32>, vel: Vector3<f32>, omega: Vector3<f32>) -> Self {
+        Self { loc, vel, omega }
+    }
+
+    /// Step forward by `dt` seconds under `gravity`, bouncing off the floor.
+    pub fn step(&mut self, dt: f32, gravity: f32) {
+        if self.loc.z < rl::BALL_RADIUS && self.vel.z < 0.0 {
+            self.collide(&Vector3::z_axis(), Vector3::zeros());
+        }
+
+        self.vel += (self.vel * Self::DRAG + Vector3::z() * gravity) * dt;
+        self.loc += self.vel * dt;
+    }
+
+    /// Touch a surface facing `normal` (pointing from the surface towards the
+    /// ball) that's moving at `surface_vel`, e.g., the floor, or a car.
+    pub fn collide(&mut self, normal: &Unit<Vector3<f32>>, surface_vel: Vector3<f32>) {
+        let mass = rl::BALL_MASS;
+        let inertia = 0.4 * mass * rl::BALL_RADIUS * rl::BALL_RADIUS;
+
+        // From the center of the ball to where it touches.
+        let contact = -normal.into_inner() * rl::BALL_RADIUS;
+        let reduced_mass = 1.0 / (1.0 / mass + contact.norm_squared() / inertia);
+
+        let rel_vel = self.vel - surface_vel;
+        let v_perp = normal.into_inner() * rel_vel.dot(normal).min(0.0);
+        let v_para = rel_vel - v_perp - contact.cross(&self.omega);
+        let ratio = v_perp.norm() / v_para.norm().max(1e-4);
+
+        let impulse = v_perp * -(1.0 + Self::RESTITUTION) * mass
+            + v_para * -(Self::GRIP * ratio).min(1.0) * Self::FRICTION * reduced_mass;
+
+        self.vel += impulse / mass;
+        self.omega += contact.cross(&impulse) / inertia;
+        let spin = self.omega.norm();
+        if spin > Self::MAX_OMEGA {
+            self.omega *= Self::MAX_OMEGA / spin;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ball::Ball;
+    use common::rl;
+    use nalgebra::{Point3, Vector3};
+
+    fn land(omega: Vector3<f32>) -> Ball {
+        let mut ball = Ball::new(
+            Point3::new(0.0, 0.0, 300.0),
+            Vector3::new(0.0, 1000.0, -500.0),
+            omega,
+        );
+        for _ in 0..60 {
+            ball.step(rl::PHYSICS_DT, rl::GRAVITY);
+        }
+        ball
+    }
+
+    #[test]
+    fn backspin_checks_up() {
+        let plain = land(Vector3::zeros());
+        let backspin = land(Vector3::new(6.0, 0.0, 0.0));
+        assert!(plain.vel.y < 1000.0);
+        assert!(backspin.vel.y < plain.vel.y - 200.0);
+    }
+
+    #[test]
+    fn topspin_kicks_forward() {
+        let plain = land(Vector3::zeros());
+        let topspin = land(Vector3::new(-6.0, 0.0, 0.0));
+        assert!(topspin.vel.y > plain.vel.y + 200.0);
+    }
+
+    #[test]
+    fn skidding_picks_up_spin() {
+        let ball = land(Vector3::zeros());
+        // Rolling towards +y means spinning around -x.
+        assert!(ball.omega.x < -1.0);
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(clippy::float_cmp)]
 
-use crate::math::{linear_interpolate_find_index, linear_interpolate_use_index};
+use crate::math::{
+    linear_interpolate, linear_interpolate_find_index, linear_interpolate_use_index,
+};
 use common::rl;
 use oven::data;
 
@@ -11,6 +13,7 @@ pub struct Car1D {
     distance: f32,
     speed: f32,
     boost: f32,
+    boost_cap: f32,
 }
 
 impl Default for Car1D {
@@ -20,6 +23,7 @@ impl Default for Car1D {
             distance: 0.0,
             speed: 0.0,
             boost: 100.0,
+            boost_cap: rl::CAR_MAX_SPEED,
         }
     }
 }
@@ -46,6 +50,16 @@ impl Car1D {
         self
     }
 
+    /// Only boost up to this speed, then hold it on throttle alone, the way a
+    /// driver feathers boost to stay supersonic.
+    pub fn with_boost_cap(mut self, boost_cap: f32) -> Self {
+        assert!(boost_cap >= rl::CAR_NORMAL_SPEED);
+        assert!(boost_cap <= rl::CAR_MAX_SPEED);
+
+        self.boost_cap = boost_cap;
+        self
+    }
+
     pub fn time(&self) -> f32 {
         self.time
     }
@@ -68,7 +82,7 @@ impl Car1D {
             return; // Rigorously simulate zero time elapsing.
         }
 
-        if boost && self.boost <= EPS {
+        if boost && (self.boost <= EPS || self.at_boost_cap()) {
             boost = false;
         }
 
@@ -101,7 +115,7 @@ impl Car1D {
     pub fn advance_by_distance(&mut self, distance: f32, throttle: f32, mut boost: bool) {
         assert!(distance > 0.0);
 
-        if boost && self.boost <= EPS {
+        if boost && (self.boost <= EPS || self.at_boost_cap()) {
             boost = false;
         }
 
@@ -131,6 +145,31 @@ impl Car1D {
         self.advance_by_distance(distance - curve.distance, throttle, boost);
     }
 
+    fn at_boost_cap(&self) -> bool {
+        // Allow slight rounding errors
+        self.boost_cap < rl::CAR_MAX_SPEED && self.speed >= self.boost_cap - 1.0
+    }
+
+    /// How long it takes to boost from the current speed to the boost cap.
+    fn time_to_boost_cap(&self) -> f32 {
+        if self.boost_cap >= rl::CAR_MAX_SPEED {
+            return f32::INFINITY;
+        }
+        let table = &data::boost::CAR_VEL_Y;
+        linear_interpolate(table, &data::boost::TIME, self.boost_cap)
+            - linear_interpolate(table, &data::boost::TIME, self.speed)
+    }
+
+    /// How far it takes to boost from the current speed to the boost cap.
+    fn distance_to_boost_cap(&self) -> f32 {
+        if self.boost_cap >= rl::CAR_MAX_SPEED {
+            return f32::INFINITY;
+        }
+        let table = &data::boost::CAR_VEL_Y;
+        linear_interpolate(table, &data::boost::CAR_LOC_Y, self.boost_cap)
+            - linear_interpolate(table, &data::boost::CAR_LOC_Y, self.speed)
+    }
+
     fn next_curve(speed: f32, throttle: f32, boost: bool) -> Curve {
         if throttle == 0.0 && !boost && speed == 0.0 {
             Curve::ConstantSpeed
@@ -170,7 +209,7 @@ impl Car1D {
     }
 
     fn calc_boost_by_time(&self, dt: f32) -> CurveResult {
-        let dt = dt.min(self.boost / rl::BOOST_DEPLETION);
+        let dt = (dt.min(self.boost / rl::BOOST_DEPLETION)).min(self.time_to_boost_cap());
         let boost_used = dt * rl::BOOST_DEPLETION;
         let (dt, distance, new_speed) = Self::lookup_advance_by_time(dt, self.speed, 1.0, true);
         CurveResult {
@@ -212,6 +251,7 @@ impl Car1D {
     }
 
     fn calc_boost_by_distance(&self, distance: f32) -> CurveResult {
+        let distance = distance.min(self.distance_to_boost_cap());
         let (dt, distance, new_speed) =
             Self::lookup_advance_by_distance(distance, self.speed, 1.0, true);
 
@@ -447,6 +487,27 @@ mod tests {
         assert!(99.4 <= car.boost() && car.boost() < 99.5);
     }
 
+    #[test]
+    fn advance_boost_to_boost_cap() {
+        let mut car = Car1D::new().with_speed(1500.0).with_boost_cap(2225.0);
+        car.advance(3.0, 1.0, true);
+        assert!((car.speed() - 2225.0).abs() < 1.0);
+        assert!((car.time() - 3.0).abs() < 1e-2);
+
+        let mut uncapped = Car1D::new().with_speed(1500.0);
+        uncapped.advance(3.0, 1.0, true);
+        assert!(car.distance() < uncapped.distance());
+        assert!(car.boost() > uncapped.boost() + 20.0);
+    }
+
+    #[test]
+    fn advance_by_distance_boost_to_boost_cap() {
+        let mut car = Car1D::new().with_speed(1500.0).with_boost_cap(2225.0);
+        car.advance_by_distance(5000.0, 1.0, true);
+        assert!((car.distance() - 5000.0).abs() <= 1e-2);
+        assert!((car.speed() - 2225.0).abs() < 1.0);
+    }
+
     #[test]
     fn advance_boost_with_no_boost() {
         let mut car = Car1D::new().with_boost(0.0);
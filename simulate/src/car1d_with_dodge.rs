@@ -0,0 +1,239 @@
+use crate::{
+    car1d::Car1D,
+    car_forward_dodge::{CarForwardDodge, CarForwardDodge1D},
+};
+use common::rl;
+use std::f32;
+
+const EPS: f32 = 1e-3;
+
+/// Like `Car1D` at full throttle, but whenever the car isn't boosting and is
+/// going the right speed, it front flips, the way a car with no boost gets
+/// around. Each flip adds the dodge impulse, which `Car1D` knows nothing about.
+pub struct Car1DWithDodge {
+    time: f32,
+    distance: f32,
+    speed: f32,
+    boost: f32,
+    boost_cap: f32,
+    /// Only start a flip that lands at least `RUNWAY_MARGIN` short of this
+    /// distance.
+    runway: f32,
+    /// The flip in progress, and how far into it we are.
+    dodge: Option<(CarForwardDodge1D, f32)>,
+    dodges: u32,
+}
+
+impl Default for Car1DWithDodge {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            distance: 0.0,
+            speed: 0.0,
+            boost: 100.0,
+            boost_cap: rl::CAR_MAX_SPEED,
+            runway: f32::INFINITY,
+            dodge: None,
+            dodges: 0,
+        }
+    }
+}
+
+impl Car1DWithDodge {
+    /// Below this speed, a flip leaves us slow and floating for too long.
+    pub const MIN_DODGE_SPEED: f32 = 1000.0;
+    /// Above this speed, a flip barely adds anything.
+    pub const MAX_DODGE_SPEED: f32 = 1900.0;
+    /// Land with at least this much runway left, so whatever comes next has
+    /// time to react.
+    pub const RUNWAY_MARGIN: f32 = 500.0;
+    /// Drive in steps this long, so we notice when the speed is right to flip.
+    const STEP: f32 = 1.0 / 120.0;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = Car1D::new().with_speed(speed).speed();
+        self
+    }
+
+    pub fn with_boost(mut self, boost: f32) -> Self {
+        self.boost = Car1D::new().with_boost(boost).boost();
+        self
+    }
+
+    /// See `Car1D::with_boost_cap`.
+    pub fn with_boost_cap(mut self, boost_cap: f32) -> Self {
+        Car1D::new().with_boost_cap(boost_cap);
+        self.boost_cap = boost_cap;
+        self
+    }
+
+    pub fn with_runway(mut self, runway: f32) -> Self {
+        self.runway = runway;
+        self
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn boost(&self) -> f32 {
+        self.boost
+    }
+
+    /// How many flips we've started so far.
+    pub fn dodges(&self) -> u32 {
+        self.dodges
+    }
+
+    pub fn advance(&mut self, dt: f32, boost: bool) {
+        assert!(dt >= 0.0);
+        let end_time = self.time + dt;
+        while end_time - self.time >= EPS {
+            self.step(end_time - self.time, f32::INFINITY, boost);
+        }
+    }
+
+    pub fn advance_by_distance(&mut self, distance: f32, boost: bool) {
+        assert!(distance > 0.0);
+        let end_distance = self.distance + distance;
+        while end_distance - self.distance >= EPS {
+            self.step(f32::INFINITY, end_distance - self.distance, boost);
+        }
+    }
+
+    /// Advance by no more than `max_dt` or `max_distance`, whichever comes
+    /// first.
+    fn step(&mut self, max_dt: f32, max_distance: f32, boost: bool) {
+        if self.dodge.is_none() && self.should_dodge(boost) {
+            self.dodge = Some((CarForwardDodge::calc_1d(self.speed), 0.0));
+            self.dodges += 1;
+        }
+
+        if let Some((dodge, elapsed)) = &mut self.dodge {
+            // The car holds its speed in the air. It jumps, then the dodge
+            // impulse kicks in.
+            let impulse_time = dodge.jump_duration + dodge.wait_duration;
+            let (speed, phase_end) = if *elapsed < impulse_time - EPS {
+                (dodge.start_speed, impulse_time)
+            } else {
+                (dodge.end_speed, dodge.duration())
+            };
+            let dt = (phase_end - *elapsed).min(max_dt).min(max_distance / speed);
+            *elapsed += dt;
+            self.time += dt;
+            self.distance += speed * dt;
+            self.speed = speed;
+            if *elapsed >= dodge.duration() - EPS {
+                self.speed = dodge.end_speed;
+                self.dodge = None;
+            }
+            return;
+        }
+
+        // Once there's no chance of another flip, skip straight to the end.
+        let dt = if self.done_dodging() {
+            max_dt
+        } else {
+            max_dt.min(Self::STEP)
+        };
+        let mut sim = self.car1d();
+        if dt.is_finite() {
+            sim.advance(dt, 1.0, boost);
+        }
+        if !dt.is_finite() || sim.distance() > max_distance {
+            sim = self.car1d();
+            sim.advance_by_distance(max_distance, 1.0, boost);
+        }
+        self.time += sim.time();
+        self.distance += sim.distance();
+        self.speed = sim.speed();
+        self.boost = sim.boost();
+    }
+
+    /// A `Car1D` starting from where we are now.
+    fn car1d(&self) -> Car1D {
+        Car1D::new()
+            .with_speed(self.speed)
+            .with_boost(self.boost)
+            .with_boost_cap(self.boost_cap)
+    }
+
+    fn should_dodge(&self, boost: bool) -> bool {
+        if boost && self.boost > EPS {
+            return false;
+        }
+        if self.speed < Self::MIN_DODGE_SPEED || self.speed > Self::MAX_DODGE_SPEED {
+            return false;
+        }
+        let dodge = CarForwardDodge::calc_1d(self.speed);
+        self.distance + dodge.end_dist + Self::RUNWAY_MARGIN <= self.runway
+    }
+
+    /// Returns `true` if we'll never flip again. At full throttle the car never
+    /// slows down, and the runway only gets shorter.
+    fn done_dodging(&self) -> bool {
+        let shortest_dodge = CarForwardDodge::calc_1d(Self::MIN_DODGE_SPEED);
+        self.speed > Self::MAX_DODGE_SPEED
+            || self.distance + shortest_dodge.end_dist + Self::RUNWAY_MARGIN > self.runway
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{car1d::Car1D, car1d_with_dodge::Car1DWithDodge};
+
+    #[test]
+    fn flipping_beats_driving() {
+        let mut plain = Car1D::new().with_speed(1200.0).with_boost(0.0);
+        plain.advance(3.0, 1.0, false);
+        let mut flipping = Car1DWithDodge::new().with_speed(1200.0).with_boost(0.0);
+        flipping.advance(3.0, false);
+
+        assert!(flipping.dodges() >= 1);
+        assert!((flipping.time() - 3.0).abs() < 1e-3);
+        assert!(flipping.distance() > plain.distance() + 500.0);
+    }
+
+    #[test]
+    fn boost_first() {
+        let mut plain = Car1D::new().with_speed(1200.0);
+        plain.advance(0.5, 1.0, true);
+        let mut flipping = Car1DWithDodge::new().with_speed(1200.0);
+        flipping.advance(0.5, true);
+
+        assert_eq!(flipping.dodges(), 0);
+        assert!((flipping.distance() - plain.distance()).abs() < 1.0);
+        assert!((flipping.boost() - plain.boost()).abs() < 0.1);
+    }
+
+    #[test]
+    fn needs_runway() {
+        let mut car = Car1DWithDodge::new()
+            .with_speed(1200.0)
+            .with_boost(0.0)
+            .with_runway(1500.0);
+        car.advance_by_distance(1500.0, false);
+        assert_eq!(car.dodges(), 0);
+        assert!((car.distance() - 1500.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn advance_by_distance_lands_on_the_spot() {
+        let mut car = Car1DWithDodge::new().with_speed(1200.0).with_boost(0.0);
+        car.advance_by_distance(5000.0, false);
+        assert!(car.dodges() >= 1);
+        assert!((car.distance() - 5000.0).abs() < 1e-2);
+    }
+}
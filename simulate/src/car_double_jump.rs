@@ -0,0 +1,95 @@
+use common::{kinematics::kinematic_time, rl};
+
+/// How long to let go of the button between the two jumps. The game needs to
+/// see it released for at least a frame.
+pub const RELEASE_TIME: f32 = 2.0 / 120.0;
+
+/// When to press jump the second time, counting from the first press.
+pub const SECOND_JUMP_TIME: f32 = rl::CAR_JUMP_FORCE_TIME + RELEASE_TIME;
+
+/// The highest a fast double jump can reach, under the given gravity.
+///
+/// A fast double jump holds the first jump for as long as it pushes us up,
+/// lets go for `RELEASE_TIME`, then jumps again. The second jump is a single
+/// impulse with no extra force for holding the button.
+pub fn max_z(gravity: f32) -> f32 {
+    let (z_2, v_2) = second_jump(gravity);
+    rl::OCTANE_NEUTRAL_Z + z_2 + v_2 * v_2 / (2.0 * -gravity)
+}
+
+/// How long a fast double jump takes to lift the car to `z`, or `None` if it
+/// can't.
+pub fn time_to_z(z: f32, gravity: f32) -> Option<f32> {
+    let dz = z - rl::OCTANE_NEUTRAL_Z;
+    if dz <= 0.0 {
+        return Some(0.0);
+    }
+    if z > max_z(gravity) {
+        return None;
+    }
+
+    // Stage 1: holding the first jump.
+    let a = rl::CAR_JUMP_ACCEL + gravity;
+    if let Some(t) = kinematic_time(dz, rl::CAR_JUMP_IMPULSE_SPEED, a) {
+        if t < rl::CAR_JUMP_FORCE_TIME {
+            return Some(t);
+        }
+    }
+
+    // Stage 2: the button is released, waiting to jump again.
+    let (z_1, v_1) = force_phase(gravity);
+    if let Some(t) = kinematic_time(dz - z_1, v_1, gravity) {
+        if t < RELEASE_TIME {
+            return Some(rl::CAR_JUMP_FORCE_TIME + t);
+        }
+    }
+
+    // Stage 3: coasting after the second jump.
+    let (z_2, v_2) = second_jump(gravity);
+    kinematic_time(dz - z_2, v_2, gravity).map(|t| SECOND_JUMP_TIME + t)
+}
+
+/// The height gained and the vertical speed at the moment we have to let go of
+/// the first jump.
+fn force_phase(gravity: f32) -> (f32, f32) {
+    let t = rl::CAR_JUMP_FORCE_TIME;
+    let v_0 = rl::CAR_JUMP_IMPULSE_SPEED;
+    let a = rl::CAR_JUMP_ACCEL + gravity;
+    (v_0 * t + 0.5 * a * t * t, v_0 + a * t)
+}
+
+/// The height gained and the vertical speed just after the second jump.
+fn second_jump(gravity: f32) -> (f32, f32) {
+    let (z_1, v_1) = force_phase(gravity);
+    let t = RELEASE_TIME;
+    let z_2 = z_1 + v_1 * t + 0.5 * gravity * t * t;
+    let v_2 = v_1 + gravity * t + rl::CAR_JUMP_IMPULSE_SPEED;
+    (z_2, v_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{car_double_jump, car_single_jump};
+    use common::rl;
+
+    #[test]
+    fn max_z() {
+        let max_z = car_double_jump::max_z(rl::GRAVITY);
+        assert!(max_z > 500.0 && max_z < 530.0, "{}", max_z);
+        assert_eq!(car_double_jump::time_to_z(max_z + 1.0, rl::GRAVITY), None);
+        // Lower gravity, higher jump.
+        assert!(car_double_jump::max_z(rl::GRAVITY / 2.0) > max_z);
+    }
+
+    #[test]
+    fn time_to_z() {
+        let time = |z| car_double_jump::time_to_z(z, rl::GRAVITY).unwrap();
+        assert_eq!(time(rl::OCTANE_NEUTRAL_Z), 0.0);
+        assert!(time(50.0) < rl::CAR_JUMP_FORCE_TIME);
+        assert!(time(300.0) > car_double_jump::SECOND_JUMP_TIME);
+        assert!(time(300.0) < time(400.0));
+        // The second jump gets us there much faster than one jump would.
+        let single = car_single_jump::time_to_z(car_single_jump::JUMP_MAX_Z).unwrap();
+        assert!(time(car_single_jump::JUMP_MAX_Z) < single - 0.25);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::math::linear_interpolate;
+
+/// Speeds at which the turning circle was measured, holding full steer at
+/// full throttle on flat ground.
+const SPEEDS: &[f32] = &[0.0, 500.0, 1000.0, 1500.0, 1750.0, 2300.0];
+/// The curvature (the inverse of the radius) of the turning circle at each of
+/// `SPEEDS`.
+///
+/// Source: measured in game. These agree with RLUtilities, and `Calibrate`
+/// checks them against the live game.
+const CURVATURES: &[f32] = &[0.0069, 0.00398, 0.00235, 0.001375, 0.0011, 0.00088];
+
+/// The curvature of the tightest turn a car can make at the given speed.
+pub fn max_curvature(speed: f32) -> f32 {
+    linear_interpolate(SPEEDS, CURVATURES, speed)
+}
+
+/// The radius of the tightest turn a car can make at the given speed.
+pub fn turn_radius(speed: f32) -> f32 {
+    1.0 / max_curvature(speed)
+}
+
+/// How fast the car's heading changes, in rad/s, at the given speed with full
+/// steer.
+pub fn angular_velocity(speed: f32) -> f32 {
+    speed * max_curvature(speed)
+}
+
+/// The fastest a car can go and still hold a turn of the given radius.
+pub fn max_speed_for_radius(radius: f32) -> f32 {
+    // Curvature falls as speed rises, so walk the table to invert it.
+    let curvature = 1.0 / radius;
+    if curvature >= CURVATURES[0] {
+        return SPEEDS[0];
+    }
+    (SPEEDS.windows(2).zip(CURVATURES.windows(2)))
+        .find(|(_, c)| curvature >= c[1])
+        .map(|(s, c)| s[0] + (s[1] - s[0]) * (curvature - c[0]) / (c[1] - c[0]))
+        .unwrap_or(SPEEDS[SPEEDS.len() - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::car_turn::{angular_velocity, max_speed_for_radius, turn_radius};
+
+    #[test]
+    fn faster_means_wider() {
+        assert!(turn_radius(500.0) < turn_radius(1000.0));
+        assert!(turn_radius(1000.0) < turn_radius(2300.0));
+        assert!((turn_radius(1000.0) - 1.0 / 0.00235).abs() < 1.0);
+    }
+
+    #[test]
+    fn angular_velocity_peaks_at_moderate_speed() {
+        assert!(angular_velocity(0.0) == 0.0);
+        assert!(angular_velocity(1000.0) > angular_velocity(2300.0));
+    }
+
+    #[test]
+    fn max_speed_for_radius_round_trip() {
+        for &speed in &[250.0, 900.0, 1600.0, 2000.0] {
+            let radius = turn_radius(speed);
+            assert!((max_speed_for_radius(radius) - speed).abs() < 1.0);
+        }
+    }
+}
@@ -4,19 +4,25 @@
 #![allow(clippy::unreadable_literal)]
 
 pub use crate::{
+    ball::Ball,
     car::{Car, CarSimulateError},
     car1d::Car1D,
+    car1d_with_dodge::Car1DWithDodge,
     car_forward_dodge::{CarForwardDodge, CarForwardDodge1D},
     car_powerslide_turn::{CarPowerslideTurn, CarPowerslideTurnBlueprint},
     collision::ball_car_distance,
     math::linear_interpolate,
 };
 
+mod ball;
 mod car;
 mod car1d;
+mod car1d_with_dodge;
+pub mod car_double_jump;
 mod car_forward_dodge;
 pub mod car_jump;
 mod car_powerslide_turn;
 pub mod car_single_jump;
+pub mod car_turn;
 mod collision;
 mod math;